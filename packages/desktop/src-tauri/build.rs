@@ -1,3 +1,101 @@
 fn main() {
-    tauri_build::build()
+    // OWASP A01:2025 – Broken Access Control: app commands get autogenerated
+    // `allow-*` permissions, so a window can only invoke the commands its
+    // capability grants (see capabilities/). Every new command must be listed here.
+    tauri_build::try_build(tauri_build::Attributes::new().app_manifest(
+        tauri_build::AppManifest::new().commands(&[
+            "execute_api_request",
+            "cancel_request",
+            "fetch_spec",
+            "fetch_spec_sftp",
+            "fetch_spec_object",
+            "fetch_spec_package",
+            "list_forge_repos",
+            "search_forge_specs",
+            "import_forge_spec",
+            "list_registry_subjects",
+            "list_registry_versions",
+            "get_registry_schema",
+            "generate_operation_snippet",
+            "list_body_examples",
+            "materialize_body_example",
+            "get_request_defaults",
+            "capture_response_example",
+            "edit_spec",
+            "rename_operation_id",
+            "rename_tag",
+            "list_spec_templates",
+            "new_spec",
+            "save_spec_template",
+            "get_lint_profile",
+            "set_lint_profile",
+            "record_lint_result",
+            "list_lint_results",
+            "find_route_conflicts",
+            "find_similar_schemas",
+            "list_cookies",
+            "set_cookie",
+            "delete_cookie",
+            "clear_cookies",
+            "set_cookie_persistence",
+            "dependency_graph",
+            "component_impact",
+            "export_dependency_graph",
+            "audit_sensitive_fields",
+            "personal_data_inventory",
+            "export_personal_data_inventory",
+            "list_extension_definitions",
+            "register_extension",
+            "unregister_extension",
+            "spec_extensions",
+            "filter_operations_by_extension",
+            "deprecation_report",
+            "find_deprecated_requests",
+            "list_observed_deprecations",
+            "forget_observed_deprecation",
+            "get_proxy_settings",
+            "set_proxy_settings",
+            "get_trusted_certificates",
+            "set_trusted_certificates",
+            "list_insecure_hosts",
+            "add_insecure_host",
+            "remove_insecure_host",
+            "get_environment_mapping",
+            "set_environment_mapping",
+            "rewrite_for_environment",
+            "list_certificate_pins",
+            "set_certificate_pins",
+            "fetch_environment_values",
+            "request_oauth_token",
+            "refresh_oauth_token",
+            "list_oauth_tokens",
+            "remove_oauth_token",
+            "list_inferred_schemas",
+            "schema_drift",
+            "clear_inferred_schemas",
+            "get_coverage",
+            "clear_coverage",
+            "execute_request_matrix",
+            "run_access_control_test",
+            "resolve_host",
+            "probe_connectivity",
+            "set_clipboard_watch",
+            "set_finder_items",
+            "fuzzy_find",
+            "bind_shortcut",
+            "unbind_shortcut",
+            "list_shortcuts",
+            "save_session",
+            "load_session",
+            "set_tray_pins",
+            "export_fixtures",
+            "execute_jsonrpc",
+            "build_odata_url",
+            "fetch_odata_metadata",
+            "get_http_client_defaults",
+            "set_http_client_defaults",
+            "close_splashscreen",
+        ]),
+    ))
+    .expect("failed to run tauri-build");
 }
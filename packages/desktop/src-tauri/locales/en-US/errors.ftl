@@ -0,0 +1,92 @@
+# Backend error catalog (en-US).
+#
+# Message ids are the stable error codes returned to the frontend in
+# `CommandError.code`. Translations live in sibling locale directories and must
+# keep the same ids and variables. Untranslatable library text (resolver and
+# TLS errors, parser messages) travels separately in `CommandError.detail`.
+
+## URL validation / SSRF protection
+
+invalid_url = Invalid URL
+disallowed_scheme = Disallowed URL scheme: '{ $scheme }'. Only http/https are permitted.
+missing_host = URL has no host
+invalid_hostname = Invalid hostname: '{ $host }'
+blocked_host = Blocked host: '{ $host }' is a cloud metadata endpoint.
+blocked_ip = Blocked IP: { $ip } is in private range { $range }. Direct access to internal networks is not permitted.
+blocked_port = Blocked port: { $port } is not allowed for outbound requests.
+
+## Request construction
+
+disallowed_method = Disallowed HTTP method: '{ $method }'
+window_not_allowed = This command is not available to the '{ $window }' window.
+invalid_header_name = Invalid header name: '{ $name }'
+invalid_header_value = Invalid header value for '{ $name }'
+empty_batch = A JSON-RPC batch needs at least one call.
+operation_not_found = Operation { $method } { $path } is not in the spec.
+component_not_found = No component '{ $component }' in the spec.
+invalid_spec_edit = Cannot edit '{ $pointer }' in the spec.
+invalid_name = '{ $name }' is not a valid name.
+name_in_use = '{ $name }' is already used in the spec.
+name_not_found = '{ $name }' is not used in the spec.
+template_not_found = No spec template named '{ $name }'.
+invalid_template = Spec templates must be OpenAPI or Swagger documents.
+body_conflict = A request can have only one of a body, a body file or a multipart form.
+file_unreadable = Cannot read file '{ $path }'.
+file_too_large = '{ $path }' exceeds the { $limit } upload limit.
+invalid_path = '{ $path }' is not a usable path.
+invalid_key_file = SSH key file not found: '{ $path }'
+invalid_shortcut = Invalid shortcut: '{ $shortcut }'
+invalid_manifest = '{ $path }' is not a valid JSON workspace manifest.
+unknown_ruleset = Unknown lint ruleset '{ $name }'.
+invalid_cookie = Invalid cookie '{ $name }'.
+example_not_found = No example '{ $name }' for { $content_type }.
+client_build_failed = Failed to build HTTP client
+invalid_tls_policy = Invalid TLS policy: minimum version { $min } is above maximum { $max }.
+invalid_setting = '{ $setting }' must be between { $min } and { $max }.
+tls_version_unsupported = TLS { $version } is not supported. Only TLS 1.2 and 1.3 are available.
+http3_unavailable = HTTP/3 needs a build with HTTP/3 support, an https:// URL and a TLS policy allowing TLS 1.3.
+invalid_proxy = Invalid proxy '{ $url }'. Use an http, https, socks5 or socks5h URL.
+invalid_client_identity = '{ $path }' is not a usable client certificate or key. Use PEM or PKCS#12, with the right passphrase.
+invalid_ca_certificate = The CA certificate is not a valid PEM certificate.
+invalid_host = '{ $host }' is not a host name or IP address. Wildcards and ports are not allowed.
+invalid_value_mapping = Cannot map '{ $value }': mapped values must be non-empty and distinct.
+invalid_certificate_pin = '{ $pin }' is not a base64 SHA-256 hash of a public key.
+invalid_dotenv_key = The DOTENV_KEY does not open this vault.
+vault_environment_not_found = The vault has no '{ $environment }' environment.
+oauth_token_not_found = No OAuth token for '{ $profile }'. Sign in first.
+oauth_token_expired = The OAuth token for '{ $profile }' has expired. Sign in again.
+invalid_signing_key = RSA-SHA1 signing needs a PEM RSA private key.
+workspace_spec_not_found = No spec '{ $spec_id }' in the workspace.
+
+## Backend environment
+
+storage_failed = Failed to access app storage
+tool_unavailable = '{ $tool }' is not available on this system.
+shortcut_unavailable = Shortcut '{ $shortcut }' could not be registered. It may be in use by another application.
+tray_unavailable = The system tray is not available.
+credentials_unavailable = No AWS credentials found for profile '{ $profile }'.
+file_write_failed = Cannot write to '{ $path }'.
+
+## Transport
+
+dns_failed = DNS lookup failed
+connect_failed = Connection failed
+tls_handshake_failed = TLS handshake failed
+proxy_auth_required = The proxy requires authentication. Add credentials to the proxy settings.
+proxy_auth_rejected = The proxy rejected the credentials in the proxy settings.
+timeout = Request timed out
+cancelled = Request cancelled
+request_failed = Request failed
+
+## Response handling
+
+body_read_failed = Failed to read body
+body_too_large = Response body exceeds { $limit } limit.
+http_status = Request failed: HTTP { $status }
+invalid_utf8 = Response content is not valid UTF-8.
+invalid_response = The server returned a response in an unexpected format.
+package_version_not_found = Package version '{ $version }' was not found.
+spec_not_found = No spec file matching '{ $path }' was found in the package.
+integrity_mismatch = The download does not match its published checksum.
+certificate_pin_mismatch = The server's certificate does not match the keys pinned for its host.
+oauth_failed = OAuth sign-in failed: { $error }
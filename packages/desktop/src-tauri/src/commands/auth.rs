@@ -0,0 +1,900 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Read;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use base64::Engine;
+use md5::{Digest, Md5};
+use ring::rand::{SecureRandom, SystemRandom};
+use ring::{digest, hmac};
+use rsa::pkcs1::DecodeRsaPrivateKey;
+use rsa::pkcs8::DecodePrivateKey;
+use rsa::{Pkcs1v15Sign, RsaPrivateKey};
+use serde::{Deserialize, Serialize};
+use sha1::Sha1;
+
+use super::aws::{self, AwsCredentials, SigningScope};
+use super::{validate_method, validate_url, ApiRequest, ApiResponse};
+use crate::error::{CommandError, ErrorCode};
+
+/// Every challenge is answered once, so the nonce is never reused.
+const NONCE_COUNT: &str = "00000001";
+
+/// Payload hash for multipart bodies, whose bytes are only known once sent.
+/// S3 accepts it; other AWS services reject the request.
+const UNSIGNED_PAYLOAD: &str = "UNSIGNED-PAYLOAD";
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+/// Credentials `execute_api_request` encodes itself, instead of a
+/// hand-crafted `Authorization` header.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "scheme", rename_all = "snake_case")]
+pub enum RequestAuth {
+    /// Sent upfront with the request.
+    Basic { username: String, password: String },
+    /// Sent in answer to the server's `401` challenge, which costs a round
+    /// trip (RFC 7616).
+    Digest { username: String, password: String },
+    /// Signed upfront, for APIs still on OAuth 1.0a (RFC 5849).
+    Oauth1(Oauth1Credentials),
+    /// Signed upfront with AWS Signature Version 4, for AWS APIs and
+    /// IAM-protected API Gateway endpoints.
+    AwsSigv4(AwsSigv4Credentials),
+}
+
+/// Consumer and token credentials for OAuth 1.0a signing. The token is
+/// left out for two-legged requests, and to obtain a request token.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Oauth1Credentials {
+    pub consumer_key: String,
+    #[serde(default)]
+    pub consumer_secret: String,
+    #[serde(default)]
+    pub token: Option<String>,
+    #[serde(default)]
+    pub token_secret: String,
+    #[serde(default)]
+    pub signature_method: SignatureMethod,
+    /// PEM private key (PKCS#8 or PKCS#1) for `RSA-SHA1`, which signs with
+    /// it instead of the secrets.
+    #[serde(default)]
+    pub private_key: Option<String>,
+    #[serde(default)]
+    pub realm: Option<String>,
+}
+
+/// AWS credentials and the scope requests are signed for.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AwsSigv4Credentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    /// For temporary credentials from STS.
+    #[serde(default)]
+    pub session_token: Option<String>,
+    pub region: String,
+    /// The signing name of the service, e.g. `execute-api` for API Gateway.
+    pub service: String,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SignatureMethod {
+    #[default]
+    #[serde(rename = "HMAC-SHA1")]
+    HmacSha1,
+    #[serde(rename = "RSA-SHA1")]
+    RsaSha1,
+    #[serde(rename = "PLAINTEXT")]
+    Plaintext,
+}
+
+impl SignatureMethod {
+    fn as_str(self) -> &'static str {
+        match self {
+            SignatureMethod::HmacSha1 => "HMAC-SHA1",
+            SignatureMethod::RsaSha1 => "RSA-SHA1",
+            SignatureMethod::Plaintext => "PLAINTEXT",
+        }
+    }
+}
+
+// Never print the secrets, even in debug logs.
+impl fmt::Debug for RequestAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (scheme, username) = match self {
+            RequestAuth::Basic { username, .. } => ("Basic", username),
+            RequestAuth::Digest { username, .. } => ("Digest", username),
+            RequestAuth::Oauth1(credentials) => {
+                return f
+                    .debug_struct("Oauth1")
+                    .field("consumer_key", &credentials.consumer_key)
+                    .field("token", &credentials.token)
+                    .field("signature_method", &credentials.signature_method)
+                    .finish_non_exhaustive();
+            }
+            RequestAuth::AwsSigv4(credentials) => {
+                return f
+                    .debug_struct("AwsSigv4")
+                    .field("access_key_id", &credentials.access_key_id)
+                    .field("region", &credentials.region)
+                    .field("service", &credentials.service)
+                    .finish_non_exhaustive();
+            }
+        };
+        f.debug_struct(scheme)
+            .field("username", username)
+            .field("password", &"***")
+            .finish()
+    }
+}
+
+/// The hash a `Digest` challenge asks for; `-sess` variants hash the
+/// credentials with the nonces once more.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Algorithm {
+    Md5,
+    Sha256,
+    Sha512_256,
+}
+
+impl Algorithm {
+    /// The algorithm and whether it is a session variant.
+    fn parse(name: &str) -> Option<(Algorithm, bool)> {
+        let upper = name.to_ascii_uppercase();
+        let (base, session) = match upper.strip_suffix("-SESS") {
+            Some(base) => (base, true),
+            None => (upper.as_str(), false),
+        };
+        let algorithm = match base {
+            "MD5" => Algorithm::Md5,
+            "SHA-256" => Algorithm::Sha256,
+            "SHA-512-256" => Algorithm::Sha512_256,
+            _ => return None,
+        };
+        Some((algorithm, session))
+    }
+
+    fn hash(self, data: &str) -> String {
+        let bytes = match self {
+            Algorithm::Md5 => Md5::digest(data.as_bytes()).to_vec(),
+            Algorithm::Sha256 => digest::digest(&digest::SHA256, data.as_bytes())
+                .as_ref()
+                .to_vec(),
+            Algorithm::Sha512_256 => digest::digest(&digest::SHA512_256, data.as_bytes())
+                .as_ref()
+                .to_vec(),
+        };
+        bytes.iter().map(|b| format!("{b:02x}")).collect()
+    }
+}
+
+// ─── Upfront ─────────────────────────────────────────────────────────────────
+
+fn has_authorization(headers: &HashMap<String, String>) -> bool {
+    headers
+        .keys()
+        .any(|name| name.eq_ignore_ascii_case("authorization"))
+}
+
+/// `request` with its `Basic` credentials, `OAuth` or SigV4 signature in
+/// the `Authorization` header. An `Authorization` header the request sets
+/// itself is kept.
+pub(super) fn apply_upfront(request: &ApiRequest) -> Result<ApiRequest, CommandError> {
+    let mut request = request.clone();
+    if has_authorization(&request.headers) {
+        return Ok(request);
+    }
+    let headers = match &request.auth {
+        Some(RequestAuth::Basic { username, password }) => {
+            let encoded =
+                base64::engine::general_purpose::STANDARD.encode(format!("{username}:{password}"));
+            vec![("Authorization".to_string(), format!("Basic {encoded}"))]
+        }
+        Some(RequestAuth::Oauth1(credentials)) => {
+            let method = validate_method(&request.method, request.extended_methods)?;
+            let authorization = oauth1_authorization(
+                credentials,
+                method.as_str(),
+                &validate_url(&request.url)?,
+                &form_params(&request),
+                &cnonce().ok_or_else(|| {
+                    CommandError::new(ErrorCode::RequestFailed)
+                        .detail("no randomness for the nonce")
+                })?,
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |elapsed| elapsed.as_secs()),
+            )?;
+            vec![("Authorization".to_string(), authorization)]
+        }
+        Some(RequestAuth::AwsSigv4(credentials)) => {
+            let method = validate_method(&request.method, request.extended_methods)?;
+            sigv4_headers(
+                credentials,
+                method.as_str(),
+                &validate_url(&request.url)?,
+                &payload_sha256(&request)?,
+                &aws::amz_date(SystemTime::now()),
+            )
+        }
+        None | Some(RequestAuth::Digest { .. }) => return Ok(request),
+    };
+    for (name, value) in headers {
+        request
+            .headers
+            .retain(|existing, _| !existing.eq_ignore_ascii_case(&name));
+        request.headers.insert(name, value);
+    }
+    Ok(request)
+}
+
+// ─── OAuth 1.0a ──────────────────────────────────────────────────────────────
+
+/// Parameters of a form-encoded body, which the signature covers; other
+/// bodies are not signed.
+fn form_params(request: &ApiRequest) -> Vec<(String, String)> {
+    let is_form = request.headers.iter().any(|(name, value)| {
+        name.eq_ignore_ascii_case("content-type")
+            && value
+                .trim_start()
+                .to_ascii_lowercase()
+                .starts_with("application/x-www-form-urlencoded")
+    });
+    match &request.body {
+        Some(body) if is_form && request.multipart.is_none() => {
+            url::form_urlencoded::parse(body.as_bytes())
+                .into_owned()
+                .collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// The signature base string of RFC 5849 §3.4.1: the method, the URL
+/// without its query, and every query, body and `oauth_` parameter sorted.
+fn signature_base_string(
+    method: &str,
+    url: &url::Url,
+    params: impl IntoIterator<Item = (String, String)>,
+) -> String {
+    let port = url
+        .port()
+        .map(|port| format!(":{port}"))
+        .unwrap_or_default();
+    let base_uri = format!(
+        "{}://{}{port}{}",
+        url.scheme(),
+        url.host_str().unwrap_or_default(),
+        url.path()
+    );
+    let mut params: Vec<(String, String)> = params
+        .into_iter()
+        .chain(url.query_pairs().into_owned())
+        .map(|(name, value)| {
+            (
+                aws::uri_encode(&name, false),
+                aws::uri_encode(&value, false),
+            )
+        })
+        .collect();
+    params.sort();
+    let normalized = params
+        .iter()
+        .map(|(name, value)| format!("{name}={value}"))
+        .collect::<Vec<_>>()
+        .join("&");
+    format!(
+        "{}&{}&{}",
+        method.to_ascii_uppercase(),
+        aws::uri_encode(&base_uri, false),
+        aws::uri_encode(&normalized, false)
+    )
+}
+
+/// RSA-SHA1 signature of `base_string`. ring only signs RSA with SHA-256
+/// and up.
+fn rsa_sha1(private_key: Option<&str>, base_string: &str) -> Result<Vec<u8>, CommandError> {
+    let invalid = || CommandError::new(ErrorCode::InvalidSigningKey);
+    let pem = private_key.ok_or_else(invalid)?;
+    let key = RsaPrivateKey::from_pkcs8_pem(pem)
+        .or_else(|_| RsaPrivateKey::from_pkcs1_pem(pem))
+        .map_err(|_| invalid())?;
+    key.sign(
+        Pkcs1v15Sign::new::<Sha1>(),
+        &Sha1::digest(base_string.as_bytes()),
+    )
+    .map_err(|e| invalid().detail(e))
+}
+
+/// The `Authorization` value signing a request to `url` with `credentials`.
+fn oauth1_authorization(
+    credentials: &Oauth1Credentials,
+    method: &str,
+    url: &url::Url,
+    body_params: &[(String, String)],
+    nonce: &str,
+    timestamp: u64,
+) -> Result<String, CommandError> {
+    let mut params = vec![
+        ("oauth_consumer_key", credentials.consumer_key.clone()),
+        ("oauth_nonce", nonce.to_string()),
+        (
+            "oauth_signature_method",
+            credentials.signature_method.as_str().to_string(),
+        ),
+        ("oauth_timestamp", timestamp.to_string()),
+        ("oauth_version", "1.0".to_string()),
+    ];
+    if let Some(token) = &credentials.token {
+        params.push(("oauth_token", token.clone()));
+    }
+
+    let key = format!(
+        "{}&{}",
+        aws::uri_encode(&credentials.consumer_secret, false),
+        aws::uri_encode(&credentials.token_secret, false)
+    );
+    let engine = base64::engine::general_purpose::STANDARD;
+    let signature = match credentials.signature_method {
+        SignatureMethod::Plaintext => key,
+        method_name => {
+            let base_string = signature_base_string(
+                method,
+                url,
+                params
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.clone()))
+                    .chain(body_params.iter().cloned()),
+            );
+            if method_name == SignatureMethod::RsaSha1 {
+                engine.encode(rsa_sha1(credentials.private_key.as_deref(), &base_string)?)
+            } else {
+                let key = hmac::Key::new(hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY, key.as_bytes());
+                engine.encode(hmac::sign(&key, base_string.as_bytes()))
+            }
+        }
+    };
+    params.push(("oauth_signature", signature));
+    params.sort();
+
+    let mut fields: Vec<String> = credentials
+        .realm
+        .iter()
+        .map(|realm| format!(r#"realm="{}""#, quote(realm)))
+        .collect();
+    fields.extend(
+        params
+            .iter()
+            .map(|(name, value)| format!(r#"{name}="{}""#, aws::uri_encode(value, false))),
+    );
+    Ok(format!("OAuth {}", fields.join(", ")))
+}
+
+// ─── Signature Version 4 ─────────────────────────────────────────────────────
+
+/// SHA-256 of the body as SigV4 signs it. A body file is read once here to
+/// hash it, then again as it is sent.
+fn payload_sha256(request: &ApiRequest) -> Result<String, CommandError> {
+    if request.multipart.is_some() {
+        return Ok(UNSIGNED_PAYLOAD.to_string());
+    }
+    let Some(path) = &request.body_file else {
+        return Ok(aws::sha256_hex(
+            request.body.as_deref().unwrap_or_default().as_bytes(),
+        ));
+    };
+    let unreadable = || CommandError::new(ErrorCode::FileUnreadable).arg("path", path);
+    let mut file = std::fs::File::open(path).map_err(|_| unreadable())?;
+    let mut context = digest::Context::new(&digest::SHA256);
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        match file.read(&mut buf).map_err(|_| unreadable())? {
+            0 => break,
+            n => context.update(&buf[..n]),
+        }
+    }
+    Ok(context
+        .finish()
+        .as_ref()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect())
+}
+
+/// The headers signing a request with `credentials`, `Authorization`
+/// included.
+fn sigv4_headers(
+    credentials: &AwsSigv4Credentials,
+    method: &str,
+    url: &url::Url,
+    payload_sha256: &str,
+    amz_date: &str,
+) -> Vec<(String, String)> {
+    let host = match url.port() {
+        Some(port) => format!("{}:{port}", url.host_str().unwrap_or_default()),
+        None => url.host_str().unwrap_or_default().to_string(),
+    };
+    let mut signed = vec![
+        ("host".to_string(), host),
+        ("x-amz-date".to_string(), amz_date.to_string()),
+    ];
+    if let Some(token) = &credentials.session_token {
+        signed.push(("x-amz-security-token".to_string(), token.clone()));
+    }
+    // S3 wants the payload hash as a header as well
+    if credentials.service == "s3" {
+        signed.push((
+            "x-amz-content-sha256".to_string(),
+            payload_sha256.to_string(),
+        ));
+    }
+    let scope = SigningScope {
+        region: &credentials.region,
+        service: &credentials.service,
+        amz_date,
+    };
+    let authorization = aws::authorization(
+        &AwsCredentials {
+            access_key_id: credentials.access_key_id.clone(),
+            secret_access_key: credentials.secret_access_key.clone(),
+            session_token: credentials.session_token.clone(),
+        },
+        &scope,
+        method,
+        url,
+        &signed,
+        payload_sha256,
+    );
+
+    // reqwest sets Host from the URL
+    signed.retain(|(name, _)| name != "host");
+    signed.push(("authorization".to_string(), authorization));
+    signed
+}
+
+// ─── Digest ──────────────────────────────────────────────────────────────────
+
+/// The challenges of a `WWW-Authenticate` value, as lower-case schemes
+/// with their auth-params by lower-case name.
+fn challenges(value: &str) -> Vec<(String, HashMap<String, String>)> {
+    let mut challenges: Vec<(String, HashMap<String, String>)> = Vec::new();
+    let mut rest = value.trim_start();
+    while !rest.is_empty() {
+        let end = rest
+            .find(|c: char| c == '=' || c == ',' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        let token = &rest[..end];
+        rest = rest[end..].trim_start();
+        if let Some(after) = rest.strip_prefix('=') {
+            let after = after.trim_start();
+            let (value, remaining) = match after.strip_prefix('"') {
+                Some(quoted) => unquote(quoted),
+                None => {
+                    let end = after.find(',').unwrap_or(after.len());
+                    (after[..end].trim().to_string(), &after[end..])
+                }
+            };
+            if let Some((_, params)) = challenges.last_mut() {
+                params.insert(token.to_ascii_lowercase(), value);
+            }
+            rest = remaining;
+        } else if !token.is_empty() {
+            challenges.push((token.to_ascii_lowercase(), HashMap::new()));
+        }
+        rest = rest.trim_start_matches(|c: char| c == ',' || c.is_whitespace());
+    }
+    challenges
+}
+
+/// A quoted-string after its opening quote, and what follows it.
+fn unquote(input: &str) -> (String, &str) {
+    let mut value = String::new();
+    let mut chars = input.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => value.extend(chars.next().map(|(_, escaped)| escaped)),
+            '"' => return (value, &input[i + 1..]),
+            _ => value.push(c),
+        }
+    }
+    (value, "")
+}
+
+fn quote(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn cnonce() -> Option<String> {
+    let mut bytes = [0u8; 16];
+    SystemRandom::new().fill(&mut bytes).ok()?;
+    Some(bytes.iter().map(|b| format!("{b:02x}")).collect())
+}
+
+/// The `Authorization` value answering `challenge` for `method` on `uri`;
+/// none for algorithms it does not know, or when the server only takes
+/// `auth-int` protection.
+fn digest_authorization(
+    username: &str,
+    password: &str,
+    method: &str,
+    uri: &str,
+    challenge: &HashMap<String, String>,
+    cnonce: &str,
+) -> Option<String> {
+    let realm = challenge.get("realm").map_or("", String::as_str);
+    let nonce = challenge.get("nonce")?;
+    let algorithm_name = challenge.get("algorithm").map_or("MD5", String::as_str);
+    let (algorithm, session) = Algorithm::parse(algorithm_name)?;
+    // Without a qop the server speaks RFC 2069
+    let qop = match challenge.get("qop") {
+        Some(offered) => Some(
+            offered
+                .split(',')
+                .map(str::trim)
+                .find(|qop| qop.eq_ignore_ascii_case("auth"))?,
+        ),
+        None => None,
+    };
+
+    let mut ha1 = algorithm.hash(&format!("{username}:{realm}:{password}"));
+    if session {
+        ha1 = algorithm.hash(&format!("{ha1}:{nonce}:{cnonce}"));
+    }
+    let ha2 = algorithm.hash(&format!("{method}:{uri}"));
+    let response = match qop {
+        Some(qop) => algorithm.hash(&format!("{ha1}:{nonce}:{NONCE_COUNT}:{cnonce}:{qop}:{ha2}")),
+        None => algorithm.hash(&format!("{ha1}:{nonce}:{ha2}")),
+    };
+
+    let mut authorization = format!(
+        r#"Digest username="{}", realm="{}", nonce="{}", uri="{}", algorithm={algorithm_name}, response="{response}""#,
+        quote(username),
+        quote(realm),
+        quote(nonce),
+        quote(uri),
+    );
+    if let Some(qop) = qop {
+        authorization.push_str(&format!(
+            r#", qop={qop}, nc={NONCE_COUNT}, cnonce="{cnonce}""#
+        ));
+    }
+    if let Some(opaque) = challenge.get("opaque") {
+        authorization.push_str(&format!(r#", opaque="{}""#, quote(opaque)));
+    }
+    Some(authorization)
+}
+
+/// `request` again with its `Digest` credentials answering the `401`
+/// challenge of `response`; none when there is nothing to answer. A
+/// challenge from a redirect target is not answered, so credentials never
+/// follow a redirect.
+pub(super) fn answer_challenge(request: &ApiRequest, response: &ApiResponse) -> Option<ApiRequest> {
+    let Some(RequestAuth::Digest { username, password }) = &request.auth else {
+        return None;
+    };
+    if response.status != 401
+        || !response.redirects.is_empty()
+        || has_authorization(&request.headers)
+    {
+        return None;
+    }
+    let (_, challenge) = challenges(response.headers.get("www-authenticate")?)
+        .into_iter()
+        .find(|(scheme, _)| scheme == "digest")?;
+    let url = url::Url::parse(&request.url).ok()?;
+    let method = validate_method(&request.method, request.extended_methods).ok()?;
+    let authorization = digest_authorization(
+        username,
+        password,
+        method.as_str(),
+        &url[url::Position::BeforePath..url::Position::AfterQuery],
+        &challenge,
+        &cnonce()?,
+    )?;
+
+    let mut headers = request.headers.clone();
+    headers.insert("Authorization".to_string(), authorization);
+    Some(ApiRequest {
+        headers,
+        ..request.clone()
+    })
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHALLENGE: &str = r#"Digest realm="http-auth@example.org", qop="auth, auth-int", algorithm=SHA-256, nonce="7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v", opaque="FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS""#;
+
+    fn digest_auth(username: &str) -> Option<RequestAuth> {
+        Some(RequestAuth::Digest {
+            username: username.to_string(),
+            password: "Circle of Life".to_string(),
+        })
+    }
+
+    #[test]
+    fn test_basic_credentials_are_encoded_upfront() {
+        let request = ApiRequest {
+            auth: Some(RequestAuth::Basic {
+                username: "alice".to_string(),
+                password: "hunter2".to_string(),
+            }),
+            ..Default::default()
+        };
+        assert_eq!(
+            apply_upfront(&request).unwrap().headers["Authorization"],
+            "Basic YWxpY2U6aHVudGVyMg=="
+        );
+
+        let mut explicit = request.clone();
+        explicit
+            .headers
+            .insert("authorization".to_string(), "Bearer t".to_string());
+        assert_eq!(apply_upfront(&explicit).unwrap().headers, explicit.headers);
+        assert!(!format!("{:?}", request.auth).contains("hunter2"));
+    }
+
+    fn oauth1_credentials(signature_method: SignatureMethod) -> Oauth1Credentials {
+        Oauth1Credentials {
+            consumer_key: "dpf43f3p2l4k3l03".to_string(),
+            consumer_secret: "kd94hf93k423kf44".to_string(),
+            token: Some("nnch734d00sl2jdk".to_string()),
+            token_secret: "pfkkdhi9sl3r4s00".to_string(),
+            signature_method,
+            private_key: None,
+            realm: Some("Photos".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_signature_base_string_matches_rfc_5849_example() {
+        let url =
+            url::Url::parse("http://example.com/request?b5=%3D%253D&a3=a&c%40=&a2=r%20b").unwrap();
+        let params = [
+            ("oauth_consumer_key", "9djdj82h48djs9d2"),
+            ("oauth_token", "kkk9d7dh3k39sjv7"),
+            ("oauth_signature_method", "HMAC-SHA1"),
+            ("oauth_timestamp", "137131201"),
+            ("oauth_nonce", "7d8f3e4a"),
+        ]
+        .map(|(name, value)| (name.to_string(), value.to_string()));
+        let request = ApiRequest {
+            headers: HashMap::from([(
+                "Content-Type".to_string(),
+                "application/x-www-form-urlencoded".to_string(),
+            )]),
+            body: Some("c2&a3=2+q".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            signature_base_string(
+                "post",
+                &url,
+                params.into_iter().chain(form_params(&request))
+            ),
+            "POST&http%3A%2F%2Fexample.com%2Frequest&a2%3Dr%2520b%26a3%3D2%2520q\
+             %26a3%3Da%26b5%3D%253D%25253D%26c%2540%3D%26c2%3D%26oauth_consumer_\
+             key%3D9djdj82h48djs9d2%26oauth_nonce%3D7d8f3e4a%26oauth_signature_m\
+             ethod%3DHMAC-SHA1%26oauth_timestamp%3D137131201%26oauth_token%3Dkkk\
+             9d7dh3k39sjv7"
+        );
+    }
+
+    #[test]
+    fn test_oauth1_requests_are_signed() {
+        let url =
+            url::Url::parse("http://photos.example.net/photos?file=vacation.jpg&size=original")
+                .unwrap();
+        let sign = |credentials: &Oauth1Credentials| {
+            oauth1_authorization(
+                credentials,
+                "GET",
+                &url,
+                &[],
+                "kllo9940pd9333jh",
+                1191242096,
+            )
+        };
+        assert_eq!(
+            sign(&oauth1_credentials(SignatureMethod::HmacSha1)).unwrap(),
+            "OAuth realm=\"Photos\", oauth_consumer_key=\"dpf43f3p2l4k3l03\", \
+             oauth_nonce=\"kllo9940pd9333jh\", \
+             oauth_signature=\"tR3%2BTy81lMeYAr%2FFid0kMTYa%2FWM%3D\", \
+             oauth_signature_method=\"HMAC-SHA1\", oauth_timestamp=\"1191242096\", \
+             oauth_token=\"nnch734d00sl2jdk\", oauth_version=\"1.0\""
+        );
+        assert!(sign(&oauth1_credentials(SignatureMethod::Plaintext))
+            .unwrap()
+            .contains("oauth_signature=\"kd94hf93k423kf44%26pfkkdhi9sl3r4s00\""));
+        assert_eq!(
+            sign(&oauth1_credentials(SignatureMethod::RsaSha1))
+                .unwrap_err()
+                .code,
+            ErrorCode::InvalidSigningKey
+        );
+
+        let request = ApiRequest {
+            method: "GET".to_string(),
+            url: url.to_string(),
+            auth: Some(RequestAuth::Oauth1(oauth1_credentials(
+                SignatureMethod::HmacSha1,
+            ))),
+            ..Default::default()
+        };
+        let signed = apply_upfront(&request).unwrap();
+        assert!(signed.headers["Authorization"].starts_with("OAuth realm=\"Photos\""));
+        assert!(!format!("{:?}", request.auth).contains("kd94hf93k423kf44"));
+    }
+
+    #[test]
+    fn test_sigv4_matches_aws_test_suite() {
+        let mut credentials = AwsSigv4Credentials {
+            access_key_id: "AKIDEXAMPLE".to_string(),
+            secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".to_string(),
+            session_token: None,
+            region: "us-east-1".to_string(),
+            service: "service".to_string(),
+        };
+        let sign = |credentials: &AwsSigv4Credentials, url: &str| {
+            sigv4_headers(
+                credentials,
+                "GET",
+                &url::Url::parse(url).unwrap(),
+                aws::EMPTY_PAYLOAD_SHA256,
+                "20150830T123600Z",
+            )
+        };
+
+        // get-vanilla
+        assert_eq!(
+            sign(&credentials, "https://example.amazonaws.com/"),
+            vec![
+                ("x-amz-date".to_string(), "20150830T123600Z".to_string()),
+                (
+                    "authorization".to_string(),
+                    "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, \
+                     SignedHeaders=host;x-amz-date, \
+                     Signature=5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31"
+                        .to_string()
+                ),
+            ]
+        );
+        // get-vanilla-query-order-key-case
+        assert!(sign(
+            &credentials,
+            "https://example.amazonaws.com/?Param2=value2&Param1=value1"
+        )[1]
+        .1
+        .ends_with("Signature=b97d918cfa904a5beff61c982a1b6f458b799221646efd99d3219ec94cdf2500"));
+
+        credentials.session_token = Some("session".to_string());
+        credentials.service = "s3".to_string();
+        let headers = sign(&credentials, "https://bucket.s3.amazonaws.com/key");
+        let names: Vec<&str> = headers.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "x-amz-date",
+                "x-amz-security-token",
+                "x-amz-content-sha256",
+                "authorization"
+            ]
+        );
+        assert!(headers[3]
+            .1
+            .contains("SignedHeaders=host;x-amz-content-sha256;x-amz-date;x-amz-security-token,"));
+    }
+
+    #[test]
+    fn test_sigv4_hashes_the_body() {
+        let request = ApiRequest {
+            body: Some("Action=ListUsers&Version=2010-05-08".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            payload_sha256(&request).unwrap(),
+            aws::sha256_hex(b"Action=ListUsers&Version=2010-05-08")
+        );
+        assert_eq!(
+            payload_sha256(&ApiRequest::default()).unwrap(),
+            aws::EMPTY_PAYLOAD_SHA256
+        );
+        let missing = ApiRequest {
+            body_file: Some("/nonexistent/yasp-body".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            payload_sha256(&missing).unwrap_err().code,
+            ErrorCode::FileUnreadable
+        );
+    }
+
+    #[test]
+    fn test_challenges_are_parsed() {
+        let parsed = challenges(&format!(r#"Basic realm="a\"b", {CHALLENGE}"#));
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].0, "basic");
+        assert_eq!(parsed[0].1["realm"], "a\"b");
+        assert_eq!(parsed[1].0, "digest");
+        assert_eq!(parsed[1].1["qop"], "auth, auth-int");
+        assert_eq!(parsed[1].1["algorithm"], "SHA-256");
+    }
+
+    #[test]
+    fn test_digest_matches_rfc_7616_examples() {
+        let cnonce = "f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ";
+        let mut challenge = challenges(CHALLENGE).remove(0).1;
+        let answer = |challenge: &HashMap<String, String>| {
+            digest_authorization(
+                "Mufasa",
+                "Circle of Life",
+                "GET",
+                "/dir/index.html",
+                challenge,
+                cnonce,
+            )
+            .unwrap()
+        };
+        assert_eq!(
+            answer(&challenge),
+            "Digest username=\"Mufasa\", realm=\"http-auth@example.org\", \
+             nonce=\"7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v\", uri=\"/dir/index.html\", \
+             algorithm=SHA-256, response=\"753927fa0e85d155564e2e272a28d1802ca10daf4496794697cf8db5856cb6c1\", \
+             qop=auth, nc=00000001, cnonce=\"f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ\", \
+             opaque=\"FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS\""
+        );
+
+        challenge.insert("algorithm".to_string(), "MD5".to_string());
+        assert!(answer(&challenge).contains("response=\"8ca523f5e9506fed4657c9700eebdbec\""));
+
+        challenge.insert("qop".to_string(), "auth-int".to_string());
+        assert_eq!(
+            digest_authorization("Mufasa", "", "GET", "/", &challenge, cnonce),
+            None
+        );
+    }
+
+    #[test]
+    fn test_only_direct_digest_challenges_are_answered() {
+        let request = ApiRequest {
+            method: "get".to_string(),
+            url: "https://api.example.com/dir/index.html?page=2".to_string(),
+            auth: digest_auth("Mufasa"),
+            ..Default::default()
+        };
+        let mut response = ApiResponse {
+            status: 401,
+            status_text: "Unauthorized".to_string(),
+            headers: HashMap::from([("www-authenticate".to_string(), CHALLENGE.to_string())]),
+            body: String::new(),
+            body_encoding: Default::default(),
+            body_path: None,
+            content_type: None,
+            duration_ms: 0,
+            http_version: Default::default(),
+            timing: Default::default(),
+            redirects: Vec::new(),
+            tls: None,
+            tls_unverified_hosts: Vec::new(),
+            certificates: Vec::new(),
+        };
+
+        let answered = answer_challenge(&request, &response).unwrap();
+        let authorization = &answered.headers["Authorization"];
+        assert!(authorization.starts_with("Digest username=\"Mufasa\""));
+        assert!(authorization.contains("uri=\"/dir/index.html?page=2\""));
+        assert!(answer_challenge(&answered, &response).is_none());
+
+        response.status = 200;
+        assert!(answer_challenge(&request, &response).is_none());
+        response.status = 401;
+        response.headers.insert(
+            "www-authenticate".to_string(),
+            "Basic realm=\"api\"".to_string(),
+        );
+        assert!(answer_challenge(&request, &response).is_none());
+    }
+}
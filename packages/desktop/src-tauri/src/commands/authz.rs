@@ -0,0 +1,126 @@
+use serde::{Deserialize, Serialize};
+
+use super::http::HttpClients;
+use super::matrix::{run_as, Identity, MatrixResult};
+use super::{ensure_main_window, ApiRequest};
+use crate::error::CommandError;
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AuthzOperation {
+    /// Shown in the report, e.g. `DELETE /orders/{id}` or an operationId.
+    pub label: String,
+    pub request: ApiRequest,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthzVerdict {
+    /// The restricted identity was refused (401, 403, or 404 for APIs that
+    /// hide resources they won't authorize).
+    Enforced,
+    /// The restricted identity succeeded where only the privileged one
+    /// should — a likely broken access control finding.
+    Allowed,
+    /// No conclusion: the privileged baseline did not succeed, a request
+    /// failed, or the restricted identity got some other status.
+    Inconclusive,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AuthzResult {
+    pub label: String,
+    pub verdict: AuthzVerdict,
+    pub privileged: MatrixResult,
+    pub restricted: MatrixResult,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AuthzReport {
+    pub results: Vec<AuthzResult>,
+    /// Labels of operations with an `Allowed` verdict.
+    pub findings: Vec<String>,
+}
+
+// ─── Verdicts ─────────────────────────────────────────────────────────────────
+
+fn verdict(privileged: Option<u16>, restricted: Option<u16>) -> AuthzVerdict {
+    let baseline_ok = privileged.is_some_and(|status| (200..300).contains(&status));
+    match restricted {
+        _ if !baseline_ok => AuthzVerdict::Inconclusive,
+        Some(401 | 403 | 404) => AuthzVerdict::Enforced,
+        Some(status) if (200..300).contains(&status) => AuthzVerdict::Allowed,
+        _ => AuthzVerdict::Inconclusive,
+    }
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// Replay each operation under a privileged and a restricted identity and
+/// report operations the restricted identity can unexpectedly perform.
+///
+/// The privileged run establishes that the operation works at all and runs
+/// first. Operations are replayed for real, so state-changing requests
+/// (POST/PUT/DELETE) should target disposable data.
+#[tauri::command]
+pub async fn run_access_control_test(
+    webview: tauri::Webview,
+    clients: tauri::State<'_, HttpClients>,
+    privileged: Identity,
+    restricted: Identity,
+    operations: Vec<AuthzOperation>,
+) -> Result<AuthzReport, CommandError> {
+    ensure_main_window(webview.label())?;
+
+    let mut results = Vec::with_capacity(operations.len());
+    for operation in operations {
+        let privileged_result = run_as(&clients, &operation.request, &privileged).await;
+        let restricted_result = run_as(&clients, &operation.request, &restricted).await;
+        results.push(AuthzResult {
+            verdict: verdict(privileged_result.status, restricted_result.status),
+            label: operation.label,
+            privileged: privileged_result,
+            restricted: restricted_result,
+        });
+    }
+
+    let findings = results
+        .iter()
+        .filter(|result| result.verdict == AuthzVerdict::Allowed)
+        .map(|result| result.label.clone())
+        .collect();
+    Ok(AuthzReport { results, findings })
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verdict_flags_restricted_success() {
+        assert_eq!(verdict(Some(200), Some(200)), AuthzVerdict::Allowed);
+        assert_eq!(verdict(Some(201), Some(204)), AuthzVerdict::Allowed);
+    }
+
+    #[test]
+    fn test_verdict_accepts_refusals() {
+        assert_eq!(verdict(Some(200), Some(401)), AuthzVerdict::Enforced);
+        assert_eq!(verdict(Some(200), Some(403)), AuthzVerdict::Enforced);
+        assert_eq!(verdict(Some(200), Some(404)), AuthzVerdict::Enforced);
+    }
+
+    #[test]
+    fn test_verdict_needs_a_working_baseline() {
+        assert_eq!(verdict(Some(500), Some(200)), AuthzVerdict::Inconclusive);
+        assert_eq!(verdict(None, Some(200)), AuthzVerdict::Inconclusive);
+    }
+
+    #[test]
+    fn test_verdict_is_inconclusive_for_other_outcomes() {
+        assert_eq!(verdict(Some(200), Some(500)), AuthzVerdict::Inconclusive);
+        assert_eq!(verdict(Some(200), None), AuthzVerdict::Inconclusive);
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+
+use super::{ensure_main_window, MAIN_WINDOW};
+use crate::error::{CommandError, ErrorCode};
+
+/// Emitted to the main window with an `ActionInvocation` when a bound
+/// shortcut fires. The frontend owns saved requests, environments and specs,
+/// so it performs the action.
+pub const ACTION_EVENT: &str = "automation://action";
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutomationAction {
+    RunRequest,
+    SwitchEnvironment,
+    OpenSpec,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionInvocation {
+    pub action: AutomationAction,
+    /// Id of the saved request, environment or spec the action applies to.
+    pub target: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShortcutBinding {
+    /// Accelerator as entered, e.g. `CmdOrCtrl+Shift+R`.
+    pub accelerator: String,
+    #[serde(flatten)]
+    pub invocation: ActionInvocation,
+}
+
+/// Global shortcuts currently bound to actions.
+#[derive(Default)]
+pub struct Automation(Mutex<HashMap<Shortcut, ShortcutBinding>>);
+
+// ─── Helpers ─────────────────────────────────────────────────────────────────
+
+fn parse_shortcut(accelerator: &str) -> Result<Shortcut, CommandError> {
+    accelerator.parse().map_err(|e| {
+        CommandError::new(ErrorCode::InvalidShortcut)
+            .arg("shortcut", accelerator)
+            .detail(e)
+    })
+}
+
+fn dispatch(app: &AppHandle, shortcut: &Shortcut) {
+    let automation = app.state::<Automation>();
+    let bindings = automation.0.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(binding) = bindings.get(shortcut) {
+        let _ = app.emit_to(MAIN_WINDOW, ACTION_EVENT, binding.invocation.clone());
+    }
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// Bind a system-wide shortcut to an action, replacing any action already
+/// bound to the same shortcut.
+#[tauri::command]
+pub fn bind_shortcut(
+    app: AppHandle,
+    webview: tauri::Webview,
+    automation: State<'_, Automation>,
+    binding: ShortcutBinding,
+) -> Result<(), CommandError> {
+    ensure_main_window(webview.label())?;
+
+    let shortcut = parse_shortcut(&binding.accelerator)?;
+    let mut bindings = automation.0.lock().unwrap_or_else(|e| e.into_inner());
+    if !bindings.contains_key(&shortcut) {
+        app.global_shortcut()
+            .on_shortcut(shortcut, |app, shortcut, event| {
+                if event.state == ShortcutState::Pressed {
+                    dispatch(app, shortcut);
+                }
+            })
+            .map_err(|e| {
+                CommandError::new(ErrorCode::ShortcutUnavailable)
+                    .arg("shortcut", binding.accelerator.as_str())
+                    .detail(e)
+            })?;
+    }
+    bindings.insert(shortcut, binding);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn unbind_shortcut(
+    app: AppHandle,
+    webview: tauri::Webview,
+    automation: State<'_, Automation>,
+    accelerator: String,
+) -> Result<(), CommandError> {
+    ensure_main_window(webview.label())?;
+
+    let shortcut = parse_shortcut(&accelerator)?;
+    let mut bindings = automation.0.lock().unwrap_or_else(|e| e.into_inner());
+    if bindings.remove(&shortcut).is_some() {
+        app.global_shortcut().unregister(shortcut).map_err(|e| {
+            CommandError::new(ErrorCode::ShortcutUnavailable)
+                .arg("shortcut", accelerator.as_str())
+                .detail(e)
+        })?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_shortcuts(
+    webview: tauri::Webview,
+    automation: State<'_, Automation>,
+) -> Result<Vec<ShortcutBinding>, CommandError> {
+    ensure_main_window(webview.label())?;
+
+    let bindings = automation.0.lock().unwrap_or_else(|e| e.into_inner());
+    let mut list: Vec<ShortcutBinding> = bindings.values().cloned().collect();
+    list.sort_by(|a, b| a.accelerator.cmp(&b.accelerator));
+    Ok(list)
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binding_deserializes_flat() {
+        let binding: ShortcutBinding = serde_json::from_str(
+            r#"{"accelerator":"CmdOrCtrl+Shift+R","action":"run_request","target":"req-1"}"#,
+        )
+        .unwrap();
+        assert_eq!(binding.invocation.action, AutomationAction::RunRequest);
+        assert_eq!(binding.invocation.target, "req-1");
+    }
+
+    #[test]
+    fn test_invocation_serializes_snake_case() {
+        let invocation = ActionInvocation {
+            action: AutomationAction::SwitchEnvironment,
+            target: "staging".to_string(),
+        };
+        assert_eq!(
+            serde_json::to_value(&invocation).unwrap(),
+            serde_json::json!({"action": "switch_environment", "target": "staging"})
+        );
+    }
+}
@@ -0,0 +1,396 @@
+use std::fmt::Display;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tokio::io::AsyncWriteExt;
+
+use crate::error::{CommandError, ErrorCode, ErrorPhase};
+
+/// Binary bodies up to this size are returned inline as base64; larger ones
+/// are written to a file so they don't cross the IPC bridge.
+const INLINE_BINARY_LIMIT: usize = 1024 * 1024;
+
+const RESPONSES_DIR: &str = "responses";
+
+/// Directory in the app cache for large binary bodies, set at startup. Until
+/// then, and in the stdio interface, they are returned inline.
+static RESPONSES: OnceLock<PathBuf> = OnceLock::new();
+
+static NEXT_FILE: AtomicU64 = AtomicU64::new(0);
+
+/// Types that are binary even when the bytes happen to be valid UTF-8.
+const BINARY_TYPES: &[&str] = &[
+    "application/octet-stream",
+    "application/pdf",
+    "application/zip",
+    "application/gzip",
+    "application/protobuf",
+    "application/x-protobuf",
+    "application/grpc",
+    "application/msgpack",
+    "application/wasm",
+];
+
+/// Magic numbers for common binary formats, used when the server sends no
+/// `Content-Type`.
+const SIGNATURES: &[(&[u8], &str)] = &[
+    (b"\x89PNG\r\n\x1a\n", "image/png"),
+    (b"\xff\xd8\xff", "image/jpeg"),
+    (b"GIF8", "image/gif"),
+    (b"%PDF-", "application/pdf"),
+    (b"PK\x03\x04", "application/zip"),
+    (b"\x1f\x8b", "application/gzip"),
+    (b"\0asm", "application/wasm"),
+];
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+/// How `ApiResponse::body` holds the response body.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BodyEncoding {
+    /// Text, with invalid UTF-8 replaced.
+    #[default]
+    Text,
+    /// Binary, base64-encoded.
+    Base64,
+    /// Binary and too large to inline, or downloaded with
+    /// `ApiRequest::download_to`: `body` is empty and the bytes are in the
+    /// file at `ApiResponse::body_path`.
+    File,
+}
+
+pub(crate) struct DecodedBody {
+    pub body: String,
+    pub encoding: BodyEncoding,
+    pub path: Option<String>,
+    pub content_type: Option<String>,
+}
+
+// ─── Detection ───────────────────────────────────────────────────────────────
+
+fn essence(content_type: &str) -> String {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+fn is_textual(essence: &str) -> bool {
+    essence.starts_with("text/")
+        || essence.ends_with("+json")
+        || essence.ends_with("+xml")
+        || [
+            "application/json",
+            "application/xml",
+            "application/javascript",
+            "application/x-www-form-urlencoded",
+            "application/yaml",
+            "application/graphql",
+        ]
+        .contains(&essence)
+}
+
+fn is_binary_type(essence: &str) -> bool {
+    ["image/", "audio/", "video/", "font/"]
+        .iter()
+        .any(|prefix| essence.starts_with(prefix))
+        || BINARY_TYPES.contains(&essence)
+}
+
+fn sniff(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        return Some("image/webp");
+    }
+    SIGNATURES
+        .iter()
+        .find(|(magic, _)| bytes.starts_with(magic))
+        .map(|(_, content_type)| *content_type)
+}
+
+fn extension(essence: &str) -> &'static str {
+    match essence {
+        "image/png" => "png",
+        "image/jpeg" => "jpg",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        "application/pdf" => "pdf",
+        "application/zip" => "zip",
+        "application/gzip" => "gz",
+        "application/wasm" => "wasm",
+        _ => "bin",
+    }
+}
+
+// ─── Decoding ────────────────────────────────────────────────────────────────
+
+/// Startup task: remove the bodies written by the previous run and write
+/// large binary bodies to the responses directory from now on.
+pub(crate) fn init(app: &AppHandle) -> Result<(), CommandError> {
+    let dir = app
+        .path()
+        .app_cache_dir()
+        .map_err(|e| CommandError::new(ErrorCode::StorageFailed).detail(e))?
+        .join(RESPONSES_DIR);
+    clear_dir(&dir)?;
+    let _ = RESPONSES.set(dir);
+    Ok(())
+}
+
+fn clear_dir(dir: &Path) -> Result<(), CommandError> {
+    match std::fs::remove_dir_all(dir) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+            Err(CommandError::new(ErrorCode::StorageFailed).detail(e))
+        }
+        _ => std::fs::create_dir_all(dir)
+            .map_err(|e| CommandError::new(ErrorCode::StorageFailed).detail(e)),
+    }
+}
+
+/// Write `bytes` to a new file in `dir`. Existing files are never opened,
+/// let alone overwritten.
+fn spill(dir: &Path, bytes: &[u8], essence: &str) -> Result<PathBuf, CommandError> {
+    loop {
+        let n = NEXT_FILE.fetch_add(1, Ordering::Relaxed);
+        let path = dir.join(format!("{n}.{}", extension(essence)));
+        match std::fs::File::options()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+        {
+            Ok(mut file) => {
+                return file
+                    .write_all(bytes)
+                    .map(|_| path)
+                    .map_err(|e| CommandError::new(ErrorCode::StorageFailed).detail(e))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(CommandError::new(ErrorCode::StorageFailed).detail(e)),
+        }
+    }
+}
+
+/// Decode a response body for the frontend. Textual types stay text;
+/// binary types, and anything that is not valid UTF-8, become base64 or a
+/// file in the responses directory. The content type is taken from the
+/// header, else sniffed.
+pub(crate) fn decode_body(
+    bytes: &[u8],
+    header_content_type: Option<&str>,
+) -> Result<DecodedBody, CommandError> {
+    let content_type = header_content_type
+        .map(str::to_string)
+        .or_else(|| sniff(bytes).map(str::to_string));
+    let essence = content_type.as_deref().map(essence).unwrap_or_default();
+
+    let binary =
+        !is_textual(&essence) && (is_binary_type(&essence) || std::str::from_utf8(bytes).is_err());
+    let spill_dir = RESPONSES
+        .get()
+        .filter(|_| bytes.len() > INLINE_BINARY_LIMIT);
+    let (body, encoding, path) = if !binary {
+        let text = String::from_utf8_lossy(bytes).into_owned();
+        (text, BodyEncoding::Text, None)
+    } else if let Some(dir) = spill_dir {
+        let path = spill(dir, bytes, &essence)?;
+        (
+            String::new(),
+            BodyEncoding::File,
+            Some(path.to_string_lossy().into_owned()),
+        )
+    } else {
+        let encoded = base64::engine::general_purpose::STANDARD.encode(bytes);
+        (encoded, BodyEncoding::Base64, None)
+    };
+    Ok(DecodedBody {
+        body,
+        encoding,
+        path,
+        content_type,
+    })
+}
+
+// ─── Downloads ───────────────────────────────────────────────────────────────
+
+fn write_failed(path: &Path, detail: impl Display) -> CommandError {
+    CommandError::new(ErrorCode::FileWriteFailed)
+        .arg("path", path.display())
+        .detail(detail)
+}
+
+/// A response body streamed to a user-chosen file rather than buffered, so
+/// it has no size limit. Bytes go to a `.part` file beside the target and
+/// are renamed into place once complete; a download that fails or is
+/// cancelled removes its partial file and leaves the target untouched.
+pub(crate) struct Download {
+    path: PathBuf,
+    partial: PathBuf,
+    /// Taken when finished, so the file is closed before it is renamed.
+    file: Option<tokio::fs::File>,
+}
+
+impl Download {
+    pub(crate) async fn create(path: &str) -> Result<Self, CommandError> {
+        let path = PathBuf::from(path);
+        if !path.is_absolute() {
+            return Err(write_failed(&path, "the path must be absolute"));
+        }
+        let mut partial = path.clone().into_os_string();
+        partial.push(".part");
+        let partial = PathBuf::from(partial);
+        let file = tokio::fs::File::create(&partial)
+            .await
+            .map_err(|e| write_failed(&path, e))?;
+        Ok(Self {
+            path,
+            partial,
+            file: Some(file),
+        })
+    }
+
+    pub(crate) async fn write(&mut self, chunk: &[u8]) -> Result<(), CommandError> {
+        let Some(file) = &mut self.file else {
+            return Ok(());
+        };
+        file.write_all(chunk)
+            .await
+            .map_err(|e| write_failed(&self.path, e).phase(ErrorPhase::Receive))
+    }
+
+    /// Move the complete body into place and return its path.
+    pub(crate) async fn finish(mut self) -> Result<String, CommandError> {
+        if let Some(mut file) = self.file.take() {
+            file.flush()
+                .await
+                .map_err(|e| write_failed(&self.path, e).phase(ErrorPhase::Receive))?;
+        }
+        tokio::fs::rename(&self.partial, &self.path)
+            .await
+            .map_err(|e| write_failed(&self.path, e).phase(ErrorPhase::Receive))?;
+        Ok(self.path.to_string_lossy().into_owned())
+    }
+}
+
+impl Drop for Download {
+    fn drop(&mut self) {
+        // After a successful rename the partial file no longer exists
+        drop(self.file.take());
+        let _ = std::fs::remove_file(&self.partial);
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_stays_text_even_if_invalid_utf8() {
+        let decoded = decode_body(b"{\"a\":1}", Some("application/json")).unwrap();
+        assert_eq!(decoded.encoding, BodyEncoding::Text);
+        assert_eq!(decoded.body, "{\"a\":1}");
+
+        let decoded = decode_body(b"caf\xe9", Some("text/plain; charset=latin1")).unwrap();
+        assert_eq!(decoded.encoding, BodyEncoding::Text);
+        assert_eq!(decoded.body, "caf\u{fffd}");
+    }
+
+    #[test]
+    fn test_binary_is_base64_and_sniffed_without_header() {
+        let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+        let decoded = decode_body(png, None).unwrap();
+        assert_eq!(decoded.encoding, BodyEncoding::Base64);
+        assert_eq!(decoded.content_type.as_deref(), Some("image/png"));
+        assert_eq!(
+            base64::engine::general_purpose::STANDARD
+                .decode(&decoded.body)
+                .unwrap(),
+            png
+        );
+
+        // Declared binary types are binary even when the bytes are ASCII
+        let decoded = decode_body(b"\x08\x96\x01", Some("application/x-protobuf")).unwrap();
+        assert_eq!(decoded.encoding, BodyEncoding::Base64);
+        // Unknown types fall back to checking the bytes
+        let decoded = decode_body(b"plain", None).unwrap();
+        assert_eq!(decoded.encoding, BodyEncoding::Text);
+        assert_eq!(decoded.content_type, None);
+    }
+
+    fn responses_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("yasp-responses-{name}-{}", std::process::id()))
+    }
+
+    #[test]
+    fn test_large_binary_is_written_to_a_file() {
+        let dir = RESPONSES.get_or_init(|| responses_dir("decode"));
+        clear_dir(dir).unwrap();
+        let bytes = vec![0xffu8; INLINE_BINARY_LIMIT + 1];
+        let decoded = decode_body(&bytes, Some("application/pdf")).unwrap();
+        assert_eq!(decoded.encoding, BodyEncoding::File);
+        assert!(decoded.body.is_empty());
+        let path = PathBuf::from(decoded.path.unwrap());
+        assert_eq!(path.parent().unwrap(), dir);
+        assert_eq!(path.extension().unwrap(), "pdf");
+        assert_eq!(std::fs::read(&path).unwrap(), bytes);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_spill_never_overwrites_and_old_spills_are_cleared() {
+        let dir = responses_dir("spill");
+        clear_dir(&dir).unwrap();
+        let next = NEXT_FILE.load(Ordering::Relaxed);
+        let taken = dir.join(format!("{next}.pdf"));
+        std::fs::write(&taken, "kept").unwrap();
+
+        let path = spill(&dir, b"new", "application/pdf").unwrap();
+        assert_ne!(path, taken);
+        assert_eq!(std::fs::read_to_string(&taken).unwrap(), "kept");
+        assert_eq!(std::fs::read(&path).unwrap(), b"new");
+
+        clear_dir(&dir).unwrap();
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 0);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    fn download_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("yasp-download-{name}-{}.bin", std::process::id()))
+    }
+
+    #[tokio::test]
+    async fn test_download_is_renamed_into_place_when_finished() {
+        let path = download_path("finished");
+        let mut download = Download::create(&path.to_string_lossy()).await.unwrap();
+        download.write(b"PK\x03\x04").await.unwrap();
+        download.write(b"rest").await.unwrap();
+        assert!(!path.exists());
+        assert_eq!(download.finish().await.unwrap(), path.to_string_lossy());
+        assert_eq!(std::fs::read(&path).unwrap(), b"PK\x03\x04rest");
+        assert!(!PathBuf::from(format!("{}.part", path.display())).exists());
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn test_abandoned_download_leaves_no_file() {
+        let path = download_path("abandoned");
+        let partial = PathBuf::from(format!("{}.part", path.display()));
+        let mut download = Download::create(&path.to_string_lossy()).await.unwrap();
+        download.write(b"partial").await.unwrap();
+        assert!(partial.exists());
+        drop(download);
+        assert!(!partial.exists());
+        assert!(!path.exists());
+
+        let err = Download::create("export.zip").await.err().unwrap();
+        assert_eq!(err.code, ErrorCode::FileWriteFailed);
+    }
+}
@@ -0,0 +1,287 @@
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+
+use super::snippet::REDACTED_VALUE;
+use crate::error::{CommandError, ErrorCode};
+
+/// OWASP A06:2025 – Identification and Authentication Failures: captured
+/// examples end up in a shared spec, so credential-like fields are always
+/// redacted, in addition to the fields the user names.
+const REDACTED_FIELDS: &[&str] = &[
+    "password",
+    "secret",
+    "token",
+    "access_token",
+    "refresh_token",
+    "id_token",
+    "api_key",
+    "apikey",
+    "client_secret",
+    "authorization",
+];
+
+const METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+/// A response from history to record as an example.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CapturedResponse {
+    pub status: u16,
+    /// `Content-Type` of the response; JSON bodies are stored parsed.
+    pub content_type: String,
+    pub body: String,
+}
+
+// ─── Redaction ───────────────────────────────────────────────────────────────
+
+fn redact(value: &mut Value, fields: &[String]) {
+    match value {
+        Value::Object(map) => {
+            for (key, entry) in map.iter_mut() {
+                let key = key.to_ascii_lowercase();
+                if REDACTED_FIELDS.contains(&key.as_str())
+                    || fields.iter().any(|field| field.eq_ignore_ascii_case(&key))
+                {
+                    *entry = Value::String(REDACTED_VALUE.to_string());
+                } else {
+                    redact(entry, fields);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| redact(item, fields)),
+        _ => {}
+    }
+}
+
+/// The example value: parsed and redacted for JSON, the raw text otherwise.
+fn example_value(response: &CapturedResponse, fields: &[String]) -> Value {
+    let essence = response
+        .content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    let is_json = essence == "application/json" || essence.ends_with("+json");
+    match serde_json::from_str::<Value>(&response.body) {
+        Ok(mut value) if is_json => {
+            redact(&mut value, fields);
+            value
+        }
+        _ => Value::String(response.body.clone()),
+    }
+}
+
+// ─── Spec Editing ────────────────────────────────────────────────────────────
+
+fn object<'a>(value: &'a mut Value, key: &str) -> &'a mut Map<String, Value> {
+    let entry = value
+        .as_object_mut()
+        .expect("caller passes an object")
+        .entry(key)
+        .or_insert_with(|| json!({}));
+    if !entry.is_object() {
+        *entry = json!({});
+    }
+    entry.as_object_mut().expect("just made an object")
+}
+
+/// The response entry for `status`: the exact code, else its `2XX`-style
+/// range, else a new entry for the exact code.
+fn response_entry(responses: &mut Map<String, Value>, status: u16) -> &mut Value {
+    let exact = status.to_string();
+    let range = format!("{}XX", status / 100);
+    let lower_range = range.to_ascii_lowercase();
+    let key = [exact.clone(), range, lower_range]
+        .into_iter()
+        .find(|key| responses.get(key).is_some_and(Value::is_object))
+        .unwrap_or(exact);
+    responses.entry(key).or_insert_with(|| {
+        let reason = reqwest::StatusCode::from_u16(status)
+            .ok()
+            .and_then(|status| status.canonical_reason())
+            .unwrap_or("Response");
+        json!({ "description": reason })
+    })
+}
+
+fn add_example(
+    spec: &mut Value,
+    path: &str,
+    method: &str,
+    name: &str,
+    response: &CapturedResponse,
+    value: Value,
+) -> Result<(), CommandError> {
+    let swagger2 = spec["swagger"].as_str().is_some_and(|v| v.starts_with('2'));
+    let method = method.to_ascii_lowercase();
+    let operation = spec
+        .get_mut("paths")
+        .and_then(|paths| paths.get_mut(path))
+        .and_then(|item| item.get_mut(&method))
+        .filter(|operation| METHODS.contains(&method.as_str()) && operation.is_object())
+        .ok_or_else(|| {
+            CommandError::new(ErrorCode::OperationNotFound)
+                .arg("method", method.to_uppercase())
+                .arg("path", path)
+        })?;
+
+    let entry = response_entry(object(operation, "responses"), response.status);
+    let media_type = response
+        .content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+    if swagger2 {
+        // Swagger 2.0 has one unnamed example per MIME type
+        object(entry, "examples").insert(media_type, value);
+    } else {
+        let media = object(entry, "content")
+            .entry(media_type)
+            .or_insert_with(|| json!({}));
+        object(media, "examples").insert(
+            name.to_string(),
+            json!({ "summary": format!("Captured {} response", response.status), "value": value }),
+        );
+    }
+    Ok(())
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// Record a response from history as a named example of `method path` and
+/// return the updated spec document, for the frontend to serialize back to
+/// its original JSON or YAML and save.
+///
+/// The example goes under the response for the exact status, else its
+/// `2XX`-style range, else a new response entry. An existing example with the
+/// same name is replaced. JSON bodies are redacted: credential-like fields
+/// and every field named in `redact` (case-insensitive, at any depth) become
+/// `<redacted>`.
+#[tauri::command]
+pub fn capture_response_example(
+    mut spec: Value,
+    path: String,
+    method: String,
+    name: String,
+    response: CapturedResponse,
+    redact: Option<Vec<String>>,
+) -> Result<Value, CommandError> {
+    let value = example_value(&response, &redact.unwrap_or_default());
+    add_example(&mut spec, &path, &method, &name, &response, value)?;
+    Ok(spec)
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec() -> Value {
+        json!({
+            "openapi": "3.0.3",
+            "paths": {
+                "/users/{id}": {
+                    "get": { "responses": { "2XX": { "description": "OK" } } }
+                }
+            }
+        })
+    }
+
+    fn response(status: u16, content_type: &str, body: &str) -> CapturedResponse {
+        CapturedResponse {
+            status,
+            content_type: content_type.to_string(),
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_capture_adds_redacted_named_example_under_range() {
+        let updated = capture_response_example(
+            spec(),
+            "/users/{id}".to_string(),
+            "GET".to_string(),
+            "alice".to_string(),
+            response(
+                200,
+                "application/json; charset=utf-8",
+                r#"{"name":"Alice","email":"a@example.com","session":{"Token":"abc"}}"#,
+            ),
+            Some(vec!["EMAIL".to_string()]),
+        )
+        .unwrap();
+        let example = &updated["paths"]["/users/{id}"]["get"]["responses"]["2XX"]["content"]
+            ["application/json"]["examples"]["alice"];
+        assert_eq!(
+            example["value"],
+            json!({ "name": "Alice", "email": "<redacted>", "session": { "Token": "<redacted>" } })
+        );
+        assert_eq!(example["summary"], "Captured 200 response");
+    }
+
+    #[test]
+    fn test_capture_creates_missing_status_with_description() {
+        let updated = capture_response_example(
+            spec(),
+            "/users/{id}".to_string(),
+            "get".to_string(),
+            "missing".to_string(),
+            response(404, "text/plain", "no such user"),
+            None,
+        )
+        .unwrap();
+        let entry = &updated["paths"]["/users/{id}"]["get"]["responses"]["404"];
+        assert_eq!(entry["description"], "Not Found");
+        assert_eq!(
+            entry["content"]["text/plain"]["examples"]["missing"]["value"],
+            "no such user"
+        );
+    }
+
+    #[test]
+    fn test_capture_uses_swagger2_examples_map() {
+        let mut swagger = spec();
+        swagger["swagger"] = json!("2.0");
+        let updated = capture_response_example(
+            swagger,
+            "/users/{id}".to_string(),
+            "get".to_string(),
+            "ignored".to_string(),
+            response(200, "application/json", r#"{"id":1}"#),
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            updated["paths"]["/users/{id}"]["get"]["responses"]["2XX"]["examples"]
+                ["application/json"],
+            json!({ "id": 1 })
+        );
+    }
+
+    #[test]
+    fn test_capture_rejects_unknown_operation() {
+        for (path, method) in [
+            ("/users/{id}", "post"),
+            ("/orders", "get"),
+            ("/users/{id}", "parameters"),
+        ] {
+            let err = capture_response_example(
+                spec(),
+                path.to_string(),
+                method.to_string(),
+                "x".to_string(),
+                response(200, "application/json", "{}"),
+                None,
+            )
+            .unwrap_err();
+            assert_eq!(err.code, ErrorCode::OperationNotFound);
+        }
+    }
+}
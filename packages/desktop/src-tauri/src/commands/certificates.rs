@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Mutex;
+
+use base64::Engine;
+use ring::digest;
+use rustls_pki_types::CertificateDer;
+use serde::{Deserialize, Serialize};
+use x509_parser::prelude::{FromDer, GeneralName, X509Certificate};
+use x509_parser::public_key::PublicKey;
+
+use super::aws::sha256_hex;
+
+/// Hosts whose chains are kept; the cache starts over beyond that.
+const MAX_HOSTS: usize = 256;
+
+/// Certificate chains servers presented in their latest handshake, by
+/// lower-case host. Pooled connections do not handshake again, so responses
+/// are matched with a chain by host rather than by connection.
+static PEER_CHAINS: Mutex<Option<HashMap<String, Vec<CertificateDer<'static>>>>> = Mutex::new(None);
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+/// One certificate of the chain a server presented.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CertificateSummary {
+    /// Distinguished name, e.g. `CN=api.example.com, O=Example`.
+    pub subject: String,
+    pub issuer: String,
+    /// DNS names and IP addresses the certificate is valid for.
+    pub subject_alt_names: Vec<String>,
+    /// Unix time in seconds.
+    pub not_before: i64,
+    /// Unix time in seconds.
+    pub not_after: i64,
+    /// `RSA 2048`, `EC 256`, or the key algorithm's OID.
+    pub key_type: String,
+    /// Hex SHA-256 fingerprint.
+    pub fingerprint: String,
+    /// Base64 SHA-256 hash of the public key, as `set_certificate_pins`
+    /// takes it.
+    pub spki_sha256: String,
+}
+
+// ─── Chains ──────────────────────────────────────────────────────────────────
+
+/// Keep the chain `host` presented, leaf first.
+pub(super) fn record(
+    host: &str,
+    end_entity: &CertificateDer<'_>,
+    intermediates: &[CertificateDer<'_>],
+) {
+    let chain = std::iter::once(end_entity)
+        .chain(intermediates)
+        .map(|certificate| certificate.clone().into_owned())
+        .collect();
+    let host = host.to_ascii_lowercase();
+    let mut chains = PEER_CHAINS.lock().unwrap_or_else(|e| e.into_inner());
+    let chains = chains.get_or_insert_with(HashMap::new);
+    if chains.len() >= MAX_HOSTS && !chains.contains_key(&host) {
+        chains.clear();
+    }
+    chains.insert(host, chain);
+}
+
+/// The chain the host of `url` presented, leaf first; empty for plain
+/// `http://` URLs.
+pub(super) fn peer_chain(url: &url::Url) -> Vec<CertificateSummary> {
+    let host = match url.host() {
+        Some(url::Host::Domain(domain)) if url.scheme() == "https" => domain.to_ascii_lowercase(),
+        Some(url::Host::Ipv4(ip)) if url.scheme() == "https" => ip.to_string(),
+        Some(url::Host::Ipv6(ip)) if url.scheme() == "https" => ip.to_string(),
+        _ => return Vec::new(),
+    };
+    let chains = PEER_CHAINS.lock().unwrap_or_else(|e| e.into_inner());
+    chains
+        .as_ref()
+        .and_then(|chains| chains.get(&host))
+        .into_iter()
+        .flatten()
+        .filter_map(summarize)
+        .collect()
+}
+
+// ─── Parsing ─────────────────────────────────────────────────────────────────
+
+/// The summary of a DER certificate; none when it does not parse.
+pub(super) fn summarize(der: &CertificateDer<'_>) -> Option<CertificateSummary> {
+    let (_, certificate) = X509Certificate::from_der(der).ok()?;
+    let subject_alt_names = certificate
+        .subject_alternative_name()
+        .ok()
+        .flatten()
+        .map(|extension| {
+            extension
+                .value
+                .general_names
+                .iter()
+                .filter_map(|name| match name {
+                    GeneralName::DNSName(dns) => Some(dns.to_string()),
+                    GeneralName::IPAddress(bytes) => ip_address(bytes),
+                    _ => None,
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let public_key = certificate.public_key();
+    let key_type = match public_key.parsed() {
+        Ok(PublicKey::RSA(key)) => format!("RSA {}", key.key_size()),
+        Ok(PublicKey::EC(point)) => format!("EC {}", point.key_size()),
+        _ => public_key.algorithm.algorithm.to_id_string(),
+    };
+    let validity = certificate.validity();
+    Some(CertificateSummary {
+        subject: certificate.subject().to_string(),
+        issuer: certificate.issuer().to_string(),
+        subject_alt_names,
+        not_before: validity.not_before.timestamp(),
+        not_after: validity.not_after.timestamp(),
+        key_type,
+        fingerprint: sha256_hex(der),
+        spki_sha256: spki_hash(&certificate),
+    })
+}
+
+/// Base64 SHA-256 hash of the SubjectPublicKeyInfo of a DER certificate,
+/// the form certificate pins take; none when it does not parse.
+pub(super) fn spki_sha256(der: &CertificateDer<'_>) -> Option<String> {
+    let (_, certificate) = X509Certificate::from_der(der).ok()?;
+    Some(spki_hash(&certificate))
+}
+
+fn spki_hash(certificate: &X509Certificate<'_>) -> String {
+    let hash = digest::digest(&digest::SHA256, certificate.public_key().raw);
+    base64::engine::general_purpose::STANDARD.encode(hash)
+}
+
+fn ip_address(bytes: &[u8]) -> Option<String> {
+    let ip = match bytes.len() {
+        4 => IpAddr::from(<[u8; 4]>::try_from(bytes).ok()?),
+        16 => IpAddr::from(<[u8; 16]>::try_from(bytes).ok()?),
+        _ => return None,
+    };
+    Some(ip.to_string())
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustls_pki_types::pem::PemObject;
+
+    const SERVER_CERT: &str = "-----BEGIN CERTIFICATE-----
+MIIB0jCCAXmgAwIBAgIUDZXChPU99fJDm/k8KpfiXOT+G+gwCgYIKoZIzj0EAwIw
+JzEWMBQGA1UEAwwNYXBpLnlhc3AudGVzdDENMAsGA1UECgwEWUFTUDAgFw0yNjEw
+MTUwMzU0NDhaGA8yMTI2MDkyMTAzNTQ0OFowJzEWMBQGA1UEAwwNYXBpLnlhc3Au
+dGVzdDENMAsGA1UECgwEWUFTUDBZMBMGByqGSM49AgEGCCqGSM49AwEHA0IABKly
+FCTqk7ohaF7qhyl73EQ448O30dZr0RSJWnYTSYmqojEjAuzRFmMf/qAcps/HYkkL
+WRZH4kw/9900l8IqmeCjgYAwfjAdBgNVHQ4EFgQUq6WgOOXPrFMxD46E34XvNmgF
+bKcwHwYDVR0jBBgwFoAUq6WgOOXPrFMxD46E34XvNmgFbKcwDwYDVR0TAQH/BAUw
+AwEB/zArBgNVHREEJDAigg1hcGkueWFzcC50ZXN0ggsqLnlhc3AudGVzdIcEfwAA
+ATAKBggqhkjOPQQDAgNHADBEAiBMYqvK1vDQ58XciBhO7woA2B4rnTPniq/+Qden
+be1ILgIgEBm72YV9154R7fUb5LjX8ecQmtyo3/Y2wd5KDRqMbkY=
+-----END CERTIFICATE-----
+";
+
+    #[test]
+    fn test_certificate_is_summarized() {
+        let der = CertificateDer::from_pem_slice(SERVER_CERT.as_bytes()).unwrap();
+        assert_eq!(
+            summarize(&der).unwrap(),
+            CertificateSummary {
+                subject: "CN=api.yasp.test, O=YASP".to_string(),
+                issuer: "CN=api.yasp.test, O=YASP".to_string(),
+                subject_alt_names: vec![
+                    "api.yasp.test".to_string(),
+                    "*.yasp.test".to_string(),
+                    "127.0.0.1".to_string(),
+                ],
+                not_before: 1792036488,
+                not_after: 4945636488,
+                key_type: "EC 256".to_string(),
+                fingerprint: sha256_hex(&der),
+                spki_sha256: "XxWKssZLC9DSwUKA5ltcjtU3+RxUEntfw92CnQIkgU0=".to_string(),
+            }
+        );
+        assert_eq!(summarize(&CertificateDer::from(vec![0x30, 0x00])), None);
+    }
+
+    #[test]
+    fn test_chain_is_looked_up_by_https_host() {
+        let der = CertificateDer::from_pem_slice(SERVER_CERT.as_bytes()).unwrap();
+        record("Chain.Yasp.Test", &der, std::slice::from_ref(&der));
+
+        let url = |url: &str| url::Url::parse(url).unwrap();
+        let chain = peer_chain(&url("https://chain.yasp.test:8443/pets"));
+        assert_eq!(chain.len(), 2);
+        assert_eq!(chain[0].subject, "CN=api.yasp.test, O=YASP");
+        assert!(peer_chain(&url("http://chain.yasp.test/pets")).is_empty());
+        assert!(peer_chain(&url("https://other.yasp.test/pets")).is_empty());
+    }
+}
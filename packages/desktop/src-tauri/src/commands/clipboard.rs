@@ -0,0 +1,338 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+use super::{ensure_main_window, validate_url, ApiRequest};
+use crate::error::CommandError;
+
+/// Emitted with a `ClipboardImport` when a copied spec URL or curl command
+/// is detected; the frontend offers the import as a toast.
+pub const DETECTED_EVENT: &str = "clipboard://detected";
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// Anything longer is neither a URL nor a hand-copied curl command.
+const MAX_TEXT_LEN: usize = 64 * 1024;
+
+const SPEC_PATH_HINTS: &[&str] = &["openapi", "swagger", "api-docs", "asyncapi"];
+
+/// Short options whose value may be attached (`-XPOST`) or separate.
+const SHORT_VALUE_OPTIONS: &[&str] = &[
+    "-X", "-H", "-d", "-A", "-e", "-b", "-u", "-o", "-m", "-x", "-w", "-F", "-T", "-E",
+];
+/// Long options that consume the next token but are not imported.
+const IGNORED_VALUE_OPTIONS: &[&str] = &[
+    "--user",
+    "--output",
+    "--max-time",
+    "--connect-timeout",
+    "--proxy",
+    "--retry",
+    "--write-out",
+    "--cacert",
+    "--cert",
+    "--key",
+    "--form",
+    "--upload-file",
+    "--resolve",
+];
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ClipboardImport {
+    SpecUrl { url: String },
+    Curl { request: Box<ApiRequest> },
+}
+
+/// Opt-in clipboard monitor. Off until the frontend enables it; while off
+/// the clipboard is never read.
+#[derive(Default)]
+pub struct ClipboardWatch {
+    enabled: AtomicBool,
+    started: AtomicBool,
+}
+
+// ─── Detection ───────────────────────────────────────────────────────────────
+
+pub(super) fn classify(text: &str) -> Option<ClipboardImport> {
+    let text = text.trim();
+    if text.is_empty() || text.len() > MAX_TEXT_LEN {
+        return None;
+    }
+
+    if text.starts_with("curl ") || text.starts_with("curl\t") {
+        return parse_curl(text).map(|request| ClipboardImport::Curl {
+            request: Box::new(request),
+        });
+    }
+
+    if text.contains(char::is_whitespace) {
+        return None;
+    }
+    // OWASP A09:2025 – SSRF: only offer URLs that fetch_spec would accept
+    let url = validate_url(text).ok()?;
+    let path = url.path().to_ascii_lowercase();
+    let looks_like_spec = SPEC_PATH_HINTS.iter().any(|hint| path.contains(hint))
+        || path.ends_with(".yaml")
+        || path.ends_with(".yml");
+    looks_like_spec.then(|| ClipboardImport::SpecUrl { url: url.into() })
+}
+
+/// Split a POSIX shell command line into words: single quotes are literal,
+/// double quotes honour `\` before `"`, `\`, `$` and `` ` ``, and a
+/// backslash-newline continues the line. `None` on an unterminated quote.
+fn shell_words(text: &str) -> Option<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                let current = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next()? {
+                        '\'' => break,
+                        c => current.push(c),
+                    }
+                }
+            }
+            '"' => {
+                let current = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        '\\' => match chars.next()? {
+                            c @ ('"' | '\\' | '$' | '`') => current.push(c),
+                            '\n' => {}
+                            c => {
+                                current.push('\\');
+                                current.push(c);
+                            }
+                        },
+                        c => current.push(c),
+                    }
+                }
+            }
+            '\\' => match chars.next() {
+                Some('\n') | None => {}
+                Some('\r') if chars.peek() == Some(&'\n') => {
+                    chars.next();
+                }
+                Some(c) => word.get_or_insert_with(String::new).push(c),
+            },
+            c if c.is_whitespace() => words.extend(word.take()),
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    words.extend(word);
+    Some(words)
+}
+
+/// Import a curl command line as an editor request. Options that have no
+/// request-editor equivalent (output, timeouts, proxies, multipart forms,
+/// `-u` credentials) are skipped; auth is configured in the editor instead.
+pub(super) fn parse_curl(text: &str) -> Option<ApiRequest> {
+    let words = shell_words(text)?;
+    let mut words = words.into_iter();
+    if words.next()? != "curl" {
+        return None;
+    }
+
+    let mut method = None;
+    let mut url = None;
+    let mut headers = HashMap::new();
+    let mut data: Vec<String> = Vec::new();
+
+    while let Some(word) = words.next() {
+        let (option, attached) = match SHORT_VALUE_OPTIONS
+            .iter()
+            .find(|short| word.len() > 2 && word.starts_with(**short))
+        {
+            Some(short) => (short.to_string(), Some(word[2..].to_string())),
+            None => (word, None),
+        };
+        let mut value = || attached.clone().or_else(|| words.next());
+
+        match option.as_str() {
+            "-X" | "--request" => method = Some(value()?.to_uppercase()),
+            "--url" => url = Some(value()?),
+            "-H" | "--header" => {
+                let header = value()?;
+                if let Some((name, value)) = header.split_once(':') {
+                    headers.insert(name.trim().to_string(), value.trim().to_string());
+                }
+            }
+            "-A" | "--user-agent" => {
+                headers.insert("User-Agent".to_string(), value()?);
+            }
+            "-e" | "--referer" => {
+                headers.insert("Referer".to_string(), value()?);
+            }
+            "-b" | "--cookie" => {
+                headers.insert("Cookie".to_string(), value()?);
+            }
+            "-d" | "--data" | "--data-raw" | "--data-binary" | "--data-ascii" => {
+                data.push(value()?)
+            }
+            "--json" => {
+                data.push(value()?);
+                headers
+                    .entry("Content-Type".to_string())
+                    .or_insert_with(|| "application/json".to_string());
+                headers
+                    .entry("Accept".to_string())
+                    .or_insert_with(|| "application/json".to_string());
+            }
+            "-u" | "-o" | "-m" | "-x" | "-w" | "-F" | "-T" | "-E" => {
+                value()?;
+            }
+            option if IGNORED_VALUE_OPTIONS.contains(&option) => {
+                value()?;
+            }
+            option if option.starts_with('-') => {}
+            other if url.is_none() => url = Some(other.to_string()),
+            _ => {}
+        }
+    }
+
+    let url = url?;
+    // curl assumes http:// for scheme-less URLs
+    let url = if url.contains("://") {
+        url
+    } else {
+        format!("http://{url}")
+    };
+    // Repeated -d values are joined the way curl sends them
+    let body = (!data.is_empty()).then(|| data.join("&"));
+    let method = method.unwrap_or_else(|| if body.is_some() { "POST" } else { "GET" }.to_string());
+
+    Some(ApiRequest {
+        method,
+        url,
+        headers,
+        body,
+        ..Default::default()
+    })
+}
+
+async fn watch_clipboard(app: AppHandle) {
+    let mut last_seen: Option<String> = None;
+    loop {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        if !app.state::<ClipboardWatch>().enabled.load(Ordering::SeqCst) {
+            last_seen = None;
+            continue;
+        }
+
+        // Non-text content (images, files) reads as an error; skip it
+        let Ok(text) = app.clipboard().read_text() else {
+            continue;
+        };
+        if last_seen.as_deref() == Some(text.as_str()) {
+            continue;
+        }
+        if let Some(import) = classify(&text) {
+            let _ = app.emit(DETECTED_EVENT, import);
+        }
+        last_seen = Some(text);
+    }
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// Turn the clipboard monitor on or off. The polling task is started on the
+/// first enable and idles without touching the clipboard while disabled.
+///
+/// Clipboard text never leaves the machine; only detected imports are
+/// emitted to the main window.
+#[tauri::command]
+pub fn set_clipboard_watch(
+    app: AppHandle,
+    webview: tauri::Webview,
+    enabled: bool,
+) -> Result<(), CommandError> {
+    ensure_main_window(webview.label())?;
+
+    let watch = app.state::<ClipboardWatch>();
+    watch.enabled.store(enabled, Ordering::SeqCst);
+    if enabled && !watch.started.swap(true, Ordering::SeqCst) {
+        tauri::async_runtime::spawn(watch_clipboard(app.clone()));
+    }
+    Ok(())
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shell_words_handles_quotes_and_continuations() {
+        let words = shell_words("curl -H 'A: b c' \\\n  \"x\\\"y\" it\\'s").unwrap();
+        assert_eq!(words, vec!["curl", "-H", "A: b c", "x\"y", "it's"]);
+        assert!(shell_words("curl 'unterminated").is_none());
+    }
+
+    #[test]
+    fn test_parse_curl_reads_method_headers_and_body() {
+        let request = parse_curl(
+            "curl -X POST 'https://api.example.com/pets' \\\n  -H 'Content-Type: application/json' \\\n  --data-raw '{\"name\":\"Rex\"}'",
+        )
+        .unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.url, "https://api.example.com/pets");
+        assert_eq!(request.headers["Content-Type"], "application/json");
+        assert_eq!(request.body.as_deref(), Some(r#"{"name":"Rex"}"#));
+    }
+
+    #[test]
+    fn test_parse_curl_infers_method_and_scheme() {
+        let get = parse_curl("curl -sL api.example.com/pets").unwrap();
+        assert_eq!(get.method, "GET");
+        assert_eq!(get.url, "http://api.example.com/pets");
+
+        let post = parse_curl("curl https://api.example.com -d a=1 -d b=2").unwrap();
+        assert_eq!(post.method, "POST");
+        assert_eq!(post.body.as_deref(), Some("a=1&b=2"));
+    }
+
+    #[test]
+    fn test_parse_curl_accepts_attached_short_options() {
+        let request =
+            parse_curl("curl -XDELETE -H'Accept: */*' https://api.example.com/pets/1").unwrap();
+        assert_eq!(request.method, "DELETE");
+        assert_eq!(request.headers["Accept"], "*/*");
+        assert_eq!(request.url, "https://api.example.com/pets/1");
+    }
+
+    #[test]
+    fn test_classify_detects_spec_urls() {
+        assert!(matches!(
+            classify("  https://petstore3.swagger.io/api/v3/openapi.json\n"),
+            Some(ClipboardImport::SpecUrl { .. })
+        ));
+        assert!(matches!(
+            classify("https://example.com/specs/orders.yaml"),
+            Some(ClipboardImport::SpecUrl { .. })
+        ));
+        assert!(classify("https://example.com/blog/post").is_none());
+        assert!(classify("http://127.0.0.1/openapi.json").is_none());
+        assert!(classify("some openapi notes").is_none());
+    }
+
+    #[test]
+    fn test_classify_detects_curl() {
+        assert!(matches!(
+            classify("curl https://api.example.com/pets"),
+            Some(ClipboardImport::Curl { .. })
+        ));
+        assert!(classify("curling is a sport").is_none());
+    }
+}
@@ -0,0 +1,471 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use cookie_store::{CookieDomain, CookieError, CookieExpiration, CookieStore, RawCookie};
+use reqwest::header::HeaderValue;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+use url::Url;
+
+use super::ensure_main_window;
+use super::store::JsonStore;
+use crate::error::{CommandError, ErrorCode};
+
+const COOKIES_FILE: &str = "cookies.json";
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+/// A cookie as listed and edited in the cookie manager.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredCookie {
+    pub name: String,
+    pub value: String,
+    /// Host the cookie is sent to, without a leading dot.
+    pub domain: String,
+    /// Sent to `domain` only, not to its subdomains.
+    #[serde(default)]
+    pub host_only: bool,
+    #[serde(default = "default_path")]
+    pub path: String,
+    /// Unix time in seconds; `None` for a session cookie.
+    #[serde(default)]
+    pub expires: Option<i64>,
+    #[serde(default)]
+    pub secure: bool,
+    #[serde(default)]
+    pub http_only: bool,
+}
+
+fn default_path() -> String {
+    "/".to_string()
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CookieJarContents {
+    /// Whether the jar is saved across restarts.
+    pub persist: bool,
+    pub cookies: Vec<StoredCookie>,
+}
+
+/// One workspace's cookies. Requests run in the workspace use a client that
+/// stores `Set-Cookie` headers here and sends matching cookies back, like a
+/// browser, redirects included.
+pub struct WorkspaceJar {
+    workspace_id: String,
+    store: Mutex<CookieStore>,
+    persist: AtomicBool,
+    /// Set when a response stored cookies since the jar was last saved.
+    changed: AtomicBool,
+}
+
+/// Cookie jars by workspace id, loaded from disk on first use.
+#[derive(Default)]
+pub struct CookieJars(Mutex<HashMap<String, Arc<WorkspaceJar>>>);
+
+/// Saved jars by workspace id. Only workspaces with persistence turned on
+/// have an entry.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CookieFile {
+    workspaces: HashMap<String, Vec<cookie_store::Cookie<'static>>>,
+}
+
+// ─── Jars ────────────────────────────────────────────────────────────────────
+
+impl WorkspaceJar {
+    fn new(workspace_id: &str, saved: Option<Vec<cookie_store::Cookie<'static>>>) -> Self {
+        let persist = saved.is_some();
+        let cookies = saved
+            .into_iter()
+            .flatten()
+            .map(Ok::<_, std::convert::Infallible>);
+        let Ok(store) = CookieStore::from_cookies(cookies, false);
+        Self {
+            workspace_id: workspace_id.to_string(),
+            store: Mutex::new(store),
+            persist: AtomicBool::new(persist),
+            changed: AtomicBool::new(false),
+        }
+    }
+
+    pub(crate) fn workspace_id(&self) -> &str {
+        &self.workspace_id
+    }
+
+    fn store(&self) -> MutexGuard<'_, CookieStore> {
+        self.store.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn persists(&self) -> bool {
+        self.persist.load(Ordering::Relaxed)
+    }
+
+    fn contents(&self) -> CookieJarContents {
+        let mut cookies: Vec<StoredCookie> = self.store().iter_unexpired().map(stored).collect();
+        cookies.sort_by(|a, b| (&a.domain, &a.path, &a.name).cmp(&(&b.domain, &b.path, &b.name)));
+        CookieJarContents {
+            persist: self.persists(),
+            cookies,
+        }
+    }
+}
+
+impl reqwest::cookie::CookieStore for WorkspaceJar {
+    fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &HeaderValue>, url: &Url) {
+        let cookies = cookie_headers
+            .filter_map(|value| std::str::from_utf8(value.as_bytes()).ok())
+            .filter_map(|value| RawCookie::parse(value.to_string()).ok());
+        self.store().store_response_cookies(cookies, url);
+        self.changed.store(true, Ordering::Relaxed);
+    }
+
+    fn cookies(&self, url: &Url) -> Option<HeaderValue> {
+        let header = self
+            .store()
+            .get_request_values(url)
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect::<Vec<_>>()
+            .join("; ");
+        if header.is_empty() {
+            return None;
+        }
+        HeaderValue::from_str(&header).ok()
+    }
+}
+
+impl CookieJars {
+    /// The workspace's jar, with its saved cookies the first time it is used.
+    pub(crate) fn jar(
+        &self,
+        path: &Path,
+        workspace_id: &str,
+    ) -> Result<Arc<WorkspaceJar>, CommandError> {
+        let mut jars = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(jar) = jars.get(workspace_id) {
+            return Ok(jar.clone());
+        }
+        let saved = COOKIES.read(path)?.workspaces.remove(workspace_id);
+        let jar = Arc::new(WorkspaceJar::new(workspace_id, saved));
+        jars.insert(workspace_id.to_string(), jar.clone());
+        Ok(jar)
+    }
+}
+
+fn stored(cookie: &cookie_store::Cookie<'static>) -> StoredCookie {
+    StoredCookie {
+        name: cookie.name().to_string(),
+        value: cookie.value().to_string(),
+        domain: String::from(&cookie.domain),
+        host_only: matches!(cookie.domain, CookieDomain::HostOnly(_)),
+        path: String::from(&cookie.path),
+        expires: match &cookie.expires {
+            CookieExpiration::AtUtc(at) => Some(at.unix_timestamp()),
+            CookieExpiration::SessionEnd => None,
+        },
+        secure: cookie.secure().unwrap_or(false),
+        http_only: cookie.http_only().unwrap_or(false),
+    }
+}
+
+fn is_token(s: &str) -> bool {
+    !s.is_empty()
+        && s.bytes()
+            .all(|b| b.is_ascii_graphic() && !b"()<>@,;:\\\"/[]?={}".contains(&b))
+}
+
+/// The cookie as a `Set-Cookie` value and a URL it could have come from,
+/// so it is stored exactly as if a server had set it.
+fn to_set_cookie(cookie: &StoredCookie, now: i64) -> Result<(String, Url), CommandError> {
+    let invalid = || CommandError::new(ErrorCode::InvalidCookie).arg("name", &cookie.name);
+    let domain = cookie.domain.trim().trim_start_matches('.');
+    if !is_token(&cookie.name)
+        || cookie
+            .value
+            .bytes()
+            .any(|b| !b.is_ascii_graphic() || b == b';')
+        || !cookie.path.starts_with('/')
+        || cookie.path.contains(';')
+    {
+        return Err(invalid());
+    }
+    let url = Url::parse(&format!("https://{domain}{}", cookie.path))
+        .ok()
+        .filter(|url| url.host_str().is_some_and(|host| !host.is_empty()))
+        .ok_or_else(|| invalid().detail("invalid domain"))?;
+
+    let mut header = format!("{}={}; Path={}", cookie.name, cookie.value, cookie.path);
+    if !cookie.host_only {
+        header.push_str(&format!("; Domain={domain}"));
+    }
+    if let Some(expires) = cookie.expires {
+        header.push_str(&format!("; Max-Age={}", expires - now));
+    }
+    if cookie.secure {
+        header.push_str("; Secure");
+    }
+    if cookie.http_only {
+        header.push_str("; HttpOnly");
+    }
+    Ok((header, url))
+}
+
+fn set(jar: &WorkspaceJar, cookie: &StoredCookie) -> Result<(), CommandError> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs() as i64);
+    let (header, url) = to_set_cookie(cookie, now)?;
+    match jar.store().parse(&header, &url) {
+        // An already expired cookie only removes its namesake, if any
+        Ok(_) | Err(CookieError::Expired) => Ok(()),
+        Err(e) => Err(CommandError::new(ErrorCode::InvalidCookie)
+            .arg("name", &cookie.name)
+            .detail(e)),
+    }
+}
+
+// ─── Storage ─────────────────────────────────────────────────────────────────
+
+static COOKIES: JsonStore<CookieFile> = JsonStore::new(COOKIES_FILE);
+
+pub(crate) fn cookies_path(app: &AppHandle) -> Result<PathBuf, CommandError> {
+    COOKIES.path(app)
+}
+
+/// Save the jar if it persists, or drop it from the file if it doesn't.
+/// Session cookies are saved too, so a login survives a restart.
+fn save(path: &Path, jar: &WorkspaceJar) -> Result<(), CommandError> {
+    COOKIES.update(path, |file| {
+        if jar.persists() {
+            let cookies = jar.store().iter_unexpired().cloned().collect();
+            file.workspaces.insert(jar.workspace_id.clone(), cookies);
+        } else {
+            file.workspaces.remove(&jar.workspace_id);
+        }
+        Ok(())
+    })
+}
+
+/// Save cookies stored by responses since the last save, if the jar persists.
+pub(crate) fn save_if_changed(path: &Path, jar: &WorkspaceJar) -> Result<(), CommandError> {
+    if jar.persists() && jar.changed.swap(false, Ordering::Relaxed) {
+        save(path, jar)
+    } else {
+        Ok(())
+    }
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// The workspace's unexpired cookies, by domain, path and name.
+#[tauri::command]
+pub fn list_cookies(
+    app: AppHandle,
+    webview: tauri::Webview,
+    jars: State<'_, CookieJars>,
+    workspace_id: String,
+) -> Result<CookieJarContents, CommandError> {
+    ensure_main_window(webview.label())?;
+
+    Ok(jars.jar(&cookies_path(&app)?, &workspace_id)?.contents())
+}
+
+/// Add a cookie to the workspace's jar, or replace the one with the same
+/// domain, path and name. An `expires` in the past removes it.
+#[tauri::command]
+pub fn set_cookie(
+    app: AppHandle,
+    webview: tauri::Webview,
+    jars: State<'_, CookieJars>,
+    workspace_id: String,
+    cookie: StoredCookie,
+) -> Result<(), CommandError> {
+    ensure_main_window(webview.label())?;
+
+    let path = cookies_path(&app)?;
+    let jar = jars.jar(&path, &workspace_id)?;
+    set(&jar, &cookie)?;
+    if jar.persists() {
+        save(&path, &jar)?;
+    }
+    Ok(())
+}
+
+/// Remove one cookie; `false` when the jar has no such cookie.
+#[tauri::command]
+pub fn delete_cookie(
+    app: AppHandle,
+    webview: tauri::Webview,
+    jars: State<'_, CookieJars>,
+    workspace_id: String,
+    domain: String,
+    path: String,
+    name: String,
+) -> Result<bool, CommandError> {
+    ensure_main_window(webview.label())?;
+
+    let file = cookies_path(&app)?;
+    let jar = jars.jar(&file, &workspace_id)?;
+    let removed = jar.store().remove(&domain, &path, &name).is_some();
+    if removed && jar.persists() {
+        save(&file, &jar)?;
+    }
+    Ok(removed)
+}
+
+/// Empty the workspace's jar, e.g. to log out of a session-based API.
+#[tauri::command]
+pub fn clear_cookies(
+    app: AppHandle,
+    webview: tauri::Webview,
+    jars: State<'_, CookieJars>,
+    workspace_id: String,
+) -> Result<(), CommandError> {
+    ensure_main_window(webview.label())?;
+
+    let path = cookies_path(&app)?;
+    let jar = jars.jar(&path, &workspace_id)?;
+    jar.store().clear();
+    if jar.persists() {
+        save(&path, &jar)?;
+    }
+    Ok(())
+}
+
+/// Keep the workspace's cookies across restarts, or stop keeping them and
+/// forget the saved copy. Cookies in memory are unaffected either way.
+#[tauri::command]
+pub fn set_cookie_persistence(
+    app: AppHandle,
+    webview: tauri::Webview,
+    jars: State<'_, CookieJars>,
+    workspace_id: String,
+    persist: bool,
+) -> Result<(), CommandError> {
+    ensure_main_window(webview.label())?;
+
+    let path = cookies_path(&app)?;
+    let jar = jars.jar(&path, &workspace_id)?;
+    jar.persist.store(persist, Ordering::Relaxed);
+    save(&path, &jar)
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::cookie::CookieStore as _;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("yasp-cookies-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir.join(COOKIES_FILE)
+    }
+
+    fn cookie(name: &str, domain: &str) -> StoredCookie {
+        StoredCookie {
+            name: name.to_string(),
+            value: "abc123".to_string(),
+            domain: domain.to_string(),
+            host_only: false,
+            path: "/".to_string(),
+            expires: None,
+            secure: false,
+            http_only: true,
+        }
+    }
+
+    #[test]
+    fn test_response_cookies_are_sent_back_to_matching_urls() {
+        let jar = WorkspaceJar::new("ws", None);
+        let login = Url::parse("https://api.example.com/auth/login").unwrap();
+        let set_cookies = [
+            HeaderValue::from_static("session=s1; Path=/; HttpOnly"),
+            HeaderValue::from_static("pref=dark; Path=/auth"),
+        ];
+        jar.set_cookies(&mut set_cookies.iter(), &login);
+
+        let orders = Url::parse("https://api.example.com/orders").unwrap();
+        assert_eq!(jar.cookies(&orders).unwrap(), "session=s1");
+        let other = Url::parse("https://other.example.com/").unwrap();
+        assert!(jar.cookies(&other).is_none());
+
+        let contents = jar.contents();
+        assert_eq!(contents.cookies.len(), 2);
+        assert_eq!(contents.cookies[0].name, "session");
+        assert_eq!(contents.cookies[1].path, "/auth");
+        assert!(contents.cookies[0].host_only);
+        assert!(contents.cookies[0].http_only);
+    }
+
+    #[test]
+    fn test_edited_cookies_are_validated() {
+        let jar = WorkspaceJar::new("ws", None);
+        set(&jar, &cookie("token", ".example.com")).unwrap();
+        let url = Url::parse("https://api.example.com/v1").unwrap();
+        assert_eq!(jar.cookies(&url).unwrap(), "token=abc123");
+
+        for bad in [
+            cookie("bad name", "example.com"),
+            cookie("token", ""),
+            StoredCookie {
+                value: "a; Domain=evil.com".to_string(),
+                ..cookie("token", "example.com")
+            },
+        ] {
+            let err = set(&jar, &bad).unwrap_err();
+            assert_eq!(err.code, ErrorCode::InvalidCookie);
+        }
+
+        let (header, _) = to_set_cookie(
+            &StoredCookie {
+                expires: Some(1_000_060),
+                host_only: true,
+                ..cookie("token", "api.example.com")
+            },
+            1_000_000,
+        )
+        .unwrap();
+        assert_eq!(header, "token=abc123; Path=/; Max-Age=60; HttpOnly");
+    }
+
+    #[test]
+    fn test_persisted_jars_are_reloaded() {
+        let path = temp_path("persist");
+        let jars = CookieJars::default();
+        let jar = jars.jar(&path, "ws").unwrap();
+        set(&jar, &cookie("session", "example.com")).unwrap();
+        jar.persist.store(true, Ordering::Relaxed);
+        save(&path, &jar).unwrap();
+
+        let reloaded = CookieJars::default().jar(&path, "ws").unwrap();
+        assert!(reloaded.persists());
+        assert_eq!(reloaded.contents().cookies, jar.contents().cookies);
+        assert!(!CookieJars::default()
+            .jar(&path, "other")
+            .unwrap()
+            .persists());
+
+        jar.persist.store(false, Ordering::Relaxed);
+        save(&path, &jar).unwrap();
+        assert!(COOKIES.read(&path).unwrap().workspaces.is_empty());
+    }
+
+    #[test]
+    fn test_corrupt_file_is_never_overwritten() {
+        let path = temp_path("corrupt");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, b"{not json").unwrap();
+
+        let jar = WorkspaceJar::new("ws", None);
+        jar.persist.store(true, Ordering::Relaxed);
+        assert_eq!(
+            save(&path, &jar).unwrap_err().code,
+            ErrorCode::StorageFailed
+        );
+        let err = CookieJars::default().jar(&path, "ws").err().unwrap();
+        assert_eq!(err.code, ErrorCode::StorageFailed);
+        assert_eq!(std::fs::read(&path).unwrap(), b"{not json");
+    }
+}
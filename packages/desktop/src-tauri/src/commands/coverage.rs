@@ -0,0 +1,540 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::AppHandle;
+
+use super::drift::resolve;
+use super::ensure_main_window;
+use super::routes::{self, WorkspaceSpec};
+use super::store::CachedJsonStore;
+use crate::error::{CommandError, ErrorCode};
+
+const TRAFFIC_FILE: &str = "traffic.json";
+
+const METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Endpoints kept; the least recently called are dropped first.
+const MAX_ENDPOINTS: usize = 2_000;
+
+/// Parameter locations a request can be checked for. Request bodies
+/// (`body` and `formData` in Swagger 2) are not parameters here.
+const PARAMETER_LOCATIONS: &[&str] = &["path", "query", "header", "cookie"];
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+/// What the requests to one URL have exercised.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct ExercisedEndpoint {
+    /// Upper-case HTTP method.
+    method: String,
+    /// Request URL without its query.
+    url: String,
+    calls: u64,
+    /// Unix time in seconds of the latest call.
+    last_seen: u64,
+    statuses: BTreeSet<u16>,
+    query: BTreeSet<String>,
+    /// Lower-case header names.
+    headers: BTreeSet<String>,
+    cookies: BTreeSet<String>,
+}
+
+/// Requests answered by an API, one entry per method and URL, least
+/// recently called first.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(crate) struct TrafficStore {
+    endpoints: Vec<ExercisedEndpoint>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Tally {
+    pub exercised: usize,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct ParameterRef {
+    pub name: String,
+    /// `path`, `query`, `header` or `cookie`.
+    pub location: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OperationCoverage {
+    pub method: String,
+    pub path: String,
+    pub operation_id: Option<String>,
+    pub calls: u64,
+    pub last_seen: Option<u64>,
+    /// Declared response codes (`200`, `4XX`, `default`) some response had.
+    pub statuses_exercised: Vec<String>,
+    pub statuses_missing: Vec<String>,
+    /// Response statuses the operation does not declare.
+    pub statuses_undeclared: Vec<u16>,
+    pub parameters_exercised: Vec<ParameterRef>,
+    pub parameters_missing: Vec<ParameterRef>,
+}
+
+/// Which parts of a spec recorded traffic has exercised.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SpecCoverage {
+    pub spec_id: String,
+    pub operations: Tally,
+    pub statuses: Tally,
+    pub parameters: Tally,
+    /// By path, then method.
+    pub details: Vec<OperationCoverage>,
+}
+
+// ─── Sampling ────────────────────────────────────────────────────────────────
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Add a request and its response status to its endpoint, dropping the
+/// least recently called endpoint when full.
+fn record(
+    store: &mut TrafficStore,
+    method: &str,
+    url: &str,
+    headers: &HashMap<String, String>,
+    status: u16,
+) {
+    let Ok(parsed) = url::Url::parse(url) else {
+        return;
+    };
+    let mut endpoint = ExercisedEndpoint {
+        method: method.to_ascii_uppercase(),
+        url: url.split(['?', '#']).next().unwrap_or(url).to_string(),
+        calls: 0,
+        last_seen: 0,
+        statuses: BTreeSet::new(),
+        query: BTreeSet::new(),
+        headers: BTreeSet::new(),
+        cookies: BTreeSet::new(),
+    };
+    let index = store
+        .endpoints
+        .iter()
+        .position(|seen| (&seen.method, &seen.url) == (&endpoint.method, &endpoint.url));
+    if let Some(index) = index {
+        endpoint = store.endpoints.remove(index);
+    }
+
+    endpoint.calls += 1;
+    endpoint.last_seen = now();
+    endpoint.statuses.insert(status);
+    endpoint
+        .query
+        .extend(parsed.query_pairs().map(|(name, _)| name.into_owned()));
+    for (name, value) in headers {
+        let name = name.to_ascii_lowercase();
+        if name == "cookie" {
+            let names = value.split(';').filter_map(|pair| pair.split('=').next());
+            endpoint.cookies.extend(
+                names
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .map(str::to_string),
+            );
+        }
+        endpoint.headers.insert(name);
+    }
+
+    store.endpoints.push(endpoint);
+    if store.endpoints.len() > MAX_ENDPOINTS {
+        store.endpoints.remove(0);
+    }
+}
+
+// ─── Coverage ────────────────────────────────────────────────────────────────
+
+/// Path-item parameters overridden by operation parameters of the same
+/// name and location.
+fn parameters(spec: &Value, item: &Value, operation: &Value) -> Vec<ParameterRef> {
+    let mut declared = BTreeMap::new();
+    for parameter in item["parameters"]
+        .as_array()
+        .into_iter()
+        .chain(operation["parameters"].as_array())
+        .flatten()
+    {
+        let parameter = resolve(spec, parameter);
+        let (Some(name), Some(location)) = (parameter["name"].as_str(), parameter["in"].as_str())
+        else {
+            continue;
+        };
+        if PARAMETER_LOCATIONS.contains(&location) {
+            declared.insert((location, name.to_ascii_lowercase()), name);
+        }
+    }
+    declared
+        .into_iter()
+        .map(|((location, _), name)| ParameterRef {
+            name: name.to_string(),
+            location: location.to_string(),
+        })
+        .collect()
+}
+
+fn exercised(parameter: &ParameterRef, endpoint: &ExercisedEndpoint) -> bool {
+    match parameter.location.as_str() {
+        // The request matched the path template, so it had every path parameter
+        "path" => true,
+        "query" => endpoint.query.contains(&parameter.name),
+        "header" => endpoint
+            .headers
+            .contains(&parameter.name.to_ascii_lowercase()),
+        "cookie" => endpoint.cookies.contains(&parameter.name),
+        _ => false,
+    }
+}
+
+/// The declared response code `status` falls under: the exact code, else
+/// its range (`2XX`), else `default`.
+fn declared_status(codes: &[String], status: u16) -> Option<&String> {
+    let exact = status.to_string();
+    let range = format!("{}XX", status / 100);
+    codes
+        .iter()
+        .find(|code| **code == exact)
+        .or_else(|| codes.iter().find(|code| code.eq_ignore_ascii_case(&range)))
+        .or_else(|| codes.iter().find(|code| *code == "default"))
+}
+
+fn operation_coverage(
+    spec: &Value,
+    path: &str,
+    item: &Value,
+    method: &str,
+    operation: &Value,
+    endpoints: &[&ExercisedEndpoint],
+) -> OperationCoverage {
+    let codes: Vec<String> = operation["responses"]
+        .as_object()
+        .into_iter()
+        .flat_map(|responses| responses.keys().cloned())
+        .collect();
+    let statuses: BTreeSet<u16> = endpoints
+        .iter()
+        .flat_map(|endpoint| endpoint.statuses.iter().copied())
+        .collect();
+    let hit: BTreeSet<&String> = statuses
+        .iter()
+        .filter_map(|status| declared_status(&codes, *status))
+        .collect();
+    let (statuses_exercised, statuses_missing) =
+        codes.iter().cloned().partition(|code| hit.contains(code));
+
+    let (parameters_exercised, parameters_missing) = parameters(spec, item, operation)
+        .into_iter()
+        .partition(|parameter| {
+            endpoints
+                .iter()
+                .any(|endpoint| exercised(parameter, endpoint))
+        });
+
+    OperationCoverage {
+        method: method.to_ascii_uppercase(),
+        path: path.to_string(),
+        operation_id: operation["operationId"].as_str().map(str::to_string),
+        calls: endpoints.iter().map(|endpoint| endpoint.calls).sum(),
+        last_seen: endpoints.iter().map(|endpoint| endpoint.last_seen).max(),
+        statuses_exercised,
+        statuses_missing,
+        statuses_undeclared: statuses
+            .into_iter()
+            .filter(|status| declared_status(&codes, *status).is_none())
+            .collect(),
+        parameters_exercised,
+        parameters_missing,
+    }
+}
+
+/// Coverage of the spec `spec_id`. Calls are matched against every
+/// workspace spec, so an endpoint counts for the spec that describes it
+/// most specifically.
+fn coverage(
+    specs: &[WorkspaceSpec],
+    spec_id: &str,
+    endpoints: &[ExercisedEndpoint],
+) -> Result<SpecCoverage, CommandError> {
+    let spec = &specs
+        .iter()
+        .find(|spec| spec.id == spec_id)
+        .ok_or_else(|| CommandError::new(ErrorCode::WorkspaceSpecNotFound).arg("spec_id", spec_id))?
+        .spec;
+
+    let mut calls: HashMap<(String, String), Vec<&ExercisedEndpoint>> = HashMap::new();
+    for endpoint in endpoints {
+        let Some(route) = url::Url::parse(&endpoint.url)
+            .ok()
+            .and_then(|url| routes::match_route(specs, &endpoint.method, url.path()))
+            .filter(|route| route.spec_id == spec_id)
+        else {
+            continue;
+        };
+        calls
+            .entry((endpoint.method.clone(), route.path))
+            .or_default()
+            .push(endpoint);
+    }
+
+    let mut report = SpecCoverage {
+        spec_id: spec_id.to_string(),
+        operations: Tally::default(),
+        statuses: Tally::default(),
+        parameters: Tally::default(),
+        details: Vec::new(),
+    };
+    for (path, item) in spec["paths"].as_object().into_iter().flatten() {
+        for method in METHODS {
+            let Some(operation) = item.get(*method).filter(|op| op.is_object()) else {
+                continue;
+            };
+            let key = (method.to_ascii_uppercase(), path.clone());
+            let endpoints = calls.get(&key).map(Vec::as_slice).unwrap_or_default();
+            let details = operation_coverage(spec, path, item, method, operation, endpoints);
+
+            report.operations.total += 1;
+            report.operations.exercised += usize::from(details.calls > 0);
+            report.statuses.exercised += details.statuses_exercised.len();
+            report.statuses.total +=
+                details.statuses_exercised.len() + details.statuses_missing.len();
+            report.parameters.exercised += details.parameters_exercised.len();
+            report.parameters.total +=
+                details.parameters_exercised.len() + details.parameters_missing.len();
+            report.details.push(details);
+        }
+    }
+    Ok(report)
+}
+
+impl TrafficStore {
+    /// Record a request, as `observe` does for the GUI's requests.
+    pub(crate) fn record(
+        &mut self,
+        method: &str,
+        url: &str,
+        headers: &HashMap<String, String>,
+        status: u16,
+    ) {
+        record(self, method, url, headers, status);
+    }
+
+    pub(crate) fn coverage(
+        &self,
+        specs: &[WorkspaceSpec],
+        spec_id: &str,
+    ) -> Result<SpecCoverage, CommandError> {
+        coverage(specs, spec_id, &self.endpoints)
+    }
+}
+
+// ─── Storage ─────────────────────────────────────────────────────────────────
+
+static TRAFFIC: CachedJsonStore<TrafficStore> = CachedJsonStore::new(TRAFFIC_FILE);
+
+/// Record which parts of its endpoint a request exercised. Only names are
+/// kept, never parameter or header values.
+pub(crate) async fn observe(
+    app: &AppHandle,
+    method: &str,
+    url: &str,
+    headers: &HashMap<String, String>,
+    status: u16,
+) -> Result<(), CommandError> {
+    let path = TRAFFIC.path(app)?;
+    let (method, url, headers) = (method.to_string(), url.to_string(), headers.clone());
+    tauri::async_runtime::spawn_blocking(move || {
+        TRAFFIC.update(&path, |store| {
+            record(store, &method, &url, &headers, status)
+        })
+    })
+    .await
+    .map_err(|e| CommandError::new(ErrorCode::StorageFailed).detail(e))?
+}
+
+/// Write the traffic recorded since the last save, e.g. before the app exits.
+pub(crate) fn save(app: &AppHandle) -> Result<(), CommandError> {
+    TRAFFIC.save(&TRAFFIC.path(app)?)
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// Which operations, response codes and parameters of a spec the requests
+/// sent so far have exercised. `specs` are the workspace's specs, with
+/// `spec_id` among them.
+#[tauri::command]
+pub fn get_coverage(
+    app: AppHandle,
+    spec_id: String,
+    specs: Vec<WorkspaceSpec>,
+) -> Result<SpecCoverage, CommandError> {
+    TRAFFIC.read(&TRAFFIC.path(&app)?, |store| {
+        coverage(&specs, &spec_id, &store.endpoints)
+    })?
+}
+
+/// Forget the recorded traffic, to measure coverage afresh.
+#[tauri::command]
+pub fn clear_coverage(app: AppHandle, webview: tauri::Webview) -> Result<(), CommandError> {
+    ensure_main_window(webview.label())?;
+
+    TRAFFIC.replace(&TRAFFIC.path(&app)?, TrafficStore::default());
+    Ok(())
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn specs() -> Vec<WorkspaceSpec> {
+        vec![WorkspaceSpec {
+            id: "pets".to_string(),
+            spec: json!({
+                "openapi": "3.0.3",
+                "servers": [{ "url": "https://api.example.com/v1" }],
+                "paths": {
+                    "/pets": {
+                        "get": {
+                            "operationId": "listPets",
+                            "parameters": [
+                                { "name": "limit", "in": "query" },
+                                { "$ref": "#/components/parameters/Tenant" }
+                            ],
+                            "responses": { "200": {}, "4XX": {}, "default": {} }
+                        },
+                        "post": { "responses": { "201": {} } }
+                    },
+                    "/pets/{id}": {
+                        "parameters": [{ "name": "id", "in": "path", "required": true }],
+                        "get": {
+                            "parameters": [{ "name": "session", "in": "cookie" }],
+                            "responses": { "200": {}, "404": {} }
+                        }
+                    }
+                },
+                "components": {
+                    "parameters": { "Tenant": { "name": "X-Tenant", "in": "header" } }
+                }
+            }),
+        }]
+    }
+
+    fn parameter(name: &str, location: &str) -> ParameterRef {
+        ParameterRef {
+            name: name.to_string(),
+            location: location.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_coverage_counts_exercised_operations_statuses_and_parameters() {
+        let mut store = TrafficStore::default();
+        let tenant = HashMap::from([("x-tenant".to_string(), "acme".to_string())]);
+        let session =
+            HashMap::from([("Cookie".to_string(), "session=abc; theme=dark".to_string())]);
+        record(
+            &mut store,
+            "get",
+            "https://api.example.com/v1/pets?limit=5",
+            &HashMap::new(),
+            200,
+        );
+        record(
+            &mut store,
+            "GET",
+            "https://api.example.com/v1/pets",
+            &tenant,
+            429,
+        );
+        record(
+            &mut store,
+            "GET",
+            "https://api.example.com/v1/pets/7",
+            &session,
+            200,
+        );
+        record(
+            &mut store,
+            "GET",
+            "https://api.example.com/v1/pets/8",
+            &HashMap::new(),
+            302,
+        );
+        record(
+            &mut store,
+            "GET",
+            "https://api.example.com/v1/owners",
+            &HashMap::new(),
+            200,
+        );
+        assert_eq!(store.endpoints.len(), 4);
+        assert_eq!(store.endpoints[0].calls, 2);
+
+        let report = coverage(&specs(), "pets", &store.endpoints).unwrap();
+        assert_eq!(
+            report.operations,
+            Tally {
+                exercised: 2,
+                total: 3
+            }
+        );
+        assert_eq!(
+            report.statuses,
+            Tally {
+                exercised: 3,
+                total: 6
+            }
+        );
+        assert_eq!(
+            report.parameters,
+            Tally {
+                exercised: 4,
+                total: 4
+            }
+        );
+
+        let list = &report.details[0];
+        assert_eq!(list.operation_id.as_deref(), Some("listPets"));
+        assert_eq!(list.calls, 2);
+        assert_eq!(list.statuses_exercised, vec!["200", "4XX"]);
+        assert_eq!(list.statuses_missing, vec!["default"]);
+        assert_eq!(
+            list.parameters_exercised,
+            vec![parameter("X-Tenant", "header"), parameter("limit", "query")]
+        );
+
+        let create = &report.details[1];
+        assert_eq!((create.method.as_str(), create.calls), ("POST", 0));
+        assert_eq!(create.statuses_missing, vec!["201"]);
+
+        let get = &report.details[2];
+        assert_eq!(get.calls, 2);
+        assert_eq!(get.statuses_missing, vec!["404"]);
+        assert_eq!(get.statuses_undeclared, vec![302]);
+        assert_eq!(
+            get.parameters_exercised,
+            vec![parameter("session", "cookie"), parameter("id", "path")]
+        );
+    }
+
+    #[test]
+    fn test_unknown_spec_is_an_error() {
+        let err = coverage(&specs(), "billing", &[]).unwrap_err();
+        assert_eq!(err.code, ErrorCode::WorkspaceSpecNotFound);
+    }
+}
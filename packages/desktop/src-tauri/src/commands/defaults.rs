@@ -0,0 +1,569 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::drift::resolve;
+use super::ApiRequest;
+use crate::error::{CommandError, ErrorCode};
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+/// The spec operation a request is built for.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct SpecOperation {
+    pub spec: Value,
+    /// The path as written in the spec, e.g. `/pets/{petId}`.
+    pub path: String,
+    /// Credentials by security scheme name: `user:password` for HTTP basic,
+    /// else the token or API key.
+    #[serde(default)]
+    pub credentials: HashMap<String, String>,
+}
+
+// Never print the credentials, even in debug logs.
+impl fmt::Debug for SpecOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpecOperation")
+            .field("path", &self.path)
+            .field("credentials", &self.credentials.keys().collect::<Vec<_>>())
+            .finish_non_exhaustive()
+    }
+}
+
+/// How a security scheme's credential is sent.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AuthKind {
+    /// `Authorization: Basic`.
+    Basic,
+    /// `Authorization: Bearer`.
+    Bearer { bearer_format: Option<String> },
+    /// `location` is `header`, `query` or `cookie`.
+    ApiKey { location: String, name: String },
+    /// A bearer token obtained from the flows of the scheme.
+    #[serde(rename = "oauth2")]
+    OAuth2 { scopes: Vec<String> },
+    /// A bearer token obtained from the provider at `url`.
+    OpenIdConnect { url: String, scopes: Vec<String> },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AuthScheme {
+    /// Name of the scheme in the spec.
+    pub name: String,
+    #[serde(flatten)]
+    pub kind: AuthKind,
+}
+
+/// What a request for an operation starts from.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct RequestDefaults {
+    /// `Accept` from the success responses' content types and
+    /// `Content-Type` from the request body's.
+    pub headers: BTreeMap<String, String>,
+    /// Schemes of the security requirement to satisfy, all of them; empty
+    /// when the operation needs no credentials.
+    pub auth: Vec<AuthScheme>,
+}
+
+// ─── Headers ─────────────────────────────────────────────────────────────────
+
+fn is_json(content_type: &str) -> bool {
+    let essence = content_type.split(';').next().unwrap_or("").trim();
+    essence == "application/json" || essence.ends_with("+json")
+}
+
+/// Swagger 2 media types of the operation, else of the spec.
+fn media_types<'a>(spec: &'a Value, operation: &'a Value, field: &str) -> Vec<&'a str> {
+    operation[field]
+        .as_array()
+        .or_else(|| spec[field].as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect()
+}
+
+/// Content types of the success responses, JSON first.
+fn accept(spec: &Value, operation: &Value) -> Vec<String> {
+    let mut types: Vec<String> = Vec::new();
+    if spec.get("swagger").is_some() {
+        types.extend(
+            media_types(spec, operation, "produces")
+                .into_iter()
+                .map(str::to_string),
+        );
+    } else {
+        for (code, response) in operation["responses"].as_object().into_iter().flatten() {
+            if !code.starts_with('2') {
+                continue;
+            }
+            let content = resolve(spec, response)["content"].as_object();
+            for content_type in content.into_iter().flat_map(|content| content.keys()) {
+                if !types.contains(content_type) {
+                    types.push(content_type.clone());
+                }
+            }
+        }
+    }
+    types.sort_by_key(|content_type| !is_json(content_type));
+    types
+}
+
+/// The request body's content type, JSON when the operation takes it.
+/// Multipart bodies are left out: their boundary is generated per request.
+fn content_type(spec: &Value, operation: &Value) -> Option<String> {
+    let types: Vec<&str> = if spec.get("swagger").is_some() {
+        let has_body = operation["parameters"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .any(|parameter| {
+                matches!(
+                    resolve(spec, parameter)["in"].as_str(),
+                    Some("body" | "formData")
+                )
+            });
+        if !has_body {
+            return None;
+        }
+        media_types(spec, operation, "consumes")
+    } else {
+        let content = resolve(spec, &operation["requestBody"])["content"].as_object();
+        content
+            .into_iter()
+            .flat_map(|content| content.keys())
+            .map(String::as_str)
+            .collect()
+    };
+    let types: Vec<&str> = types
+        .into_iter()
+        .filter(|content_type| !content_type.starts_with("multipart/"))
+        .collect();
+    types
+        .iter()
+        .find(|content_type| is_json(content_type))
+        .or(types.first())
+        .map(|content_type| content_type.to_string())
+}
+
+// ─── Security ────────────────────────────────────────────────────────────────
+
+/// The scheme named `name`, if requests can satisfy it. HTTP schemes other
+/// than basic and bearer (digest, mutual TLS) cannot.
+fn scheme(spec: &Value, name: &str, scopes: &Value) -> Option<AuthScheme> {
+    let definition = spec["components"]["securitySchemes"]
+        .get(name)
+        .or_else(|| spec["securityDefinitions"].get(name))?;
+    let definition = resolve(spec, definition);
+    let scopes: Vec<String> = scopes
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|scope| scope.as_str().map(str::to_string))
+        .collect();
+    let kind = match definition["type"].as_str()? {
+        // Swagger 2
+        "basic" => AuthKind::Basic,
+        "http" => match definition["scheme"].as_str()?.to_ascii_lowercase().as_str() {
+            "basic" => AuthKind::Basic,
+            "bearer" => AuthKind::Bearer {
+                bearer_format: definition["bearerFormat"].as_str().map(str::to_string),
+            },
+            _ => return None,
+        },
+        "apiKey" => AuthKind::ApiKey {
+            location: definition["in"].as_str()?.to_string(),
+            name: definition["name"].as_str()?.to_string(),
+        },
+        "oauth2" => AuthKind::OAuth2 { scopes },
+        "openIdConnect" => AuthKind::OpenIdConnect {
+            url: definition["openIdConnectUrl"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+            scopes,
+        },
+        _ => return None,
+    };
+    Some(AuthScheme {
+        name: name.to_string(),
+        kind,
+    })
+}
+
+/// The security requirement to satisfy: the first one with credentials
+/// `available` for all its schemes, else the first one requests can
+/// satisfy. The operation's requirements replace the spec's.
+fn select_auth(spec: &Value, operation: &Value, available: &[String]) -> Vec<AuthScheme> {
+    let requirements = operation
+        .get("security")
+        .or_else(|| spec.get("security"))
+        .and_then(Value::as_array);
+    let options: Vec<Vec<AuthScheme>> = requirements
+        .into_iter()
+        .flatten()
+        .filter_map(|requirement| {
+            requirement
+                .as_object()?
+                .iter()
+                .map(|(name, scopes)| scheme(spec, name, scopes))
+                .collect()
+        })
+        .collect();
+    let mut options = options.into_iter();
+    let first = options.next().unwrap_or_default();
+    let credentialed = |option: &Vec<AuthScheme>| {
+        !option.is_empty() && option.iter().all(|scheme| available.contains(&scheme.name))
+    };
+    if credentialed(&first) {
+        return first;
+    }
+    options.find(credentialed).unwrap_or(first)
+}
+
+fn has_header(headers: &HashMap<String, String>, name: &str) -> bool {
+    headers.keys().any(|key| key.eq_ignore_ascii_case(name))
+}
+
+/// Whether the `Cookie` header value `cookies` sets the cookie `name`.
+fn has_cookie(cookies: &str, name: &str) -> bool {
+    cookies
+        .split(';')
+        .any(|pair| pair.trim().split('=').next() == Some(name))
+}
+
+/// Send `credential` as `scheme` asks, unless the request already carries
+/// a credential in that place.
+fn authorize(
+    scheme: &AuthScheme,
+    credential: &str,
+    url: &mut url::Url,
+    headers: &mut HashMap<String, String>,
+) {
+    let authorization = match &scheme.kind {
+        AuthKind::Basic => format!(
+            "Basic {}",
+            base64::engine::general_purpose::STANDARD.encode(credential)
+        ),
+        AuthKind::Bearer { .. } | AuthKind::OAuth2 { .. } | AuthKind::OpenIdConnect { .. } => {
+            format!("Bearer {credential}")
+        }
+        AuthKind::ApiKey { location, name } => {
+            match location.as_str() {
+                "header" if !has_header(headers, name) => {
+                    headers.insert(name.clone(), credential.to_string());
+                }
+                "query" if !url.query_pairs().any(|(key, _)| key == name.as_str()) => {
+                    url.query_pairs_mut().append_pair(name, credential);
+                }
+                "cookie" => {
+                    let pair = format!("{name}={credential}");
+                    match headers
+                        .iter_mut()
+                        .find(|(key, _)| key.eq_ignore_ascii_case("cookie"))
+                    {
+                        Some((_, cookies)) if has_cookie(cookies, name) => {}
+                        Some((_, cookies)) => *cookies = format!("{cookies}; {pair}"),
+                        None => {
+                            headers.insert("Cookie".to_string(), pair);
+                        }
+                    }
+                }
+                _ => {}
+            }
+            return;
+        }
+    };
+    if !has_header(headers, "authorization") {
+        headers.insert("Authorization".to_string(), authorization);
+    }
+}
+
+// ─── Resolution ──────────────────────────────────────────────────────────────
+
+/// Defaults of the `method` operation at `path` of `spec`, given the
+/// schemes credentials are `available` for.
+fn request_defaults(
+    spec: &Value,
+    method: &str,
+    path: &str,
+    available: &[String],
+) -> Result<RequestDefaults, CommandError> {
+    let operation = spec["paths"]
+        .get(path)
+        .map(|item| resolve(spec, item))
+        .and_then(|item| item.get(method.to_ascii_lowercase()))
+        .filter(|operation| operation.is_object())
+        .ok_or_else(|| {
+            CommandError::new(ErrorCode::OperationNotFound)
+                .arg("method", method.to_ascii_uppercase())
+                .arg("path", path)
+        })?;
+
+    let mut headers = BTreeMap::new();
+    let accepted = accept(spec, operation);
+    if !accepted.is_empty() {
+        headers.insert("Accept".to_string(), accepted.join(", "));
+    }
+    if let Some(content_type) = content_type(spec, operation) {
+        headers.insert("Content-Type".to_string(), content_type);
+    }
+    Ok(RequestDefaults {
+        headers,
+        auth: select_auth(spec, operation, available),
+    })
+}
+
+/// `request` with its operation's defaults filled in: headers it leaves
+/// unset, `Content-Type` only when it has a body, and the credentials of
+/// the selected security requirement.
+pub(super) fn apply_defaults(request: &ApiRequest) -> Result<ApiRequest, CommandError> {
+    let Some(operation) = &request.operation else {
+        return Ok(request.clone());
+    };
+    let available: Vec<String> = operation.credentials.keys().cloned().collect();
+    let defaults = request_defaults(
+        &operation.spec,
+        &request.method,
+        &operation.path,
+        &available,
+    )?;
+
+    let mut headers = request.headers.clone();
+    let has_body = request.body.is_some() || request.body_file.is_some();
+    for (name, value) in defaults.headers {
+        if (has_body || name != "Content-Type") && !has_header(&headers, &name) {
+            headers.insert(name, value);
+        }
+    }
+    let mut url = url::Url::parse(&request.url)
+        .map_err(|e| CommandError::new(ErrorCode::InvalidUrl).detail(e))?;
+    for scheme in &defaults.auth {
+        if let Some(credential) = operation.credentials.get(&scheme.name) {
+            authorize(scheme, credential, &mut url, &mut headers);
+        }
+    }
+    Ok(ApiRequest {
+        url: url.into(),
+        headers,
+        operation: None,
+        ..request.clone()
+    })
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// Headers and security schemes a new request for the `method` operation at
+/// `path` starts from. The security requirement is picked among those the
+/// schemes in `available` (those with credentials set up) satisfy, if any.
+///
+/// `execute_api_request` applies the same defaults to requests sent with
+/// their `operation`.
+#[tauri::command]
+pub fn get_request_defaults(
+    spec: Value,
+    method: String,
+    path: String,
+    available: Option<Vec<String>>,
+) -> Result<RequestDefaults, CommandError> {
+    request_defaults(&spec, &method, &path, &available.unwrap_or_default())
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn spec() -> Value {
+        json!({
+            "openapi": "3.0.3",
+            "security": [{ "apiKey": [] }],
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "responses": {
+                            "200": {
+                                "content": {
+                                    "application/xml": {},
+                                    "application/json": {}
+                                }
+                            },
+                            "404": { "content": { "application/problem+json": {} } }
+                        }
+                    },
+                    "post": {
+                        "security": [
+                            { "oauth": ["pets:write"] },
+                            { "basic": [], "apiKey": [] }
+                        ],
+                        "requestBody": {
+                            "content": {
+                                "multipart/form-data": {},
+                                "application/x-www-form-urlencoded": {}
+                            }
+                        },
+                        "responses": { "201": { "description": "Created" } }
+                    },
+                    "delete": { "security": [], "responses": {} }
+                }
+            },
+            "components": {
+                "securitySchemes": {
+                    "apiKey": { "type": "apiKey", "in": "query", "name": "key" },
+                    "basic": { "type": "http", "scheme": "Basic" },
+                    "oauth": { "type": "oauth2", "flows": {} }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_defaults_come_from_content_types_and_security() {
+        let none = [];
+        let get = request_defaults(&spec(), "GET", "/pets", &none).unwrap();
+        assert_eq!(
+            get.headers,
+            BTreeMap::from([(
+                "Accept".to_string(),
+                "application/json, application/xml".to_string()
+            )])
+        );
+        assert_eq!(
+            get.auth,
+            vec![AuthScheme {
+                name: "apiKey".to_string(),
+                kind: AuthKind::ApiKey {
+                    location: "query".to_string(),
+                    name: "key".to_string(),
+                },
+            }]
+        );
+
+        let post = request_defaults(&spec(), "post", "/pets", &none).unwrap();
+        assert_eq!(
+            post.headers["Content-Type"],
+            "application/x-www-form-urlencoded"
+        );
+        assert_eq!(
+            post.auth[0].kind,
+            AuthKind::OAuth2 {
+                scopes: vec!["pets:write".to_string()]
+            }
+        );
+
+        // Credentials at hand pick the requirement
+        let available = ["basic".to_string(), "apiKey".to_string()];
+        let post = request_defaults(&spec(), "POST", "/pets", &available).unwrap();
+        let names: Vec<&str> = post
+            .auth
+            .iter()
+            .map(|scheme| scheme.name.as_str())
+            .collect();
+        assert_eq!(names, ["apiKey", "basic"]);
+
+        let delete = request_defaults(&spec(), "DELETE", "/pets", &available).unwrap();
+        assert!(delete.auth.is_empty());
+        assert_eq!(
+            request_defaults(&spec(), "PUT", "/pets", &none)
+                .unwrap_err()
+                .code,
+            ErrorCode::OperationNotFound
+        );
+    }
+
+    #[test]
+    fn test_swagger2_defaults() {
+        let spec = json!({
+            "swagger": "2.0",
+            "produces": ["application/json"],
+            "consumes": ["application/xml", "application/json"],
+            "securityDefinitions": { "basic": { "type": "basic" } },
+            "paths": {
+                "/pets": {
+                    "get": { "security": [{ "basic": [] }], "responses": {} },
+                    "post": {
+                        "parameters": [{ "in": "body", "name": "pet" }],
+                        "responses": {}
+                    }
+                }
+            }
+        });
+        let get = request_defaults(&spec, "GET", "/pets", &[]).unwrap();
+        assert_eq!(get.headers["Accept"], "application/json");
+        assert!(!get.headers.contains_key("Content-Type"));
+        assert_eq!(get.auth[0].kind, AuthKind::Basic);
+
+        let post = request_defaults(&spec, "POST", "/pets", &[]).unwrap();
+        assert_eq!(post.headers["Content-Type"], "application/json");
+    }
+
+    #[test]
+    fn test_request_keeps_what_it_sets() {
+        let request = ApiRequest {
+            method: "POST".to_string(),
+            url: "https://api.example.com/pets?key=mine".to_string(),
+            headers: HashMap::from([("accept".to_string(), "text/csv".to_string())]),
+            body: Some("name=Rex".to_string()),
+            operation: Some(SpecOperation {
+                spec: spec(),
+                path: "/pets".to_string(),
+                credentials: HashMap::from([
+                    ("basic".to_string(), "alice:hunter2".to_string()),
+                    ("apiKey".to_string(), "k3y".to_string()),
+                ]),
+            }),
+            ..Default::default()
+        };
+        let resolved = apply_defaults(&request).unwrap();
+        assert!(resolved.operation.is_none());
+        assert_eq!(resolved.url, "https://api.example.com/pets?key=mine");
+        assert_eq!(resolved.headers["accept"], "text/csv");
+        assert!(!resolved.headers.contains_key("Accept"));
+        assert_eq!(
+            resolved.headers["Content-Type"],
+            "application/x-www-form-urlencoded"
+        );
+        assert_eq!(
+            resolved.headers["Authorization"],
+            "Basic YWxpY2U6aHVudGVyMg=="
+        );
+        assert!(!format!("{request:?}").contains("hunter2"));
+
+        let get = ApiRequest {
+            method: "GET".to_string(),
+            url: "https://api.example.com/pets".to_string(),
+            body: None,
+            ..request
+        };
+        let resolved = apply_defaults(&get).unwrap();
+        assert_eq!(resolved.url, "https://api.example.com/pets?key=k3y");
+        assert!(!resolved.headers.contains_key("Content-Type"));
+        assert!(!resolved.headers.contains_key("Authorization"));
+    }
+
+    #[test]
+    fn test_api_key_cookie_matches_the_name_exactly() {
+        let scheme = AuthScheme {
+            name: "session".to_string(),
+            kind: AuthKind::ApiKey {
+                location: "cookie".to_string(),
+                name: "sid".to_string(),
+            },
+        };
+        let mut url = url::Url::parse("https://api.example.com/pets").unwrap();
+        let mut headers = HashMap::from([("Cookie".to_string(), "xsid=1; theme=dark".to_string())]);
+        authorize(&scheme, "s3cret", &mut url, &mut headers);
+        assert_eq!(headers["Cookie"], "xsid=1; theme=dark; sid=s3cret");
+
+        let mut headers =
+            HashMap::from([("cookie".to_string(), "theme=dark; sid=mine".to_string())]);
+        authorize(&scheme, "s3cret", &mut url, &mut headers);
+        assert_eq!(headers["cookie"], "theme=dark; sid=mine");
+    }
+}
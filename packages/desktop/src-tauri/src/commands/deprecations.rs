@@ -0,0 +1,589 @@
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::AppHandle;
+use tauri_plugin_notification::NotificationExt;
+
+use super::aws::amz_date;
+use super::ensure_main_window;
+use super::extensions::parse_date;
+use super::routes::{self, RouteRef, WorkspaceSpec};
+use super::store::JsonStore;
+use crate::error::CommandError;
+
+const DEPRECATIONS_FILE: &str = "deprecations.json";
+
+const METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Extensions holding the date an operation goes away.
+const REMOVAL_EXTENSIONS: &[&str] = &["x-sunset", "x-removal-date"];
+
+/// Observations kept; the oldest are dropped first.
+const MAX_OBSERVATIONS: usize = 1_000;
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+/// A response that announced its endpoint's deprecation through the
+/// `Deprecation` (RFC 9745) or `Sunset` (RFC 8594) headers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ObservedDeprecation {
+    /// Upper-case HTTP method.
+    pub method: String,
+    /// Request URL without its query.
+    pub url: String,
+    /// `YYYY-MM-DD` when the header holds a date, else its raw value.
+    pub deprecation: Option<String>,
+    /// `YYYY-MM-DD` when the header holds a date, else its raw value.
+    pub sunset: Option<String>,
+    /// `Link` with `rel="deprecation"` or `rel="sunset"`: migration notes.
+    pub link: Option<String>,
+    /// Unix time in seconds of the latest such response.
+    pub observed_at: u64,
+}
+
+/// Deprecation headers seen in responses, one per method and URL, oldest
+/// first.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct DeprecationStore {
+    observed: Vec<ObservedDeprecation>,
+}
+
+/// An operation that is deprecated, scheduled for removal, or answered as
+/// such by the API.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DeprecatedOperation {
+    pub spec_id: String,
+    pub method: String,
+    pub path: String,
+    pub operation_id: Option<String>,
+    /// `deprecated: true` in the spec.
+    pub deprecated: bool,
+    /// From `x-deprecated-date`.
+    pub deprecated_since: Option<String>,
+    /// From `x-sunset` or `x-removal-date` on the operation or its path
+    /// item, else from an observed `Sunset` header.
+    pub removal_date: Option<String>,
+    /// The latest response from this operation with deprecation headers.
+    pub observed: Option<ObservedDeprecation>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DeprecationReport {
+    /// Soonest removal first; operations without a date last.
+    pub operations: Vec<DeprecatedOperation>,
+    /// Observed deprecations no spec operation matches.
+    pub unmatched: Vec<ObservedDeprecation>,
+}
+
+/// A request of a collection about to run.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PlannedRequest {
+    /// Frontend id of the saved request.
+    pub id: String,
+    pub method: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DeprecationWarning {
+    pub request_id: String,
+    pub operation: DeprecatedOperation,
+}
+
+// ─── Headers ─────────────────────────────────────────────────────────────────
+
+/// `YYYY-MM-DD` of an IMF-fixdate, `Sun, 06 Nov 1994 08:49:37 GMT`.
+fn http_date(value: &str) -> Option<String> {
+    let mut parts = value.split_whitespace().skip(1);
+    let day: u32 = parts.next()?.parse().ok()?;
+    let month = parts.next()?;
+    let month = MONTHS.iter().position(|m| *m == month)? + 1;
+    let year: u32 = parts.next()?.parse().ok()?;
+    Some(format!("{year:04}-{month:02}-{day:02}"))
+}
+
+/// `YYYY-MM-DD` of a structured field date, `@1688169599`.
+fn unix_date(value: &str) -> Option<String> {
+    let secs: u64 = value.strip_prefix('@')?.parse().ok()?;
+    let stamp = amz_date(UNIX_EPOCH + Duration::from_secs(secs));
+    Some(format!("{}-{}-{}", &stamp[..4], &stamp[4..6], &stamp[6..8]))
+}
+
+fn header_date(value: &str) -> String {
+    let value = value.trim();
+    unix_date(value)
+        .or_else(|| http_date(value))
+        .unwrap_or_else(|| value.to_string())
+}
+
+/// Target of the first `Link` entry with a `deprecation` or `sunset` relation.
+fn deprecation_link(link: &str) -> Option<String> {
+    link.split(',').find_map(|entry| {
+        let (target, params) = entry.split_once(';')?;
+        let relation = params.split(';').any(|param| {
+            let param = param.trim().to_ascii_lowercase();
+            param == "rel=\"deprecation\""
+                || param == "rel=\"sunset\""
+                || param == "rel=deprecation"
+                || param == "rel=sunset"
+        });
+        relation.then(|| {
+            target
+                .trim()
+                .trim_start_matches('<')
+                .trim_end_matches('>')
+                .to_string()
+        })
+    })
+}
+
+impl ObservedDeprecation {
+    /// The announcement in a response's headers, if it has one. `headers`
+    /// are keyed by lower-case name, as in `ApiResponse`.
+    fn from_response(method: &str, url: &str, headers: &HashMap<String, String>) -> Option<Self> {
+        let deprecation = headers.get("deprecation").map(|v| header_date(v));
+        let sunset = headers.get("sunset").map(|v| header_date(v));
+        if deprecation.is_none() && sunset.is_none() {
+            return None;
+        }
+        Some(ObservedDeprecation {
+            method: method.to_ascii_uppercase(),
+            url: url.split(['?', '#']).next().unwrap_or(url).to_string(),
+            deprecation,
+            sunset,
+            link: headers.get("link").and_then(|link| deprecation_link(link)),
+            observed_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        })
+    }
+
+    fn same_endpoint(&self, method: &str, url: &str) -> bool {
+        self.method.eq_ignore_ascii_case(method) && self.url == url
+    }
+
+    /// Notification text for this announcement.
+    fn notice(&self) -> String {
+        let endpoint = format!("{} {}", self.method, self.url);
+        match &self.sunset {
+            Some(sunset) => format!("{endpoint} is deprecated and will be removed on {sunset}."),
+            None => format!("{endpoint} is deprecated."),
+        }
+    }
+}
+
+/// Note `observation`, replacing the endpoint's previous one. Returns whether
+/// it is news: the endpoint had not announced this before.
+fn merge(store: &mut DeprecationStore, observation: ObservedDeprecation) -> bool {
+    let previous = store
+        .observed
+        .iter()
+        .position(|seen| seen.same_endpoint(&observation.method, &observation.url))
+        .map(|index| store.observed.remove(index));
+    let news = previous.is_none_or(|previous| {
+        (&previous.deprecation, &previous.sunset, &previous.link)
+            != (
+                &observation.deprecation,
+                &observation.sunset,
+                &observation.link,
+            )
+    });
+    store.observed.push(observation);
+    if store.observed.len() > MAX_OBSERVATIONS {
+        store.observed.remove(0);
+    }
+    news
+}
+
+// ─── Storage ─────────────────────────────────────────────────────────────────
+
+static DEPRECATIONS: JsonStore<DeprecationStore> = JsonStore::new(DEPRECATIONS_FILE);
+
+/// Keep the deprecation a response announces, if any, and tell the user with
+/// a system notification the first time an endpoint announces it.
+pub(crate) fn observe(
+    app: &AppHandle,
+    method: &str,
+    url: &str,
+    headers: &HashMap<String, String>,
+) -> Result<(), CommandError> {
+    let Some(observation) = ObservedDeprecation::from_response(method, url, headers) else {
+        return Ok(());
+    };
+    let notice = observation.notice();
+    let news = DEPRECATIONS.update(&DEPRECATIONS.path(app)?, |store| {
+        Ok(merge(store, observation))
+    })?;
+    if news {
+        let _ = app
+            .notification()
+            .builder()
+            .title("API deprecation")
+            .body(notice)
+            .show();
+    }
+    Ok(())
+}
+
+// ─── Report ──────────────────────────────────────────────────────────────────
+
+fn date_extension(objects: [&Value; 2], names: &[&str]) -> Option<String> {
+    objects.iter().find_map(|object| {
+        names
+            .iter()
+            .find_map(|name| parse_date(object[*name].as_str()?))
+    })
+}
+
+fn url_path(url: &str) -> Option<String> {
+    url::Url::parse(url).ok().map(|url| url.path().to_string())
+}
+
+fn build_report(
+    specs: &[WorkspaceSpec],
+    observations: &[ObservedDeprecation],
+) -> DeprecationReport {
+    // Each observation goes to the operation serving its URL
+    let mut matched: HashMap<(String, String, String), &ObservedDeprecation> = HashMap::new();
+    let mut unmatched = Vec::new();
+    for observation in observations {
+        let route = url_path(&observation.url)
+            .and_then(|path| routes::match_route(specs, &observation.method, &path));
+        match route {
+            Some(RouteRef { spec_id, path, .. }) => {
+                matched.insert((spec_id, observation.method.clone(), path), observation);
+            }
+            None => unmatched.push(observation.clone()),
+        }
+    }
+
+    let mut operations = Vec::new();
+    for spec in specs {
+        for (path, item) in spec.spec["paths"].as_object().into_iter().flatten() {
+            for method in METHODS {
+                let Some(operation) = item.get(*method).filter(|op| op.is_object()) else {
+                    continue;
+                };
+                let method = method.to_ascii_uppercase();
+                let observed = matched
+                    .get(&(spec.id.clone(), method.clone(), path.clone()))
+                    .map(|observed| (*observed).clone());
+                let deprecated = operation["deprecated"].as_bool().unwrap_or(false);
+                let deprecated_since = date_extension([operation, item], &["x-deprecated-date"]);
+                let removal_date =
+                    date_extension([operation, item], REMOVAL_EXTENSIONS).or_else(|| {
+                        observed
+                            .as_ref()
+                            .and_then(|observed| parse_date(observed.sunset.as_deref()?))
+                    });
+                if !deprecated
+                    && deprecated_since.is_none()
+                    && removal_date.is_none()
+                    && observed.is_none()
+                {
+                    continue;
+                }
+                operations.push(DeprecatedOperation {
+                    spec_id: spec.id.clone(),
+                    method,
+                    path: path.clone(),
+                    operation_id: operation["operationId"].as_str().map(str::to_string),
+                    deprecated,
+                    deprecated_since,
+                    removal_date,
+                    observed,
+                });
+            }
+        }
+    }
+    operations.sort_by(|a, b| {
+        (
+            a.removal_date.is_none(),
+            &a.removal_date,
+            &a.spec_id,
+            &a.path,
+        )
+            .cmp(&(
+                b.removal_date.is_none(),
+                &b.removal_date,
+                &b.spec_id,
+                &b.path,
+            ))
+    });
+    DeprecationReport {
+        operations,
+        unmatched,
+    }
+}
+
+fn warnings(
+    specs: &[WorkspaceSpec],
+    report: &DeprecationReport,
+    requests: &[PlannedRequest],
+) -> Vec<DeprecationWarning> {
+    requests
+        .iter()
+        .filter_map(|request| {
+            let route = routes::match_route(specs, &request.method, &url_path(&request.url)?)?;
+            let operation = report.operations.iter().find(|operation| {
+                operation.spec_id == route.spec_id
+                    && operation.path == route.path
+                    && operation.method.eq_ignore_ascii_case(&request.method)
+            })?;
+            Some(DeprecationWarning {
+                request_id: request.id.clone(),
+                operation: operation.clone(),
+            })
+        })
+        .collect()
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// Deprecated operations across the workspace specs: marked `deprecated`,
+/// dated by `x-deprecated-date`, `x-sunset` or `x-removal-date`, or seen
+/// answering with `Deprecation`/`Sunset` headers.
+#[tauri::command]
+pub fn deprecation_report(
+    app: AppHandle,
+    specs: Vec<WorkspaceSpec>,
+) -> Result<DeprecationReport, CommandError> {
+    let store = DEPRECATIONS.read(&DEPRECATIONS.path(&app)?)?;
+    Ok(build_report(&specs, &store.observed))
+}
+
+/// Requests of a collection that still call deprecated operations, so the
+/// runner can warn before it starts.
+#[tauri::command]
+pub fn find_deprecated_requests(
+    app: AppHandle,
+    specs: Vec<WorkspaceSpec>,
+    requests: Vec<PlannedRequest>,
+) -> Result<Vec<DeprecationWarning>, CommandError> {
+    let store = DEPRECATIONS.read(&DEPRECATIONS.path(&app)?)?;
+    let report = build_report(&specs, &store.observed);
+    Ok(warnings(&specs, &report, &requests))
+}
+
+/// Every endpoint that announced its deprecation in a response, newest
+/// last.
+#[tauri::command]
+pub fn list_observed_deprecations(
+    app: AppHandle,
+) -> Result<Vec<ObservedDeprecation>, CommandError> {
+    Ok(DEPRECATIONS.read(&DEPRECATIONS.path(&app)?)?.observed)
+}
+
+/// Forget an endpoint's announcement; it is noted and notified again if the
+/// endpoint keeps sending it. Returns `false` when none was kept.
+#[tauri::command]
+pub fn forget_observed_deprecation(
+    app: AppHandle,
+    webview: tauri::Webview,
+    method: String,
+    url: String,
+) -> Result<bool, CommandError> {
+    ensure_main_window(webview.label())?;
+
+    DEPRECATIONS.update(&DEPRECATIONS.path(&app)?, |store| {
+        let before = store.observed.len();
+        store
+            .observed
+            .retain(|seen| !seen.same_endpoint(&method, &url));
+        Ok(store.observed.len() != before)
+    })
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn headers(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    fn specs() -> Vec<WorkspaceSpec> {
+        vec![WorkspaceSpec {
+            id: "pets".to_string(),
+            spec: json!({
+                "openapi": "3.0.3",
+                "servers": [{ "url": "https://api.example.com/v1" }],
+                "paths": {
+                    "/pets": {
+                        "get": { "operationId": "listPets" },
+                        "post": { "deprecated": true, "x-sunset": "2026-01-31" }
+                    },
+                    "/pets/{id}": {
+                        "x-deprecated-date": "2025-06-01",
+                        "get": { "operationId": "getPet" },
+                        "delete": { "operationId": "deletePet" }
+                    },
+                    "/owners": { "get": {} }
+                }
+            }),
+        }]
+    }
+
+    #[test]
+    fn test_header_dates() {
+        assert_eq!(header_date("@1688169599"), "2023-06-30");
+        assert_eq!(header_date("Sat, 31 Jan 2026 23:59:59 GMT"), "2026-01-31");
+        assert_eq!(header_date("true"), "true");
+        assert_eq!(
+            deprecation_link(
+                r#"<https://example.com/next>; rel="next", <https://example.com/migrate>; rel="deprecation""#
+            ),
+            Some("https://example.com/migrate".to_string())
+        );
+    }
+
+    fn observe(
+        store: &mut DeprecationStore,
+        method: &str,
+        url: &str,
+        pairs: &[(&str, &str)],
+    ) -> bool {
+        ObservedDeprecation::from_response(method, url, &headers(pairs))
+            .is_some_and(|observation| merge(store, observation))
+    }
+
+    #[test]
+    fn test_announcements_are_news_once_per_endpoint() {
+        let mut store = DeprecationStore::default();
+        let url = "https://api.example.com/v1/owners?page=2";
+        assert!(!observe(&mut store, "get", url, &[]));
+        assert!(store.observed.is_empty());
+
+        let sunset = ("sunset", "Sat, 31 Jan 2026 00:00:00 GMT");
+        assert!(observe(&mut store, "get", url, &[sunset]));
+        assert!(!observe(&mut store, "GET", url, &[sunset]));
+        // A new removal date is announced again
+        assert!(observe(
+            &mut store,
+            "get",
+            url,
+            &[("sunset", "Sun, 01 Mar 2026 00:00:00 GMT")]
+        ));
+
+        assert_eq!(store.observed.len(), 1);
+        let observed = &store.observed[0];
+        assert_eq!(observed.url, "https://api.example.com/v1/owners");
+        assert_eq!(observed.sunset.as_deref(), Some("2026-03-01"));
+        assert_eq!(
+            observed.notice(),
+            "GET https://api.example.com/v1/owners is deprecated and will be removed on 2026-03-01."
+        );
+    }
+
+    #[test]
+    fn test_observations_are_persisted() {
+        let path =
+            std::env::temp_dir().join(format!("yasp-deprecations-{}.json", std::process::id()));
+        let observation = ObservedDeprecation::from_response(
+            "DELETE",
+            "https://api.example.com/v1/pets/7",
+            &headers(&[("deprecation", "@1688169599")]),
+        )
+        .unwrap();
+        assert!(DEPRECATIONS
+            .update(&path, |store| Ok(merge(store, observation.clone())))
+            .unwrap());
+        assert!(!DEPRECATIONS
+            .update(&path, |store| Ok(merge(store, observation.clone())))
+            .unwrap());
+        assert_eq!(
+            DEPRECATIONS.read(&path).unwrap().observed,
+            vec![observation]
+        );
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_report_merges_spec_and_observed_deprecations() {
+        let mut store = DeprecationStore::default();
+        observe(
+            &mut store,
+            "GET",
+            "https://api.example.com/v1/owners",
+            &[
+                ("deprecation", "@1688169599"),
+                ("sunset", "Sun, 01 Mar 2026 00:00:00 GMT"),
+            ],
+        );
+        observe(
+            &mut store,
+            "GET",
+            "https://legacy.example.com/report",
+            &[("deprecation", "true")],
+        );
+        let report = build_report(&specs(), &store.observed);
+
+        let operations: Vec<(&str, &str, Option<&str>)> = report
+            .operations
+            .iter()
+            .map(|op| {
+                (
+                    op.method.as_str(),
+                    op.path.as_str(),
+                    op.removal_date.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            operations,
+            vec![
+                ("POST", "/pets", Some("2026-01-31")),
+                ("GET", "/owners", Some("2026-03-01")),
+                ("GET", "/pets/{id}", None),
+                ("DELETE", "/pets/{id}", None),
+            ]
+        );
+        assert!(report.operations[0].deprecated);
+        assert_eq!(
+            report.operations[2].deprecated_since.as_deref(),
+            Some("2025-06-01")
+        );
+        assert_eq!(report.unmatched.len(), 1);
+        assert_eq!(report.unmatched[0].url, "https://legacy.example.com/report");
+    }
+
+    #[test]
+    fn test_warnings_for_collection_requests() {
+        let specs = specs();
+        let report = build_report(&specs, &[]);
+        let requests = [
+            ("list", "GET", "https://api.example.com/v1/pets"),
+            ("create", "POST", "https://api.example.com/v1/pets"),
+            (
+                "remove",
+                "DELETE",
+                "https://api.example.com/v1/pets/7?force=true",
+            ),
+        ]
+        .map(|(id, method, url)| PlannedRequest {
+            id: id.to_string(),
+            method: method.to_string(),
+            url: url.to_string(),
+        });
+        let warned: Vec<String> = warnings(&specs, &report, &requests)
+            .into_iter()
+            .map(|warning| warning.request_id)
+            .collect();
+        assert_eq!(warned, vec!["create", "remove"]);
+    }
+}
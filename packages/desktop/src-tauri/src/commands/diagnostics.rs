@@ -0,0 +1,343 @@
+use std::net::{IpAddr, SocketAddr};
+use std::time::{Duration, Instant};
+
+use hickory_resolver::proto::rr::{RData, RecordType};
+use hickory_resolver::TokioAsyncResolver;
+use serde::Serialize;
+use tokio::net::TcpStream;
+use tokio::process::Command;
+
+use super::{check_ip_allowed, ensure_main_window, validate_url};
+use crate::error::{CommandError, ErrorCode};
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DnsRecord {
+    /// `A`, `AAAA` or `CNAME`.
+    pub record_type: String,
+    /// Owner name of the record, so a CNAME chain can be followed.
+    pub name: String,
+    pub value: String,
+    pub ttl: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ResolvedAddress {
+    pub ip: IpAddr,
+    /// Why requests to this address are refused by the network policy, or
+    /// `None` when they are allowed.
+    pub blocked: Option<CommandError>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DnsLookup {
+    pub hostname: String,
+    pub records: Vec<DnsRecord>,
+    pub addresses: Vec<ResolvedAddress>,
+    pub duration_ms: u64,
+    /// Name servers from the system configuration the query was sent to.
+    /// The resolver does not report which of them produced the answer.
+    pub resolvers: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConnectAttempt {
+    pub address: SocketAddr,
+    /// TCP handshake time; `None` when the connect failed or was not tried.
+    pub connect_ms: Option<u64>,
+    /// Connect failure, or the policy error when the address is blocked.
+    pub error: Option<CommandError>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConnectivityProbe {
+    pub host: String,
+    pub port: u16,
+    pub resolve_ms: u64,
+    pub attempts: Vec<ConnectAttempt>,
+    /// True when at least one address accepted a TCP connection — the
+    /// network path works and any failure is in the API itself.
+    pub reachable: bool,
+    /// Raw output of the system traceroute, when requested.
+    pub traceroute: Option<String>,
+    pub traceroute_error: Option<CommandError>,
+}
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const TRACEROUTE_TIMEOUT: Duration = Duration::from_secs(60);
+const TRACEROUTE_MAX_HOPS: &str = "30";
+
+// ─── Helpers ─────────────────────────────────────────────────────────────────
+
+fn parse_hostname(hostname: &str) -> Result<url::Host, CommandError> {
+    let hostname = hostname.trim();
+    if hostname.is_empty() {
+        return Err(CommandError::new(ErrorCode::MissingHost));
+    }
+    url::Host::parse(hostname).map_err(|e| {
+        CommandError::new(ErrorCode::InvalidHostname)
+            .arg("host", hostname)
+            .detail(e)
+    })
+}
+
+fn resolved_address(ip: IpAddr) -> ResolvedAddress {
+    ResolvedAddress {
+        ip,
+        blocked: check_ip_allowed(&ip).err(),
+    }
+}
+
+fn dns_record(record: &hickory_resolver::proto::rr::Record) -> Option<DnsRecord> {
+    let value = match record.data()? {
+        RData::A(a) => a.0.to_string(),
+        RData::AAAA(aaaa) => aaaa.0.to_string(),
+        RData::CNAME(cname) => cname.0.to_utf8(),
+        _ => return None,
+    };
+    let record_type = match record.record_type() {
+        RecordType::A => "A",
+        RecordType::AAAA => "AAAA",
+        _ => "CNAME",
+    };
+    Some(DnsRecord {
+        record_type: record_type.to_string(),
+        name: record.name().to_utf8(),
+        value,
+        ttl: record.ttl(),
+    })
+}
+
+async fn connect_attempt(address: SocketAddr) -> ConnectAttempt {
+    if let Err(error) = check_ip_allowed(&address.ip()) {
+        return ConnectAttempt {
+            address,
+            connect_ms: None,
+            error: Some(error),
+        };
+    }
+
+    let start = Instant::now();
+    let (connect_ms, error) =
+        match tokio::time::timeout(CONNECT_TIMEOUT, TcpStream::connect(address)).await {
+            Ok(Ok(_)) => (Some(start.elapsed().as_millis() as u64), None),
+            Ok(Err(e)) => (
+                None,
+                Some(CommandError::new(ErrorCode::ConnectFailed).detail(e)),
+            ),
+            Err(_) => (None, Some(CommandError::new(ErrorCode::Timeout))),
+        };
+    ConnectAttempt {
+        address,
+        connect_ms,
+        error,
+    }
+}
+
+/// System traceroute invocation with numeric output and a bounded hop count.
+fn traceroute_command(ip: IpAddr) -> (&'static str, Vec<String>) {
+    let (program, flags): (_, &[&str]) = if cfg!(windows) {
+        ("tracert", &["-d", "-w", "2000", "-h", TRACEROUTE_MAX_HOPS])
+    } else {
+        (
+            "traceroute",
+            &["-n", "-q", "1", "-w", "2", "-m", TRACEROUTE_MAX_HOPS],
+        )
+    };
+    let args = flags
+        .iter()
+        .map(|flag| flag.to_string())
+        .chain([ip.to_string()])
+        .collect();
+    (program, args)
+}
+
+/// Raw ICMP/UDP sockets need elevated privileges on every desktop OS, so the
+/// hop trace is delegated to the platform's traceroute binary.
+async fn run_traceroute(ip: IpAddr) -> Result<String, CommandError> {
+    let (program, args) = traceroute_command(ip);
+    let output = tokio::time::timeout(
+        TRACEROUTE_TIMEOUT,
+        Command::new(program)
+            .args(&args)
+            .kill_on_drop(true)
+            .output(),
+    )
+    .await
+    .map_err(|_| CommandError::new(ErrorCode::Timeout))?
+    .map_err(|e| {
+        CommandError::new(ErrorCode::ToolUnavailable)
+            .arg("tool", program)
+            .detail(e)
+    })?;
+
+    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    Ok(text)
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// Resolve `hostname` through the system resolver configuration and report
+/// every A/AAAA/CNAME record, how long resolution took, and whether each
+/// address passes the SSRF policy — so "connection failed" can be told apart
+/// from "resolves somewhere we refuse to go".
+///
+/// IP literals skip the lookup and are only checked against the policy.
+#[tauri::command]
+pub async fn resolve_host(
+    webview: tauri::Webview,
+    hostname: String,
+) -> Result<DnsLookup, CommandError> {
+    ensure_main_window(webview.label())?;
+
+    let domain = match parse_hostname(&hostname)? {
+        url::Host::Domain(domain) => domain,
+        url::Host::Ipv4(ip) => return Ok(literal_lookup(hostname, IpAddr::V4(ip))),
+        url::Host::Ipv6(ip) => return Ok(literal_lookup(hostname, IpAddr::V6(ip))),
+    };
+
+    let (config, options) = hickory_resolver::system_conf::read_system_conf()
+        .map_err(|e| CommandError::new(ErrorCode::DnsFailed).detail(e))?;
+    let resolvers = config
+        .name_servers()
+        .iter()
+        .map(|server| format!("{} ({})", server.socket_addr, server.protocol))
+        .collect();
+    let resolver = TokioAsyncResolver::tokio(config, options);
+
+    let start = Instant::now();
+    let lookup = resolver
+        .lookup_ip(domain.as_str())
+        .await
+        .map_err(|e| CommandError::new(ErrorCode::DnsFailed).detail(e))?;
+    let duration_ms = start.elapsed().as_millis() as u64;
+
+    Ok(DnsLookup {
+        hostname,
+        records: lookup
+            .as_lookup()
+            .records()
+            .iter()
+            .filter_map(dns_record)
+            .collect(),
+        addresses: lookup.iter().map(resolved_address).collect(),
+        duration_ms,
+        resolvers,
+    })
+}
+
+fn literal_lookup(hostname: String, ip: IpAddr) -> DnsLookup {
+    DnsLookup {
+        hostname,
+        records: Vec::new(),
+        addresses: vec![resolved_address(ip)],
+        duration_ms: 0,
+        resolvers: Vec::new(),
+    }
+}
+
+/// Time a TCP connect to every address of `host:port` and optionally trace
+/// the route, to tell "the API is down" apart from "my network is broken".
+///
+/// OWASP A09:2025 – SSRF: the host and port go through the same policy as
+/// API requests, and blocked addresses are reported without being touched.
+/// The traceroute targets the first allowed address, never the hostname, so
+/// it cannot re-resolve somewhere else.
+#[tauri::command]
+pub async fn probe_connectivity(
+    webview: tauri::Webview,
+    host: String,
+    port: u16,
+    traceroute: bool,
+) -> Result<ConnectivityProbe, CommandError> {
+    ensure_main_window(webview.label())?;
+
+    let parsed = parse_hostname(&host)?;
+    validate_url(&format!("https://{parsed}:{port}/"))?;
+
+    let start = Instant::now();
+    let addresses: Vec<SocketAddr> = tokio::net::lookup_host((parsed.to_string(), port))
+        .await
+        .map_err(|e| CommandError::new(ErrorCode::DnsFailed).detail(e))?
+        .collect();
+    let resolve_ms = start.elapsed().as_millis() as u64;
+
+    let mut attempts = Vec::with_capacity(addresses.len());
+    for address in addresses {
+        attempts.push(connect_attempt(address).await);
+    }
+    let reachable = attempts.iter().any(|a| a.connect_ms.is_some());
+
+    let target = attempts
+        .iter()
+        .find(|a| !matches!(&a.error, Some(e) if e.code == ErrorCode::BlockedIp))
+        .map(|a| a.address.ip());
+    let (traceroute, traceroute_error) = match target {
+        Some(ip) if traceroute => match run_traceroute(ip).await {
+            Ok(output) => (Some(output), None),
+            Err(error) => (None, Some(error)),
+        },
+        _ => (None, None),
+    };
+
+    Ok(ConnectivityProbe {
+        host,
+        port,
+        resolve_ms,
+        attempts,
+        reachable,
+        traceroute,
+        traceroute_error,
+    })
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hostname_accepts_domains_and_literals() {
+        assert!(matches!(
+            parse_hostname(" api.example.com "),
+            Ok(url::Host::Domain(_))
+        ));
+        assert!(matches!(parse_hostname("10.0.0.1"), Ok(url::Host::Ipv4(_))));
+        assert!(matches!(parse_hostname("[::1]"), Ok(url::Host::Ipv6(_))));
+    }
+
+    #[test]
+    fn test_parse_hostname_rejects_invalid_input() {
+        assert_eq!(parse_hostname("").unwrap_err().code, ErrorCode::MissingHost);
+        assert_eq!(
+            parse_hostname("exa mple.com").unwrap_err().code,
+            ErrorCode::InvalidHostname
+        );
+    }
+
+    #[test]
+    fn test_resolved_address_reports_policy() {
+        let private = resolved_address("192.168.1.10".parse().unwrap());
+        assert_eq!(private.blocked.unwrap().code, ErrorCode::BlockedIp);
+        assert!(resolved_address("93.184.216.34".parse().unwrap())
+            .blocked
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_connect_attempt_skips_blocked_addresses() {
+        let attempt = connect_attempt("127.0.0.1:8080".parse().unwrap()).await;
+        assert!(attempt.connect_ms.is_none());
+        assert_eq!(attempt.error.unwrap().code, ErrorCode::BlockedIp);
+    }
+
+    #[test]
+    fn test_traceroute_targets_numeric_ip() {
+        let (_, args) = traceroute_command("93.184.216.34".parse().unwrap());
+        assert_eq!(args.last().unwrap(), "93.184.216.34");
+        assert!(args.iter().any(|a| a == TRACEROUTE_MAX_HOPS));
+    }
+}
@@ -0,0 +1,698 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use tauri::AppHandle;
+
+use super::body::BodyEncoding;
+use super::ensure_main_window;
+use super::routes::{self, WorkspaceSpec};
+use super::store::CachedJsonStore;
+use super::ApiResponse;
+use crate::error::{CommandError, ErrorCode};
+
+const SHAPES_FILE: &str = "response_shapes.json";
+
+/// Endpoints kept; the least recently seen are dropped first.
+const MAX_ENDPOINTS: usize = 500;
+/// Fields kept per endpoint, so huge documents do not bloat the file.
+const MAX_FIELDS: usize = 1_000;
+/// Array elements sampled per array.
+const MAX_ITEMS: usize = 50;
+const MAX_DEPTH: usize = 16;
+
+/// Path of the whole body in field paths such as `$.items[].id`.
+const ROOT: &str = "$";
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JsonType {
+    Null,
+    Boolean,
+    Integer,
+    Number,
+    String,
+    Array,
+    Object,
+}
+
+/// How often a field had each type.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct FieldStats {
+    types: BTreeMap<JsonType, u64>,
+}
+
+/// Every field seen in the JSON responses of one URL with one status.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct ObservedShape {
+    method: String,
+    /// Request URL without its query.
+    url: String,
+    status: u16,
+    samples: u64,
+    /// Unix time in seconds.
+    last_seen: u64,
+    fields: BTreeMap<String, FieldStats>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ShapeStore {
+    shapes: Vec<ObservedShape>,
+}
+
+/// A JSON Schema inferred from the responses of one URL.
+#[derive(Debug, Clone, Serialize)]
+pub struct InferredSchema {
+    pub method: String,
+    pub url: String,
+    pub status: u16,
+    pub samples: u64,
+    pub last_seen: u64,
+    /// Properties seen in every sample are `required`.
+    pub schema: Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TypeMismatch {
+    pub field: String,
+    pub declared: Vec<JsonType>,
+    pub observed: Vec<JsonType>,
+}
+
+/// Where the responses of an operation differ from its declared schema.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SchemaDrift {
+    pub spec_id: String,
+    pub method: String,
+    pub path: String,
+    pub status: u16,
+    /// Responses compared, across all URLs of the operation.
+    pub samples: u64,
+    /// Fields seen in responses that the schema does not declare.
+    pub undocumented: Vec<String>,
+    /// Declared fields never seen in a response.
+    pub unobserved: Vec<String>,
+    /// Fields seen with types the schema does not allow.
+    pub type_mismatches: Vec<TypeMismatch>,
+}
+
+// ─── Inference ───────────────────────────────────────────────────────────────
+
+fn type_of(value: &Value) -> JsonType {
+    match value {
+        Value::Null => JsonType::Null,
+        Value::Bool(_) => JsonType::Boolean,
+        Value::Number(n) if n.is_i64() || n.is_u64() => JsonType::Integer,
+        Value::Number(_) => JsonType::Number,
+        Value::String(_) => JsonType::String,
+        Value::Array(_) => JsonType::Array,
+        Value::Object(_) => JsonType::Object,
+    }
+}
+
+/// The field `path` is nested in; `None` for the root.
+fn parent(path: &str) -> Option<&str> {
+    if let Some(items) = path.strip_suffix("[]") {
+        return Some(items);
+    }
+    path.rsplit_once('.').map(|(parent, _)| parent)
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+impl ObservedShape {
+    fn new(method: &str, url: &str, status: u16) -> Self {
+        Self {
+            method: method.to_ascii_uppercase(),
+            url: url.split(['?', '#']).next().unwrap_or(url).to_string(),
+            status,
+            samples: 0,
+            last_seen: 0,
+            fields: BTreeMap::new(),
+        }
+    }
+
+    fn record(&mut self, value: &Value, path: String, depth: usize) {
+        if depth > MAX_DEPTH
+            || (!self.fields.contains_key(&path) && self.fields.len() >= MAX_FIELDS)
+        {
+            return;
+        }
+        match value {
+            Value::Object(map) => {
+                for (name, field) in map {
+                    self.record(field, format!("{path}.{name}"), depth + 1);
+                }
+            }
+            Value::Array(items) => {
+                for item in items.iter().take(MAX_ITEMS) {
+                    self.record(item, format!("{path}[]"), depth + 1);
+                }
+            }
+            _ => {}
+        }
+        *self
+            .fields
+            .entry(path)
+            .or_default()
+            .types
+            .entry(type_of(value))
+            .or_default() += 1;
+    }
+
+    /// Add one response body.
+    fn observe(&mut self, body: &Value) {
+        self.record(body, ROOT.to_string(), 0);
+        self.samples += 1;
+        self.last_seen = now();
+    }
+
+    fn merge(&mut self, other: &ObservedShape) {
+        for (path, stats) in &other.fields {
+            let merged = self.fields.entry(path.clone()).or_default();
+            for (json_type, count) in &stats.types {
+                *merged.types.entry(*json_type).or_default() += count;
+            }
+        }
+        self.samples += other.samples;
+        self.last_seen = self.last_seen.max(other.last_seen);
+    }
+
+    fn children<'a>(&'a self, path: &'a str) -> impl Iterator<Item = (&'a str, &'a FieldStats)> {
+        self.fields.iter().filter_map(move |(field, stats)| {
+            let name = field.strip_prefix(path)?.strip_prefix('.')?;
+            (!name.contains('.') && !name.ends_with("[]")).then_some((name, stats))
+        })
+    }
+
+    /// JSON Schema of the values seen at `path`.
+    fn schema_at(&self, path: &str) -> Value {
+        let Some(stats) = self.fields.get(path) else {
+            return json!({});
+        };
+        let types: Vec<JsonType> = stats.types.keys().copied().collect();
+        let mut schema = Map::new();
+        schema.insert(
+            "type".to_string(),
+            match types.as_slice() {
+                [single] => json!(single),
+                _ => json!(types),
+            },
+        );
+        if let Some(objects) = stats.types.get(&JsonType::Object) {
+            let mut properties = Map::new();
+            let mut required = Vec::new();
+            for (name, child) in self.children(path) {
+                properties.insert(name.to_string(), self.schema_at(&format!("{path}.{name}")));
+                if child.types.values().sum::<u64>() == *objects {
+                    required.push(name);
+                }
+            }
+            schema.insert("properties".to_string(), Value::Object(properties));
+            if !required.is_empty() {
+                schema.insert("required".to_string(), json!(required));
+            }
+        }
+        if stats.types.contains_key(&JsonType::Array) {
+            schema.insert("items".to_string(), self.schema_at(&format!("{path}[]")));
+        }
+        Value::Object(schema)
+    }
+
+    fn inferred(&self) -> InferredSchema {
+        let mut schema = self.schema_at(ROOT);
+        schema["$schema"] = json!("https://json-schema.org/draft/2020-12/schema");
+        InferredSchema {
+            method: self.method.clone(),
+            url: self.url.clone(),
+            status: self.status,
+            samples: self.samples,
+            last_seen: self.last_seen,
+            schema,
+        }
+    }
+}
+
+/// Add a response body to its endpoint's shape, dropping the least recently
+/// seen endpoint when full.
+fn merge_response(store: &mut ShapeStore, method: &str, url: &str, status: u16, body: &Value) {
+    let mut shape = ObservedShape::new(method, url, status);
+    let index = store.shapes.iter().position(|seen| {
+        (&seen.method, &seen.url, seen.status) == (&shape.method, &shape.url, shape.status)
+    });
+    if let Some(index) = index {
+        shape = store.shapes.remove(index);
+    }
+    shape.observe(body);
+    store.shapes.push(shape);
+    if store.shapes.len() > MAX_ENDPOINTS {
+        store.shapes.remove(0);
+    }
+}
+
+// ─── Declared schemas ────────────────────────────────────────────────────────
+
+/// The target of a local `$ref`, else the value itself.
+pub(super) fn resolve<'a>(spec: &'a Value, value: &'a Value) -> &'a Value {
+    let mut value = value;
+    for _ in 0..MAX_DEPTH {
+        match value["$ref"].as_str().and_then(|r| r.strip_prefix('#')) {
+            Some(pointer) => match spec.pointer(pointer) {
+                Some(target) => value = target,
+                None => break,
+            },
+            None => break,
+        }
+    }
+    value
+}
+
+fn declared_types(schema: &Value) -> BTreeSet<JsonType> {
+    let names: Vec<&str> = match &schema["type"] {
+        Value::String(name) => vec![name.as_str()],
+        Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+    let mut types: BTreeSet<JsonType> = names
+        .into_iter()
+        .filter_map(|name| serde_json::from_value(json!(name)).ok())
+        .collect();
+    if schema["nullable"].as_bool() == Some(true) || schema["x-nullable"].as_bool() == Some(true) {
+        types.insert(JsonType::Null);
+    }
+    types
+}
+
+/// The fields a schema declares, flattened to the same paths as observed
+/// shapes. An empty type set allows anything.
+#[derive(Default)]
+struct Declared<'a> {
+    spec: &'a Value,
+    /// `$ref`s being expanded, to stop at recursive schemas.
+    expanding: Vec<&'a str>,
+    fields: BTreeMap<String, BTreeSet<JsonType>>,
+    /// Objects that allow properties beyond those listed.
+    open: BTreeSet<String>,
+}
+
+impl<'a> Declared<'a> {
+    fn schema(&mut self, schema: &'a Value, path: &str, depth: usize) {
+        if depth > MAX_DEPTH {
+            return;
+        }
+        if let Some(reference) = schema["$ref"].as_str() {
+            if self.expanding.contains(&reference) {
+                return;
+            }
+            self.expanding.push(reference);
+            self.schema(resolve(self.spec, schema), path, depth + 1);
+            self.expanding.pop();
+            return;
+        }
+
+        self.fields
+            .entry(path.to_string())
+            .or_default()
+            .extend(declared_types(schema));
+        let properties = schema["properties"].as_object();
+        let combined = ["allOf", "oneOf", "anyOf"]
+            .iter()
+            .any(|combined| schema[*combined].is_array());
+        let free_form = properties.is_none()
+            && !combined
+            && !schema["items"].is_object()
+            && matches!(schema["type"].as_str(), None | Some("object"));
+        let additional = !matches!(
+            schema["additionalProperties"],
+            Value::Null | Value::Bool(false)
+        );
+        if free_form || additional {
+            self.open.insert(path.to_string());
+        }
+
+        for (name, property) in properties.into_iter().flatten() {
+            self.schema(property, &format!("{path}.{name}"), depth + 1);
+        }
+        if schema["items"].is_object() {
+            self.schema(&schema["items"], &format!("{path}[]"), depth + 1);
+        }
+        for combined in ["allOf", "oneOf", "anyOf"] {
+            for member in schema[combined].as_array().into_iter().flatten() {
+                self.schema(member, path, depth + 1);
+            }
+        }
+    }
+}
+
+/// The JSON schema declared for `status` responses of an operation: the
+/// exact code, else its range (`2XX`), else `default`.
+fn response_schema<'a>(spec: &'a Value, operation: &'a Value, status: u16) -> Option<&'a Value> {
+    let responses = &operation["responses"];
+    let response = [
+        status.to_string(),
+        format!("{}XX", status / 100),
+        "default".to_string(),
+    ]
+    .iter()
+    .find_map(|code| responses.get(code))?;
+    let response = resolve(spec, response);
+    if response["schema"].is_object() {
+        return Some(&response["schema"]);
+    }
+    response["content"]
+        .as_object()?
+        .iter()
+        .find(|(media_type, _)| media_type.contains("json"))
+        .map(|(_, media)| &media["schema"])
+        .filter(|schema| schema.is_object())
+}
+
+fn allows(declared: &BTreeSet<JsonType>, observed: JsonType) -> bool {
+    declared.is_empty()
+        || declared.contains(&observed)
+        || (observed == JsonType::Integer && declared.contains(&JsonType::Number))
+}
+
+/// Whether `path` or one of the fields it is nested in is in `paths`.
+fn within(paths: &BTreeSet<String>, path: &str) -> bool {
+    let mut current = Some(path);
+    while let Some(path) = current {
+        if paths.contains(path) {
+            return true;
+        }
+        current = parent(path);
+    }
+    false
+}
+
+fn compare(
+    spec_id: &str,
+    path: &str,
+    shape: &ObservedShape,
+    declared: &Declared<'_>,
+) -> SchemaDrift {
+    // Only the outermost missing field of a missing object is reported
+    let mut undocumented = Vec::new();
+    for field in shape.fields.keys() {
+        let reported = parent(field).is_some_and(|parent| {
+            !declared.fields.contains_key(parent) || within(&declared.open, parent)
+        });
+        if !declared.fields.contains_key(field) && !reported {
+            undocumented.push(field.clone());
+        }
+    }
+    let mut unobserved = Vec::new();
+    for field in declared.fields.keys() {
+        let reported = parent(field).is_some_and(|parent| !shape.fields.contains_key(parent));
+        if !shape.fields.contains_key(field) && !reported {
+            unobserved.push(field.clone());
+        }
+    }
+    let type_mismatches = shape
+        .fields
+        .iter()
+        .filter_map(|(field, stats)| {
+            let allowed = declared.fields.get(field)?;
+            let observed: Vec<JsonType> = stats.types.keys().copied().collect();
+            observed
+                .iter()
+                .any(|json_type| !allows(allowed, *json_type))
+                .then(|| TypeMismatch {
+                    field: field.clone(),
+                    declared: allowed.iter().copied().collect(),
+                    observed,
+                })
+        })
+        .collect();
+
+    SchemaDrift {
+        spec_id: spec_id.to_string(),
+        method: shape.method.clone(),
+        path: path.to_string(),
+        status: shape.status,
+        samples: shape.samples,
+        undocumented,
+        unobserved,
+        type_mismatches,
+    }
+}
+
+/// Drift of each operation with observed JSON responses and a declared JSON
+/// response schema for their status.
+fn drift(specs: &[WorkspaceSpec], shapes: &[ObservedShape]) -> Vec<SchemaDrift> {
+    // URLs of the same operation, e.g. /pets/1 and /pets/2, are compared as one
+    let mut operations: HashMap<(String, String, String, u16), ObservedShape> = HashMap::new();
+    for shape in shapes {
+        let Some(route) = url::Url::parse(&shape.url)
+            .ok()
+            .and_then(|url| routes::match_route(specs, &shape.method, url.path()))
+        else {
+            continue;
+        };
+        let key = (
+            route.spec_id,
+            route.path,
+            shape.method.clone(),
+            shape.status,
+        );
+        match operations.get_mut(&key) {
+            Some(merged) => merged.merge(shape),
+            None => {
+                operations.insert(key, shape.clone());
+            }
+        }
+    }
+
+    let mut drifts: Vec<SchemaDrift> = operations
+        .into_iter()
+        .filter_map(|((spec_id, path, _, _), shape)| {
+            let spec = &specs.iter().find(|spec| spec.id == spec_id)?.spec;
+            let operation = &spec["paths"][&path][shape.method.to_ascii_lowercase()];
+            let schema = response_schema(spec, operation, shape.status)?;
+            let mut declared = Declared {
+                spec,
+                ..Default::default()
+            };
+            declared.schema(schema, ROOT, 0);
+            Some(compare(&spec_id, &path, &shape, &declared))
+        })
+        .collect();
+    drifts.sort_by(|a, b| {
+        (&a.spec_id, &a.path, &a.method, a.status).cmp(&(&b.spec_id, &b.path, &b.method, b.status))
+    });
+    drifts
+}
+
+// ─── Storage ─────────────────────────────────────────────────────────────────
+
+static SHAPES: CachedJsonStore<ShapeStore> = CachedJsonStore::new(SHAPES_FILE);
+
+/// Add a JSON response to the shape of its URL. Other responses are ignored.
+/// The body is parsed on a blocking thread.
+pub(crate) async fn observe(
+    app: &AppHandle,
+    method: &str,
+    url: &str,
+    response: &ApiResponse,
+) -> Result<(), CommandError> {
+    let is_json = response
+        .content_type
+        .as_deref()
+        .is_some_and(|content_type| content_type.contains("json"));
+    if response.body_encoding != BodyEncoding::Text || !is_json {
+        return Ok(());
+    }
+    let path = SHAPES.path(app)?;
+    let (method, url, status) = (method.to_string(), url.to_string(), response.status);
+    let body = response.body.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        // A body that is not JSON after all has no shape to keep
+        let Ok(body) = serde_json::from_str::<Value>(&body) else {
+            return Ok(());
+        };
+        SHAPES.update(&path, |store| {
+            merge_response(store, &method, &url, status, &body)
+        })
+    })
+    .await
+    .map_err(|e| CommandError::new(ErrorCode::StorageFailed).detail(e))?
+}
+
+/// Write the shapes observed since the last save, e.g. before the app exits.
+pub(crate) fn save(app: &AppHandle) -> Result<(), CommandError> {
+    SHAPES.save(&SHAPES.path(app)?)
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// JSON Schemas inferred from the JSON responses of each URL and status,
+/// most recently seen last.
+#[tauri::command]
+pub fn list_inferred_schemas(app: AppHandle) -> Result<Vec<InferredSchema>, CommandError> {
+    SHAPES.read(&SHAPES.path(&app)?, |store| {
+        store.shapes.iter().map(ObservedShape::inferred).collect()
+    })
+}
+
+/// Compare the observed responses of each spec operation with its declared
+/// response schema.
+#[tauri::command]
+pub fn schema_drift(
+    app: AppHandle,
+    specs: Vec<WorkspaceSpec>,
+) -> Result<Vec<SchemaDrift>, CommandError> {
+    SHAPES.read(&SHAPES.path(&app)?, |store| drift(&specs, &store.shapes))
+}
+
+/// Forget the observed responses, to start tracking afresh, e.g. after an
+/// API release.
+#[tauri::command]
+pub fn clear_inferred_schemas(app: AppHandle, webview: tauri::Webview) -> Result<(), CommandError> {
+    ensure_main_window(webview.label())?;
+
+    SHAPES.replace(&SHAPES.path(&app)?, ShapeStore::default());
+    Ok(())
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn observed(bodies: &[Value]) -> ShapeStore {
+        let mut store = ShapeStore::default();
+        for (id, body) in bodies.iter().enumerate() {
+            let url = format!("https://api.example.com/v1/pets/{id}?expand=owner");
+            merge_response(&mut store, "get", &url, 200, body);
+        }
+        store
+    }
+
+    #[test]
+    fn test_inferred_schema_marks_fields_seen_in_every_sample_required() {
+        let mut store = ShapeStore::default();
+        for body in [
+            json!({ "id": 1, "tags": ["a"], "owner": null }),
+            json!({ "id": 2, "tags": [], "owner": { "name": "Ann" }, "weight": 4.5 }),
+        ] {
+            merge_response(
+                &mut store,
+                "GET",
+                "https://api.example.com/pets?page=1",
+                200,
+                &body,
+            );
+        }
+        assert_eq!(store.shapes.len(), 1);
+        let inferred = store.shapes[0].inferred();
+        assert_eq!(inferred.url, "https://api.example.com/pets");
+        assert_eq!(inferred.samples, 2);
+        assert_eq!(
+            inferred.schema,
+            json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "type": "object",
+                "properties": {
+                    "id": { "type": "integer" },
+                    "owner": {
+                        "type": ["null", "object"],
+                        "properties": { "name": { "type": "string" } },
+                        "required": ["name"]
+                    },
+                    "tags": { "type": "array", "items": { "type": "string" } },
+                    "weight": { "type": "number" }
+                },
+                "required": ["id", "owner", "tags"]
+            })
+        );
+    }
+
+    #[test]
+    fn test_drift_reports_undocumented_unobserved_and_mismatched_fields() {
+        let specs = vec![WorkspaceSpec {
+            id: "pets".to_string(),
+            spec: json!({
+                "openapi": "3.0.3",
+                "servers": [{ "url": "https://api.example.com/v1" }],
+                "paths": {
+                    "/pets/{id}": {
+                        "get": {
+                            "responses": {
+                                "2XX": {
+                                    "content": {
+                                        "application/json": {
+                                            "schema": { "$ref": "#/components/schemas/Pet" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                },
+                "components": {
+                    "schemas": {
+                        "Pet": {
+                            "type": "object",
+                            "properties": {
+                                "id": { "type": "integer" },
+                                "name": { "type": "string" },
+                                "birthday": { "type": "string", "nullable": true },
+                                "vet": {
+                                    "type": "object",
+                                    "properties": { "phone": { "type": "string" } }
+                                },
+                                "labels": { "type": "object", "additionalProperties": true }
+                            }
+                        }
+                    }
+                }
+            }),
+        }];
+        let store = observed(&[
+            json!({ "id": "7", "name": "Rex", "birthday": null, "labels": { "x": 1 },
+                    "chip": { "code": "123", "vendor": "acme" } }),
+            json!({ "id": "8", "name": "Tom", "birthday": "2020-01-01", "labels": {} }),
+        ]);
+
+        let drifts = drift(&specs, &store.shapes);
+        assert_eq!(drifts.len(), 1);
+        let drift = &drifts[0];
+        assert_eq!(
+            (drift.path.as_str(), drift.status, drift.samples),
+            ("/pets/{id}", 200, 2)
+        );
+        assert_eq!(drift.undocumented, vec!["$.chip"]);
+        assert_eq!(drift.unobserved, vec!["$.vet"]);
+        assert_eq!(
+            drift.type_mismatches,
+            vec![TypeMismatch {
+                field: "$.id".to_string(),
+                declared: vec![JsonType::Integer],
+                observed: vec![JsonType::String],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_operations_without_declared_json_schema_are_skipped() {
+        let specs = vec![WorkspaceSpec {
+            id: "pets".to_string(),
+            spec: json!({
+                "swagger": "2.0",
+                "host": "api.example.com",
+                "basePath": "/v1",
+                "paths": { "/pets/{id}": { "get": { "responses": { "404": {} } } } }
+            }),
+        }];
+        assert!(drift(&specs, &observed(&[json!({ "id": 1 })]).shapes).is_empty());
+    }
+}
@@ -0,0 +1,834 @@
+use std::fmt::Display;
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::error::{CommandError, ErrorCode};
+
+/// Fields `edit_spec` may change. Structure (paths, schemas, parameters) is
+/// edited in the source file, not from YASP.
+const EDITABLE_FIELDS: &[&str] = &["description", "summary", "tags", "example", "examples"];
+
+/// Objects keyed by names the spec author chose, not by fields: a property
+/// called `description` is not a description.
+const NAMED_MAPS: &[&str] = &[
+    "paths",
+    "webhooks",
+    "schemas",
+    "definitions",
+    "properties",
+    "patternProperties",
+    "responses",
+    "requestBodies",
+    "parameters",
+    "headers",
+    "content",
+    "examples",
+    "links",
+    "callbacks",
+    "securitySchemes",
+];
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SpecEdit {
+    /// JSON Pointer (RFC 6901) to the value, e.g. `/paths/~1pets/get/summary`.
+    /// Missing keys along the way are created.
+    pub pointer: String,
+    pub value: Value,
+}
+
+/// Where a pointer leads in the source text.
+enum Target<'p> {
+    /// The text of the existing value.
+    Found(Range<usize>),
+    /// The deepest existing object, and the segments missing below it.
+    Missing {
+        container: usize,
+        missing: &'p [String],
+    },
+}
+
+// ─── Pointers ────────────────────────────────────────────────────────────────
+
+fn edit_error(pointer: &str, reason: impl Display) -> CommandError {
+    CommandError::new(ErrorCode::InvalidSpecEdit)
+        .arg("pointer", pointer)
+        .detail(reason)
+}
+
+fn parse_pointer(pointer: &str) -> Result<Vec<String>, CommandError> {
+    let segments: Vec<String> = pointer
+        .strip_prefix('/')
+        .ok_or_else(|| edit_error(pointer, "the pointer must start with '/'"))?
+        .split('/')
+        .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+        .collect();
+    let mut is_name = false;
+    let mut editable = false;
+    for segment in &segments {
+        editable = !is_name && EDITABLE_FIELDS.contains(&segment.as_str());
+        is_name = !is_name && NAMED_MAPS.contains(&segment.as_str());
+    }
+    if !editable {
+        return Err(edit_error(
+            pointer,
+            "only descriptions, summaries, tags and examples can be edited",
+        ));
+    }
+    Ok(segments)
+}
+
+/// `[b, c]` and `value` become `{ "c": value }` under key `b`.
+fn nest(segments: &[String], value: Value) -> Value {
+    segments.iter().rev().fold(value, |value, key| {
+        Value::Object(Map::from_iter([(key.clone(), value)]))
+    })
+}
+
+/// Leading whitespace of the line containing `pos`.
+fn line_indent(text: &str, pos: usize) -> &str {
+    let start = text[..pos].rfind('\n').map_or(0, |i| i + 1);
+    let line = &text[start..];
+    &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+}
+
+// ─── JSON ────────────────────────────────────────────────────────────────────
+
+struct JsonMember {
+    key: Option<String>,
+    start: usize,
+    value: Range<usize>,
+}
+
+fn skip_ws(bytes: &[u8], mut pos: usize) -> usize {
+    while bytes.get(pos).is_some_and(u8::is_ascii_whitespace) {
+        pos += 1;
+    }
+    pos
+}
+
+/// End of the string literal whose opening quote is at `pos`.
+fn string_end(bytes: &[u8], pos: usize) -> Option<usize> {
+    let mut i = pos + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return Some(i + 1),
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+/// End of the value starting at `pos`.
+fn value_end(bytes: &[u8], pos: usize) -> Option<usize> {
+    match bytes.get(pos)? {
+        b'"' => string_end(bytes, pos),
+        b'{' | b'[' => {
+            let mut depth = 0;
+            let mut i = pos;
+            while i < bytes.len() {
+                match bytes[i] {
+                    b'"' => {
+                        i = string_end(bytes, i)?;
+                        continue;
+                    }
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Some(i + 1);
+                        }
+                    }
+                    _ => {}
+                }
+                i += 1;
+            }
+            None
+        }
+        _ => {
+            let mut i = pos;
+            while bytes
+                .get(i)
+                .is_some_and(|b| !b.is_ascii_whitespace() && !b",}]".contains(b))
+            {
+                i += 1;
+            }
+            (i > pos).then_some(i)
+        }
+    }
+}
+
+/// Members of the object or array opening at `start`.
+fn json_members(text: &str, start: usize) -> Option<Vec<JsonMember>> {
+    let bytes = text.as_bytes();
+    let object = bytes[start] == b'{';
+    let mut members = Vec::new();
+    let mut pos = skip_ws(bytes, start + 1);
+    if matches!(bytes.get(pos), Some(b'}' | b']')) {
+        return Some(members);
+    }
+    loop {
+        let member_start = pos;
+        let key = if object {
+            let end = string_end(bytes, pos)?;
+            let key: String = serde_json::from_str(&text[pos..end]).ok()?;
+            pos = skip_ws(bytes, end);
+            if bytes.get(pos) != Some(&b':') {
+                return None;
+            }
+            pos = skip_ws(bytes, pos + 1);
+            Some(key)
+        } else {
+            None
+        };
+        let end = value_end(bytes, pos)?;
+        members.push(JsonMember {
+            key,
+            start: member_start,
+            value: pos..end,
+        });
+        pos = skip_ws(bytes, end);
+        match bytes.get(pos)? {
+            b',' => pos = skip_ws(bytes, pos + 1),
+            b'}' | b']' => return Some(members),
+            _ => return None,
+        }
+    }
+}
+
+fn locate_json<'p>(
+    text: &str,
+    pointer: &str,
+    segments: &'p [String],
+) -> Result<Target<'p>, CommandError> {
+    let malformed = || edit_error(pointer, "the document is not valid JSON");
+    let mut value = skip_ws(text.as_bytes(), 0)..0;
+    for (depth, segment) in segments.iter().enumerate() {
+        let container = value.start;
+        let members = match text.as_bytes().get(container) {
+            Some(b'{' | b'[') => json_members(text, container).ok_or_else(malformed)?,
+            _ => {
+                return Err(edit_error(
+                    pointer,
+                    format!("'{segment}' is not in a container"),
+                ))
+            }
+        };
+        let member = if text.as_bytes()[container] == b'{' {
+            members
+                .iter()
+                .find(|member| member.key.as_deref() == Some(segment))
+        } else {
+            segment.parse::<usize>().ok().and_then(|i| members.get(i))
+        };
+        match member {
+            Some(member) => value = member.value.clone(),
+            None if text.as_bytes()[container] == b'{' => {
+                return Ok(Target::Missing {
+                    container,
+                    missing: &segments[depth..],
+                })
+            }
+            None => return Err(edit_error(pointer, format!("no item {segment}"))),
+        }
+    }
+    Ok(Target::Found(value))
+}
+
+/// Serialize in the document's style: compact for single-line documents,
+/// otherwise pretty with its indent unit, continuing at `indent`.
+fn to_json(value: &Value, compact: bool, unit: &str, indent: &str) -> String {
+    if compact {
+        return value.to_string();
+    }
+    let mut out = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(unit.as_bytes());
+    let mut serializer = serde_json::Serializer::with_formatter(&mut out, formatter);
+    value
+        .serialize(&mut serializer)
+        .expect("serializing a Value cannot fail");
+    String::from_utf8(out)
+        .expect("serde_json writes UTF-8")
+        .replace('\n', &format!("\n{indent}"))
+}
+
+fn edit_json(text: &str, edit: &SpecEdit, segments: &[String]) -> Result<String, CommandError> {
+    let compact = !text.trim_end().contains('\n');
+    let unit = text
+        .lines()
+        .map(|line| line_indent(line, 0))
+        .find(|indent| !indent.is_empty())
+        .unwrap_or("  ");
+
+    let mut text = text.to_string();
+    match locate_json(&text, &edit.pointer, segments)? {
+        Target::Found(range) => {
+            let indent = line_indent(&text, range.start).to_string();
+            text.replace_range(range, &to_json(&edit.value, compact, unit, &indent));
+        }
+        Target::Missing { container, missing } => {
+            let key = Value::String(missing[0].clone()).to_string();
+            let value = nest(&missing[1..], edit.value.clone());
+            let members = json_members(&text, container)
+                .ok_or_else(|| edit_error(&edit.pointer, "the document is not valid JSON"))?;
+            let separator = if compact { ":" } else { ": " };
+            match members.last() {
+                Some(last) => {
+                    let indent = line_indent(&text, last.start).to_string();
+                    let line_break = if compact {
+                        String::new()
+                    } else {
+                        format!("\n{indent}")
+                    };
+                    let member = format!(
+                        ",{line_break}{key}{separator}{}",
+                        to_json(&value, compact, unit, &indent)
+                    );
+                    text.insert_str(last.value.end, &member);
+                }
+                None => {
+                    let close = skip_ws(text.as_bytes(), container + 1);
+                    let member = if compact {
+                        format!("{key}:{}", to_json(&value, true, unit, ""))
+                    } else {
+                        let outer = line_indent(&text, container).to_string();
+                        let inner = format!("{outer}{unit}");
+                        format!(
+                            "\n{inner}{key}: {}\n{outer}",
+                            to_json(&value, false, unit, &inner)
+                        )
+                    };
+                    text.replace_range(container + 1..close, &member);
+                }
+            }
+        }
+    }
+    Ok(text)
+}
+
+// ─── YAML ────────────────────────────────────────────────────────────────────
+
+/// Lines `[start, end)` whose content starts at column `col`. The first line
+/// may have other text before `col` (a sequence item's `- `).
+#[derive(Clone, Copy)]
+struct Block {
+    start: usize,
+    end: usize,
+    col: usize,
+}
+
+/// A mapping entry or sequence item. `value` runs from just after the `:`
+/// or `-` to the end of its last content line.
+struct YamlNode {
+    key: Option<String>,
+    line: usize,
+    col: usize,
+    value: Range<usize>,
+    end_line: usize,
+}
+
+struct Yaml<'a> {
+    /// Byte offset and text of each line.
+    lines: Vec<(usize, &'a str)>,
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+/// Blank, comment and document-marker lines, which never hold values.
+fn is_ignorable(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.is_empty() || trimmed.starts_with('#') || trimmed == "---"
+}
+
+fn is_item(content: &str) -> bool {
+    content == "-" || content.starts_with("- ")
+}
+
+/// A mapping key at the start of `content` and the text after its colon.
+fn parse_key(content: &str) -> Option<(String, &str)> {
+    let (key, rest) = if content.starts_with('"') {
+        let end = string_end(content.as_bytes(), 0)?;
+        let key: String = serde_json::from_str(&content[..end]).ok()?;
+        (key, &content[end..])
+    } else if let Some(quoted) = content.strip_prefix('\'') {
+        let mut end = 0;
+        loop {
+            end += quoted[end..].find('\'')?;
+            if quoted[end + 1..].starts_with('\'') {
+                end += 2;
+            } else {
+                break;
+            }
+        }
+        (quoted[..end].replace("''", "'"), &quoted[end + 1..])
+    } else {
+        let colon = content
+            .match_indices(':')
+            .map(|(i, _)| i)
+            .find(|&i| matches!(content.as_bytes().get(i + 1), None | Some(b' ')))?;
+        (content[..colon].trim_end().to_string(), &content[colon..])
+    };
+    let rest = rest.trim_start_matches(' ').strip_prefix(':')?;
+    Some((key, rest))
+}
+
+/// Text after a key or dash, without its comment: empty for nested blocks.
+fn inline_value(rest: &str) -> &str {
+    let rest = rest.trim();
+    if rest.starts_with('#') {
+        ""
+    } else {
+        rest
+    }
+}
+
+impl<'a> Yaml<'a> {
+    fn new(text: &'a str) -> Self {
+        let mut offset = 0;
+        let lines = text
+            .split('\n')
+            .map(|line| {
+                let entry = (offset, line);
+                offset += line.len() + 1;
+                entry
+            })
+            .collect();
+        Self { lines }
+    }
+
+    fn content(&self, line: usize, block: Block) -> &'a str {
+        let text = self.lines[line].1;
+        text.get(block.col..).unwrap_or_default()
+    }
+
+    /// Lines of `block` that start an entry or item.
+    fn node_lines(&self, block: Block) -> Vec<usize> {
+        (block.start..block.end)
+            .filter(|&line| {
+                let text = self.lines[line].1;
+                !is_ignorable(text) && (line == block.start || indent_of(text) == block.col)
+            })
+            .filter(|&line| {
+                // Sequences may sit at their parent key's indent
+                line == block.start
+                    || !is_item(self.content(line, block))
+                    || self.is_sequence(block)
+            })
+            .collect()
+    }
+
+    fn is_sequence(&self, block: Block) -> bool {
+        is_item(self.content(block.start, block))
+    }
+
+    /// Line after the last content line in `[start, end)`.
+    fn content_end(&self, start: usize, end: usize) -> usize {
+        (start + 1..end)
+            .rev()
+            .find(|&line| !is_ignorable(self.lines[line].1))
+            .map_or(start + 1, |line| line + 1)
+    }
+
+    fn nodes(&self, block: Block) -> Result<Vec<YamlNode>, &'static str> {
+        let sequence = self.is_sequence(block);
+        let starts = self.node_lines(block);
+        let mut nodes = Vec::with_capacity(starts.len());
+        for (i, &line) in starts.iter().enumerate() {
+            let next = starts.get(i + 1).copied().unwrap_or(block.end);
+            let end_line = self.content_end(line, next);
+            let (offset, text) = self.lines[line];
+            let content = self.content(line, block);
+            let (key, rest) = if sequence {
+                (None, &content[1..])
+            } else {
+                let (key, rest) = parse_key(content).ok_or("only block mappings can be edited")?;
+                (Some(key), rest)
+            };
+            let (last_offset, last_text) = self.lines[end_line - 1];
+            nodes.push(YamlNode {
+                key,
+                line,
+                col: block.col,
+                value: offset + text.len() - rest.len()..last_offset + last_text.len(),
+                end_line,
+            });
+        }
+        Ok(nodes)
+    }
+
+    /// The block holding `node`'s value, for descending into it.
+    fn child(&self, node: &YamlNode, sequence_item: bool) -> Result<Block, &'static str> {
+        let (offset, text) = self.lines[node.line];
+        let inline = inline_value(&text[node.value.start - offset..]);
+        if sequence_item && !inline.is_empty() {
+            return Ok(Block {
+                start: node.line,
+                end: node.end_line,
+                col: node.col + 2,
+            });
+        }
+        if inline.starts_with(['{', '[']) {
+            return Err("flow collections cannot be edited inside");
+        }
+        if !inline.is_empty() {
+            return Err("a scalar has no fields");
+        }
+        let start = (node.line + 1..node.end_line)
+            .find(|&line| !is_ignorable(self.lines[line].1))
+            .ok_or("an empty value has no fields")?;
+        Ok(Block {
+            start,
+            end: node.end_line,
+            col: indent_of(self.lines[start].1),
+        })
+    }
+}
+
+/// Plain scalars that read back as the same string.
+fn is_plain(value: &str) -> bool {
+    const RESERVED: &[&str] = &[
+        "true", "false", "null", "yes", "no", "on", "off", "y", "n", "~",
+    ];
+    !value.is_empty()
+        && value.trim() == value
+        && !value.starts_with([
+            '-', '?', ':', ',', '[', ']', '{', '}', '#', '&', '*', '!', '|', '>', '\'', '"', '%',
+            '@', '`', '.',
+        ])
+        && !value.starts_with(|c: char| c.is_ascii_digit())
+        && !value.contains(": ")
+        && !value.contains(" #")
+        && !value.ends_with(':')
+        && !value.chars().any(char::is_control)
+        && !RESERVED.contains(&value.to_ascii_lowercase().as_str())
+}
+
+fn yaml_scalar(value: &Value) -> String {
+    match value {
+        Value::String(text) if is_plain(text) => text.clone(),
+        // JSON escapes are valid in YAML double-quoted scalars
+        other => other.to_string(),
+    }
+}
+
+/// `|` literal for multi-line text, when it round-trips exactly.
+fn block_scalar(text: &str, indent: usize) -> Option<String> {
+    let (body, chomp) = match text.strip_suffix('\n') {
+        Some(body) => (body, "|"),
+        None => (text, "|-"),
+    };
+    if body.starts_with([' ', '\t']) || body.ends_with('\n') || body.contains('\r') {
+        return None;
+    }
+    let pad = " ".repeat(indent);
+    let lines: Vec<String> = body
+        .split('\n')
+        .map(|line| {
+            if line.is_empty() {
+                String::new()
+            } else {
+                format!("{pad}{line}")
+            }
+        })
+        .collect();
+    Some(format!(" {chomp}\n{}", lines.join("\n")))
+}
+
+/// Text following `key:` for `value`, nested content at column `indent`.
+fn yaml_value(value: &Value, indent: usize) -> String {
+    let pad = " ".repeat(indent);
+    match value {
+        Value::Object(map) if !map.is_empty() => map
+            .iter()
+            .map(|(key, value)| {
+                format!(
+                    "\n{pad}{}:{}",
+                    yaml_scalar(&Value::String(key.clone())),
+                    yaml_value(value, indent + 2)
+                )
+            })
+            .collect(),
+        Value::Array(items) if !items.is_empty() => items
+            .iter()
+            .map(|item| format!("\n{pad}-{}", yaml_item(item, indent)))
+            .collect(),
+        Value::String(text) if text.contains('\n') => {
+            block_scalar(text, indent).unwrap_or_else(|| format!(" {value}"))
+        }
+        other => format!(" {}", yaml_scalar(other)),
+    }
+}
+
+/// Text following the `-` of a sequence item at column `col`; a mapping's
+/// first key shares the dash's line.
+fn yaml_item(item: &Value, col: usize) -> String {
+    match item {
+        Value::Object(map) if !map.is_empty() => {
+            let pad = " ".repeat(col + 2);
+            map.iter()
+                .enumerate()
+                .map(|(i, (key, value))| {
+                    let lead = if i == 0 {
+                        " ".to_string()
+                    } else {
+                        format!("\n{pad}")
+                    };
+                    format!(
+                        "{lead}{}:{}",
+                        yaml_scalar(&Value::String(key.clone())),
+                        yaml_value(value, col + 4)
+                    )
+                })
+                .collect()
+        }
+        other => yaml_value(other, col + 2),
+    }
+}
+
+fn edit_yaml(text: &str, edit: &SpecEdit, segments: &[String]) -> Result<String, CommandError> {
+    let yaml = Yaml::new(text);
+    let fail = |reason: &str| edit_error(&edit.pointer, reason);
+    let root = (0..yaml.lines.len())
+        .find(|&line| !is_ignorable(yaml.lines[line].1))
+        .ok_or_else(|| fail("the document is empty"))?;
+    let mut block = Block {
+        start: root,
+        end: yaml.lines.len(),
+        col: indent_of(yaml.lines[root].1),
+    };
+
+    for (depth, segment) in segments.iter().enumerate() {
+        let sequence = yaml.is_sequence(block);
+        let nodes = yaml.nodes(block).map_err(fail)?;
+        let node = if sequence {
+            segment.parse::<usize>().ok().and_then(|i| nodes.get(i))
+        } else {
+            nodes
+                .iter()
+                .find(|node| node.key.as_deref() == Some(segment))
+        };
+        let Some(node) = node else {
+            if sequence {
+                return Err(fail(&format!("no item {segment}")));
+            }
+            let last = nodes.last().ok_or_else(|| fail("empty mapping"))?;
+            let entry = format!(
+                "\n{}{}:{}",
+                " ".repeat(block.col),
+                yaml_scalar(&Value::String(segment.clone())),
+                yaml_value(
+                    &nest(&segments[depth + 1..], edit.value.clone()),
+                    block.col + 2
+                )
+            );
+            let mut text = text.to_string();
+            text.insert_str(last.value.end, &entry);
+            return Ok(text);
+        };
+
+        if depth + 1 == segments.len() {
+            let replacement = if sequence {
+                yaml_item(&edit.value, node.col)
+            } else {
+                // Keep the indent of a nested block being replaced; sequences
+                // may share their key's column, mappings may not
+                let indent = match yaml.child(node, false) {
+                    Ok(child) if child.col > node.col || edit.value.is_array() => child.col,
+                    _ => node.col + 2,
+                };
+                yaml_value(&edit.value, indent)
+            };
+            let mut text = text.to_string();
+            text.replace_range(node.value.clone(), &replacement);
+            return Ok(text);
+        }
+        block = yaml.child(node, sequence).map_err(fail)?;
+    }
+    Err(fail("the whole document cannot be replaced"))
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// Apply edits to a spec's source text, in order, changing only the edited
+/// values: comments, key order and formatting elsewhere are kept, so the
+/// saved file diffs cleanly.
+///
+/// Only descriptions, summaries, tags and examples can be edited. YAML specs
+/// must use block style along the edited path; a flow collection (`{…}`,
+/// `[…]`) can be replaced but not edited inside.
+#[tauri::command]
+pub fn edit_spec(content: String, edits: Vec<SpecEdit>) -> Result<String, CommandError> {
+    let json = content.trim_start().starts_with('{');
+    edits.iter().try_fold(content, |content, edit| {
+        let segments = parse_pointer(&edit.pointer)?;
+        if json {
+            edit_json(&content, edit, &segments)
+        } else {
+            edit_yaml(&content, edit, &segments)
+        }
+    })
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn edit(pointer: &str, value: Value) -> SpecEdit {
+        SpecEdit {
+            pointer: pointer.to_string(),
+            value,
+        }
+    }
+
+    const YAML_SPEC: &str = "\
+openapi: 3.0.3
+# Service metadata
+info:
+  title: Pets
+  description: Old text # inline note is replaced
+  version: '1.0'
+paths:
+  /pets/{id}:
+    get:
+      summary: Get a pet
+      tags:
+      - pets
+      responses:
+        '200':
+          description: OK
+tags:
+  - name: pets
+    description: Pet operations
+";
+
+    #[test]
+    fn test_yaml_replaces_only_the_edited_value() {
+        let edited = edit_spec(
+            YAML_SPEC.to_string(),
+            vec![
+                edit("/info/description", json!("Line one\nLine two")),
+                edit("/paths/~1pets~1{id}/get/tags", json!(["pets", "public"])),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            edited,
+            YAML_SPEC
+                .replace(
+                    "description: Old text # inline note is replaced",
+                    "description: |-\n    Line one\n    Line two"
+                )
+                .replace("      - pets\n", "      - pets\n      - public\n")
+        );
+    }
+
+    #[test]
+    fn test_yaml_adds_missing_keys_and_edits_sequence_items() {
+        let edited = edit_spec(
+            YAML_SPEC.to_string(),
+            vec![
+                edit(
+                    "/paths/~1pets~1{id}/get/responses/200/content/application~1json/examples",
+                    json!({ "rex": { "value": { "id": 1, "name": "Rex: the dog" } } }),
+                ),
+                edit("/tags/0/description", json!("true")),
+            ],
+        )
+        .unwrap();
+        assert!(edited.contains(
+            "          description: OK\n          content:\n            application/json:\n              \
+             examples:\n                rex:\n                  value:\n                    id: 1\n                    \
+             name: \"Rex: the dog\"\n"
+        ));
+        assert!(edited.ends_with("  - name: pets\n    description: \"true\"\n"));
+    }
+
+    #[test]
+    fn test_json_keeps_indent_and_untouched_members() {
+        let spec = "{\n    \"info\": {\n        \"title\": \"Pets\",\n        \"summary\": \"x\"\n    },\n    \"paths\": {}\n}\n";
+        let edited = edit_spec(
+            spec.to_string(),
+            vec![
+                edit("/info/summary", json!("Pet store")),
+                edit("/info/description", json!({ "a": [1] })),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            edited,
+            "{\n    \"info\": {\n        \"title\": \"Pets\",\n        \"summary\": \"Pet store\",\n        \
+             \"description\": {\n            \"a\": [\n                1\n            ]\n        }\n    },\n    \
+             \"paths\": {}\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_json_fills_empty_objects_and_compact_documents() {
+        let edited = edit_spec(
+            "{\"paths\":{},\"tags\":[]}".to_string(),
+            vec![edit("/paths/~1a/get/summary", json!("A"))],
+        )
+        .unwrap();
+        assert_eq!(
+            edited,
+            "{\"paths\":{\"/a\":{\"get\":{\"summary\":\"A\"}}},\"tags\":[]}"
+        );
+
+        let edited = edit_spec(
+            "{\n  \"info\": {}\n}".to_string(),
+            vec![edit("/info/description", json!("d"))],
+        )
+        .unwrap();
+        assert_eq!(
+            edited,
+            "{\n  \"info\": {\n    \"description\": \"d\"\n  }\n}"
+        );
+    }
+
+    #[test]
+    fn test_rejects_structural_and_unsupported_edits() {
+        for (spec, pointer) in [
+            (YAML_SPEC, "/paths/~1pets~1{id}/get/operationId"),
+            (YAML_SPEC, "/tags/5/description"),
+            ("info: { title: x }\n", "/info/description"),
+            ("{\"info\": 1}", "/info/description"),
+        ] {
+            let err = edit_spec(spec.to_string(), vec![edit(pointer, json!("x"))]).unwrap_err();
+            assert_eq!(err.code, ErrorCode::InvalidSpecEdit, "{pointer}");
+        }
+    }
+
+    #[test]
+    fn test_rejects_names_and_values_below_editable_fields() {
+        let spec = "components:\n  schemas:\n    Pet:\n      properties:\n        description:\n          type: string\n";
+        for pointer in [
+            "/components/schemas/Pet/properties/description",
+            "/components/schemas/Pet/properties/description/type",
+            "/paths/~1pets/get/description/type",
+            "/paths/summary/get/operationId",
+        ] {
+            let err = edit_spec(spec.to_string(), vec![edit(pointer, json!("x"))]).unwrap_err();
+            assert_eq!(err.code, ErrorCode::InvalidSpecEdit, "{pointer}");
+        }
+
+        // A schema named like a named map still has an editable description
+        let edited = edit_spec(
+            spec.to_string(),
+            vec![edit(
+                "/components/schemas/properties/description",
+                json!("d"),
+            )],
+        )
+        .unwrap();
+        assert!(edited.contains("    properties:\n      description: d\n"));
+    }
+}
@@ -0,0 +1,411 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use super::ensure_main_window;
+use super::store::JsonStore;
+use super::ApiRequest;
+use crate::error::{CommandError, ErrorCode};
+
+const MAPPINGS_FILE: &str = "environment_mappings.json";
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+/// A value that differs between two environments, e.g. an account id.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValueMapping {
+    pub from: String,
+    pub to: String,
+}
+
+/// How requests change when switching from one environment to another.
+/// The frontend owns the environments; they are referred to by id.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnvironmentMapping {
+    pub from: String,
+    pub to: String,
+    pub values: Vec<ValueMapping>,
+}
+
+/// Where a request was rewritten: `url`, `header:<name>` or `body`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct RewriteChange {
+    pub location: String,
+    pub from: String,
+    pub to: String,
+    /// Occurrences replaced.
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RewrittenRequest {
+    pub request: ApiRequest,
+    pub changes: Vec<RewriteChange>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct MappingStore {
+    /// By workspace id.
+    workspaces: HashMap<String, Vec<EnvironmentMapping>>,
+}
+
+// ─── Rewriting ───────────────────────────────────────────────────────────────
+
+impl EnvironmentMapping {
+    fn validate(&self) -> Result<(), CommandError> {
+        let mut seen = HashSet::new();
+        for value in &self.values {
+            if value.from.is_empty() || !seen.insert(value.from.as_str()) {
+                return Err(
+                    CommandError::new(ErrorCode::InvalidValueMapping).arg("value", &value.from)
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// The mapping for switching back.
+    fn reversed(&self) -> Self {
+        Self {
+            from: self.to.clone(),
+            to: self.from.clone(),
+            values: self
+                .values
+                .iter()
+                .map(|value| ValueMapping {
+                    from: value.to.clone(),
+                    to: value.from.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Whether `from` occurs at `at` in `text` as a whole token: a value
+/// starting or ending in a word character must not run on into the
+/// surrounding text, so `123` does not match inside `41234`.
+fn matches_at(text: &str, at: usize, from: &str) -> bool {
+    if !text[at..].starts_with(from) {
+        return false;
+    }
+    let before = text[..at].chars().next_back();
+    let after = text[at + from.len()..].chars().next();
+    let first = from.chars().next();
+    let last = from.chars().next_back();
+    let runs_on = |edge: Option<char>, next: Option<char>| {
+        edge.is_some_and(is_word) && next.is_some_and(is_word)
+    };
+    !runs_on(first, before) && !runs_on(last, after)
+}
+
+/// `text` with every mapped value replaced in one pass, so a replacement
+/// is never replaced again. The longest value wins where several match.
+fn replace_values(
+    text: &str,
+    values: &[ValueMapping],
+    tally: &mut BTreeMap<usize, usize>,
+) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut at = 0;
+    while at < text.len() {
+        let hit = values
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| matches_at(text, at, &value.from))
+            .max_by_key(|(_, value)| value.from.len());
+        match hit {
+            Some((index, value)) => {
+                out.push_str(&value.to);
+                at += value.from.len();
+                *tally.entry(index).or_default() += 1;
+            }
+            None => {
+                let c = text[at..].chars().next().unwrap_or_default();
+                out.push(c);
+                at += c.len_utf8();
+            }
+        }
+    }
+    out
+}
+
+/// `url` under `to_base` instead of `from_base`, if it is under it.
+fn rebase(url: &str, from_base: &str, to_base: &str) -> Option<String> {
+    let from_base = from_base.trim_end_matches('/');
+    let rest = url.strip_prefix(from_base)?;
+    if !(rest.is_empty() || rest.starts_with(['/', '?', '#'])) {
+        return None;
+    }
+    Some(format!("{}{rest}", to_base.trim_end_matches('/')))
+}
+
+/// Rewrite `request` for the target environment: its base URL, then the
+/// mapped values in its URL, header values and text body. Multipart parts
+/// and body files are sent as they are.
+fn rewrite(
+    request: &ApiRequest,
+    mapping: &EnvironmentMapping,
+    base_urls: Option<(&str, &str)>,
+) -> RewrittenRequest {
+    let mut request = request.clone();
+    let mut changes = Vec::new();
+    if let Some((from_base, to_base)) = base_urls {
+        if let Some(url) = rebase(&request.url, from_base, to_base) {
+            request.url = url;
+            changes.push(RewriteChange {
+                location: "url".to_string(),
+                from: from_base.to_string(),
+                to: to_base.to_string(),
+                count: 1,
+            });
+        }
+    }
+
+    let mut record = |location: String, tally: BTreeMap<usize, usize>| {
+        for (index, count) in tally {
+            let value = &mapping.values[index];
+            changes.push(RewriteChange {
+                location: location.clone(),
+                from: value.from.clone(),
+                to: value.to.clone(),
+                count,
+            });
+        }
+    };
+    let mut tally = BTreeMap::new();
+    request.url = replace_values(&request.url, &mapping.values, &mut tally);
+    record("url".to_string(), tally);
+
+    for (name, value) in &mut request.headers {
+        let mut tally = BTreeMap::new();
+        *value = replace_values(value, &mapping.values, &mut tally);
+        record(format!("header:{name}"), tally);
+    }
+    if let Some(body) = &mut request.body {
+        let mut tally = BTreeMap::new();
+        *body = replace_values(body, &mapping.values, &mut tally);
+        record("body".to_string(), tally);
+    }
+    changes.sort();
+    RewrittenRequest { request, changes }
+}
+
+// ─── Storage ─────────────────────────────────────────────────────────────────
+
+static MAPPINGS: JsonStore<MappingStore> = JsonStore::new(MAPPINGS_FILE);
+
+/// The mapping from `from` to `to`, else the reverse of the one from `to`
+/// to `from`, else an empty one.
+fn find_mapping(
+    store: &MappingStore,
+    workspace_id: &str,
+    from: &str,
+    to: &str,
+) -> EnvironmentMapping {
+    let mappings = store.workspaces.get(workspace_id);
+    let find = |from: &str, to: &str| {
+        mappings
+            .into_iter()
+            .flatten()
+            .find(|mapping| mapping.from == from && mapping.to == to)
+    };
+    if let Some(mapping) = find(from, to) {
+        return mapping.clone();
+    }
+    if let Some(mapping) = find(to, from) {
+        return mapping.reversed();
+    }
+    EnvironmentMapping {
+        from: from.to_string(),
+        to: to.to_string(),
+        values: Vec::new(),
+    }
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// The values rewritten when switching a workspace's requests from the
+/// environment `from` to `to`. A mapping set for the opposite direction is
+/// returned reversed.
+#[tauri::command]
+pub fn get_environment_mapping(
+    app: AppHandle,
+    workspace_id: String,
+    from: String,
+    to: String,
+) -> Result<EnvironmentMapping, CommandError> {
+    let store = MAPPINGS.read(&MAPPINGS.path(&app)?)?;
+    Ok(find_mapping(&store, &workspace_id, &from, &to))
+}
+
+/// Set the values rewritten between two environments of a workspace. The
+/// mapping serves both directions until the opposite one is set too; no
+/// values removes it.
+#[tauri::command]
+pub fn set_environment_mapping(
+    app: AppHandle,
+    webview: tauri::Webview,
+    workspace_id: String,
+    mapping: EnvironmentMapping,
+) -> Result<(), CommandError> {
+    ensure_main_window(webview.label())?;
+    mapping.validate()?;
+
+    MAPPINGS.update(&MAPPINGS.path(&app)?, |store| {
+        let mappings = store.workspaces.entry(workspace_id).or_default();
+        mappings.retain(|m| !(m.from == mapping.from && m.to == mapping.to));
+        if !mapping.values.is_empty() {
+            mappings.push(mapping);
+        }
+        Ok(())
+    })
+}
+
+/// Rewrite a request for the environment `to`: a URL under
+/// `from_base_url` moves under `to_base_url`, and the mapped values in the
+/// URL, header values and text body are replaced as whole tokens. Returns
+/// the request and what changed, for review before it is saved or sent.
+#[tauri::command]
+pub fn rewrite_for_environment(
+    app: AppHandle,
+    workspace_id: String,
+    from: String,
+    to: String,
+    request: ApiRequest,
+    from_base_url: Option<String>,
+    to_base_url: Option<String>,
+) -> Result<RewrittenRequest, CommandError> {
+    let store = MAPPINGS.read(&MAPPINGS.path(&app)?)?;
+    let mapping = find_mapping(&store, &workspace_id, &from, &to);
+    let base_urls = from_base_url.as_deref().zip(to_base_url.as_deref());
+    Ok(rewrite(&request, &mapping, base_urls))
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapping() -> EnvironmentMapping {
+        EnvironmentMapping {
+            from: "staging".to_string(),
+            to: "prod".to_string(),
+            values: vec![
+                ValueMapping {
+                    from: "1234".to_string(),
+                    to: "9876".to_string(),
+                },
+                ValueMapping {
+                    from: "acct-1234".to_string(),
+                    to: "acct-5555".to_string(),
+                },
+            ],
+        }
+    }
+
+    fn change(location: &str, from: &str, to: &str, count: usize) -> RewriteChange {
+        RewriteChange {
+            location: location.to_string(),
+            from: from.to_string(),
+            to: to.to_string(),
+            count,
+        }
+    }
+
+    #[test]
+    fn test_rewrite_moves_base_url_and_mapped_values() {
+        let request = ApiRequest {
+            method: "POST".to_string(),
+            url: "https://staging.api.example.com/v1/accounts/1234/orders?ref=41234".to_string(),
+            headers: HashMap::from([("X-Account".to_string(), "acct-1234".to_string())]),
+            body: Some(r#"{"account":"1234","note":"order 12345"}"#.to_string()),
+            ..Default::default()
+        };
+        let rewritten = rewrite(
+            &request,
+            &mapping(),
+            Some((
+                "https://staging.api.example.com/",
+                "https://api.example.com",
+            )),
+        );
+        assert_eq!(
+            rewritten.request.url,
+            "https://api.example.com/v1/accounts/9876/orders?ref=41234"
+        );
+        assert_eq!(rewritten.request.headers["X-Account"], "acct-5555");
+        assert_eq!(
+            rewritten.request.body.as_deref(),
+            Some(r#"{"account":"9876","note":"order 12345"}"#)
+        );
+        assert_eq!(
+            rewritten.changes,
+            vec![
+                change("body", "1234", "9876", 1),
+                change("header:X-Account", "acct-1234", "acct-5555", 1),
+                change("url", "1234", "9876", 1),
+                change(
+                    "url",
+                    "https://staging.api.example.com/",
+                    "https://api.example.com",
+                    1
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_replacements_are_not_replaced_again() {
+        let values = vec![
+            ValueMapping {
+                from: "a".to_string(),
+                to: "b".to_string(),
+            },
+            ValueMapping {
+                from: "b".to_string(),
+                to: "a".to_string(),
+            },
+        ];
+        let mut tally = BTreeMap::new();
+        assert_eq!(replace_values("a/b", &values, &mut tally), "b/a");
+        assert_eq!(
+            rebase("https://stagingx.example.com", "https://staging", "x"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_mappings_serve_the_reverse_switch() {
+        let path = std::env::temp_dir()
+            .join(format!("yasp-environments-{}", std::process::id()))
+            .join(MAPPINGS_FILE);
+        let _ = std::fs::remove_file(&path);
+        MAPPINGS
+            .update(&path, |store| {
+                store.workspaces.insert("ws".to_string(), vec![mapping()]);
+                Ok(())
+            })
+            .unwrap();
+        let store = MAPPINGS.read(&path).unwrap();
+        assert_eq!(find_mapping(&store, "ws", "staging", "prod"), mapping());
+        let back = find_mapping(&store, "ws", "prod", "staging");
+        assert_eq!(back.values[0].from, "9876");
+        assert_eq!(back.values[0].to, "1234");
+        assert!(find_mapping(&store, "other", "staging", "prod")
+            .values
+            .is_empty());
+
+        let mut duplicate = mapping();
+        duplicate.values.push(duplicate.values[0].clone());
+        assert_eq!(
+            duplicate.validate().unwrap_err().code,
+            ErrorCode::InvalidValueMapping
+        );
+    }
+}
@@ -0,0 +1,500 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::error::{CommandError, ErrorCode};
+
+/// Fixed so materialized bodies are stable across calls; placeholder parts
+/// carry no user data that could contain it.
+const MULTIPART_BOUNDARY: &str = "----YaspExampleBoundary7MA4YWxkTrZu0gW";
+
+/// Nested schemas deeper than this sample as `null`, which also stops
+/// self-referencing schemas the frontend has already dereferenced.
+const MAX_SAMPLE_DEPTH: usize = 8;
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+/// One ready-to-send body an operation's `requestBody` declares.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BodyExample {
+    pub content_type: String,
+    /// Key in the media type's `examples` map; `None` for the single
+    /// `example` value or a body sampled from the schema.
+    pub name: Option<String>,
+    pub summary: Option<String>,
+    /// No example was declared, so the body is generated from the schema.
+    pub from_schema: bool,
+    /// Set for examples published only as an `externalValue` URL, which the
+    /// frontend fetches like a spec.
+    pub external_value: Option<String>,
+}
+
+/// A file part left empty in a multipart body, for the user to attach.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlaceholderFile {
+    pub field: String,
+    pub filename: String,
+    pub content_type: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MaterializedBody {
+    /// Value for the `Content-Type` header, including the multipart boundary.
+    pub content_type: String,
+    pub body: String,
+    pub placeholder_files: Vec<PlaceholderFile>,
+}
+
+// ─── Schema Sampling ─────────────────────────────────────────────────────────
+
+/// The first non-null `type`; OpenAPI 3.1 allows a list.
+fn schema_type(schema: &Value) -> Option<&str> {
+    match &schema["type"] {
+        Value::String(kind) => Some(kind),
+        Value::Array(kinds) => kinds
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|k| *k != "null"),
+        _ if schema.get("properties").is_some() => Some("object"),
+        _ => None,
+    }
+}
+
+fn sample_string(schema: &Value) -> Value {
+    let sample = match schema["format"].as_str().unwrap_or_default() {
+        "date-time" => "2024-01-01T00:00:00Z",
+        "date" => "2024-01-01",
+        "time" => "00:00:00",
+        "email" => "user@example.com",
+        "uuid" => "00000000-0000-0000-0000-000000000000",
+        "uri" | "url" => "https://example.com",
+        "hostname" => "example.com",
+        "ipv4" => "192.0.2.1",
+        "ipv6" => "2001:db8::1",
+        "binary" | "byte" | "base64" => "",
+        _ => "string",
+    };
+    Value::String(sample.to_string())
+}
+
+/// A representative value for `schema`: its own example, default, const or
+/// first enum member, otherwise a placeholder of the right shape.
+fn sample(schema: &Value, depth: usize) -> Value {
+    if depth > MAX_SAMPLE_DEPTH {
+        return Value::Null;
+    }
+    for key in ["example", "default", "const"] {
+        if let Some(value) = schema.get(key) {
+            return value.clone();
+        }
+    }
+    if let Some(first) = schema["examples"].as_array().and_then(|e| e.first()) {
+        return first.clone();
+    }
+    if let Some(first) = schema["enum"].as_array().and_then(|e| e.first()) {
+        return first.clone();
+    }
+    if let Some(parts) = schema["allOf"].as_array() {
+        let mut merged = Map::new();
+        for part in parts {
+            if let Value::Object(fields) = sample(part, depth + 1) {
+                merged.extend(fields);
+            }
+        }
+        return Value::Object(merged);
+    }
+    for key in ["oneOf", "anyOf"] {
+        if let Some(first) = schema[key].as_array().and_then(|s| s.first()) {
+            return sample(first, depth + 1);
+        }
+    }
+
+    match schema_type(schema) {
+        Some("object") => Value::Object(
+            schema["properties"]
+                .as_object()
+                .into_iter()
+                .flatten()
+                .map(|(name, property)| (name.clone(), sample(property, depth + 1)))
+                .collect(),
+        ),
+        Some("array") => Value::Array(vec![sample(&schema["items"], depth + 1)]),
+        Some("string") => sample_string(schema),
+        Some("integer") => Value::from(0),
+        Some("number") => Value::from(0.0),
+        Some("boolean") => Value::Bool(false),
+        _ => Value::Null,
+    }
+}
+
+// ─── Serialization ───────────────────────────────────────────────────────────
+
+/// The media type without parameters, lowercased.
+fn essence(content_type: &str) -> String {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+fn is_json(essence: &str) -> bool {
+    essence == "application/json" || essence.ends_with("+json")
+}
+
+/// Strings are sent verbatim; structured values as JSON.
+fn as_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => serde_json::to_string_pretty(other).unwrap_or_default(),
+    }
+}
+
+fn form_urlencoded(value: &Value) -> String {
+    let mut form = url::form_urlencoded::Serializer::new(String::new());
+    for (name, value) in value.as_object().into_iter().flatten() {
+        match value {
+            // Arrays repeat the key (`explode: true`, the form default)
+            Value::Array(items) => {
+                for item in items {
+                    form.append_pair(name, &as_text(item));
+                }
+            }
+            Value::Null => {}
+            other => {
+                form.append_pair(name, &as_text(other));
+            }
+        }
+    }
+    form.finish()
+}
+
+/// Whether a multipart property is a file upload: a binary string (3.0),
+/// a `contentMediaType` (3.1), or an encoding with a non-text content type.
+fn is_file_field(property: &Value, encoding: &Value) -> bool {
+    let property = if schema_type(property) == Some("array") {
+        &property["items"]
+    } else {
+        property
+    };
+    let binary = matches!(property["format"].as_str(), Some("binary" | "base64"));
+    let media = property.get("contentMediaType").is_some();
+    let encoded = encoding["contentType"]
+        .as_str()
+        .is_some_and(|content_type| {
+            let essence = essence(content_type);
+            !essence.starts_with("text/") && !is_json(&essence)
+        });
+    binary || media || encoded
+}
+
+/// `name="…"` parameters escape quotes and line breaks as browsers do.
+fn disposition_name(name: &str) -> String {
+    name.replace('"', "%22")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn multipart(value: &Value, media: &Value) -> (String, Vec<PlaceholderFile>) {
+    let mut body = String::new();
+    let mut files = Vec::new();
+    for (field, value) in value.as_object().into_iter().flatten() {
+        let encoding = &media["encoding"][field];
+        let property = &media["schema"]["properties"][field];
+        let name = disposition_name(field);
+        body.push_str(&format!("--{MULTIPART_BOUNDARY}\r\n"));
+
+        if is_file_field(property, encoding) {
+            let content_type = encoding["contentType"]
+                .as_str()
+                .and_then(|types| types.split(',').next())
+                .or(property["contentMediaType"].as_str())
+                .unwrap_or("application/octet-stream")
+                .trim()
+                .to_string();
+            let filename = format!("{name}.bin");
+            body.push_str(&format!(
+                "Content-Disposition: form-data; name=\"{name}\"; filename=\"{filename}\"\r\n\
+                 Content-Type: {content_type}\r\n\r\n\r\n"
+            ));
+            files.push(PlaceholderFile {
+                field: field.clone(),
+                filename,
+                content_type,
+            });
+            continue;
+        }
+
+        body.push_str(&format!(
+            "Content-Disposition: form-data; name=\"{name}\"\r\n"
+        ));
+        let content_type = encoding["contentType"].as_str().or(match value {
+            Value::Object(_) | Value::Array(_) => Some("application/json"),
+            _ => None,
+        });
+        if let Some(content_type) = content_type {
+            body.push_str(&format!("Content-Type: {content_type}\r\n"));
+        }
+        body.push_str(&format!("\r\n{}\r\n", as_text(value)));
+    }
+    body.push_str(&format!("--{MULTIPART_BOUNDARY}--\r\n"));
+    (body, files)
+}
+
+fn materialize(content_type: &str, value: &Value, media: &Value) -> MaterializedBody {
+    let essence = essence(content_type);
+    if essence.starts_with("multipart/") {
+        let (body, placeholder_files) = multipart(value, media);
+        return MaterializedBody {
+            content_type: format!("{essence}; boundary={MULTIPART_BOUNDARY}"),
+            body,
+            placeholder_files,
+        };
+    }
+    let body = if is_json(&essence) {
+        serde_json::to_string_pretty(value).unwrap_or_default()
+    } else if essence == "application/x-www-form-urlencoded" {
+        form_urlencoded(value)
+    } else {
+        as_text(value)
+    };
+    MaterializedBody {
+        content_type: content_type.to_string(),
+        body,
+        placeholder_files: Vec::new(),
+    }
+}
+
+// ─── Lookup ──────────────────────────────────────────────────────────────────
+
+fn media_types(request_body: &Value) -> impl Iterator<Item = (&String, &Value)> {
+    request_body["content"].as_object().into_iter().flatten()
+}
+
+fn examples_of(content_type: &str, media: &Value) -> Vec<BodyExample> {
+    let example =
+        |name: Option<&String>, summary: Option<&str>, external: Option<&str>| BodyExample {
+            content_type: content_type.to_string(),
+            name: name.cloned(),
+            summary: summary.map(str::to_string),
+            from_schema: false,
+            external_value: external.map(str::to_string),
+        };
+
+    let mut examples: Vec<BodyExample> = media["examples"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(name, entry)| {
+            example(
+                Some(name),
+                entry["summary"].as_str(),
+                entry["externalValue"].as_str(),
+            )
+        })
+        .collect();
+    if media.get("example").is_some() {
+        examples.insert(0, example(None, None, None));
+    }
+    if examples.is_empty() && media.get("schema").is_some() {
+        examples.push(BodyExample {
+            from_schema: true,
+            ..example(None, None, None)
+        });
+    }
+    examples
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// Every body the operation's `requestBody` offers, one per media type and
+/// named example, in spec order. Media types without examples get one body
+/// sampled from their schema.
+///
+/// `request_body` is the operation's dereferenced Request Body Object, as
+/// parsed by the frontend.
+#[tauri::command]
+pub fn list_body_examples(request_body: Value) -> Vec<BodyExample> {
+    media_types(&request_body)
+        .flat_map(|(content_type, media)| examples_of(content_type, media))
+        .collect()
+}
+
+/// Serialize one of the bodies from `list_body_examples` for sending.
+///
+/// JSON is pretty-printed, form bodies are URL-encoded and multipart bodies
+/// get one part per field, with file fields left as empty placeholders.
+#[tauri::command]
+pub fn materialize_body_example(
+    request_body: Value,
+    content_type: String,
+    name: Option<String>,
+) -> Result<MaterializedBody, CommandError> {
+    let not_found = || {
+        CommandError::new(ErrorCode::ExampleNotFound)
+            .arg("name", name.as_deref().unwrap_or("example"))
+            .arg("content_type", &content_type)
+    };
+    let media = request_body["content"]
+        .get(&content_type)
+        .ok_or_else(not_found)?;
+
+    let value = match &name {
+        Some(name) => {
+            let entry = media["examples"].get(name).ok_or_else(not_found)?;
+            match entry.get("value") {
+                Some(value) => value.clone(),
+                None if entry.get("externalValue").is_some() => {
+                    return Err(not_found().detail("published only as an externalValue"))
+                }
+                None => return Err(not_found()),
+            }
+        }
+        None => match media.get("example") {
+            Some(example) => example.clone(),
+            None => sample(&media["schema"], 0),
+        },
+    };
+    Ok(materialize(&content_type, &value, media))
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn request_body() -> Value {
+        json!({
+            "content": {
+                "application/json": {
+                    "schema": { "type": "object" },
+                    "examples": {
+                        "minimal": { "summary": "Name only", "value": { "name": "Rex" } },
+                        "remote": { "externalValue": "https://example.com/pet.json" }
+                    }
+                },
+                "application/x-www-form-urlencoded": {
+                    "schema": {
+                        "type": "object",
+                        "properties": {
+                            "name": { "type": "string", "example": "Rex Jr" },
+                            "tags": { "type": "array", "items": { "enum": ["a", "b"] } }
+                        }
+                    }
+                },
+                "multipart/form-data": {
+                    "schema": {
+                        "type": "object",
+                        "properties": {
+                            "meta": { "type": "object", "properties": { "id": { "type": "integer" } } },
+                            "photo": { "type": "string", "format": "binary" }
+                        }
+                    },
+                    "encoding": { "photo": { "contentType": "image/png, image/jpeg" } }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_list_body_examples_covers_named_and_schema_bodies() {
+        let examples = list_body_examples(request_body());
+        let keys: Vec<_> = examples
+            .iter()
+            .map(|e| (e.content_type.as_str(), e.name.as_deref(), e.from_schema))
+            .collect();
+        assert_eq!(
+            keys,
+            [
+                ("application/json", Some("minimal"), false),
+                ("application/json", Some("remote"), false),
+                ("application/x-www-form-urlencoded", None, true),
+                ("multipart/form-data", None, true),
+            ]
+        );
+        assert_eq!(examples[0].summary.as_deref(), Some("Name only"));
+        assert_eq!(
+            examples[1].external_value.as_deref(),
+            Some("https://example.com/pet.json")
+        );
+    }
+
+    #[test]
+    fn test_materialize_json_and_form_bodies() {
+        let json_body = materialize_body_example(
+            request_body(),
+            "application/json".to_string(),
+            Some("minimal".to_string()),
+        )
+        .unwrap();
+        assert_eq!(json_body.body, "{\n  \"name\": \"Rex\"\n}");
+
+        let form = materialize_body_example(
+            request_body(),
+            "application/x-www-form-urlencoded".to_string(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(form.body, "name=Rex+Jr&tags=a");
+    }
+
+    #[test]
+    fn test_materialize_multipart_leaves_file_placeholders() {
+        let body =
+            materialize_body_example(request_body(), "multipart/form-data".to_string(), None)
+                .unwrap();
+        assert_eq!(
+            body.content_type,
+            format!("multipart/form-data; boundary={MULTIPART_BOUNDARY}")
+        );
+        assert!(body.body.contains(
+            "name=\"meta\"\r\nContent-Type: application/json\r\n\r\n{\n  \"id\": 0\n}\r\n"
+        ));
+        assert!(body
+            .body
+            .contains("name=\"photo\"; filename=\"photo.bin\"\r\nContent-Type: image/png\r\n"));
+        assert!(body
+            .body
+            .ends_with(&format!("--{MULTIPART_BOUNDARY}--\r\n")));
+        assert_eq!(
+            body.placeholder_files,
+            [PlaceholderFile {
+                field: "photo".to_string(),
+                filename: "photo.bin".to_string(),
+                content_type: "image/png".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_materialize_rejects_unknown_and_external_examples() {
+        for (content_type, name) in [
+            ("application/json", Some("missing")),
+            ("application/json", Some("remote")),
+            ("text/plain", None),
+        ] {
+            let err = materialize_body_example(
+                request_body(),
+                content_type.to_string(),
+                name.map(str::to_string),
+            )
+            .unwrap_err();
+            assert_eq!(err.code, ErrorCode::ExampleNotFound);
+        }
+    }
+
+    #[test]
+    fn test_sample_stops_at_max_depth() {
+        let mut schema = json!({ "type": "string" });
+        for _ in 0..=MAX_SAMPLE_DEPTH {
+            schema = json!({ "type": "object", "properties": { "child": schema } });
+        }
+        let mut value = &sample(&schema, 0);
+        for _ in 0..MAX_SAMPLE_DEPTH {
+            value = &value["child"];
+        }
+        assert_eq!(value["child"], Value::Null);
+    }
+}
@@ -0,0 +1,636 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::AppHandle;
+
+use super::ensure_main_window;
+use super::store::JsonStore;
+use crate::error::{CommandError, ErrorCode};
+
+const EXTENSIONS_FILE: &str = "extensions.json";
+
+const METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Extensions understood without registration.
+const BUILTIN: &[(&str, ExtensionKind, &str)] = &[
+    (
+        "x-rate-limit",
+        ExtensionKind::RateLimit,
+        "Requests allowed per time window",
+    ),
+    (
+        "x-internal",
+        ExtensionKind::Flag,
+        "Not part of the public API",
+    ),
+    (
+        "x-deprecated-date",
+        ExtensionKind::Date,
+        "When the operation was or will be deprecated",
+    ),
+    (
+        "x-sunset",
+        ExtensionKind::Date,
+        "When the operation will be removed",
+    ),
+];
+
+/// Values of these keywords are data, not spec objects, and are not
+/// searched for extensions.
+const DATA_KEYWORDS: &[&str] = &["example", "examples", "default", "enum", "const"];
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+/// How an extension's value is parsed, and so how the UI renders it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExtensionKind {
+    /// `true`/`false`, e.g. a badge.
+    Flag,
+    /// `YYYY-MM-DD`; date-times are cut to their date.
+    Date,
+    Number,
+    Text,
+    /// `100`, `"100/minute"` or `{ "limit": 100, "period": "minute" }`.
+    RateLimit,
+    /// Any JSON, shown as-is.
+    Json,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtensionDefinition {
+    /// Starts with `x-`.
+    pub name: String,
+    pub kind: ExtensionKind,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Bundled with the app rather than registered by the user.
+    #[serde(default)]
+    pub builtin: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RateLimit {
+    pub limit: u64,
+    /// `None` when the spec gives no window.
+    pub window_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum ExtensionValue {
+    Flag(bool),
+    Date(String),
+    Number(f64),
+    Text(String),
+    RateLimit(RateLimit),
+    Json(Value),
+}
+
+/// One extension found in a spec.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExtensionUse {
+    /// JSON pointer to the object carrying the extension; `""` for the root.
+    pub pointer: String,
+    pub name: String,
+    /// `None` for extensions nobody registered.
+    pub kind: Option<ExtensionKind>,
+    /// `None` when the value does not fit the registered kind; the UI then
+    /// shows `raw`. Unregistered extensions are kept as `Json`.
+    pub value: Option<ExtensionValue>,
+    pub raw: Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "condition", rename_all = "snake_case")]
+pub enum ExtensionCondition {
+    Present,
+    Absent,
+    /// Same JSON value; a flag also matches the strings `"true"`/`"false"`.
+    Equals {
+        value: Value,
+    },
+    /// A date extension strictly before `date` (`YYYY-MM-DD`).
+    Before {
+        date: String,
+    },
+    /// A date extension on or after `date`.
+    After {
+        date: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExtensionFilter {
+    pub name: String,
+    #[serde(flatten)]
+    pub condition: ExtensionCondition,
+}
+
+/// An operation matching every filter, with the extensions that apply to
+/// it: its own, and its path item's unless it overrides them.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExtensionMatch {
+    /// Upper-case HTTP method.
+    pub method: String,
+    pub path: String,
+    pub operation_id: Option<String>,
+    pub extensions: Vec<ExtensionUse>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ExtensionStore {
+    custom: Vec<ExtensionDefinition>,
+}
+
+// ─── Storage ─────────────────────────────────────────────────────────────────
+
+static EXTENSIONS: JsonStore<ExtensionStore> = JsonStore::new(EXTENSIONS_FILE);
+
+/// Built-in definitions, then the user's.
+fn definitions(store: &ExtensionStore) -> Vec<ExtensionDefinition> {
+    let mut all: Vec<ExtensionDefinition> = BUILTIN
+        .iter()
+        .map(|(name, kind, description)| ExtensionDefinition {
+            name: name.to_string(),
+            kind: *kind,
+            description: Some(description.to_string()),
+            builtin: true,
+        })
+        .collect();
+    all.extend(store.custom.iter().cloned());
+    all
+}
+
+fn validate_definition(definition: &ExtensionDefinition) -> Result<(), CommandError> {
+    let name = &definition.name;
+    let valid = name.len() > 2
+        && name.starts_with("x-")
+        && !name.chars().any(char::is_whitespace)
+        && !BUILTIN.iter().any(|(builtin, _, _)| builtin == name);
+    if valid {
+        Ok(())
+    } else {
+        Err(CommandError::new(ErrorCode::InvalidName).arg("name", name))
+    }
+}
+
+// ─── Parsing ─────────────────────────────────────────────────────────────────
+
+fn parse_flag(raw: &Value) -> Option<bool> {
+    match raw {
+        Value::Bool(flag) => Some(*flag),
+        Value::String(text) => text.parse().ok(),
+        _ => None,
+    }
+}
+
+/// `YYYY-MM-DD`, or the date part of an RFC 3339 date-time.
+pub(super) fn parse_date(raw: &str) -> Option<String> {
+    let date = raw.get(..10)?;
+    let digits = |range: std::ops::Range<usize>| date[range].bytes().all(|b| b.is_ascii_digit());
+    let rest_ok = raw.len() == 10 || raw[10..].starts_with(['T', 't', ' ']);
+    (digits(0..4)
+        && &date[4..5] == "-"
+        && digits(5..7)
+        && &date[7..8] == "-"
+        && digits(8..10)
+        && rest_ok)
+        .then(|| date.to_string())
+}
+
+/// `minute`, `hours`, `1h`, `60s`, or a number of seconds.
+fn parse_window(raw: &Value) -> Option<u64> {
+    if let Some(secs) = raw.as_u64() {
+        return Some(secs);
+    }
+    let text = raw.as_str()?.trim().to_ascii_lowercase();
+    let unit = |name: &str| match name.trim_end_matches('s') {
+        "second" | "sec" | "" => Some(1),
+        "minute" | "min" | "m" => Some(60),
+        "hour" | "h" => Some(3600),
+        "day" | "d" => Some(86_400),
+        _ => None,
+    };
+    if let Some(secs) = unit(&text) {
+        return Some(secs);
+    }
+    let split = text.find(|c: char| !c.is_ascii_digit())?;
+    let count: u64 = text[..split].parse().ok()?;
+    Some(count * unit(text[split..].trim())?)
+}
+
+fn parse_rate_limit(raw: &Value) -> Option<RateLimit> {
+    match raw {
+        Value::Number(_) => Some(RateLimit {
+            limit: raw.as_u64()?,
+            window_secs: None,
+        }),
+        Value::String(text) => {
+            let (limit, window) = text
+                .split_once('/')
+                .or_else(|| text.split_once(" per "))
+                .unwrap_or((text, ""));
+            Some(RateLimit {
+                limit: limit.trim().parse().ok()?,
+                window_secs: match window.trim() {
+                    "" => None,
+                    window => Some(parse_window(&Value::String(window.to_string()))?),
+                },
+            })
+        }
+        Value::Object(fields) => {
+            let limit = ["limit", "requests", "max"]
+                .iter()
+                .find_map(|key| fields.get(*key)?.as_u64())?;
+            let window = ["period", "window", "interval"]
+                .iter()
+                .find_map(|key| fields.get(*key));
+            Some(RateLimit {
+                limit,
+                window_secs: match window {
+                    Some(window) => Some(parse_window(window)?),
+                    None => None,
+                },
+            })
+        }
+        _ => None,
+    }
+}
+
+fn parse(kind: ExtensionKind, raw: &Value) -> Option<ExtensionValue> {
+    Some(match kind {
+        ExtensionKind::Flag => ExtensionValue::Flag(parse_flag(raw)?),
+        ExtensionKind::Date => ExtensionValue::Date(parse_date(raw.as_str()?)?),
+        ExtensionKind::Number => ExtensionValue::Number(raw.as_f64()?),
+        ExtensionKind::Text => ExtensionValue::Text(raw.as_str()?.to_string()),
+        ExtensionKind::RateLimit => ExtensionValue::RateLimit(parse_rate_limit(raw)?),
+        ExtensionKind::Json => ExtensionValue::Json(raw.clone()),
+    })
+}
+
+fn extension_use(
+    definitions: &[ExtensionDefinition],
+    pointer: String,
+    name: &str,
+    raw: &Value,
+) -> ExtensionUse {
+    let kind = definitions
+        .iter()
+        .find(|definition| definition.name == name)
+        .map(|definition| definition.kind);
+    ExtensionUse {
+        pointer,
+        name: name.to_string(),
+        kind,
+        value: parse(kind.unwrap_or(ExtensionKind::Json), raw),
+        raw: raw.clone(),
+    }
+}
+
+fn escape(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+/// Every `x-` key in the spec.
+fn collect(
+    definitions: &[ExtensionDefinition],
+    value: &Value,
+    pointer: &str,
+    found: &mut Vec<ExtensionUse>,
+) {
+    match value {
+        Value::Object(fields) => {
+            for (key, child) in fields {
+                if key.starts_with("x-") {
+                    found.push(extension_use(definitions, pointer.to_string(), key, child));
+                } else if !DATA_KEYWORDS.contains(&key.as_str()) {
+                    collect(
+                        definitions,
+                        child,
+                        &format!("{pointer}/{}", escape(key)),
+                        found,
+                    );
+                }
+            }
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                collect(definitions, item, &format!("{pointer}/{index}"), found);
+            }
+        }
+        _ => {}
+    }
+}
+
+// ─── Filters ─────────────────────────────────────────────────────────────────
+
+impl ExtensionFilter {
+    fn matches(&self, extensions: &[ExtensionUse]) -> bool {
+        let found = extensions.iter().find(|e| e.name == self.name);
+        let date = || match found?.value.as_ref()? {
+            ExtensionValue::Date(date) => Some(date.as_str()),
+            _ => None,
+        };
+        match &self.condition {
+            ExtensionCondition::Present => found.is_some(),
+            ExtensionCondition::Absent => found.is_none(),
+            ExtensionCondition::Equals { value } => found.is_some_and(|found| {
+                found.raw == *value
+                    || matches!(
+                        (&found.value, value),
+                        (Some(ExtensionValue::Flag(flag)), Value::Bool(wanted)) if flag == wanted
+                    )
+            }),
+            ExtensionCondition::Before { date: bound } => {
+                date().is_some_and(|date| date < bound.as_str())
+            }
+            ExtensionCondition::After { date: bound } => {
+                date().is_some_and(|date| date >= bound.as_str())
+            }
+        }
+    }
+}
+
+fn own_extensions(
+    definitions: &[ExtensionDefinition],
+    object: &Value,
+    pointer: &str,
+) -> Vec<ExtensionUse> {
+    object
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(key, _)| key.starts_with("x-"))
+        .map(|(key, raw)| extension_use(definitions, pointer.to_string(), key, raw))
+        .collect()
+}
+
+fn filter_operations(
+    definitions: &[ExtensionDefinition],
+    spec: &Value,
+    filters: &[ExtensionFilter],
+) -> Vec<ExtensionMatch> {
+    let mut matches = Vec::new();
+    for (path, item) in spec["paths"].as_object().into_iter().flatten() {
+        let item_pointer = format!("/paths/{}", escape(path));
+        let inherited = own_extensions(definitions, item, &item_pointer);
+        for method in METHODS {
+            let Some(operation) = item.get(*method).filter(|op| op.is_object()) else {
+                continue;
+            };
+            let mut extensions =
+                own_extensions(definitions, operation, &format!("{item_pointer}/{method}"));
+            for extension in &inherited {
+                if !extensions.iter().any(|own| own.name == extension.name) {
+                    extensions.push(extension.clone());
+                }
+            }
+            if filters.iter().all(|filter| filter.matches(&extensions)) {
+                matches.push(ExtensionMatch {
+                    method: method.to_ascii_uppercase(),
+                    path: path.clone(),
+                    operation_id: operation["operationId"].as_str().map(str::to_string),
+                    extensions,
+                });
+            }
+        }
+    }
+    matches
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// The extensions the UI knows how to render: the built-in `x-rate-limit`,
+/// `x-internal`, `x-deprecated-date` and `x-sunset`, then those the user
+/// registered.
+#[tauri::command]
+pub fn list_extension_definitions(
+    app: AppHandle,
+) -> Result<Vec<ExtensionDefinition>, CommandError> {
+    Ok(definitions(&EXTENSIONS.read(&EXTENSIONS.path(&app)?)?))
+}
+
+/// Register a vendor extension, or change the kind of one registered
+/// before. Built-in extensions cannot be redefined.
+#[tauri::command]
+pub fn register_extension(
+    app: AppHandle,
+    webview: tauri::Webview,
+    definition: ExtensionDefinition,
+) -> Result<(), CommandError> {
+    ensure_main_window(webview.label())?;
+    validate_definition(&definition)?;
+
+    let definition = ExtensionDefinition {
+        builtin: false,
+        ..definition
+    };
+    EXTENSIONS.update(&EXTENSIONS.path(&app)?, |store| {
+        match store
+            .custom
+            .iter_mut()
+            .find(|custom| custom.name == definition.name)
+        {
+            Some(existing) => *existing = definition,
+            None => store.custom.push(definition),
+        }
+        Ok(())
+    })
+}
+
+/// Forget a registered extension; `false` if it was not registered.
+#[tauri::command]
+pub fn unregister_extension(
+    app: AppHandle,
+    webview: tauri::Webview,
+    name: String,
+) -> Result<bool, CommandError> {
+    ensure_main_window(webview.label())?;
+
+    EXTENSIONS.update(&EXTENSIONS.path(&app)?, |store| {
+        let before = store.custom.len();
+        store.custom.retain(|custom| custom.name != name);
+        Ok(store.custom.len() != before)
+    })
+}
+
+/// Every vendor extension in the spec, parsed by its registered kind.
+/// Unregistered extensions are kept with their raw value.
+#[tauri::command]
+pub fn spec_extensions(app: AppHandle, spec: Value) -> Result<Vec<ExtensionUse>, CommandError> {
+    let definitions = definitions(&EXTENSIONS.read(&EXTENSIONS.path(&app)?)?);
+    let mut found = Vec::new();
+    collect(&definitions, &spec, "", &mut found);
+    Ok(found)
+}
+
+/// Operations whose extensions match every filter, e.g. internal operations
+/// or those deprecated before a date. No filters lists every operation.
+#[tauri::command]
+pub fn filter_operations_by_extension(
+    app: AppHandle,
+    spec: Value,
+    filters: Vec<ExtensionFilter>,
+) -> Result<Vec<ExtensionMatch>, CommandError> {
+    let definitions = definitions(&EXTENSIONS.read(&EXTENSIONS.path(&app)?)?);
+    Ok(filter_operations(&definitions, &spec, &filters))
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn builtins() -> Vec<ExtensionDefinition> {
+        definitions(&ExtensionStore::default())
+    }
+
+    fn filter(name: &str, condition: ExtensionCondition) -> ExtensionFilter {
+        ExtensionFilter {
+            name: name.to_string(),
+            condition,
+        }
+    }
+
+    #[test]
+    fn test_parse_rate_limits_and_dates() {
+        let limit = |limit, window_secs| RateLimit { limit, window_secs };
+        assert_eq!(parse_rate_limit(&json!(100)), Some(limit(100, None)));
+        assert_eq!(
+            parse_rate_limit(&json!("100/minute")),
+            Some(limit(100, Some(60)))
+        );
+        assert_eq!(
+            parse_rate_limit(&json!("5000 per hour")),
+            Some(limit(5000, Some(3600)))
+        );
+        assert_eq!(
+            parse_rate_limit(&json!({ "limit": 10, "window": "15m" })),
+            Some(limit(10, Some(900)))
+        );
+        assert_eq!(parse_rate_limit(&json!("lots")), None);
+
+        assert_eq!(parse_date("2025-06-30"), Some("2025-06-30".to_string()));
+        assert_eq!(
+            parse_date("2025-06-30T12:00:00Z"),
+            Some("2025-06-30".to_string())
+        );
+        assert_eq!(parse_date("30/06/2025"), None);
+    }
+
+    #[test]
+    fn test_collect_keeps_unknown_extensions_and_skips_examples() {
+        let spec = json!({
+            "openapi": "3.0.3",
+            "x-logo": { "url": "logo.png" },
+            "paths": { "/pets/{id}": { "get": {
+                "x-internal": "true",
+                "x-deprecated-date": "soon",
+                "responses": { "200": { "content": { "application/json": {
+                    "example": { "x-not-an-extension": 1 }
+                } } } }
+            } } }
+        });
+        let mut found = Vec::new();
+        collect(&builtins(), &spec, "", &mut found);
+        assert_eq!(found.len(), 3);
+        let named = |name: &str| found.iter().find(|e| e.name == name).unwrap();
+
+        let logo = named("x-logo");
+        assert_eq!(logo.pointer, "");
+        assert_eq!(logo.kind, None);
+        assert_eq!(
+            logo.value,
+            Some(ExtensionValue::Json(json!({ "url": "logo.png" })))
+        );
+        let internal = named("x-internal");
+        assert_eq!(internal.pointer, "/paths/~1pets~1{id}/get");
+        assert_eq!(internal.value, Some(ExtensionValue::Flag(true)));
+        // Registered but malformed: the raw value is kept for display
+        let deprecated = named("x-deprecated-date");
+        assert_eq!(deprecated.kind, Some(ExtensionKind::Date));
+        assert_eq!(deprecated.value, None);
+        assert_eq!(deprecated.raw, json!("soon"));
+    }
+
+    #[test]
+    fn test_filter_operations_with_inherited_extensions() {
+        let spec = json!({
+            "paths": {
+                "/admin": {
+                    "x-internal": true,
+                    "get": { "operationId": "listAdmins" },
+                    "post": { "x-internal": false, "x-deprecated-date": "2024-01-31" }
+                },
+                "/pets": { "get": { "x-deprecated-date": "2026-03-01" } }
+            }
+        });
+        let run = |filters: &[ExtensionFilter]| -> Vec<String> {
+            filter_operations(&builtins(), &spec, filters)
+                .into_iter()
+                .map(|m| format!("{} {}", m.method, m.path))
+                .collect()
+        };
+        let internal = filter(
+            "x-internal",
+            ExtensionCondition::Equals { value: json!(true) },
+        );
+        assert_eq!(run(&[internal]), vec!["GET /admin"]);
+        assert_eq!(
+            run(&[filter(
+                "x-deprecated-date",
+                ExtensionCondition::Before {
+                    date: "2025-01-01".to_string()
+                }
+            )]),
+            vec!["POST /admin"]
+        );
+        assert_eq!(
+            run(&[
+                filter("x-deprecated-date", ExtensionCondition::Present),
+                filter("x-internal", ExtensionCondition::Absent)
+            ]),
+            vec!["GET /pets"]
+        );
+        assert_eq!(run(&[]).len(), 3);
+    }
+
+    #[test]
+    fn test_registered_extensions_parse_and_builtins_are_reserved() {
+        let path = std::env::temp_dir()
+            .join(format!("yasp-extensions-{}", std::process::id()))
+            .join(EXTENSIONS_FILE);
+        let team = ExtensionDefinition {
+            name: "x-team".to_string(),
+            kind: ExtensionKind::Text,
+            description: None,
+            builtin: false,
+        };
+        EXTENSIONS
+            .update(&path, |store| {
+                store.custom.push(team.clone());
+                Ok(())
+            })
+            .unwrap();
+        let definitions = definitions(&EXTENSIONS.read(&path).unwrap());
+        assert_eq!(definitions.len(), BUILTIN.len() + 1);
+        assert_eq!(
+            extension_use(&definitions, String::new(), "x-team", &json!("payments")).value,
+            Some(ExtensionValue::Text("payments".to_string()))
+        );
+
+        for name in ["x-internal", "team", "x-"] {
+            let definition = ExtensionDefinition {
+                name: name.to_string(),
+                ..team.clone()
+            };
+            let err = validate_definition(&definition).unwrap_err();
+            assert_eq!(err.code, ErrorCode::InvalidName);
+        }
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+}
@@ -0,0 +1,195 @@
+use std::sync::{Mutex, RwLock};
+
+use nucleo_matcher::pattern::{CaseMatching, Normalization, Pattern};
+use nucleo_matcher::{Config, Matcher, Utf32Str};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use super::ensure_main_window;
+use crate::error::CommandError;
+
+const DEFAULT_LIMIT: usize = 50;
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FinderKind {
+    Spec,
+    Operation,
+    Request,
+    Environment,
+}
+
+/// One entry of the quick-open palette, supplied by the frontend stores.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FinderItem {
+    /// Frontend identifier used to open the item.
+    pub id: String,
+    pub kind: FinderKind,
+    /// Text the query is matched against, e.g. `GET /pets/{id} listPets`.
+    pub label: String,
+    /// Secondary text shown under the label, not matched.
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FinderMatch {
+    pub item: FinderItem,
+    pub score: u32,
+    /// Matched character positions in `label`, ascending, for highlighting.
+    pub indices: Vec<u32>,
+}
+
+/// Items searched by `fuzzy_find`. Held in the backend so each keystroke only
+/// sends the query over IPC, not the whole workspace.
+pub struct FinderIndex {
+    items: RwLock<Vec<FinderItem>>,
+    matcher: Mutex<Matcher>,
+}
+
+impl Default for FinderIndex {
+    fn default() -> Self {
+        Self {
+            items: RwLock::new(Vec::new()),
+            matcher: Mutex::new(Matcher::new(Config::DEFAULT)),
+        }
+    }
+}
+
+// ─── Matching ────────────────────────────────────────────────────────────────
+
+/// fzf-style ranking: word-boundary, camelCase and consecutive-character
+/// bonuses, smart case, and space-separated terms that must all match.
+/// Ties keep the frontend's original order.
+fn rank(
+    items: &[FinderItem],
+    query: &str,
+    limit: usize,
+    matcher: &mut Matcher,
+) -> Vec<FinderMatch> {
+    let pattern = Pattern::parse(query, CaseMatching::Smart, Normalization::Smart);
+    let mut buf = Vec::new();
+
+    let mut matches: Vec<(usize, u32)> = items
+        .iter()
+        .enumerate()
+        .filter_map(|(position, item)| {
+            let score = pattern.score(Utf32Str::new(&item.label, &mut buf), matcher)?;
+            Some((position, score))
+        })
+        .collect();
+    matches.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    matches.truncate(limit);
+
+    // Indices only for the results that are returned
+    matches
+        .into_iter()
+        .map(|(position, score)| {
+            let item = items[position].clone();
+            let mut indices = Vec::new();
+            pattern.indices(Utf32Str::new(&item.label, &mut buf), matcher, &mut indices);
+            indices.sort_unstable();
+            indices.dedup();
+            FinderMatch {
+                item,
+                score,
+                indices,
+            }
+        })
+        .collect()
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// Replace the searchable items. Called by the frontend whenever specs,
+/// saved requests or environments change.
+#[tauri::command]
+pub fn set_finder_items(
+    webview: tauri::Webview,
+    index: State<'_, FinderIndex>,
+    items: Vec<FinderItem>,
+) -> Result<(), CommandError> {
+    ensure_main_window(webview.label())?;
+
+    *index.items.write().unwrap_or_else(|e| e.into_inner()) = items;
+    Ok(())
+}
+
+/// Best matches for `query`, highest score first. An empty query returns the
+/// first `limit` items unranked.
+#[tauri::command]
+pub fn fuzzy_find(
+    webview: tauri::Webview,
+    index: State<'_, FinderIndex>,
+    query: String,
+    limit: Option<usize>,
+) -> Result<Vec<FinderMatch>, CommandError> {
+    ensure_main_window(webview.label())?;
+
+    let items = index.items.read().unwrap_or_else(|e| e.into_inner());
+    let mut matcher = index.matcher.lock().unwrap_or_else(|e| e.into_inner());
+    Ok(rank(
+        &items,
+        &query,
+        limit.unwrap_or(DEFAULT_LIMIT),
+        &mut matcher,
+    ))
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(id: &str, kind: FinderKind, label: &str) -> FinderItem {
+        FinderItem {
+            id: id.to_string(),
+            kind,
+            label: label.to_string(),
+            detail: None,
+        }
+    }
+
+    fn items() -> Vec<FinderItem> {
+        vec![
+            item("spec-1", FinderKind::Spec, "Petstore API"),
+            item("op-1", FinderKind::Operation, "GET /pets listPets"),
+            item("op-2", FinderKind::Operation, "POST /pets createPet"),
+            item("env-1", FinderKind::Environment, "Production"),
+        ]
+    }
+
+    fn ids(matches: &[FinderMatch]) -> Vec<&str> {
+        matches.iter().map(|m| m.item.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_rank_filters_and_orders_by_score() {
+        let mut matcher = Matcher::new(Config::DEFAULT);
+        let matches = rank(&items(), "lspets", 10, &mut matcher);
+        assert_eq!(ids(&matches), vec!["op-1"]);
+    }
+
+    #[test]
+    fn test_rank_requires_every_term() {
+        let mut matcher = Matcher::new(Config::DEFAULT);
+        let matches = rank(&items(), "pets post", 10, &mut matcher);
+        assert_eq!(ids(&matches), vec!["op-2"]);
+    }
+
+    #[test]
+    fn test_rank_reports_sorted_indices() {
+        let mut matcher = Matcher::new(Config::DEFAULT);
+        let matches = rank(&items(), "prod", 10, &mut matcher);
+        assert_eq!(matches[0].indices, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_rank_empty_query_keeps_order_and_limit() {
+        let mut matcher = Matcher::new(Config::DEFAULT);
+        let matches = rank(&items(), "", 2, &mut matcher);
+        assert_eq!(ids(&matches), vec!["spec-1", "op-1"]);
+    }
+}
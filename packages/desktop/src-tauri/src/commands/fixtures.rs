@@ -0,0 +1,286 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::ensure_main_window;
+use crate::error::{CommandError, ErrorCode};
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+/// A captured response to write as a fixture.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ResponseFixture {
+    pub operation_id: String,
+    pub status: u16,
+    /// Example name, e.g. `not-found` or `empty-list`; distinguishes several
+    /// fixtures with the same status.
+    pub example: Option<String>,
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FixtureLoader {
+    Jest,
+    Pytest,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FixtureExport {
+    /// Written files, relative to the export directory, `/`-separated.
+    pub files: Vec<String>,
+    pub loader: Option<String>,
+}
+
+const JEST_LOADER_FILE: &str = "yasp-fixtures.js";
+const PYTEST_LOADER_FILE: &str = "yasp_fixtures.py";
+
+const JEST_LOADER: &str = r#"// Generated by YASP. Loads response fixtures exported next to this file.
+const fs = require('fs');
+const path = require('path');
+
+function loadFixture(operationId, name) {
+  const dir = path.join(__dirname, operationId);
+  const file = fs.readdirSync(dir).find((f) => path.parse(f).name === String(name));
+  if (!file) throw new Error(`No fixture '${name}' for ${operationId}`);
+  const content = fs.readFileSync(path.join(dir, file), 'utf8');
+  return file.endsWith('.json') ? JSON.parse(content) : content;
+}
+
+module.exports = { loadFixture };
+"#;
+
+const PYTEST_LOADER: &str = r#"# Generated by YASP. Loads response fixtures exported next to this file.
+import json
+from pathlib import Path
+
+import pytest
+
+FIXTURES_DIR = Path(__file__).parent
+
+
+def load_fixture(operation_id, name):
+    matches = [p for p in (FIXTURES_DIR / operation_id).iterdir() if p.stem == str(name)]
+    if not matches:
+        raise FileNotFoundError(f"No fixture '{name}' for {operation_id}")
+    content = matches[0].read_text(encoding="utf-8")
+    return json.loads(content) if matches[0].suffix == ".json" else content
+
+
+@pytest.fixture
+def api_fixture():
+    return load_fixture
+"#;
+
+// ─── Layout ──────────────────────────────────────────────────────────────────
+
+/// OWASP A01:2025 – Broken Access Control: names come from the spec and the
+/// user, so reduce them to a single safe path component (no separators, no
+/// `..`) before they touch the filesystem.
+fn file_component(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let cleaned = cleaned.trim_matches(|c| c == '.' || c == '-');
+    if cleaned.is_empty() {
+        "unnamed".to_string()
+    } else {
+        cleaned.to_string()
+    }
+}
+
+fn content_type(fixture: &ResponseFixture) -> String {
+    fixture
+        .headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+        .map(|(_, value)| value.to_ascii_lowercase())
+        .unwrap_or_default()
+}
+
+/// File extension and contents: JSON bodies are pretty-printed so fixtures
+/// diff cleanly, everything else is written verbatim.
+fn render(fixture: &ResponseFixture) -> (&'static str, String) {
+    if let Ok(json) = serde_json::from_str::<serde_json::Value>(&fixture.body) {
+        if let Ok(pretty) = serde_json::to_string_pretty(&json) {
+            return ("json", pretty + "\n");
+        }
+    }
+    let content_type = content_type(fixture);
+    let extension = if content_type.contains("xml") {
+        "xml"
+    } else if content_type.contains("html") {
+        "html"
+    } else {
+        "txt"
+    };
+    (extension, fixture.body.clone())
+}
+
+/// `<operation>/<status>[-<example>].<ext>`, with `-2`, `-3`… appended to
+/// avoid overwriting another fixture from the same export.
+fn fixture_path(fixture: &ResponseFixture, extension: &str, taken: &mut HashSet<String>) -> String {
+    let stem = match &fixture.example {
+        Some(example) => format!("{}-{}", fixture.status, file_component(example)),
+        None => fixture.status.to_string(),
+    };
+    let dir = file_component(&fixture.operation_id);
+
+    let mut path = format!("{dir}/{stem}.{extension}");
+    let mut n = 2;
+    while !taken.insert(path.clone()) {
+        path = format!("{dir}/{stem}-{n}.{extension}");
+        n += 1;
+    }
+    path
+}
+
+fn write_file(root: &Path, relative: &str, contents: &str) -> Result<(), CommandError> {
+    let path: PathBuf = relative
+        .split('/')
+        .fold(root.to_path_buf(), |p, c| p.join(c));
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| CommandError::new(ErrorCode::StorageFailed).detail(e))?;
+    }
+    std::fs::write(&path, contents)
+        .map_err(|e| CommandError::new(ErrorCode::StorageFailed).detail(e))
+}
+
+fn export(
+    dir: &Path,
+    fixtures: &[ResponseFixture],
+    loader: Option<FixtureLoader>,
+) -> Result<FixtureExport, CommandError> {
+    if !dir.is_absolute() || !dir.is_dir() {
+        return Err(CommandError::new(ErrorCode::InvalidPath).arg("path", dir.display()));
+    }
+
+    let mut taken = HashSet::new();
+    let mut files = Vec::with_capacity(fixtures.len());
+    for fixture in fixtures {
+        let (extension, contents) = render(fixture);
+        let relative = fixture_path(fixture, extension, &mut taken);
+        write_file(dir, &relative, &contents)?;
+        files.push(relative);
+    }
+
+    let loader = match loader {
+        Some(FixtureLoader::Jest) => Some((JEST_LOADER_FILE, JEST_LOADER)),
+        Some(FixtureLoader::Pytest) => Some((PYTEST_LOADER_FILE, PYTEST_LOADER)),
+        None => None,
+    };
+    if let Some((file, contents)) = loader {
+        write_file(dir, file, contents)?;
+    }
+    Ok(FixtureExport {
+        files,
+        loader: loader.map(|(file, _)| file.to_string()),
+    })
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// Write captured responses into `dir` as test fixtures, one directory per
+/// operation, optionally with a Jest or pytest helper for loading them.
+///
+/// `dir` must be an existing absolute directory, normally picked by the user
+/// in a folder dialog.
+#[tauri::command]
+pub fn export_fixtures(
+    webview: tauri::Webview,
+    dir: String,
+    fixtures: Vec<ResponseFixture>,
+    loader: Option<FixtureLoader>,
+) -> Result<FixtureExport, CommandError> {
+    ensure_main_window(webview.label())?;
+
+    export(Path::new(&dir), &fixtures, loader)
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(
+        operation_id: &str,
+        status: u16,
+        example: Option<&str>,
+        body: &str,
+    ) -> ResponseFixture {
+        ResponseFixture {
+            operation_id: operation_id.to_string(),
+            status,
+            example: example.map(str::to_string),
+            headers: HashMap::new(),
+            body: body.to_string(),
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("yasp-fixtures-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_file_component_blocks_traversal() {
+        assert_eq!(file_component("../../etc/passwd"), "etc-passwd");
+        assert_eq!(file_component("listPets"), "listPets");
+        assert_eq!(file_component(".."), "unnamed");
+    }
+
+    #[test]
+    fn test_fixture_paths_are_unique() {
+        let mut taken = HashSet::new();
+        let a = fixture("listPets", 200, None, "[]");
+        assert_eq!(fixture_path(&a, "json", &mut taken), "listPets/200.json");
+        assert_eq!(fixture_path(&a, "json", &mut taken), "listPets/200-2.json");
+        let b = fixture("getPet", 404, Some("not found"), "");
+        assert_eq!(
+            fixture_path(&b, "txt", &mut taken),
+            "getPet/404-not-found.txt"
+        );
+    }
+
+    #[test]
+    fn test_render_pretty_prints_json() {
+        let (extension, contents) = render(&fixture("listPets", 200, None, r#"{"a":1}"#));
+        assert_eq!(extension, "json");
+        assert_eq!(contents, "{\n  \"a\": 1\n}\n");
+    }
+
+    #[test]
+    fn test_export_writes_fixtures_and_loader() {
+        let dir = temp_dir("export");
+        let report = export(
+            &dir,
+            &[fixture("listPets", 200, None, "[]")],
+            Some(FixtureLoader::Pytest),
+        )
+        .unwrap();
+        assert_eq!(report.files, vec!["listPets/200.json"]);
+        assert_eq!(report.loader.as_deref(), Some(PYTEST_LOADER_FILE));
+        assert!(dir.join("listPets").join("200.json").is_file());
+        assert!(dir.join(PYTEST_LOADER_FILE).is_file());
+    }
+
+    #[test]
+    fn test_export_requires_absolute_directory() {
+        let err = export(Path::new("relative/dir"), &[], None).unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidPath);
+    }
+}
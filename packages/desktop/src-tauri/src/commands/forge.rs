@@ -0,0 +1,512 @@
+use std::collections::HashMap;
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use super::http::HttpClients;
+use super::{ensure_main_window, is_retryable_status, transport_error, validate_url};
+use crate::error::{CommandError, ErrorCode};
+
+const GITHUB_API: &str = "https://api.github.com";
+const GITLAB_API: &str = "https://gitlab.com/api/v4";
+
+const PAGE_SIZE: u32 = 100;
+/// Upper bound on pages fetched per listing, so a huge org cannot stall the UI.
+const MAX_PAGES: u32 = 10;
+
+/// Names matched by the code search; the frontend parser decides whether a
+/// hit really is a spec.
+const SPEC_FILE_QUERIES: &[&str] = &["openapi", "swagger"];
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ForgeProvider {
+    Github,
+    Gitlab,
+}
+
+/// Connection to a GitHub or GitLab instance. `api_url` is only needed for
+/// GitHub Enterprise or self-managed GitLab.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ForgeConnection {
+    pub provider: ForgeProvider,
+    pub api_url: Option<String>,
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ForgeRepo {
+    /// `owner/name` on GitHub, the project path on GitLab.
+    pub full_name: String,
+    /// GitLab project id; GitHub repos are addressed by `full_name`.
+    pub id: Option<u64>,
+    pub default_branch: Option<String>,
+    pub web_url: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ForgeSpecFile {
+    /// `full_name` of the repository in `list_forge_repos`.
+    pub repo: String,
+    pub path: String,
+    /// Branch the hit was found on; GitHub only indexes the default branch.
+    pub branch: Option<String>,
+    pub web_url: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ForgeSpecImport {
+    pub content: String,
+    /// Link back to the file on the branch it was imported from.
+    pub source_url: String,
+    pub branch: String,
+}
+
+#[derive(Deserialize)]
+struct GithubRepo {
+    full_name: String,
+    default_branch: Option<String>,
+    html_url: String,
+}
+
+#[derive(Deserialize)]
+struct GithubSearch {
+    items: Vec<GithubSearchItem>,
+}
+
+#[derive(Deserialize)]
+struct GithubSearchItem {
+    path: String,
+    html_url: String,
+    repository: GithubSearchRepo,
+}
+
+#[derive(Deserialize)]
+struct GithubSearchRepo {
+    full_name: String,
+}
+
+#[derive(Deserialize)]
+struct GitlabProject {
+    id: u64,
+    path_with_namespace: String,
+    default_branch: Option<String>,
+    web_url: String,
+}
+
+#[derive(Deserialize)]
+struct GitlabBlob {
+    path: String,
+    project_id: u64,
+    #[serde(rename = "ref")]
+    branch: String,
+}
+
+// ─── Requests ────────────────────────────────────────────────────────────────
+
+impl ForgeConnection {
+    fn base(&self) -> &str {
+        self.api_url
+            .as_deref()
+            .unwrap_or(match self.provider {
+                ForgeProvider::Github => GITHUB_API,
+                ForgeProvider::Gitlab => GITLAB_API,
+            })
+            .trim_end_matches('/')
+    }
+
+    /// API URL for `segments` below the base, each percent-encoded as one
+    /// path segment (GitLab group and file paths contain slashes).
+    fn url(&self, segments: &[&str], query: &[(&str, &str)]) -> Result<url::Url, CommandError> {
+        // OWASP A09:2025 – SSRF: self-hosted API URLs get the same policy as requests
+        let mut url = validate_url(self.base())?;
+        url.path_segments_mut()
+            .map_err(|_| CommandError::new(ErrorCode::InvalidUrl).detail(self.base()))?
+            .pop_if_empty()
+            .extend(segments);
+        if !query.is_empty() {
+            url.query_pairs_mut().extend_pairs(query);
+        }
+        Ok(url)
+    }
+
+    fn headers(&self, raw: bool) -> Result<HeaderMap, CommandError> {
+        let mut headers = HeaderMap::new();
+        let accept = match (self.provider, raw) {
+            (ForgeProvider::Github, true) => "application/vnd.github.raw",
+            (ForgeProvider::Github, false) => "application/vnd.github+json",
+            (ForgeProvider::Gitlab, _) => "application/json",
+        };
+        headers.insert(reqwest::header::ACCEPT, HeaderValue::from_static(accept));
+        if let Some(token) = self.token.as_deref().filter(|t| !t.is_empty()) {
+            let (name, value) = match self.provider {
+                ForgeProvider::Github => {
+                    (reqwest::header::AUTHORIZATION, format!("Bearer {token}"))
+                }
+                ForgeProvider::Gitlab => {
+                    (HeaderName::from_static("private-token"), token.to_string())
+                }
+            };
+            let mut value = HeaderValue::from_str(&value)
+                .map_err(|_| CommandError::new(ErrorCode::InvalidHeaderValue).arg("name", &name))?;
+            // Keep the token out of debug output
+            value.set_sensitive(true);
+            headers.insert(name, value);
+        }
+        Ok(headers)
+    }
+
+    async fn get(
+        &self,
+        clients: &HttpClients,
+        url: url::Url,
+        raw: bool,
+    ) -> Result<Vec<u8>, CommandError> {
+        // Never forward the token to another host
+        let response = clients
+            .spec_client_without_redirects(&url, None)?
+            .get(url)
+            .headers(self.headers(raw)?)
+            // GitHub rejects requests without a User-Agent
+            .header(
+                reqwest::header::USER_AGENT,
+                concat!("yasp-desktop/", env!("CARGO_PKG_VERSION")),
+            )
+            .send()
+            .await
+            .map_err(|e| transport_error(&e))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(CommandError::new(ErrorCode::HttpStatus)
+                .arg("status", status.as_u16())
+                .retryable(is_retryable_status(status)));
+        }
+
+        // OWASP A04:2025 – Insecure Design: API pages and files share the 5MB spec limit
+        let body_bytes = response
+            .bytes()
+            .await
+            .map_err(|e| CommandError::new(ErrorCode::BodyReadFailed).detail(e))?;
+        const MAX_FORGE_BYTES: usize = 5 * 1024 * 1024; // 5 MB
+        if body_bytes.len() > MAX_FORGE_BYTES {
+            return Err(CommandError::new(ErrorCode::BodyTooLarge).arg("limit", "5MB"));
+        }
+        Ok(body_bytes.to_vec())
+    }
+
+    async fn get_json<T: DeserializeOwned>(
+        &self,
+        clients: &HttpClients,
+        url: url::Url,
+    ) -> Result<T, CommandError> {
+        let body = self.get(clients, url, false).await?;
+        serde_json::from_slice(&body)
+            .map_err(|e| CommandError::new(ErrorCode::InvalidResponse).detail(e))
+    }
+
+    /// Fetch pages until a short page or `MAX_PAGES`.
+    async fn get_pages<T: DeserializeOwned>(
+        &self,
+        clients: &HttpClients,
+        segments: &[&str],
+        query: &[(&str, &str)],
+    ) -> Result<Vec<T>, CommandError> {
+        let per_page = PAGE_SIZE.to_string();
+        let mut items = Vec::new();
+        for page in 1..=MAX_PAGES {
+            let page = page.to_string();
+            let mut page_query = query.to_vec();
+            page_query.extend([("per_page", per_page.as_str()), ("page", page.as_str())]);
+            let batch: Vec<T> = self
+                .get_json(clients, self.url(segments, &page_query)?)
+                .await?;
+            let done = batch.len() < PAGE_SIZE as usize;
+            items.extend(batch);
+            if done {
+                break;
+            }
+        }
+        Ok(items)
+    }
+}
+
+fn github_repo_segments(repo: &str) -> Result<Vec<&str>, CommandError> {
+    match repo.split_once('/') {
+        Some((owner, name)) if !owner.is_empty() && !name.is_empty() && !name.contains('/') => {
+            Ok(vec!["repos", owner, name])
+        }
+        _ => Err(CommandError::new(ErrorCode::InvalidUrl)
+            .detail(format!("expected owner/name, got '{repo}'"))),
+    }
+}
+
+/// Link to a file on a branch in the forge's web UI.
+fn blob_url(provider: ForgeProvider, repo_web_url: &str, branch: &str, path: &str) -> String {
+    let separator = match provider {
+        ForgeProvider::Github => "blob",
+        ForgeProvider::Gitlab => "-/blob",
+    };
+    let path: Vec<String> = path
+        .split('/')
+        .map(|segment| super::aws::uri_encode(segment, false))
+        .collect();
+    format!(
+        "{}/{separator}/{}/{}",
+        repo_web_url.trim_end_matches('/'),
+        super::aws::uri_encode(branch, true),
+        path.join("/")
+    )
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// List the repositories of a GitHub organization or GitLab group
+/// (including subgroups).
+#[tauri::command]
+pub async fn list_forge_repos(
+    webview: tauri::Webview,
+    clients: State<'_, HttpClients>,
+    connection: ForgeConnection,
+    org: String,
+) -> Result<Vec<ForgeRepo>, CommandError> {
+    ensure_main_window(webview.label())?;
+
+    match connection.provider {
+        ForgeProvider::Github => {
+            let repos: Vec<GithubRepo> = connection
+                .get_pages(&clients, &["orgs", &org, "repos"], &[])
+                .await?;
+            Ok(repos
+                .into_iter()
+                .map(|repo| ForgeRepo {
+                    full_name: repo.full_name,
+                    id: None,
+                    default_branch: repo.default_branch,
+                    web_url: repo.html_url,
+                })
+                .collect())
+        }
+        ForgeProvider::Gitlab => {
+            let projects: Vec<GitlabProject> = connection
+                .get_pages(
+                    &clients,
+                    &["groups", &org, "projects"],
+                    &[("include_subgroups", "true"), ("archived", "false")],
+                )
+                .await?;
+            Ok(projects
+                .into_iter()
+                .map(|project| ForgeRepo {
+                    full_name: project.path_with_namespace,
+                    id: Some(project.id),
+                    default_branch: project.default_branch,
+                    web_url: project.web_url,
+                })
+                .collect())
+        }
+    }
+}
+
+/// Search an organization's code for files named like OpenAPI/Swagger
+/// documents. Both code-search APIs require a token.
+#[tauri::command]
+pub async fn search_forge_specs(
+    webview: tauri::Webview,
+    clients: State<'_, HttpClients>,
+    connection: ForgeConnection,
+    org: String,
+) -> Result<Vec<ForgeSpecFile>, CommandError> {
+    ensure_main_window(webview.label())?;
+
+    let mut files = Vec::new();
+    match connection.provider {
+        ForgeProvider::Github => {
+            for name in SPEC_FILE_QUERIES {
+                let query = format!("org:{org} filename:{name}");
+                let url = connection.url(
+                    &["search", "code"],
+                    &[("q", query.as_str()), ("per_page", "100")],
+                )?;
+                let search: GithubSearch = connection.get_json(&clients, url).await?;
+                files.extend(search.items.into_iter().map(|item| ForgeSpecFile {
+                    repo: item.repository.full_name,
+                    path: item.path,
+                    branch: None,
+                    web_url: item.html_url,
+                }));
+            }
+        }
+        ForgeProvider::Gitlab => {
+            // Blob hits only carry the project id
+            let projects: HashMap<u64, GitlabProject> = connection
+                .get_pages::<GitlabProject>(
+                    &clients,
+                    &["groups", &org, "projects"],
+                    &[("include_subgroups", "true")],
+                )
+                .await?
+                .into_iter()
+                .map(|project| (project.id, project))
+                .collect();
+            for name in SPEC_FILE_QUERIES {
+                let query = format!("filename:{name}*");
+                let blobs: Vec<GitlabBlob> = connection
+                    .get_pages(
+                        &clients,
+                        &["groups", &org, "search"],
+                        &[("scope", "blobs"), ("search", query.as_str())],
+                    )
+                    .await?;
+                files.extend(blobs.into_iter().filter_map(|blob| {
+                    let project = projects.get(&blob.project_id)?;
+                    Some(ForgeSpecFile {
+                        repo: project.path_with_namespace.clone(),
+                        web_url: blob_url(
+                            ForgeProvider::Gitlab,
+                            &project.web_url,
+                            &blob.branch,
+                            &blob.path,
+                        ),
+                        path: blob.path,
+                        branch: Some(blob.branch),
+                    })
+                }));
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Fetch one file for import, with a link back to it on `branch`.
+/// `repo` is the repository `full_name` (GitLab also accepts the project id);
+/// `repo_web_url` is the repository's `web_url` from `list_forge_repos`.
+#[tauri::command]
+pub async fn import_forge_spec(
+    webview: tauri::Webview,
+    clients: State<'_, HttpClients>,
+    connection: ForgeConnection,
+    repo: String,
+    repo_web_url: String,
+    path: String,
+    branch: String,
+) -> Result<ForgeSpecImport, CommandError> {
+    ensure_main_window(webview.label())?;
+
+    let url = match connection.provider {
+        ForgeProvider::Github => {
+            let mut segments = github_repo_segments(&repo)?;
+            segments.push("contents");
+            segments.extend(path.split('/'));
+            connection.url(&segments, &[("ref", branch.as_str())])?
+        }
+        ForgeProvider::Gitlab => connection.url(
+            &["projects", &repo, "repository", "files", &path, "raw"],
+            &[("ref", branch.as_str())],
+        )?,
+    };
+    let body = connection.get(&clients, url, true).await?;
+    let content = String::from_utf8(body).map_err(|_| CommandError::new(ErrorCode::InvalidUtf8))?;
+
+    Ok(ForgeSpecImport {
+        content,
+        source_url: blob_url(connection.provider, &repo_web_url, &branch, &path),
+        branch,
+    })
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn connection(provider: ForgeProvider) -> ForgeConnection {
+        ForgeConnection {
+            provider,
+            api_url: None,
+            token: Some("secret".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_gitlab_urls_encode_group_and_file_paths() {
+        let url = connection(ForgeProvider::Gitlab)
+            .url(
+                &[
+                    "projects",
+                    "acme/api",
+                    "repository",
+                    "files",
+                    "spec/openapi.yaml",
+                    "raw",
+                ],
+                &[("ref", "main")],
+            )
+            .unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://gitlab.com/api/v4/projects/acme%2Fapi/repository/files/spec%2Fopenapi.yaml/raw?ref=main"
+        );
+    }
+
+    #[test]
+    fn test_self_hosted_api_url_applies_ssrf_policy() {
+        let conn = ForgeConnection {
+            api_url: Some("http://10.1.2.3/api/v4".to_string()),
+            ..connection(ForgeProvider::Gitlab)
+        };
+        assert_eq!(
+            conn.url(&["groups"], &[]).unwrap_err().code,
+            ErrorCode::BlockedIp
+        );
+    }
+
+    #[test]
+    fn test_headers_use_provider_token_scheme() {
+        let github = connection(ForgeProvider::Github).headers(true).unwrap();
+        assert_eq!(github["authorization"], "Bearer secret");
+        assert_eq!(github["accept"], "application/vnd.github.raw");
+        assert!(github["authorization"].is_sensitive());
+
+        let gitlab = connection(ForgeProvider::Gitlab).headers(false).unwrap();
+        assert_eq!(gitlab["private-token"], "secret");
+        assert!(!gitlab.contains_key("authorization"));
+    }
+
+    #[test]
+    fn test_github_repo_segments_require_owner_and_name() {
+        assert_eq!(
+            github_repo_segments("acme/orders").unwrap(),
+            ["repos", "acme", "orders"]
+        );
+        assert!(github_repo_segments("acme").is_err());
+        assert!(github_repo_segments("acme/orders/extra").is_err());
+    }
+
+    #[test]
+    fn test_blob_url_links_to_branch() {
+        assert_eq!(
+            blob_url(
+                ForgeProvider::Github,
+                "https://github.com/acme/orders",
+                "release/2.0",
+                "docs/open api.yaml"
+            ),
+            "https://github.com/acme/orders/blob/release/2.0/docs/open%20api.yaml"
+        );
+        assert_eq!(
+            blob_url(
+                ForgeProvider::Gitlab,
+                "https://gitlab.com/acme/orders/",
+                "main",
+                "openapi.yaml"
+            ),
+            "https://gitlab.com/acme/orders/-/blob/main/openapi.yaml"
+        );
+    }
+}
@@ -0,0 +1,262 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::coverage::{SpecCoverage, Tally};
+use crate::error::{CommandError, ErrorCode};
+
+/// Manifests are small; anything bigger is not one.
+const MAX_MANIFEST_BYTES: u64 = 1024 * 1024;
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+/// Thresholds a headless run must meet, as fractions from 0 to 1. Unset
+/// thresholds are not checked.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QualityGate {
+    pub min_operation_coverage: Option<f64>,
+    pub min_status_coverage: Option<f64>,
+    pub min_parameter_coverage: Option<f64>,
+    /// Share of contract tests that passed.
+    pub min_pass_rate: Option<f64>,
+}
+
+/// The part of a workspace manifest the backend reads; the rest belongs to
+/// the frontend.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct WorkspaceManifest {
+    quality_gate: QualityGate,
+}
+
+/// Contract tests run by the caller.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractResults {
+    pub passed: usize,
+    pub failed: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GateMetric {
+    OperationCoverage,
+    StatusCoverage,
+    ParameterCoverage,
+    PassRate,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GateCheck {
+    pub metric: GateMetric,
+    pub threshold: f64,
+    pub actual: f64,
+    pub passed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GateReport {
+    pub passed: bool,
+    /// One per threshold set.
+    pub checks: Vec<GateCheck>,
+}
+
+// ─── Manifest ────────────────────────────────────────────────────────────────
+
+/// The `quality_gate` of the JSON workspace manifest at `path`; none when
+/// the manifest has no gate.
+pub(crate) fn read_gate(path: &str) -> Result<QualityGate, CommandError> {
+    let unreadable = |detail: &dyn std::fmt::Display| {
+        CommandError::new(ErrorCode::FileUnreadable)
+            .arg("path", path)
+            .detail(detail)
+    };
+    if !Path::new(path).is_absolute() {
+        return Err(unreadable(&"the path must be absolute"));
+    }
+    let metadata = std::fs::metadata(path).map_err(|e| unreadable(&e))?;
+    let invalid = || CommandError::new(ErrorCode::InvalidManifest).arg("path", path);
+    if metadata.len() > MAX_MANIFEST_BYTES {
+        return Err(invalid().detail("larger than 1MB"));
+    }
+    let bytes = std::fs::read(path).map_err(|e| unreadable(&e))?;
+    let manifest: WorkspaceManifest =
+        serde_json::from_slice(&bytes).map_err(|e| invalid().detail(e))?;
+    manifest.quality_gate.validate()?;
+    Ok(manifest.quality_gate)
+}
+
+// ─── Evaluation ──────────────────────────────────────────────────────────────
+
+/// Nothing to cover counts as covered.
+fn ratio(tally: Tally) -> f64 {
+    if tally.total == 0 {
+        1.0
+    } else {
+        tally.exercised as f64 / tally.total as f64
+    }
+}
+
+impl QualityGate {
+    fn thresholds(&self) -> [(GateMetric, &'static str, Option<f64>); 4] {
+        [
+            (
+                GateMetric::OperationCoverage,
+                "min_operation_coverage",
+                self.min_operation_coverage,
+            ),
+            (
+                GateMetric::StatusCoverage,
+                "min_status_coverage",
+                self.min_status_coverage,
+            ),
+            (
+                GateMetric::ParameterCoverage,
+                "min_parameter_coverage",
+                self.min_parameter_coverage,
+            ),
+            (GateMetric::PassRate, "min_pass_rate", self.min_pass_rate),
+        ]
+    }
+
+    fn validate(&self) -> Result<(), CommandError> {
+        for (_, setting, threshold) in self.thresholds() {
+            if threshold.is_some_and(|threshold| !(0.0..=1.0).contains(&threshold)) {
+                return Err(CommandError::new(ErrorCode::InvalidSetting)
+                    .arg("setting", setting)
+                    .arg("min", 0)
+                    .arg("max", 1));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check the run's coverage and contract tests. Without contract tests
+    /// the pass rate is 0, so a gate on it fails runs that tested nothing.
+    pub(crate) fn evaluate(&self, coverage: &SpecCoverage, tests: ContractResults) -> GateReport {
+        let run = tests.passed + tests.failed;
+        let pass_rate = if run == 0 {
+            0.0
+        } else {
+            tests.passed as f64 / run as f64
+        };
+        let checks: Vec<GateCheck> = self
+            .thresholds()
+            .into_iter()
+            .filter_map(|(metric, _, threshold)| {
+                let threshold = threshold?;
+                let actual = match metric {
+                    GateMetric::OperationCoverage => ratio(coverage.operations),
+                    GateMetric::StatusCoverage => ratio(coverage.statuses),
+                    GateMetric::ParameterCoverage => ratio(coverage.parameters),
+                    GateMetric::PassRate => pass_rate,
+                };
+                Some(GateCheck {
+                    metric,
+                    threshold,
+                    actual,
+                    passed: actual >= threshold,
+                })
+            })
+            .collect();
+        GateReport {
+            passed: checks.iter().all(|check| check.passed),
+            checks,
+        }
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coverage(operations: Tally) -> SpecCoverage {
+        SpecCoverage {
+            spec_id: "pets".to_string(),
+            operations,
+            statuses: Tally::default(),
+            parameters: Tally::default(),
+            details: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_gate_fails_below_thresholds() {
+        let gate = QualityGate {
+            min_operation_coverage: Some(0.8),
+            min_status_coverage: Some(0.5),
+            min_pass_rate: Some(0.95),
+            ..Default::default()
+        };
+        let report = gate.evaluate(
+            &coverage(Tally {
+                exercised: 3,
+                total: 4,
+            }),
+            ContractResults {
+                passed: 19,
+                failed: 1,
+            },
+        );
+        assert!(!report.passed);
+        let outcomes: Vec<(GateMetric, bool)> = report
+            .checks
+            .iter()
+            .map(|check| (check.metric, check.passed))
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                (GateMetric::OperationCoverage, false),
+                // A spec without declared statuses has nothing to cover
+                (GateMetric::StatusCoverage, true),
+                (GateMetric::PassRate, true),
+            ]
+        );
+
+        let untested = gate.evaluate(
+            &coverage(Tally {
+                exercised: 4,
+                total: 4,
+            }),
+            ContractResults::default(),
+        );
+        assert!(!untested.passed);
+        assert_eq!(untested.checks[2].actual, 0.0);
+    }
+
+    #[test]
+    fn test_gate_is_read_from_the_workspace_manifest() {
+        let path = std::env::temp_dir().join(format!("yasp-gate-{}.json", std::process::id()));
+        let path = path.to_string_lossy().into_owned();
+        std::fs::write(
+            &path,
+            r#"{ "name": "Pets", "quality_gate": { "min_pass_rate": 1.0 } }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            read_gate(&path).unwrap(),
+            QualityGate {
+                min_pass_rate: Some(1.0),
+                ..Default::default()
+            }
+        );
+
+        std::fs::write(&path, r#"{ "quality_gate": { "min_pass_rate": 95 } }"#).unwrap();
+        assert_eq!(
+            read_gate(&path).unwrap_err().code,
+            ErrorCode::InvalidSetting
+        );
+        std::fs::write(&path, "quality_gate: {}").unwrap();
+        assert_eq!(
+            read_gate(&path).unwrap_err().code,
+            ErrorCode::InvalidManifest
+        );
+        assert_eq!(
+            read_gate("yasp.json").unwrap_err().code,
+            ErrorCode::FileUnreadable
+        );
+    }
+}
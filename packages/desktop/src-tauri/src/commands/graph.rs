@@ -0,0 +1,458 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{CommandError, ErrorCode};
+
+const METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Literal data, whose `$ref` keys (if any) are not references.
+const DATA_KEYS: &[&str] = &["example", "default", "enum", "const"];
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeKind {
+    Operation,
+    Schema,
+    Parameter,
+    Response,
+    RequestBody,
+    Header,
+    SecurityScheme,
+    Example,
+    Link,
+    Callback,
+    PathItem,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GraphNode {
+    /// `GET /pets` for operations, the `$ref` for components.
+    pub id: String,
+    pub kind: NodeKind,
+    /// The operationId or component name.
+    pub label: String,
+}
+
+/// `from` references `to`, directly or through an inline schema.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct GraphEdge {
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyGraph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+/// What depends on a component, i.e. what a change to it could break.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ComponentImpact {
+    pub id: String,
+    /// Operations and components that reference it directly.
+    pub used_by: Vec<String>,
+    /// Components that depend on it, directly or through other components.
+    pub affected_components: Vec<String>,
+    /// Operations that depend on it, directly or through components.
+    pub affected_operations: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GraphFormat {
+    /// Graphviz
+    Dot,
+    Json,
+}
+
+// ─── Graph ───────────────────────────────────────────────────────────────────
+
+fn section_kind(section: &str) -> Option<NodeKind> {
+    Some(match section {
+        "schemas" | "definitions" => NodeKind::Schema,
+        "parameters" => NodeKind::Parameter,
+        "responses" => NodeKind::Response,
+        "requestBodies" => NodeKind::RequestBody,
+        "headers" => NodeKind::Header,
+        "securitySchemes" | "securityDefinitions" => NodeKind::SecurityScheme,
+        "examples" => NodeKind::Example,
+        "links" => NodeKind::Link,
+        "callbacks" => NodeKind::Callback,
+        "pathItems" => NodeKind::PathItem,
+        _ => return None,
+    })
+}
+
+fn escape(name: &str) -> String {
+    name.replace('~', "~0").replace('/', "~1")
+}
+
+/// The component a local `$ref` points into, e.g.
+/// `#/components/schemas/Pet/properties/id` is in `#/components/schemas/Pet`.
+fn component_of(reference: &str) -> Option<String> {
+    let segments: Vec<&str> = reference.strip_prefix("#/")?.split('/').collect();
+    let len = if segments[0] == "components" { 3 } else { 2 };
+    (segments.len() >= len).then(|| format!("#/{}", segments[..len].join("/")))
+}
+
+fn refs(value: &Value, into: &mut BTreeSet<String>) {
+    match value {
+        Value::Object(map) => {
+            if let Some(component) = map
+                .get("$ref")
+                .and_then(Value::as_str)
+                .and_then(component_of)
+            {
+                into.insert(component);
+            }
+            for (key, child) in map {
+                if !DATA_KEYS.contains(&key.as_str()) {
+                    refs(child, into);
+                }
+            }
+        }
+        Value::Array(items) => items.iter().for_each(|item| refs(item, into)),
+        _ => {}
+    }
+}
+
+/// Components by id, with their kind, name and definition.
+fn components(spec: &Value) -> Vec<(String, NodeKind, &String, &Value)> {
+    let swagger2 = spec.get("swagger").is_some();
+    let (root, prefix) = if swagger2 {
+        (spec, "#")
+    } else {
+        (&spec["components"], "#/components")
+    };
+    let Some(sections) = root.as_object() else {
+        return Vec::new();
+    };
+    sections
+        .iter()
+        .filter_map(|(section, entries)| Some((section, section_kind(section)?, entries)))
+        .flat_map(|(section, kind, entries)| {
+            entries
+                .as_object()
+                .into_iter()
+                .flatten()
+                .map(move |(name, value)| {
+                    let id = format!("{prefix}/{section}/{}", escape(name));
+                    (id, kind, name, value)
+                })
+        })
+        .collect()
+}
+
+fn security_schemes(requirements: &Value, prefix: &str, into: &mut BTreeSet<String>) {
+    for requirement in requirements.as_array().into_iter().flatten() {
+        for name in requirement
+            .as_object()
+            .into_iter()
+            .flatten()
+            .map(|(k, _)| k)
+        {
+            into.insert(format!("{prefix}/{}", escape(name)));
+        }
+    }
+}
+
+fn build(spec: &Value) -> DependencyGraph {
+    let mut nodes = BTreeMap::new();
+    let mut edges = BTreeSet::new();
+    let mut link = |from: &str, to: BTreeSet<String>| {
+        for to in to {
+            edges.insert(GraphEdge {
+                from: from.to_string(),
+                to,
+            });
+        }
+    };
+
+    for (id, kind, name, value) in components(spec) {
+        let mut deps = BTreeSet::new();
+        refs(value, &mut deps);
+        link(&id, deps);
+        nodes.insert(
+            id.clone(),
+            GraphNode {
+                id,
+                kind,
+                label: name.clone(),
+            },
+        );
+    }
+
+    let scheme_prefix = if spec.get("swagger").is_some() {
+        "#/securityDefinitions"
+    } else {
+        "#/components/securitySchemes"
+    };
+    for (path, item) in spec["paths"].as_object().into_iter().flatten() {
+        let mut shared = BTreeSet::new();
+        refs(&item["parameters"], &mut shared);
+        if let Some(component) = item["$ref"].as_str().and_then(component_of) {
+            shared.insert(component);
+        }
+        for method in METHODS {
+            let Some(operation) = item.get(*method).filter(|op| op.is_object()) else {
+                continue;
+            };
+            let id = format!("{} {path}", method.to_ascii_uppercase());
+            let mut deps = shared.clone();
+            refs(operation, &mut deps);
+            let security = operation.get("security").unwrap_or(&spec["security"]);
+            security_schemes(security, scheme_prefix, &mut deps);
+            link(&id, deps);
+            let label = operation["operationId"].as_str().unwrap_or(&id).to_string();
+            nodes.insert(
+                id.clone(),
+                GraphNode {
+                    id,
+                    kind: NodeKind::Operation,
+                    label,
+                },
+            );
+        }
+    }
+
+    // References to other files or missing components are not nodes
+    edges.retain(|edge| nodes.contains_key(&edge.to));
+    DependencyGraph {
+        nodes: nodes.into_values().collect(),
+        edges: edges.into_iter().collect(),
+    }
+}
+
+fn impact(graph: &DependencyGraph, id: &str) -> ComponentImpact {
+    let mut dependents: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for edge in &graph.edges {
+        dependents
+            .entry(edge.to.as_str())
+            .or_default()
+            .push(edge.from.as_str());
+    }
+    let used_by: BTreeSet<&str> = dependents.get(id).into_iter().flatten().copied().collect();
+
+    let mut seen = BTreeSet::from([id]);
+    let mut queue = VecDeque::from([id]);
+    while let Some(next) = queue.pop_front() {
+        for dependent in dependents.get(next).into_iter().flatten() {
+            if seen.insert(*dependent) {
+                queue.push_back(*dependent);
+            }
+        }
+    }
+    seen.remove(id);
+
+    let kinds: BTreeMap<&str, NodeKind> = graph
+        .nodes
+        .iter()
+        .map(|node| (node.id.as_str(), node.kind))
+        .collect();
+    let (affected_operations, affected_components): (Vec<&str>, Vec<&str>) = seen
+        .into_iter()
+        .partition(|dependent| kinds.get(dependent) == Some(&NodeKind::Operation));
+    let owned = |ids: Vec<&str>| ids.into_iter().map(str::to_string).collect();
+    ComponentImpact {
+        id: id.to_string(),
+        used_by: owned(used_by.into_iter().collect()),
+        affected_components: owned(affected_components),
+        affected_operations: owned(affected_operations),
+    }
+}
+
+// ─── Export ──────────────────────────────────────────────────────────────────
+
+fn dot_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn to_dot(graph: &DependencyGraph) -> String {
+    let mut dot = String::from("digraph spec {\n  rankdir=LR;\n");
+    for node in &graph.nodes {
+        let shape = match node.kind {
+            NodeKind::Operation => "box",
+            NodeKind::Schema => "ellipse",
+            _ => "component",
+        };
+        dot.push_str(&format!(
+            "  {} [label={}, shape={shape}];\n",
+            dot_string(&node.id),
+            dot_string(&node.label)
+        ));
+    }
+    for edge in &graph.edges {
+        dot.push_str(&format!(
+            "  {} -> {};\n",
+            dot_string(&edge.from),
+            dot_string(&edge.to)
+        ));
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// Operations and components of a spec, with an edge for each `$ref` and
+/// security requirement. Path-level parameters count for every operation on
+/// the path.
+#[tauri::command]
+pub fn dependency_graph(spec: Value) -> DependencyGraph {
+    build(&spec)
+}
+
+/// What uses a component and what changing it would affect. `component` is
+/// a local `$ref` such as `#/components/schemas/Pet`; a pointer into a
+/// component stands for the whole component.
+#[tauri::command]
+pub fn component_impact(spec: Value, component: String) -> Result<ComponentImpact, CommandError> {
+    let graph = build(&spec);
+    let id = component_of(&component)
+        .filter(|id| graph.nodes.iter().any(|node| &node.id == id))
+        .ok_or_else(|| {
+            CommandError::new(ErrorCode::ComponentNotFound).arg("component", &component)
+        })?;
+    Ok(impact(&graph, &id))
+}
+
+/// The dependency graph as Graphviz DOT or pretty-printed JSON.
+#[tauri::command]
+pub fn export_dependency_graph(spec: Value, format: GraphFormat) -> String {
+    let graph = build(&spec);
+    match format {
+        GraphFormat::Dot => to_dot(&graph),
+        GraphFormat::Json => serde_json::to_string_pretty(&graph).unwrap_or_default(),
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn petstore() -> Value {
+        json!({
+            "openapi": "3.0.3",
+            "security": [{ "apiKey": [] }],
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "operationId": "listPets",
+                        "responses": { "200": { "content": { "application/json": {
+                            "schema": { "type": "array", "items": { "$ref": "#/components/schemas/Pet" } }
+                        } } } }
+                    },
+                    "post": {
+                        "security": [],
+                        "requestBody": { "$ref": "#/components/requestBodies/NewPet" },
+                        "responses": { "default": { "$ref": "#/components/responses/Error" } }
+                    }
+                },
+                "/owners/{ownerId}": {
+                    "parameters": [{ "$ref": "#/components/parameters/OwnerId" }],
+                    "get": { "responses": { "200": { "$ref": "other.yaml#/Owner" } } }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "Pet": { "properties": {
+                        "tag": { "$ref": "#/components/schemas/Tag" },
+                        "parent": { "$ref": "#/components/schemas/Pet" }
+                    } },
+                    "Tag": { "properties": { "name": { "type": "string" } } },
+                    "Error": { "example": { "$ref": "not a reference" } }
+                },
+                "requestBodies": { "NewPet": { "content": { "application/json": {
+                    "schema": { "$ref": "#/components/schemas/Pet/properties/tag" }
+                } } } },
+                "responses": { "Error": { "description": "", "content": { "application/json": {
+                    "schema": { "$ref": "#/components/schemas/Error" }
+                } } } },
+                "parameters": { "OwnerId": { "name": "ownerId", "in": "path" } },
+                "securitySchemes": { "apiKey": { "type": "apiKey" } }
+            }
+        })
+    }
+
+    #[test]
+    fn test_graph_links_operations_to_components() {
+        let graph = dependency_graph(petstore());
+        let edges: Vec<(&str, &str)> = graph
+            .edges
+            .iter()
+            .map(|edge| (edge.from.as_str(), edge.to.as_str()))
+            .collect();
+        assert!(edges.contains(&("GET /pets", "#/components/schemas/Pet")));
+        assert!(edges.contains(&("GET /pets", "#/components/securitySchemes/apiKey")));
+        assert!(!edges.contains(&("POST /pets", "#/components/securitySchemes/apiKey")));
+        assert!(edges.contains(&("GET /owners/{ownerId}", "#/components/parameters/OwnerId")));
+        assert!(edges.contains(&(
+            "#/components/requestBodies/NewPet",
+            "#/components/schemas/Pet"
+        )));
+        // Example data and other files are not followed
+        assert!(!edges
+            .iter()
+            .any(|(from, _)| *from == "#/components/schemas/Error"));
+        // The path parameter and the global security requirement
+        assert_eq!(
+            edges
+                .iter()
+                .filter(|(from, _)| *from == "GET /owners/{ownerId}")
+                .count(),
+            2
+        );
+
+        let list = graph
+            .nodes
+            .iter()
+            .find(|node| node.id == "GET /pets")
+            .unwrap();
+        assert_eq!(list.label, "listPets");
+    }
+
+    #[test]
+    fn test_impact_follows_dependents_transitively() {
+        let impact = component_impact(petstore(), "#/components/schemas/Tag".to_string()).unwrap();
+        assert_eq!(impact.used_by, vec!["#/components/schemas/Pet"]);
+        assert_eq!(
+            impact.affected_components,
+            vec![
+                "#/components/requestBodies/NewPet",
+                "#/components/schemas/Pet"
+            ]
+        );
+        assert_eq!(impact.affected_operations, vec!["GET /pets", "POST /pets"]);
+
+        let err =
+            component_impact(petstore(), "#/components/schemas/Nope".to_string()).unwrap_err();
+        assert_eq!(err.code, ErrorCode::ComponentNotFound);
+    }
+
+    #[test]
+    fn test_swagger2_and_dot_export() {
+        let spec = json!({
+            "swagger": "2.0",
+            "paths": { "/a": { "get": {
+                "parameters": [{ "in": "body", "schema": { "$ref": "#/definitions/Say \"hi\"" } }]
+            } } },
+            "definitions": { "Say \"hi\"": { "type": "object" } }
+        });
+        let dot = export_dependency_graph(spec, GraphFormat::Dot);
+        assert!(dot.starts_with("digraph spec {"));
+        assert!(
+            dot.contains(r##""#/definitions/Say \"hi\"" [label="Say \"hi\"", shape=ellipse];"##)
+        );
+        assert!(dot.contains(r##""GET /a" -> "#/definitions/Say \"hi\"";"##));
+    }
+}
@@ -0,0 +1,772 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use rustls_pki_types::CertificateDer;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use super::cookies::WorkspaceJar;
+use super::ensure_main_window;
+use super::proxy::{ManualProxy, ProxyRoute, ProxySettings};
+use super::redirects;
+use super::timing::{ConnectTimingLayer, TimingResolver};
+use super::tls::{CertificatePins, ClientIdentity, TlsOptions, TlsVersion, Trust};
+use crate::error::{CommandError, ErrorCode, ErrorPhase};
+
+/// Spec downloads keep their tighter limits whatever the request defaults are.
+const SPEC_TIMEOUT: Duration = Duration::from_secs(15);
+const SPEC_MAX_REDIRECTS: usize = 3;
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+/// Settings for the shared clients used by `execute_api_request` and
+/// `fetch_spec`. Changing them rebuilds the clients on their next use.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClientDefaults {
+    /// Whole-request timeout for user requests.
+    pub timeout_secs: u64,
+    /// Time allowed to establish a connection, TLS handshake included.
+    pub connect_timeout_secs: u64,
+    /// Redirects followed before a user request fails.
+    pub max_redirects: usize,
+    /// How long an idle pooled connection is kept open for reuse.
+    pub pool_idle_timeout_secs: u64,
+    pub pool_max_idle_per_host: usize,
+}
+
+impl Default for ClientDefaults {
+    fn default() -> Self {
+        Self {
+            timeout_secs: 30,
+            connect_timeout_secs: 10,
+            max_redirects: 5,
+            pool_idle_timeout_secs: 90,
+            pool_max_idle_per_host: 16,
+        }
+    }
+}
+
+/// Which HTTP version a user request is sent with.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HttpVersionPolicy {
+    /// HTTP/2 when the server agrees to it in the TLS handshake, else
+    /// HTTP/1.1. Plain `http://` URLs use HTTP/1.1.
+    #[default]
+    Negotiate,
+    /// HTTP/1.1 even with servers that support HTTP/2.
+    Http1Only,
+    /// HTTP/2 without negotiating it first, as h2c servers expect. Fails
+    /// against servers that only speak HTTP/1.1.
+    Http2PriorKnowledge,
+    /// HTTP/3 over QUIC, for `https://` URLs of servers that support it;
+    /// there is no fallback to TCP. Connection phases are not timed. Only
+    /// available in builds with the `http3` feature.
+    Http3,
+}
+
+/// The HTTP version a response came over.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HttpVersion {
+    #[serde(rename = "0.9")]
+    Http09,
+    #[serde(rename = "1.0")]
+    Http10,
+    #[default]
+    #[serde(rename = "1.1")]
+    Http11,
+    #[serde(rename = "2")]
+    Http2,
+    #[serde(rename = "3")]
+    Http3,
+}
+
+/// Lazily built `reqwest::Client`s shared across commands, so repeated
+/// requests to the same API reuse pooled connections and TLS sessions.
+///
+/// reqwest fixes the TLS configuration, proxy, connect timeout and cookie
+/// store when a client is built, so user requests get one client per allowed
+/// TLS version range, client identity, HTTP version policy, proxy route,
+/// connect timeout and workspace (for its cookie jar and trusted CA
+/// certificates), and spec downloads one per proxy route and workspace.
+#[derive(Default)]
+pub struct HttpClients {
+    defaults: Mutex<ClientDefaults>,
+    proxy: Mutex<ProxySettings>,
+    /// URLs of the manual proxies that asked for credentials; requests
+    /// through them send the credentials upfront.
+    challenged: Mutex<HashSet<String>>,
+    /// CA certificates trusted besides the built-in roots, by workspace id.
+    trusted: Mutex<HashMap<String, Vec<CertificateDer<'static>>>>,
+    /// Hosts whose certificates are not verified, in every workspace.
+    insecure_hosts: Mutex<BTreeSet<String>>,
+    /// SPKI pins by host, in every workspace.
+    pins: Mutex<CertificatePins>,
+    api: Mutex<HashMap<ApiClientKey, reqwest::Client>>,
+    spec: Mutex<HashMap<SpecClientKey, reqwest::Client>>,
+}
+
+/// Allowed TLS versions, client identity, HTTP version policy, proxy route,
+/// connect timeout in ms and workspace.
+type ApiClientKey = (
+    Vec<TlsVersion>,
+    Option<ClientIdentity>,
+    HttpVersionPolicy,
+    ProxyRoute,
+    u64,
+    Option<String>,
+);
+
+/// Proxy route, workspace and whether redirects are followed.
+type SpecClientKey = (ProxyRoute, Option<String>, bool);
+
+// ─── Clients ─────────────────────────────────────────────────────────────────
+
+pub(super) fn check_range(
+    setting: &str,
+    value: u64,
+    min: u64,
+    max: u64,
+) -> Result<(), CommandError> {
+    if (min..=max).contains(&value) {
+        Ok(())
+    } else {
+        Err(CommandError::new(ErrorCode::InvalidSetting)
+            .arg("setting", setting)
+            .arg("min", min)
+            .arg("max", max))
+    }
+}
+
+impl HttpVersionPolicy {
+    /// Protocols offered to the server in the TLS handshake.
+    pub(super) fn alpn_protocols(self) -> Vec<Vec<u8>> {
+        match self {
+            HttpVersionPolicy::Negotiate => vec![b"h2".to_vec(), b"http/1.1".to_vec()],
+            HttpVersionPolicy::Http1Only => vec![b"http/1.1".to_vec()],
+            HttpVersionPolicy::Http2PriorKnowledge => vec![b"h2".to_vec()],
+            HttpVersionPolicy::Http3 => vec![b"h3".to_vec()],
+        }
+    }
+
+    fn apply(self, builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+        match self {
+            HttpVersionPolicy::Negotiate => builder,
+            HttpVersionPolicy::Http1Only => builder.http1_only(),
+            HttpVersionPolicy::Http2PriorKnowledge => builder.http2_prior_knowledge(),
+            #[cfg(feature = "http3")]
+            HttpVersionPolicy::Http3 => builder.http3_prior_knowledge(),
+            // Refused by `api_client` before a client is built
+            #[cfg(not(feature = "http3"))]
+            HttpVersionPolicy::Http3 => builder,
+        }
+    }
+}
+
+impl From<reqwest::Version> for HttpVersion {
+    fn from(version: reqwest::Version) -> Self {
+        match version {
+            reqwest::Version::HTTP_09 => HttpVersion::Http09,
+            reqwest::Version::HTTP_10 => HttpVersion::Http10,
+            reqwest::Version::HTTP_2 => HttpVersion::Http2,
+            reqwest::Version::HTTP_3 => HttpVersion::Http3,
+            _ => HttpVersion::Http11,
+        }
+    }
+}
+
+impl ClientDefaults {
+    fn validate(&self) -> Result<(), CommandError> {
+        check_range("timeout_secs", self.timeout_secs, 1, 600)?;
+        check_range("connect_timeout_secs", self.connect_timeout_secs, 1, 120)?;
+        check_range("max_redirects", self.max_redirects as u64, 0, 20)?;
+        check_range(
+            "pool_idle_timeout_secs",
+            self.pool_idle_timeout_secs,
+            0,
+            3600,
+        )?;
+        check_range(
+            "pool_max_idle_per_host",
+            self.pool_max_idle_per_host as u64,
+            0,
+            256,
+        )
+    }
+
+    fn builder(&self) -> reqwest::ClientBuilder {
+        reqwest::Client::builder()
+            // OWASP A05:2025 – Cryptographic Failures: enforce TLS via rustls
+            .use_rustls_tls()
+            .pool_idle_timeout(Duration::from_secs(self.pool_idle_timeout_secs))
+            .pool_max_idle_per_host(self.pool_max_idle_per_host)
+    }
+}
+
+fn build(builder: reqwest::ClientBuilder) -> Result<reqwest::Client, CommandError> {
+    builder
+        .build()
+        .map_err(|e| CommandError::new(ErrorCode::ClientBuildFailed).detail(e))
+}
+
+impl HttpClients {
+    fn defaults(&self) -> ClientDefaults {
+        self.defaults
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    pub(super) fn proxy(&self) -> ProxySettings {
+        self.proxy.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// CA certificates requests of `workspace_id` trust besides the built-in
+    /// roots.
+    pub(super) fn trusted_roots(&self, workspace_id: Option<&str>) -> Vec<CertificateDer<'static>> {
+        let trusted = self.trusted.lock().unwrap_or_else(|e| e.into_inner());
+        workspace_id
+            .and_then(|id| trusted.get(id))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// What requests of `workspace_id` trust besides the built-in roots.
+    pub(super) fn trust(&self, workspace_id: Option<&str>) -> Trust {
+        Trust {
+            roots: self.trusted_roots(workspace_id),
+            insecure_hosts: self
+                .insecure_hosts
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .clone(),
+            pins: self.pins.lock().unwrap_or_else(|e| e.into_inner()).clone(),
+        }
+    }
+
+    /// The route of a request to `url`. Credentials for an HTTP proxy are
+    /// left out until it asks for them; a SOCKS5 proxy gets them upfront,
+    /// as it asks in the handshake and never with a `407`.
+    fn route(&self, url: &url::Url, workspace_id: Option<&str>) -> ProxyRoute {
+        match self.proxy().route(url, workspace_id) {
+            ProxyRoute::Manual(proxy)
+                if proxy.auth.is_some()
+                    && !proxy.is_socks()
+                    && !self
+                        .challenged
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .contains(&proxy.url) =>
+            {
+                ProxyRoute::Manual(ManualProxy {
+                    auth: None,
+                    ..proxy
+                })
+            }
+            route => route,
+        }
+    }
+
+    /// After a `407 Proxy Authentication Required` to a request to `url`:
+    /// whether to send it again, now with the proxy's credentials. Fails
+    /// when the proxy has no credentials, or rejected them already. A 407
+    /// from a server reached directly is left to the caller.
+    pub(crate) fn answer_proxy_challenge(
+        &self,
+        url: &url::Url,
+        workspace_id: Option<&str>,
+    ) -> Result<bool, CommandError> {
+        let proxy = match self.proxy().route(url, workspace_id) {
+            ProxyRoute::Direct => return Ok(false),
+            ProxyRoute::Manual(proxy) if proxy.auth.is_some() => proxy,
+            _ => {
+                return Err(
+                    CommandError::new(ErrorCode::ProxyAuthRequired).phase(ErrorPhase::Connect)
+                )
+            }
+        };
+        let mut challenged = self.challenged.lock().unwrap_or_else(|e| e.into_inner());
+        if challenged.insert(proxy.url) {
+            Ok(true)
+        } else {
+            Err(CommandError::new(ErrorCode::ProxyAuthRejected).phase(ErrorPhase::Connect))
+        }
+    }
+
+    /// Client for user-defined requests to `url` under the given TLS and HTTP
+    /// version policies, with the default connect timeout unless
+    /// `connect_timeout_ms` overrides it, and keeping cookies in `jar` if
+    /// given. The proxy and trusted CA certificates are those of the jar's
+    /// workspace.
+    pub(crate) fn api_client(
+        &self,
+        url: &url::Url,
+        tls: &TlsOptions,
+        http_version: HttpVersionPolicy,
+        connect_timeout_ms: Option<u64>,
+        jar: Option<&Arc<WorkspaceJar>>,
+    ) -> Result<reqwest::Client, CommandError> {
+        let versions = tls.allowed_versions()?;
+        // QUIC is built on TLS 1.3
+        if http_version == HttpVersionPolicy::Http3
+            && (!cfg!(feature = "http3") || !versions.contains(&TlsVersion::Tls13))
+        {
+            return Err(CommandError::new(ErrorCode::Http3Unavailable));
+        }
+        let defaults = self.defaults();
+        if let Some(ms) = connect_timeout_ms {
+            check_range("connect_timeout_ms", ms, 1, 120_000)?;
+        }
+        let connect_timeout_ms = connect_timeout_ms.unwrap_or(defaults.connect_timeout_secs * 1000);
+        let workspace_id = jar.map(|jar| jar.workspace_id().to_string());
+        let route = self.route(url, workspace_id.as_deref());
+        let key = (
+            versions,
+            tls.client_identity.clone(),
+            http_version,
+            route.clone(),
+            connect_timeout_ms,
+            workspace_id,
+        );
+        let mut clients = self.api.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(client) = clients.get(&key) {
+            return Ok(client.clone());
+        }
+
+        let mut builder = defaults
+            .builder()
+            // Follow redirects, but cap them to prevent redirect loops
+            .redirect(redirects::policy(defaults.max_redirects))
+            .timeout(Duration::from_secs(defaults.timeout_secs))
+            .connect_timeout(Duration::from_millis(connect_timeout_ms))
+            .dns_resolver(Arc::new(TimingResolver))
+            .connector_layer(ConnectTimingLayer);
+        if let Some(jar) = jar {
+            builder = builder.cookie_provider(Arc::clone(jar));
+        }
+        let builder = route.apply(http_version.apply(builder))?;
+        let trust = self.trust(key.5.as_deref());
+        let client = build(tls.apply(builder, http_version, &trust)?)?;
+        clients.insert(key, client.clone());
+        Ok(client)
+    }
+
+    /// Client for spec downloads from `url`, routed and trusting
+    /// certificates as requests of `workspace_id` do.
+    pub(crate) fn spec_client(
+        &self,
+        url: &url::Url,
+        workspace_id: Option<&str>,
+    ) -> Result<reqwest::Client, CommandError> {
+        self.spec_client_for(url, workspace_id, true)
+    }
+
+    /// Like `spec_client`, but redirects are returned instead of followed,
+    /// for signed requests and tokens that must never reach another host.
+    pub(crate) fn spec_client_without_redirects(
+        &self,
+        url: &url::Url,
+        workspace_id: Option<&str>,
+    ) -> Result<reqwest::Client, CommandError> {
+        self.spec_client_for(url, workspace_id, false)
+    }
+
+    fn spec_client_for(
+        &self,
+        url: &url::Url,
+        workspace_id: Option<&str>,
+        follow_redirects: bool,
+    ) -> Result<reqwest::Client, CommandError> {
+        let defaults = self.defaults();
+        let key = (
+            self.route(url, workspace_id),
+            workspace_id.map(str::to_string),
+            follow_redirects,
+        );
+        let mut clients = self.spec.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(client) = clients.get(&key) {
+            return Ok(client.clone());
+        }
+
+        let redirect = if follow_redirects {
+            reqwest::redirect::Policy::limited(SPEC_MAX_REDIRECTS)
+        } else {
+            reqwest::redirect::Policy::none()
+        };
+        let builder = defaults.builder().redirect(redirect).timeout(SPEC_TIMEOUT);
+        let builder = TlsOptions::default().apply(
+            key.0.apply(builder)?,
+            HttpVersionPolicy::Negotiate,
+            &self.trust(workspace_id),
+        )?;
+        let client = build(builder)?;
+        clients.insert(key, client.clone());
+        Ok(client)
+    }
+
+    /// Replace the defaults and drop the built clients. Requests already in
+    /// flight finish on the old clients.
+    fn set_defaults(&self, defaults: ClientDefaults) -> Result<(), CommandError> {
+        defaults.validate()?;
+        *self.defaults.lock().unwrap_or_else(|e| e.into_inner()) = defaults;
+        self.clear();
+        Ok(())
+    }
+
+    /// Replace the proxy settings and drop the built clients, as
+    /// `set_defaults` does.
+    pub(super) fn set_proxy(&self, settings: ProxySettings) -> Result<(), CommandError> {
+        settings.validate()?;
+        *self.proxy.lock().unwrap_or_else(|e| e.into_inner()) = settings;
+        // New credentials wait for a challenge of their own
+        self.challenged
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+        self.clear();
+        Ok(())
+    }
+
+    /// Replace the CA certificates `workspace_id` trusts and drop the built
+    /// clients, as `set_defaults` does.
+    pub(super) fn set_trusted_roots(
+        &self,
+        workspace_id: String,
+        roots: Vec<CertificateDer<'static>>,
+    ) {
+        let mut trusted = self.trusted.lock().unwrap_or_else(|e| e.into_inner());
+        if roots.is_empty() {
+            trusted.remove(&workspace_id);
+        } else {
+            trusted.insert(workspace_id, roots);
+        }
+        drop(trusted);
+        self.clear();
+    }
+
+    /// Replace the hosts whose certificates are not verified and drop the
+    /// built clients, as `set_defaults` does.
+    pub(super) fn set_insecure_hosts(&self, hosts: BTreeSet<String>) {
+        *self
+            .insecure_hosts
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = hosts;
+        self.clear();
+    }
+
+    /// Replace the certificate pins and drop the built clients, as
+    /// `set_defaults` does.
+    pub(super) fn set_certificate_pins(&self, pins: CertificatePins) {
+        *self.pins.lock().unwrap_or_else(|e| e.into_inner()) = pins;
+        self.clear();
+    }
+
+    fn clear(&self) {
+        self.api.lock().unwrap_or_else(|e| e.into_inner()).clear();
+        self.spec.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// The settings the shared HTTP clients are built with.
+#[tauri::command]
+pub fn get_http_client_defaults(clients: State<'_, HttpClients>) -> ClientDefaults {
+    clients.defaults()
+}
+
+/// Change the shared HTTP client settings; later requests use new clients.
+#[tauri::command]
+pub fn set_http_client_defaults(
+    webview: tauri::Webview,
+    clients: State<'_, HttpClients>,
+    defaults: ClientDefaults,
+) -> Result<(), CommandError> {
+    ensure_main_window(webview.label())?;
+
+    clients.set_defaults(defaults)
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::cookies::CookieJars;
+    use crate::commands::proxy::ProxyAuth;
+
+    fn url() -> url::Url {
+        url::Url::parse("https://api.example.com/v1/orders").unwrap()
+    }
+
+    fn tls(min_version: Option<TlsVersion>) -> TlsOptions {
+        TlsOptions {
+            min_version,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_api_clients_are_reused_per_tls_policy() {
+        let clients = HttpClients::default();
+        clients
+            .api_client(&url(), &tls(None), Default::default(), None, None)
+            .unwrap();
+        // Same allowed range as no policy at all
+        clients
+            .api_client(
+                &url(),
+                &tls(Some(TlsVersion::Tls12)),
+                Default::default(),
+                None,
+                None,
+            )
+            .unwrap();
+        assert_eq!(clients.api.lock().unwrap().len(), 1);
+
+        clients
+            .api_client(
+                &url(),
+                &tls(Some(TlsVersion::Tls13)),
+                Default::default(),
+                None,
+                None,
+            )
+            .unwrap();
+        assert_eq!(clients.api.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_api_clients_are_reused_per_connect_timeout() {
+        let clients = HttpClients::default();
+        clients
+            .api_client(&url(), &tls(None), Default::default(), None, None)
+            .unwrap();
+        // Same as the 10 s default
+        clients
+            .api_client(&url(), &tls(None), Default::default(), Some(10_000), None)
+            .unwrap();
+        assert_eq!(clients.api.lock().unwrap().len(), 1);
+
+        clients
+            .api_client(&url(), &tls(None), Default::default(), Some(500), None)
+            .unwrap();
+        assert_eq!(clients.api.lock().unwrap().len(), 2);
+
+        let err = clients
+            .api_client(&url(), &tls(None), Default::default(), Some(0), None)
+            .unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidSetting);
+    }
+
+    #[test]
+    fn test_api_clients_are_separate_per_cookie_jar() {
+        let clients = HttpClients::default();
+        let jars = CookieJars::default();
+        let path = std::env::temp_dir().join(format!("yasp-http-jars-{}.json", std::process::id()));
+        let jar = jars.jar(&path, "ws").unwrap();
+        clients
+            .api_client(&url(), &tls(None), Default::default(), None, None)
+            .unwrap();
+        clients
+            .api_client(&url(), &tls(None), Default::default(), None, Some(&jar))
+            .unwrap();
+        clients
+            .api_client(&url(), &tls(None), Default::default(), None, Some(&jar))
+            .unwrap();
+        assert_eq!(clients.api.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_api_clients_are_separate_per_http_version() {
+        let clients = HttpClients::default();
+        for policy in [
+            HttpVersionPolicy::Negotiate,
+            HttpVersionPolicy::Http1Only,
+            HttpVersionPolicy::Http2PriorKnowledge,
+            HttpVersionPolicy::Http3,
+            HttpVersionPolicy::Http1Only,
+        ] {
+            let client = clients.api_client(&url(), &tls(None), policy, None, None);
+            let available = cfg!(feature = "http3") || policy != HttpVersionPolicy::Http3;
+            assert_eq!(client.is_ok(), available, "{policy:?}");
+        }
+        let expected = if cfg!(feature = "http3") { 4 } else { 3 };
+        assert_eq!(clients.api.lock().unwrap().len(), expected);
+        assert_eq!(
+            HttpVersion::from(reqwest::Version::HTTP_2),
+            HttpVersion::Http2
+        );
+        assert_eq!(serde_json::to_value(HttpVersion::Http11).unwrap(), "1.1");
+    }
+
+    #[test]
+    fn test_http3_requires_tls13() {
+        let clients = HttpClients::default();
+        let tls12_only = TlsOptions {
+            max_version: Some(TlsVersion::Tls12),
+            ..Default::default()
+        };
+        let err = clients
+            .api_client(&url(), &tls12_only, HttpVersionPolicy::Http3, None, None)
+            .unwrap_err();
+        assert_eq!(err.code, ErrorCode::Http3Unavailable);
+    }
+
+    #[test]
+    fn test_bypassed_hosts_get_a_direct_client() {
+        let clients = HttpClients::default();
+        clients
+            .set_proxy(ProxySettings {
+                bypass: vec!["internal.example".to_string()],
+                ..Default::default()
+            })
+            .unwrap();
+        let internal = url::Url::parse("https://api.internal.example/").unwrap();
+        clients
+            .api_client(&url(), &tls(None), Default::default(), None, None)
+            .unwrap();
+        clients
+            .api_client(&internal, &tls(None), Default::default(), None, None)
+            .unwrap();
+        let routes: Vec<ProxyRoute> = clients
+            .api
+            .lock()
+            .unwrap()
+            .keys()
+            .map(|key| key.3.clone())
+            .collect();
+        assert_eq!(routes.len(), 2);
+        assert!(routes.contains(&ProxyRoute::Direct));
+        assert!(routes.contains(&ProxyRoute::System));
+    }
+
+    #[test]
+    fn test_proxy_credentials_are_sent_once_challenged() {
+        let clients = HttpClients::default();
+        let proxy = ManualProxy {
+            url: "http://proxy.corp.example:3128".to_string(),
+            auth: Some(ProxyAuth::Bearer {
+                token: "t0ken".to_string(),
+            }),
+        };
+        clients
+            .set_proxy(ProxySettings {
+                proxy: ProxyRoute::Manual(proxy.clone()),
+                bypass: vec!["internal.example".to_string()],
+                ..Default::default()
+            })
+            .unwrap();
+        let anonymous = ProxyRoute::Manual(ManualProxy {
+            auth: None,
+            ..proxy.clone()
+        });
+        assert_eq!(clients.route(&url(), None), anonymous);
+
+        assert!(clients.answer_proxy_challenge(&url(), None).unwrap());
+        assert_eq!(clients.route(&url(), None), ProxyRoute::Manual(proxy));
+        let err = clients.answer_proxy_challenge(&url(), None).unwrap_err();
+        assert_eq!(err.code, ErrorCode::ProxyAuthRejected);
+
+        // Hosts reached directly answer for themselves
+        let internal = url::Url::parse("https://api.internal.example/").unwrap();
+        assert!(!clients.answer_proxy_challenge(&internal, None).unwrap());
+
+        clients.set_proxy(ProxySettings::default()).unwrap();
+        let err = clients.answer_proxy_challenge(&url(), None).unwrap_err();
+        assert_eq!(err.code, ErrorCode::ProxyAuthRequired);
+    }
+
+    #[test]
+    fn test_socks_proxy_credentials_are_sent_upfront() {
+        let clients = HttpClients::default();
+        let proxy = ProxyRoute::Manual(ManualProxy {
+            url: "socks5h://proxy.corp.example:1080".to_string(),
+            auth: Some(ProxyAuth::Basic {
+                username: "alice".to_string(),
+                password: "hunter2".to_string(),
+            }),
+        });
+        clients
+            .set_proxy(ProxySettings {
+                proxy: proxy.clone(),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(clients.route(&url(), None), proxy);
+    }
+
+    #[test]
+    fn test_spec_clients_are_kept_per_redirect_policy() {
+        let clients = HttpClients::default();
+        clients.spec_client(&url(), None).unwrap();
+        clients.spec_client_without_redirects(&url(), None).unwrap();
+        clients.spec_client(&url(), None).unwrap();
+        assert_eq!(clients.spec.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_trusted_roots_are_kept_per_workspace() {
+        let clients = HttpClients::default();
+        let root = CertificateDer::from(vec![0x30, 0x00]);
+        clients.spec_client(&url(), Some("corp")).unwrap();
+        clients.set_trusted_roots("corp".to_string(), vec![root.clone()]);
+        assert!(clients.spec.lock().unwrap().is_empty());
+        assert_eq!(clients.trusted_roots(Some("corp")), vec![root]);
+        assert!(clients.trusted_roots(Some("oss")).is_empty());
+        assert!(clients.trusted_roots(None).is_empty());
+
+        clients.set_trusted_roots("corp".to_string(), Vec::new());
+        assert!(clients.trusted.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_insecure_hosts_apply_to_every_workspace() {
+        let clients = HttpClients::default();
+        clients.spec_client(&url(), None).unwrap();
+        clients.set_insecure_hosts(BTreeSet::from(["localhost".to_string()]));
+        assert!(clients.spec.lock().unwrap().is_empty());
+        assert!(clients.trust(None).insecure_hosts.contains("localhost"));
+        assert!(clients
+            .trust(Some("corp"))
+            .insecure_hosts
+            .contains("localhost"));
+        clients
+            .api_client(&url(), &tls(None), Default::default(), None, None)
+            .unwrap();
+        clients.spec_client(&url(), None).unwrap();
+    }
+
+    #[test]
+    fn test_set_defaults_drops_built_clients() {
+        let clients = HttpClients::default();
+        clients
+            .api_client(&url(), &tls(None), Default::default(), None, None)
+            .unwrap();
+        clients.spec_client(&url(), None).unwrap();
+
+        let defaults = ClientDefaults {
+            timeout_secs: 60,
+            ..Default::default()
+        };
+        clients.set_defaults(defaults.clone()).unwrap();
+        assert!(clients.api.lock().unwrap().is_empty());
+        assert!(clients.spec.lock().unwrap().is_empty());
+        assert_eq!(clients.defaults(), defaults);
+    }
+
+    #[test]
+    fn test_set_defaults_rejects_out_of_range_values() {
+        let clients = HttpClients::default();
+        let err = clients
+            .set_defaults(ClientDefaults {
+                timeout_secs: 0,
+                ..Default::default()
+            })
+            .unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidSetting);
+        assert_eq!(clients.defaults(), ClientDefaults::default());
+    }
+}
@@ -0,0 +1,159 @@
+use std::collections::BTreeSet;
+use std::net::IpAddr;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+
+use super::ensure_main_window;
+use super::http::HttpClients;
+use super::store::JsonStore;
+use crate::error::{CommandError, ErrorCode};
+
+const INSECURE_HOSTS_FILE: &str = "insecure_hosts.json";
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+/// Hosts whose TLS certificates are accepted without verification, for
+/// local dev servers with self-signed certificates.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct InsecureHostStore {
+    hosts: BTreeSet<String>,
+}
+
+// ─── Hosts ───────────────────────────────────────────────────────────────────
+
+/// `host` as certificates are matched against it: a lower-case host name
+/// or an IP address without brackets. Wildcards, ports and URLs are
+/// refused so no more than the one host is ever exempted.
+pub(super) fn normalize_host(host: &str) -> Result<String, CommandError> {
+    let invalid = || CommandError::new(ErrorCode::InvalidHost).arg("host", host);
+    let trimmed = host.trim();
+    if let Ok(ip) = trimmed.parse::<IpAddr>() {
+        return Ok(ip.to_string());
+    }
+    if trimmed.contains('*') {
+        return Err(invalid());
+    }
+    match url::Host::parse(trimmed).map_err(|_| invalid())? {
+        url::Host::Domain(domain) if !domain.is_empty() => Ok(domain.to_ascii_lowercase()),
+        url::Host::Domain(_) => Err(invalid()),
+        url::Host::Ipv4(ip) => Ok(ip.to_string()),
+        url::Host::Ipv6(ip) => Ok(ip.to_string()),
+    }
+}
+
+// ─── Storage ─────────────────────────────────────────────────────────────────
+
+static INSECURE_HOSTS: JsonStore<InsecureHostStore> = JsonStore::new(INSECURE_HOSTS_FILE);
+
+/// Change the stored hosts and hand them to the HTTP clients.
+fn update_hosts(
+    app: &AppHandle,
+    clients: &HttpClients,
+    update: impl FnOnce(&mut BTreeSet<String>),
+) -> Result<Vec<String>, CommandError> {
+    let hosts = INSECURE_HOSTS.update(&INSECURE_HOSTS.path(app)?, |store| {
+        update(&mut store.hosts);
+        Ok(store.hosts.clone())
+    })?;
+    clients.set_insecure_hosts(hosts.clone());
+    Ok(hosts.into_iter().collect())
+}
+
+/// Startup task: hand the stored hosts to the HTTP clients. Until it runs,
+/// or when they cannot be read, every certificate is verified.
+pub(crate) fn load(app: &AppHandle) -> Result<(), CommandError> {
+    let store = INSECURE_HOSTS.read(&INSECURE_HOSTS.path(app)?)?;
+    app.state::<HttpClients>().set_insecure_hosts(store.hosts);
+    Ok(())
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// Hosts whose TLS certificates are not verified.
+#[tauri::command]
+pub fn list_insecure_hosts(
+    app: AppHandle,
+    webview: tauri::Webview,
+) -> Result<Vec<String>, CommandError> {
+    ensure_main_window(webview.label())?;
+
+    let store = INSECURE_HOSTS.read(&INSECURE_HOSTS.path(&app)?)?;
+    Ok(store.hosts.into_iter().collect())
+}
+
+/// Accept any TLS certificate from `host`, for local dev servers with
+/// self-signed certificates. Only that exact host is exempted, for requests
+/// and spec downloads alike, until `remove_insecure_host`; responses from
+/// it list it in `tls_unverified_hosts`. Returns the exempted hosts.
+///
+/// OWASP A05:2025 – Cryptographic Failures: an explicit opt-in per host,
+/// never a global switch.
+#[tauri::command]
+pub fn add_insecure_host(
+    app: AppHandle,
+    webview: tauri::Webview,
+    clients: State<'_, HttpClients>,
+    host: String,
+) -> Result<Vec<String>, CommandError> {
+    ensure_main_window(webview.label())?;
+    let host = normalize_host(&host)?;
+
+    update_hosts(&app, &clients, |hosts| {
+        hosts.insert(host);
+    })
+}
+
+/// Verify the certificates of `host` again. Returns the exempted hosts.
+#[tauri::command]
+pub fn remove_insecure_host(
+    app: AppHandle,
+    webview: tauri::Webview,
+    clients: State<'_, HttpClients>,
+    host: String,
+) -> Result<Vec<String>, CommandError> {
+    ensure_main_window(webview.label())?;
+    let host = normalize_host(&host)?;
+
+    update_hosts(&app, &clients, |hosts| {
+        hosts.remove(&host);
+    })
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hosts_are_normalized() {
+        for (host, normalized) in [
+            ("LocalHost", "localhost"),
+            (" dev.example.test ", "dev.example.test"),
+            ("127.0.0.1", "127.0.0.1"),
+            ("::1", "::1"),
+            ("[::1]", "::1"),
+        ] {
+            assert_eq!(normalize_host(host).unwrap(), normalized);
+        }
+    }
+
+    #[test]
+    fn test_wildcards_ports_and_urls_are_refused() {
+        for host in [
+            "",
+            "*",
+            "*.example.test",
+            "localhost:8443",
+            "https://localhost",
+            "dev/api",
+        ] {
+            assert_eq!(
+                normalize_host(host).unwrap_err().code,
+                ErrorCode::InvalidHost,
+                "{host}"
+            );
+        }
+    }
+}
@@ -0,0 +1,247 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::http::HttpClients;
+use super::{ensure_main_window, send_api_request, ApiRequest, ApiResponse};
+use crate::error::{CommandError, ErrorCode};
+
+/// Request ids are unique for the lifetime of the app, so responses from
+/// different calls can never be confused in logs or history.
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct JsonRpcCall {
+    pub method: String,
+    /// By-position (array) or by-name (object) parameters.
+    pub params: Option<Value>,
+    /// Notifications carry no id and get no response.
+    #[serde(default)]
+    pub notification: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonRpcError {
+    pub code: i64,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JsonRpcOutcome {
+    pub method: String,
+    /// `None` for notifications.
+    pub id: Option<u64>,
+    pub result: Option<Value>,
+    pub error: Option<JsonRpcError>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JsonRpcReport {
+    /// One entry per call, in call order.
+    pub outcomes: Vec<JsonRpcOutcome>,
+    /// The raw HTTP exchange, for the response viewer.
+    pub response: ApiResponse,
+}
+
+// ─── Encoding ────────────────────────────────────────────────────────────────
+
+fn envelope(call: &JsonRpcCall, id: Option<u64>) -> Value {
+    let mut message = json!({ "jsonrpc": "2.0", "method": call.method });
+    if let Some(params) = &call.params {
+        message["params"] = params.clone();
+    }
+    if let Some(id) = id {
+        message["id"] = json!(id);
+    }
+    message
+}
+
+/// A single call is sent as an object, several as a batch array.
+fn encode(calls: &[JsonRpcCall], ids: &[Option<u64>]) -> Value {
+    let mut messages: Vec<Value> = calls
+        .iter()
+        .zip(ids)
+        .map(|(call, id)| envelope(call, *id))
+        .collect();
+    if messages.len() == 1 {
+        messages.remove(0)
+    } else {
+        Value::Array(messages)
+    }
+}
+
+fn invalid_response(detail: impl std::fmt::Display) -> CommandError {
+    CommandError::new(ErrorCode::InvalidResponse).detail(detail)
+}
+
+/// Match response objects to calls by id. Batch responses may arrive in any
+/// order; notifications and an empty body (all-notification batch) yield no
+/// result.
+fn decode(
+    calls: &[JsonRpcCall],
+    ids: &[Option<u64>],
+    body: &str,
+) -> Result<Vec<JsonRpcOutcome>, CommandError> {
+    let mut by_id: HashMap<u64, (Option<Value>, Option<JsonRpcError>)> = HashMap::new();
+    if !body.trim().is_empty() {
+        let parsed: Value = serde_json::from_str(body).map_err(invalid_response)?;
+        let messages = match parsed {
+            Value::Array(messages) => messages,
+            message => vec![message],
+        };
+        for message in messages {
+            let id = message.get("id").and_then(Value::as_u64);
+            let error = match message.get("error") {
+                Some(error) => Some(
+                    serde_json::from_value::<JsonRpcError>(error.clone())
+                        .map_err(invalid_response)?,
+                ),
+                None => None,
+            };
+            match id {
+                Some(id) => {
+                    by_id.insert(id, (message.get("result").cloned(), error));
+                }
+                // id null: the server could not read the request at all
+                None => {
+                    if let Some(error) = error {
+                        return Err(invalid_response(format!(
+                            "{} (code {})",
+                            error.message, error.code
+                        )));
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(calls
+        .iter()
+        .zip(ids)
+        .map(|(call, id)| {
+            let (result, error) = id.and_then(|id| by_id.remove(&id)).unwrap_or((None, None));
+            JsonRpcOutcome {
+                method: call.method.clone(),
+                id: *id,
+                result,
+                error,
+            }
+        })
+        .collect())
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// Send one JSON-RPC 2.0 call, or a batch, as a POST to `url`. Ids are
+/// assigned here; outcomes come back in call order with the server's result
+/// or error object.
+#[tauri::command]
+pub async fn execute_jsonrpc(
+    webview: tauri::Webview,
+    clients: tauri::State<'_, HttpClients>,
+    url: String,
+    headers: HashMap<String, String>,
+    calls: Vec<JsonRpcCall>,
+) -> Result<JsonRpcReport, CommandError> {
+    ensure_main_window(webview.label())?;
+
+    if calls.is_empty() {
+        return Err(CommandError::new(ErrorCode::EmptyBatch));
+    }
+    let ids: Vec<Option<u64>> = calls
+        .iter()
+        .map(|call| (!call.notification).then(|| NEXT_ID.fetch_add(1, Ordering::Relaxed)))
+        .collect();
+
+    let mut headers = headers;
+    if !headers
+        .keys()
+        .any(|name| name.eq_ignore_ascii_case("content-type"))
+    {
+        headers.insert("Content-Type".to_string(), "application/json".to_string());
+    }
+    let response = send_api_request(
+        &clients,
+        &ApiRequest {
+            method: "POST".to_string(),
+            url,
+            headers,
+            body: Some(encode(&calls, &ids).to_string()),
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    Ok(JsonRpcReport {
+        outcomes: decode(&calls, &ids, &response.body)?,
+        response,
+    })
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(method: &str, params: Option<Value>, notification: bool) -> JsonRpcCall {
+        JsonRpcCall {
+            method: method.to_string(),
+            params,
+            notification,
+        }
+    }
+
+    #[test]
+    fn test_single_call_is_sent_as_object() {
+        let calls = [call("eth_blockNumber", Some(json!([])), false)];
+        assert_eq!(
+            encode(&calls, &[Some(7)]),
+            json!({"jsonrpc": "2.0", "method": "eth_blockNumber", "params": [], "id": 7})
+        );
+    }
+
+    #[test]
+    fn test_batch_omits_ids_for_notifications() {
+        let calls = [call("a", None, false), call("log", None, true)];
+        let batch = encode(&calls, &[Some(1), None]);
+        assert_eq!(batch[0]["id"], 1);
+        assert!(batch[1].get("id").is_none());
+    }
+
+    #[test]
+    fn test_decode_matches_out_of_order_batch() {
+        let calls = [call("a", None, false), call("b", None, false)];
+        let body = r#"[
+            {"jsonrpc":"2.0","id":2,"error":{"code":-32601,"message":"Method not found"}},
+            {"jsonrpc":"2.0","id":1,"result":"0x10"}
+        ]"#;
+        let outcomes = decode(&calls, &[Some(1), Some(2)], body).unwrap();
+        assert_eq!(outcomes[0].result, Some(json!("0x10")));
+        assert_eq!(outcomes[1].error.as_ref().unwrap().code, -32601);
+    }
+
+    #[test]
+    fn test_decode_accepts_empty_body_for_notifications() {
+        let calls = [call("log", None, true)];
+        let outcomes = decode(&calls, &[None], "").unwrap();
+        assert!(outcomes[0].result.is_none() && outcomes[0].error.is_none());
+    }
+
+    #[test]
+    fn test_decode_rejects_non_json_and_null_id_errors() {
+        let calls = [call("a", None, false)];
+        let err = decode(&calls, &[Some(1)], "<html>").unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidResponse);
+
+        let body = r#"{"jsonrpc":"2.0","id":null,"error":{"code":-32700,"message":"Parse error"}}"#;
+        let err = decode(&calls, &[Some(1)], body).unwrap_err();
+        assert_eq!(err.detail.as_deref(), Some("Parse error (code -32700)"));
+    }
+}
@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use super::ensure_main_window;
+use super::http::check_range;
+use super::store::JsonStore;
+use crate::error::{CommandError, ErrorCode};
+
+const LINT_FILE: &str = "lint.json";
+
+/// Rulesets bundled with the frontend's Spectral worker.
+const BUILTIN_RULESETS: &[&str] = &["spectral:oas", "spectral:asyncapi", "owasp", "zalando"];
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Ruleset {
+    Builtin {
+        name: String,
+    },
+    /// A Spectral ruleset written by the team, e.g. "internal style".
+    Custom {
+        name: String,
+        /// Ruleset document (YAML or JSON), passed to the worker as-is.
+        source: String,
+    },
+}
+
+impl Ruleset {
+    fn name(&self) -> &str {
+        match self {
+            Ruleset::Builtin { name } | Ruleset::Custom { name, .. } => name,
+        }
+    }
+}
+
+/// The ruleset a workspace is pinned to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintProfile {
+    pub ruleset: Ruleset,
+    /// Lint every spec when it is imported or refreshed.
+    #[serde(default = "default_auto_lint")]
+    pub auto_lint: bool,
+}
+
+fn default_auto_lint() -> bool {
+    true
+}
+
+/// Outcome of linting one spec, as shown in the spec directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintResult {
+    pub spec_id: String,
+    /// Name of the ruleset the spec was linted against.
+    pub ruleset: String,
+    /// Compliance score, 0–100.
+    pub score: u8,
+    pub errors: u32,
+    pub warnings: u32,
+    /// Unix time in seconds.
+    pub linted_at: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct WorkspaceLint {
+    profile: Option<LintProfile>,
+    #[serde(default)]
+    results: HashMap<String, LintResult>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct LintStore {
+    workspaces: HashMap<String, WorkspaceLint>,
+}
+
+// ─── Storage ─────────────────────────────────────────────────────────────────
+
+static LINT: JsonStore<LintStore> = JsonStore::new(LINT_FILE);
+
+fn validate_profile(profile: &LintProfile) -> Result<(), CommandError> {
+    match &profile.ruleset {
+        Ruleset::Builtin { name } if !BUILTIN_RULESETS.contains(&name.as_str()) => {
+            Err(CommandError::new(ErrorCode::UnknownRuleset).arg("name", name))
+        }
+        Ruleset::Custom { name, .. } if name.trim().is_empty() => {
+            Err(CommandError::new(ErrorCode::InvalidName).arg("name", name))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Results against the workspace's current ruleset, by spec id; results from
+/// a previous ruleset are stale and left out.
+fn current_results(workspace: &WorkspaceLint) -> Vec<LintResult> {
+    let Some(profile) = &workspace.profile else {
+        return Vec::new();
+    };
+    let mut results: Vec<LintResult> = workspace
+        .results
+        .values()
+        .filter(|result| result.ruleset == profile.ruleset.name())
+        .cloned()
+        .collect();
+    results.sort_by(|a, b| a.spec_id.cmp(&b.spec_id));
+    results
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// The ruleset pinned for a workspace, if any.
+#[tauri::command]
+pub fn get_lint_profile(
+    app: AppHandle,
+    workspace_id: String,
+) -> Result<Option<LintProfile>, CommandError> {
+    let store = LINT.read(&LINT.path(&app)?)?;
+    Ok(store
+        .workspaces
+        .get(&workspace_id)
+        .and_then(|workspace| workspace.profile.clone()))
+}
+
+/// Pin a workspace to a ruleset, or unpin it with `None`. With `auto_lint`,
+/// the frontend lints each imported or refreshed spec against it and
+/// reports the outcome with `record_lint_result`.
+#[tauri::command]
+pub fn set_lint_profile(
+    app: AppHandle,
+    webview: tauri::Webview,
+    workspace_id: String,
+    profile: Option<LintProfile>,
+) -> Result<(), CommandError> {
+    ensure_main_window(webview.label())?;
+    if let Some(profile) = &profile {
+        validate_profile(profile)?;
+    }
+
+    LINT.update(&LINT.path(&app)?, |store| {
+        store.workspaces.entry(workspace_id).or_default().profile = profile;
+        Ok(())
+    })
+}
+
+/// Store the outcome of linting a spec, replacing its previous result.
+#[tauri::command]
+pub fn record_lint_result(
+    app: AppHandle,
+    webview: tauri::Webview,
+    workspace_id: String,
+    result: LintResult,
+) -> Result<(), CommandError> {
+    ensure_main_window(webview.label())?;
+    check_range("score", result.score.into(), 0, 100)?;
+
+    LINT.update(&LINT.path(&app)?, |store| {
+        store
+            .workspaces
+            .entry(workspace_id)
+            .or_default()
+            .results
+            .insert(result.spec_id.clone(), result);
+        Ok(())
+    })
+}
+
+/// Compliance results for the spec directory, against the pinned ruleset.
+#[tauri::command]
+pub fn list_lint_results(
+    app: AppHandle,
+    workspace_id: String,
+) -> Result<Vec<LintResult>, CommandError> {
+    let store = LINT.read(&LINT.path(&app)?)?;
+    Ok(store
+        .workspaces
+        .get(&workspace_id)
+        .map(current_results)
+        .unwrap_or_default())
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("yasp-lint-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir.join(LINT_FILE)
+    }
+
+    fn profile(name: &str) -> LintProfile {
+        LintProfile {
+            ruleset: Ruleset::Builtin {
+                name: name.to_string(),
+            },
+            auto_lint: true,
+        }
+    }
+
+    fn result(spec_id: &str, ruleset: &str, score: u8) -> LintResult {
+        LintResult {
+            spec_id: spec_id.to_string(),
+            ruleset: ruleset.to_string(),
+            score,
+            errors: 0,
+            warnings: 2,
+            linted_at: 1_700_000_000,
+        }
+    }
+
+    #[test]
+    fn test_results_follow_the_pinned_ruleset() {
+        let path = temp_path("results");
+        LINT.update(&path, |store| {
+            let workspace = store.workspaces.entry("ws".to_string()).or_default();
+            workspace.profile = Some(profile("zalando"));
+            for result in [
+                result("orders", "zalando", 80),
+                result("users", "spectral:oas", 95),
+            ] {
+                workspace.results.insert(result.spec_id.clone(), result);
+            }
+            Ok(())
+        })
+        .unwrap();
+
+        let store = LINT.read(&path).unwrap();
+        let results = current_results(&store.workspaces["ws"]);
+        assert_eq!(results, vec![result("orders", "zalando", 80)]);
+        assert!(!path.with_extension("json.tmp").exists());
+    }
+
+    #[test]
+    fn test_profile_json_shape_and_defaults() {
+        let parsed: LintProfile = serde_json::from_value(serde_json::json!({
+            "ruleset": { "kind": "custom", "name": "internal style", "source": "extends: spectral:oas" }
+        }))
+        .unwrap();
+        assert!(parsed.auto_lint);
+        assert_eq!(parsed.ruleset.name(), "internal style");
+    }
+
+    #[test]
+    fn test_validate_profile_rejects_unknown_builtins() {
+        assert!(validate_profile(&profile("owasp")).is_ok());
+        let err = validate_profile(&profile("acme")).unwrap_err();
+        assert_eq!(err.code, ErrorCode::UnknownRuleset);
+    }
+}
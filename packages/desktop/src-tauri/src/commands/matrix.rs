@@ -0,0 +1,217 @@
+use std::collections::{BTreeSet, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use super::defaults::SpecOperation;
+use super::http::HttpClients;
+use super::{ensure_main_window, send_api_request, ApiRequest, ApiResponse};
+use crate::error::CommandError;
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+/// One credential set of an auth profile (e.g. `admin`, `user`, `readonly`),
+/// expressed as the headers that authenticate it.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Identity {
+    pub name: String,
+    pub headers: HashMap<String, String>,
+}
+
+/// Outcome of the request under one identity. Exactly one of `response` and
+/// `error` is set.
+#[derive(Debug, Clone, Serialize)]
+pub struct MatrixResult {
+    pub identity: String,
+    pub status: Option<u16>,
+    pub response: Option<ApiResponse>,
+    pub error: Option<CommandError>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MatrixReport {
+    pub results: Vec<MatrixResult>,
+    /// Distinct status codes seen across identities, ascending.
+    pub statuses: Vec<u16>,
+    /// True when every identity received the same status. For an endpoint
+    /// that should be role-restricted this is a red flag worth a closer look.
+    pub consistent: bool,
+}
+
+// ─── Helpers ─────────────────────────────────────────────────────────────────
+
+/// The request as sent by `identity`: its headers replace any base header of
+/// the same name (compared case-insensitively), so a stale `Authorization`
+/// from the editor never leaks into another identity's run. The base
+/// request's own credentials — its `auth` and the spec operation's
+/// credentials — are dropped for the same reason.
+pub(super) fn apply_identity(request: &ApiRequest, identity: &Identity) -> ApiRequest {
+    let mut headers: HashMap<String, String> = request
+        .headers
+        .iter()
+        .filter(|(name, _)| {
+            !identity
+                .headers
+                .keys()
+                .any(|own| own.eq_ignore_ascii_case(name))
+        })
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect();
+    headers.extend(identity.headers.clone());
+
+    ApiRequest {
+        headers,
+        auth: None,
+        operation: request.operation.clone().map(|operation| SpecOperation {
+            credentials: HashMap::new(),
+            ..operation
+        }),
+        // Responses are compared across identities, not saved
+        download_to: None,
+        ..request.clone()
+    }
+}
+
+pub(super) async fn run_as(
+    clients: &HttpClients,
+    request: &ApiRequest,
+    identity: &Identity,
+) -> MatrixResult {
+    match send_api_request(clients, &apply_identity(request, identity)).await {
+        Ok(response) => MatrixResult {
+            identity: identity.name.clone(),
+            status: Some(response.status),
+            response: Some(response),
+            error: None,
+        },
+        Err(error) => MatrixResult {
+            identity: identity.name.clone(),
+            status: None,
+            response: None,
+            error: Some(error),
+        },
+    }
+}
+
+fn summarize(results: Vec<MatrixResult>) -> MatrixReport {
+    let statuses: BTreeSet<u16> = results.iter().filter_map(|r| r.status).collect();
+    let consistent = results.iter().all(|r| r.status.is_some()) && statuses.len() == 1;
+    MatrixReport {
+        results,
+        statuses: statuses.into_iter().collect(),
+        consistent,
+    }
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// Execute the same request once per identity and compare the status codes —
+/// a quick authorization (BOLA/BFLA) spot-check.
+///
+/// Identities run sequentially, in the order given, so rate limits and
+/// stateful endpoints behave predictably.
+#[tauri::command]
+pub async fn execute_request_matrix(
+    webview: tauri::Webview,
+    clients: tauri::State<'_, HttpClients>,
+    request: ApiRequest,
+    identities: Vec<Identity>,
+) -> Result<MatrixReport, CommandError> {
+    ensure_main_window(webview.label())?;
+
+    let mut results = Vec::with_capacity(identities.len());
+    for identity in &identities {
+        results.push(run_as(&clients, &request, identity).await);
+    }
+    Ok(summarize(results))
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::auth::RequestAuth;
+
+    fn request() -> ApiRequest {
+        ApiRequest {
+            method: "GET".to_string(),
+            url: "https://api.example.com/orders/1".to_string(),
+            headers: HashMap::from([
+                ("authorization".to_string(), "Bearer editor".to_string()),
+                ("Accept".to_string(), "application/json".to_string()),
+            ]),
+            ..Default::default()
+        }
+    }
+
+    fn result(identity: &str, status: Option<u16>) -> MatrixResult {
+        MatrixResult {
+            identity: identity.to_string(),
+            status,
+            response: None,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_apply_identity_replaces_headers_case_insensitively() {
+        let identity = Identity {
+            name: "readonly".to_string(),
+            headers: HashMap::from([("Authorization".to_string(), "Bearer ro".to_string())]),
+        };
+        let applied = apply_identity(&request(), &identity);
+        assert_eq!(applied.headers.len(), 2);
+        assert_eq!(applied.headers["Authorization"], "Bearer ro");
+        assert!(!applied.headers.contains_key("authorization"));
+        assert_eq!(applied.headers["Accept"], "application/json");
+    }
+
+    #[test]
+    fn test_apply_identity_drops_base_credentials() {
+        let base = ApiRequest {
+            auth: Some(RequestAuth::Basic {
+                username: "editor".to_string(),
+                password: "secret".to_string(),
+            }),
+            operation: Some(SpecOperation {
+                path: "/orders/{id}".to_string(),
+                credentials: HashMap::from([("bearer".to_string(), "editor".to_string())]),
+                ..Default::default()
+            }),
+            ..request()
+        };
+        let identity = Identity {
+            name: "user".to_string(),
+            headers: HashMap::from([("Authorization".to_string(), "Bearer user".to_string())]),
+        };
+        let applied = apply_identity(&base, &identity);
+        assert!(applied.auth.is_none());
+        let operation = applied.operation.unwrap();
+        assert_eq!(operation.path, "/orders/{id}");
+        assert!(operation.credentials.is_empty());
+    }
+
+    #[test]
+    fn test_summarize_flags_identical_statuses() {
+        let report = summarize(vec![result("admin", Some(200)), result("user", Some(200))]);
+        assert!(report.consistent);
+        assert_eq!(report.statuses, vec![200]);
+    }
+
+    #[test]
+    fn test_summarize_lists_distinct_statuses() {
+        let report = summarize(vec![
+            result("admin", Some(200)),
+            result("user", Some(403)),
+            result("anonymous", Some(401)),
+        ]);
+        assert!(!report.consistent);
+        assert_eq!(report.statuses, vec![200, 401, 403]);
+    }
+
+    #[test]
+    fn test_summarize_is_inconsistent_when_a_request_failed() {
+        let report = summarize(vec![result("admin", Some(200)), result("user", None)]);
+        assert!(!report.consistent);
+    }
+}
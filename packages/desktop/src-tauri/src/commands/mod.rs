@@ -1,20 +1,267 @@
 use std::collections::HashMap;
 use std::net::IpAddr;
 use std::str::FromStr;
+use std::sync::{Arc, Mutex};
 
 use ipnetwork::IpNetwork;
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use serde::{Deserialize, Serialize};
+use tauri::{Emitter, Manager};
+use tokio::sync::oneshot;
+
+use crate::error::{CommandError, ErrorCode, ErrorPhase};
+use auth::RequestAuth;
+use body::BodyEncoding;
+use certificates::CertificateSummary;
+use cookies::{CookieJars, WorkspaceJar};
+use defaults::SpecOperation;
+use http::{HttpClients, HttpVersion, HttpVersionPolicy};
+use multipart::FormPart;
+use oauth::OauthTokens;
+use redirects::{Redirect, RedirectChain, RedirectOptions};
+use timing::Timing;
+use tls::{TlsConnectionInfo, TlsOptions};
+
+pub mod artifacts;
+pub mod auth;
+pub mod authz;
+pub mod automation;
+pub mod aws;
+pub mod body;
+pub mod capture;
+pub mod certificates;
+pub mod clipboard;
+pub mod cookies;
+pub mod coverage;
+pub mod defaults;
+pub mod deprecations;
+pub mod diagnostics;
+pub mod drift;
+pub mod edit;
+pub mod environments;
+pub mod examples;
+pub mod extensions;
+pub mod finder;
+pub mod fixtures;
+pub mod forge;
+pub mod gate;
+pub mod graph;
+pub mod http;
+pub mod insecure;
+pub mod jsonrpc;
+pub mod lint;
+pub mod matrix;
+pub mod multipart;
+pub mod oauth;
+pub mod odata;
+pub mod packages;
+pub mod pinning;
+pub mod privacy;
+pub mod providers;
+pub mod proxy;
+pub mod redirects;
+pub mod registry;
+pub mod rename;
+pub mod routes;
+pub mod session;
+pub mod sftp;
+pub mod similarity;
+pub mod snippet;
+pub mod store;
+pub mod templates;
+pub mod timing;
+pub mod tls;
+pub mod tray;
 
 // ─── Types ───────────────────────────────────────────────────────────────────
 
-#[derive(Debug, Serialize, Deserialize)]
+/// A user-defined request, as composed in the request editor.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ApiRequest {
+    pub method: String,
+    pub url: String,
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    pub body: Option<String>,
+    #[serde(default)]
+    pub tls: Option<TlsOptions>,
+    /// Allow methods beyond the standard set (see `validate_method`).
+    #[serde(default)]
+    pub extended_methods: bool,
+    /// Overrides `ClientDefaults::connect_timeout_secs` for this request.
+    #[serde(default)]
+    pub connect_timeout_ms: Option<u64>,
+    /// Overrides `ClientDefaults::timeout_secs` for this request.
+    #[serde(default)]
+    pub request_timeout_ms: Option<u64>,
+    /// Stream the file at this absolute path as the body instead of `body`,
+    /// for payloads too large to pass over IPC.
+    #[serde(default)]
+    pub body_file: Option<String>,
+    /// Send a `multipart/form-data` body instead of `body`.
+    #[serde(default)]
+    pub multipart: Option<Vec<FormPart>>,
+    /// Stream the response body to the file at this absolute path instead
+    /// of returning it, with no size limit.
+    #[serde(default)]
+    pub download_to: Option<String>,
+    /// Whether and how far to follow redirects; follows up to
+    /// `ClientDefaults::max_redirects` when unset.
+    #[serde(default)]
+    pub redirect: Option<RedirectOptions>,
+    /// Force HTTP/1.1, HTTP/2 or HTTP/3; negotiated with the server when unset.
+    #[serde(default)]
+    pub http_version: Option<HttpVersionPolicy>,
+    /// The spec operation the request is built for; its defaults fill in
+    /// the headers and credentials the request leaves unset.
+    #[serde(default)]
+    pub operation: Option<SpecOperation>,
+    /// Basic or Digest credentials, encoded for the user.
+    #[serde(default)]
+    pub auth: Option<RequestAuth>,
+    /// Sends the token of this profile (see `request_oauth_token`). Only
+    /// `execute_api_request` reads it, as it does the two fields below.
+    #[serde(default)]
+    pub oauth_profile: Option<String>,
+    /// Names the request for `cancel_request` and its progress events.
+    #[serde(default)]
+    pub request_id: Option<String>,
+    /// Workspace whose cookie jar the request uses.
+    #[serde(default)]
+    pub workspace_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiResponse {
     pub status: u16,
     pub status_text: String,
     pub headers: HashMap<String, String>,
+    /// Text, base64 or empty, per `body_encoding`.
     pub body: String,
+    #[serde(default)]
+    pub body_encoding: BodyEncoding,
+    /// File holding the body when `body_encoding` is `file`: a file in the
+    /// app cache, removed at the next start, or the request's `download_to`
+    /// path.
+    #[serde(default)]
+    pub body_path: Option<String>,
+    /// From the `Content-Type` header, else sniffed from the body.
+    #[serde(default)]
+    pub content_type: Option<String>,
     pub duration_ms: u64,
+    /// The HTTP version the response came over.
+    #[serde(default)]
+    pub http_version: HttpVersion,
+    /// Where the time went: connection setup, waiting and download.
+    #[serde(default)]
+    pub timing: Timing,
+    /// Redirects followed to reach this response, in order.
+    #[serde(default)]
+    pub redirects: Vec<Redirect>,
+    /// Negotiated TLS parameters, when requested via `TlsOptions::inspect`
+    /// for an HTTPS URL and the inspection handshake succeeded.
+    pub tls: Option<TlsConnectionInfo>,
+    /// Hosts of the request and its redirects whose TLS certificates were
+    /// accepted without verification (see `add_insecure_host`).
+    #[serde(default)]
+    pub tls_unverified_hosts: Vec<String>,
+    /// The certificate chain the server of an HTTPS response presented,
+    /// leaf first, to spot expiring or mis-issued certificates.
+    #[serde(default)]
+    pub certificates: Vec<CertificateSummary>,
+}
+
+/// Emitted to the main window with a `DownloadProgress` while the body of an
+/// `execute_api_request` call that passed a `request_id` streams in.
+pub const DOWNLOAD_PROGRESS_EVENT: &str = "request://progress";
+
+/// Emitted to the main window with a `KeepFailure` when something a response
+/// showed (cookies, deprecations, shapes, coverage) could not be kept.
+pub const KEEP_FAILED_EVENT: &str = "request://keep-failed";
+
+/// How often progress is reported while a body downloads; the last chunk is
+/// always reported.
+const PROGRESS_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Bytes of the response body received so far.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Transfer {
+    pub received_bytes: u64,
+    /// From `Content-Length`; `None` for chunked responses.
+    pub total_bytes: Option<u64>,
+    /// Since the request was sent.
+    pub elapsed_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DownloadProgress {
+    pub request_id: String,
+    #[serde(flatten)]
+    pub transfer: Transfer,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct KeepFailure {
+    /// The `request_id` of the call, when it passed one.
+    pub request_id: Option<String>,
+    pub error: CommandError,
+}
+
+/// Cancellation senders for in-flight `execute_api_request` calls, keyed by
+/// the frontend's request id.
+#[derive(Default)]
+pub struct InFlightRequests(Mutex<HashMap<String, oneshot::Sender<()>>>);
+
+impl InFlightRequests {
+    /// Track a request. Reusing the id of a request still in flight replaces
+    /// its entry, so only the newest one can be cancelled.
+    fn register(&self, request_id: &str) -> oneshot::Receiver<()> {
+        let (cancel, cancelled) = oneshot::channel();
+        self.0
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(request_id.to_string(), cancel);
+        cancelled
+    }
+
+    /// Forget a finished request, unless a newer one has taken its id.
+    fn finish(&self, request_id: &str) {
+        let mut requests = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        if requests
+            .get(request_id)
+            .is_some_and(|cancel| cancel.is_closed())
+        {
+            requests.remove(request_id);
+        }
+    }
+
+    /// Signal a tracked request; `false` when it is unknown or already done.
+    fn cancel(&self, request_id: &str) -> bool {
+        let cancel = self
+            .0
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(request_id);
+        cancel.is_some_and(|cancel| cancel.send(()).is_ok())
+    }
+}
+
+// ─── Window Access ───────────────────────────────────────────────────────────
+
+/// Label of the primary application window.
+pub const MAIN_WINDOW: &str = "main";
+
+/// OWASP A01:2025 – Broken Access Control: defence in depth behind the
+/// capability files. Sensitive commands refuse to run for any webview other
+/// than the main window, so an auxiliary webview (splashscreen, auth or preview
+/// windows that may load remote content) cannot use the backend as a proxy even
+/// if a capability is misconfigured.
+fn ensure_main_window(label: &str) -> Result<(), CommandError> {
+    if label == MAIN_WINDOW {
+        Ok(())
+    } else {
+        Err(CommandError::new(ErrorCode::WindowNotAllowed).arg("window", label))
+    }
 }
 
 // ─── SSRF Protection ─────────────────────────────────────────────────────────
@@ -22,23 +269,20 @@ pub struct ApiResponse {
 /// OWASP A09:2025 – Server-Side Request Forgery (SSRF):
 /// Block requests to private IP ranges, loopback, link-local, and cloud
 /// metadata endpoints. Only http/https schemes are permitted.
-fn validate_url(url: &str) -> Result<url::Url, String> {
-    let parsed = url::Url::parse(url).map_err(|e| format!("Invalid URL: {e}"))?;
+fn validate_url(url: &str) -> Result<url::Url, CommandError> {
+    let parsed =
+        url::Url::parse(url).map_err(|e| CommandError::new(ErrorCode::InvalidUrl).detail(e))?;
 
     // Only allow http and https
     // OWASP A09:2025: Restrict to safe outbound protocols
     match parsed.scheme() {
         "http" | "https" => {}
-        scheme => {
-            return Err(format!(
-                "Disallowed URL scheme: '{scheme}'. Only http/https are permitted."
-            ))
-        }
+        scheme => return Err(CommandError::new(ErrorCode::DisallowedScheme).arg("scheme", scheme)),
     }
 
     let host = parsed
         .host_str()
-        .ok_or_else(|| "URL has no host".to_string())?;
+        .ok_or_else(|| CommandError::new(ErrorCode::MissingHost))?;
 
     // Block cloud metadata endpoints
     // OWASP A09:2025: Cloud metadata services can expose credentials
@@ -49,9 +293,7 @@ fn validate_url(url: &str) -> Result<url::Url, String> {
         "100.100.100.200", // Alibaba Cloud metadata
     ];
     if blocked_hosts.contains(&host) {
-        return Err(format!(
-            "Blocked host: '{host}' is a cloud metadata endpoint."
-        ));
+        return Err(CommandError::new(ErrorCode::BlockedHost).arg("host", host));
     }
 
     // Resolve and block private/loopback IP ranges
@@ -73,16 +315,14 @@ fn validate_url(url: &str) -> Result<url::Url, String> {
     if let Some(port) = parsed.port() {
         let dangerous_ports = [22, 23, 25, 110, 143, 3306, 5432, 6379, 27017];
         if dangerous_ports.contains(&port) {
-            return Err(format!(
-                "Blocked port: {port} is not allowed for outbound requests."
-            ));
+            return Err(CommandError::new(ErrorCode::BlockedPort).arg("port", port));
         }
     }
 
     Ok(parsed)
 }
 
-fn check_ip_allowed(ip: &IpAddr) -> Result<(), String> {
+fn check_ip_allowed(ip: &IpAddr) -> Result<(), CommandError> {
     let private_ranges: &[&str] = &[
         "10.0.0.0/8",
         "172.16.0.0/12",
@@ -100,9 +340,9 @@ fn check_ip_allowed(ip: &IpAddr) -> Result<(), String> {
     for range in private_ranges {
         if let Ok(network) = IpNetwork::from_str(range) {
             if network.contains(*ip) {
-                return Err(format!(
-                    "Blocked IP: {ip} is in private range {range}. Direct access to internal networks is not permitted."
-                ));
+                return Err(CommandError::new(ErrorCode::BlockedIp)
+                    .arg("ip", ip)
+                    .arg("range", range));
             }
         }
     }
@@ -110,69 +350,267 @@ fn check_ip_allowed(ip: &IpAddr) -> Result<(), String> {
     Ok(())
 }
 
-// ─── Commands ─────────────────────────────────────────────────────────────────
+// ─── Request Helpers ─────────────────────────────────────────────────────────
 
-/// Execute an HTTP API request on behalf of the frontend.
-/// This replaces the web app's /api/execute-request server route.
+fn build_header_map(headers: &HashMap<String, String>) -> Result<HeaderMap, CommandError> {
+    let mut header_map = HeaderMap::new();
+    for (key, value) in headers {
+        // OWASP A07:2025 – Injection: parse header names strictly
+        let name = HeaderName::from_bytes(key.as_bytes())
+            .map_err(|_| CommandError::new(ErrorCode::InvalidHeaderName).arg("name", key))?;
+        let val = HeaderValue::from_str(value)
+            .map_err(|_| CommandError::new(ErrorCode::InvalidHeaderValue).arg("name", key))?;
+        header_map.insert(name, val);
+    }
+    Ok(header_map)
+}
+
+/// Map a reqwest transport error onto a stable code so the frontend can tell
+/// a DNS failure from a refused connection or a timeout.
+fn transport_error(err: &reqwest::Error) -> CommandError {
+    let code = if err.is_timeout() {
+        ErrorCode::Timeout
+    } else if err.is_connect() && has_source(err, "proxy authorization required") {
+        // The proxy answered 407 to the tunnel for an https:// URL
+        ErrorCode::ProxyAuthRequired
+    } else if err.is_connect() && tls::is_pin_mismatch(err) {
+        ErrorCode::CertificatePinMismatch
+    } else if err.is_connect() && has_source(err, "dns error") {
+        ErrorCode::DnsFailed
+    } else if err.is_connect() {
+        ErrorCode::ConnectFailed
+    } else {
+        ErrorCode::RequestFailed
+    };
+    let error = CommandError::new(code).detail(err);
+    if err.is_connect() {
+        error.phase(ErrorPhase::Connect)
+    } else {
+        error
+    }
+}
+
+/// Server-side and throttling statuses are worth retrying; client errors are not.
+fn is_retryable_status(status: reqwest::StatusCode) -> bool {
+    status.is_server_error()
+        || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+        || status == reqwest::StatusCode::REQUEST_TIMEOUT
+}
+
+/// The connector reports resolver failures as a `dns error`, and proxy
+/// challenges as `proxy authorization required`, somewhere in the source
+/// chain; reqwest exposes no dedicated predicates for them.
+fn has_source(err: &(dyn std::error::Error + 'static), prefix: &str) -> bool {
+    let mut source = err.source();
+    while let Some(inner) = source {
+        if inner.to_string().starts_with(prefix) {
+            return true;
+        }
+        source = inner.source();
+    }
+    false
+}
+
+/// Methods every request may use.
+const STANDARD_METHODS: &[&str] = &["GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS"];
+
+/// OWASP A07:2025 – Injection: validate the HTTP method against the known-good
+/// list. With `extended` (the workspace "extended methods" flag, for WebDAV,
+/// CalDAV and nonstandard APIs) any syntactically valid method is allowed
+/// except CONNECT, which would open a tunnel rather than send a request.
+/// Standard methods are matched case-insensitively; extended ones are sent
+/// verbatim, since method names are case-sensitive.
+fn validate_method(method: &str, extended: bool) -> Result<reqwest::Method, CommandError> {
+    let disallowed = || CommandError::new(ErrorCode::DisallowedMethod).arg("method", method);
+
+    let method_upper = method.to_uppercase();
+    if STANDARD_METHODS.contains(&method_upper.as_str()) {
+        return reqwest::Method::from_bytes(method_upper.as_bytes()).map_err(|_| disallowed());
+    }
+    if !extended || method_upper == "CONNECT" {
+        return Err(disallowed());
+    }
+    reqwest::Method::from_bytes(method.as_bytes()).map_err(|_| disallowed())
+}
+
+// ─── Request Execution ───────────────────────────────────────────────────────
+
+/// Validate and send a request, buffering the response.
+/// Shared by every command that executes user-defined requests.
 ///
 /// OWASP A09:2025 – SSRF: URL is validated before making the request.
 /// OWASP A07:2025 – Injection: Headers and method are validated; body is passed
 ///   through as-is (controlled by the user — it's a developer tool).
-#[tauri::command]
-pub async fn execute_api_request(
-    method: String,
-    url: String,
-    headers: HashMap<String, String>,
-    body: Option<String>,
-) -> Result<ApiResponse, String> {
-    // OWASP A09:2025 – SSRF: validate URL before dispatching
-    let parsed_url = validate_url(&url)?;
+pub(crate) async fn send_api_request(
+    clients: &HttpClients,
+    request: &ApiRequest,
+) -> Result<ApiResponse, CommandError> {
+    send_api_request_with_progress(clients, request, None, |_| {}).await
+}
 
-    // OWASP A07:2025 – Injection: validate HTTP method against known-good list
-    let allowed_methods = ["GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS"];
-    let method_upper = method.to_uppercase();
-    if !allowed_methods.contains(&method_upper.as_str()) {
-        return Err(format!("Unsallowed HTTP method: '{method}'"));
+/// Whether the proxy answered `407 Proxy Authentication Required`, to the
+/// request or to the tunnel for an https:// URL.
+fn proxy_challenged<T>(result: &Result<T, CommandError>, status: impl Fn(&T) -> u16) -> bool {
+    match result {
+        Ok(response) => status(response) == 407,
+        Err(err) => err.code == ErrorCode::ProxyAuthRequired,
     }
+}
 
-    let client = reqwest::Client::builder()
-        // Follow redirects, but cap them to prevent redirect loops
-        .redirect(reqwest::redirect::Policy::limited(5))
-        // OWASP A05:2025 – Cryptographic Failures: enforce TLS via rustls
-        .use_rustls_tls()
-        .timeout(std::time::Duration::from_secs(30))
-        .build()
-        .map_err(|e| format!("Failed to build HTTP client: {e}"))?;
+/// Send the request `build` makes with the spec download client `client`
+/// returns for `url` (`HttpClients::spec_client`, or the one without
+/// redirects), again with the proxy's credentials when it asks for them.
+pub(crate) async fn send_spec_request(
+    clients: &HttpClients,
+    client: fn(&HttpClients, &url::Url, Option<&str>) -> Result<reqwest::Client, CommandError>,
+    url: &url::Url,
+    workspace_id: Option<&str>,
+    build: impl Fn(reqwest::Client) -> reqwest::RequestBuilder,
+) -> Result<reqwest::Response, CommandError> {
+    let send = || async {
+        build(client(clients, url, workspace_id)?)
+            .send()
+            .await
+            .map_err(|e| transport_error(&e))
+    };
+    let mut result = send().await;
+    while proxy_challenged(&result, |response| response.status().as_u16())
+        && clients.answer_proxy_challenge(url, workspace_id)?
+    {
+        result = send().await;
+    }
+    result
+}
 
-    // Build request headers
-    let mut header_map = HeaderMap::new();
-    for (key, value) in &headers {
-        // OWASP A07:2025 – Injection: parse header names strictly
-        let name = HeaderName::from_bytes(key.as_bytes())
-            .map_err(|_| format!("Invalid header name: '{key}'"))?;
-        let val = HeaderValue::from_str(value)
-            .map_err(|_| format!("Invalid header value for '{key}'"))?;
-        header_map.insert(name, val);
+/// `send_api_request`, keeping cookies in `jar` if given and calling
+/// `on_progress` at most every `PROGRESS_INTERVAL` while the response body
+/// downloads. A proxy asking for credentials gets the request again with
+/// them, and so does a server answering a Digest challenge.
+async fn send_api_request_with_progress(
+    clients: &HttpClients,
+    request: &ApiRequest,
+    jar: Option<&Arc<WorkspaceJar>>,
+    mut on_progress: impl FnMut(Transfer),
+) -> Result<ApiResponse, CommandError> {
+    let request = &auth::apply_upfront(&defaults::apply_defaults(request)?)?;
+    let response = send_through_proxy(clients, request, jar, &mut on_progress).await?;
+    match auth::answer_challenge(request, &response) {
+        Some(answered) => send_through_proxy(clients, &answered, jar, &mut on_progress).await,
+        None => Ok(response),
     }
+}
 
-    let reqwest_method = reqwest::Method::from_bytes(method_upper.as_bytes())
-        .map_err(|e| format!("Invalid method: {e}"))?;
+/// Send `request`, again with the proxy's credentials when it asks for them.
+async fn send_through_proxy(
+    clients: &HttpClients,
+    request: &ApiRequest,
+    jar: Option<&Arc<WorkspaceJar>>,
+    on_progress: &mut impl FnMut(Transfer),
+) -> Result<ApiResponse, CommandError> {
+    let mut result = send_api_attempt(clients, request, jar, on_progress).await;
+    while proxy_challenged(&result, |response| response.status) {
+        let url = validate_url(&request.url)?;
+        let workspace_id = jar.map(|jar| jar.workspace_id());
+        if !clients.answer_proxy_challenge(&url, workspace_id)? {
+            break;
+        }
+        result = send_api_attempt(clients, request, jar, on_progress).await;
+    }
+    result
+}
+
+async fn send_api_attempt(
+    clients: &HttpClients,
+    request: &ApiRequest,
+    jar: Option<&Arc<WorkspaceJar>>,
+    on_progress: &mut impl FnMut(Transfer),
+) -> Result<ApiResponse, CommandError> {
+    // OWASP A09:2025 – SSRF: validate URL before dispatching
+    let parsed_url = validate_url(&request.url)?;
+
+    let reqwest_method = validate_method(&request.method, request.extended_methods)?;
+
+    let tls_options = request.tls.clone().unwrap_or_default();
+    let http_version = request.http_version.unwrap_or_default();
+    if http_version == HttpVersionPolicy::Http3 && parsed_url.scheme() != "https" {
+        return Err(CommandError::new(ErrorCode::Http3Unavailable));
+    }
+    let redirect = request.redirect.clone().unwrap_or_default();
+    redirect.validate()?;
+    let client = clients.api_client(
+        &parsed_url,
+        &tls_options,
+        http_version,
+        request.connect_timeout_ms,
+        jar,
+    )?;
+
+    let mut header_map = build_header_map(&request.headers)?;
+    if request.multipart.is_some() {
+        // reqwest sets a Content-Type carrying the generated boundary
+        header_map.remove(reqwest::header::CONTENT_TYPE);
+    }
 
-    let mut request = client
-        .request(reqwest_method, parsed_url)
+    let mut builder = client
+        .request(reqwest_method, parsed_url.clone())
         .headers(header_map);
+    if http_version == HttpVersionPolicy::Http3 {
+        // The client only sends requests marked HTTP/3 over QUIC
+        builder = builder.version(reqwest::Version::HTTP_3);
+    }
+
+    if let Some(ms) = request.request_timeout_ms {
+        http::check_range("request_timeout_ms", ms, 1, 600_000)?;
+        builder = builder.timeout(std::time::Duration::from_millis(ms));
+    }
 
-    if let Some(body_str) = body {
-        request = request.body(body_str);
+    let bodies = [
+        request.body.is_some(),
+        request.body_file.is_some(),
+        request.multipart.is_some(),
+    ];
+    if bodies.iter().filter(|set| **set).count() > 1 {
+        return Err(CommandError::new(ErrorCode::BodyConflict));
+    }
+    if let Some(body_str) = &request.body {
+        builder = builder.body(body_str.clone());
+    }
+    if let Some(path) = &request.body_file {
+        let (file, len) = multipart::open_body_file(path).await?;
+        // A known length avoids chunked uploads, which some servers refuse
+        builder = builder
+            .header(reqwest::header::CONTENT_LENGTH, len)
+            .body(file);
+        if !request
+            .headers
+            .keys()
+            .any(|name| name.eq_ignore_ascii_case("content-type"))
+        {
+            builder = builder.header(
+                reqwest::header::CONTENT_TYPE,
+                multipart::guess_content_type(path),
+            );
+        }
+    }
+    if let Some(parts) = &request.multipart {
+        builder = builder.multipart(multipart::build_form(parts).await?);
     }
+    // Created up front so an unwritable path fails before the request is sent
+    let mut download = match &request.download_to {
+        Some(path) => Some(body::Download::create(path).await?),
+        None => None,
+    };
 
+    let phases = timing::Recorder::default();
+    let chain = RedirectChain::new(redirect);
     let start = std::time::Instant::now();
-    let response = request
-        .send()
+    let mut response = chain
+        .scope(phases.scope(builder.send()))
         .await
-        .map_err(|e| format!("Request failed: {e}"))?;
-    let duration_ms = start.elapsed().as_millis() as u64;
+        .map_err(|e| transport_error(&e))?;
+    let headers_received = start.elapsed();
 
+    let http_version = HttpVersion::from(response.version());
     let status = response.status();
     let status_code = status.as_u16();
     let status_text = status.canonical_reason().unwrap_or("Unknown").to_string();
@@ -186,74 +624,323 @@ pub async fn execute_api_request(
     }
 
     // OWASP A04:2025 – Insecure Design: enforce a 10MB response limit to prevent
-    // memory exhaustion from unexpectedly large responses
-    let body_bytes = response
-        .bytes()
-        .await
-        .map_err(|e| format!("Failed to read body: {e}"))?;
+    // memory exhaustion from unexpectedly large responses. Checked per chunk,
+    // so an oversized body is abandoned as soon as it crosses the limit.
+    // Downloads go to disk as they arrive and are not limited.
     const MAX_BODY_BYTES: usize = 10 * 1024 * 1024; // 10 MB
-    if body_bytes.len() > MAX_BODY_BYTES {
-        return Err("Response body exceeds 10MB limit.".to_string());
+    let total_bytes = response.content_length();
+    let transfer = |received: usize| Transfer {
+        received_bytes: received as u64,
+        total_bytes,
+        elapsed_ms: start.elapsed().as_millis() as u64,
+    };
+    let mut body_bytes = Vec::new();
+    let mut received = 0;
+    let mut last_progress = std::time::Instant::now();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| CommandError::new(ErrorCode::BodyReadFailed).detail(e))?
+    {
+        received += chunk.len();
+        match &mut download {
+            Some(download) => download.write(&chunk).await?,
+            None => {
+                body_bytes.extend_from_slice(&chunk);
+                if body_bytes.len() > MAX_BODY_BYTES {
+                    return Err(CommandError::new(ErrorCode::BodyTooLarge).arg("limit", "10MB"));
+                }
+            }
+        }
+        if last_progress.elapsed() >= PROGRESS_INTERVAL {
+            on_progress(transfer(received));
+            last_progress = std::time::Instant::now();
+        }
     }
+    on_progress(transfer(received));
+    let timing = phases.timing(headers_received, start.elapsed());
+
+    let content_type = response_headers.get("content-type").map(String::as_str);
+    let decoded = match download {
+        Some(download) => body::DecodedBody {
+            body: String::new(),
+            encoding: BodyEncoding::File,
+            path: Some(download.finish().await?),
+            content_type: content_type.map(str::to_string),
+        },
+        None => body::decode_body(&body_bytes, content_type)?,
+    };
 
-    let body_str = String::from_utf8_lossy(&body_bytes).into_owned();
+    let trust = clients.trust(jar.map(|jar| jar.workspace_id()));
+    let redirects = chain.redirects();
+    let locations: Vec<url::Url> = redirects
+        .iter()
+        .filter_map(|redirect| url::Url::parse(&redirect.location).ok())
+        .collect();
+    let tls_unverified_hosts =
+        trust.unverified_hosts(std::iter::once(&parsed_url).chain(&locations));
+    let certificates = certificates::peer_chain(locations.last().unwrap_or(&parsed_url));
+    let tls = if tls_options.inspect && parsed_url.scheme() == "https" {
+        tls::inspect_connection(&parsed_url, &tls_options, &trust)
+            .await
+            .ok()
+    } else {
+        None
+    };
 
     Ok(ApiResponse {
         status: status_code,
         status_text,
         headers: response_headers,
-        body: body_str,
-        duration_ms,
+        body: decoded.body,
+        body_encoding: decoded.encoding,
+        body_path: decoded.path,
+        content_type: decoded.content_type,
+        duration_ms: headers_received.as_millis() as u64,
+        http_version,
+        timing,
+        redirects,
+        tls,
+        tls_unverified_hosts,
+        certificates,
     })
 }
 
+// ─── Exit ────────────────────────────────────────────────────────────────────
+
+/// Write what is only kept in memory before the app exits.
+pub fn on_exit(app: &tauri::AppHandle) {
+    let _ = drift::save(app);
+    let _ = coverage::save(app);
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// Execute an HTTP API request on behalf of the frontend.
+/// This replaces the web app's /api/execute-request server route. The
+/// fields named below are those of `request`.
+///
+/// With a `request_id`, body download progress is emitted as
+/// `DOWNLOAD_PROGRESS_EVENT` so the frontend can show a progress bar, and the
+/// request can be aborted with `cancel_request`.
+///
+/// `connect_timeout_ms` and `request_timeout_ms` override the client defaults
+/// (see `set_http_client_defaults`) for slow endpoints or fast failure.
+/// `multipart` sends text fields and files from disk as form data, and
+/// `body_file` streams a file from disk as the body. `download_to` streams
+/// the response body to a file instead, for exports and archives beyond the
+/// 10MB limit on returned bodies.
+///
+/// With a `workspace_id`, the request uses that workspace's cookie jar:
+/// cookies the server sets are kept and sent with later requests (see
+/// `list_cookies`), and hosts on its bypass list skip the proxy (see
+/// `set_proxy_settings`).
+///
+/// `redirect` turns redirect following off, changes the hop limit, or stops
+/// at 307/308 redirects rather than sending the body again; redirects that
+/// are not followed are returned as the response.
+///
+/// `tls` narrows the TLS versions allowed and, with a `client_identity`,
+/// presents a client certificate to servers that require mutual TLS.
+/// Servers are verified against the built-in roots and the workspace's CA
+/// certificates (see `set_trusted_certificates`).
+///
+/// `http_version` forces HTTP/1.1 (`http1_only`), HTTP/2 without
+/// negotiation (`http2_prior_knowledge`) or HTTP/3 over QUIC (`http3`, in
+/// builds with the `http3` feature); the version used is reported in the
+/// response either way.
+///
+/// With an `operation`, headers the request leaves unset default to the
+/// spec's (`Accept`, and `Content-Type` for requests with a body), and the
+/// operation's credentials are sent as its security requirements ask (see
+/// `get_request_defaults`).
+///
+/// `auth` sends Basic credentials upfront, Digest credentials once the
+/// server challenges the request with a `401`, an OAuth 1.0a signature
+/// over the method, URL and form parameters, or an AWS SigV4 signature for
+/// AWS APIs and IAM-protected API Gateway endpoints; an `Authorization`
+/// header the request sets itself takes precedence.
+///
+/// `oauth_profile` sends the token obtained with `request_oauth_token` for
+/// that profile as the `Authorization` header, refreshed first when it is
+/// about to expire. Like `auth`, it yields to an `Authorization` header the
+/// request sets itself.
+///
+/// Responses with `Deprecation` or `Sunset` headers are kept for
+/// `deprecation_report`, with a system notification the first time an
+/// endpoint announces its deprecation. What cannot be kept is reported with
+/// `KEEP_FAILED_EVENT` rather than failing the request.
+#[tauri::command]
+pub async fn execute_api_request(
+    webview: tauri::Webview,
+    clients: tauri::State<'_, HttpClients>,
+    in_flight: tauri::State<'_, InFlightRequests>,
+    jars: tauri::State<'_, CookieJars>,
+    oauth: tauri::State<'_, OauthTokens>,
+    mut request: ApiRequest,
+) -> Result<ApiResponse, CommandError> {
+    ensure_main_window(webview.label())?;
+
+    let authorized = request
+        .headers
+        .keys()
+        .any(|name| name.eq_ignore_ascii_case("authorization"));
+    if let (Some(profile), false) = (&request.oauth_profile, authorized) {
+        let authorization = oauth.authorization(&clients, profile).await?;
+        request
+            .headers
+            .insert("Authorization".to_string(), authorization);
+    }
+
+    let jar = match &request.workspace_id {
+        Some(workspace_id) => {
+            let path = cookies::cookies_path(webview.app_handle())?;
+            let jar = jars.jar(&path, workspace_id)?;
+            Some((path, jar))
+        }
+        None => None,
+    };
+
+    let request_id = request.request_id.clone();
+    let cancelled = request_id.as_deref().map(|id| in_flight.register(id));
+    let sent = send_api_request_with_progress(
+        &clients,
+        &request,
+        jar.as_ref().map(|(_, jar)| jar),
+        |transfer| {
+            if let Some(request_id) = &request_id {
+                let _ = webview.emit_to(
+                    MAIN_WINDOW,
+                    DOWNLOAD_PROGRESS_EVENT,
+                    DownloadProgress {
+                        request_id: request_id.clone(),
+                        transfer,
+                    },
+                );
+            }
+        },
+    );
+    let result = match cancelled {
+        Some(cancelled) => tokio::select! {
+            result = sent => result,
+            // Dropping the request future aborts the connection
+            Ok(()) = cancelled => Err(CommandError::new(ErrorCode::Cancelled)),
+        },
+        None => sent.await,
+    };
+
+    if let Some(request_id) = &request_id {
+        in_flight.finish(request_id);
+    }
+    // The response is already in hand; failing to keep what it showed
+    // (cookies, deprecations, shapes, coverage) is no reason to fail the
+    // request, so failures are only reported to the main window
+    let mut kept = Vec::new();
+    if let Some((path, jar)) = &jar {
+        kept.push(cookies::save_if_changed(path, jar));
+    }
+    if let Ok(response) = &result {
+        let app = webview.app_handle();
+        let ApiRequest {
+            method,
+            url,
+            headers,
+            ..
+        } = &request;
+        kept.push(deprecations::observe(app, method, url, &response.headers));
+        kept.push(drift::observe(app, method, url, response).await);
+        kept.push(coverage::observe(app, method, url, headers, response.status).await);
+    }
+    for error in kept.into_iter().filter_map(Result::err) {
+        let _ = webview.emit_to(
+            MAIN_WINDOW,
+            KEEP_FAILED_EVENT,
+            KeepFailure {
+                request_id: request_id.clone(),
+                error,
+            },
+        );
+    }
+    result
+}
+
+/// Abort an in-flight `execute_api_request` call started with `request_id`.
+/// The call rejects with a `cancelled` error; returns `false` when no such
+/// request is running.
+#[tauri::command]
+pub fn cancel_request(
+    webview: tauri::Webview,
+    in_flight: tauri::State<'_, InFlightRequests>,
+    request_id: String,
+) -> Result<bool, CommandError> {
+    ensure_main_window(webview.label())?;
+
+    Ok(in_flight.cancel(&request_id))
+}
+
 /// Fetch a remote OpenAPI specification by URL.
 /// This replaces the web app's /api/fetch-spec server route.
 ///
+/// With a `workspace_id`, the download is routed and verified with that
+/// workspace's bypass list and CA certificates.
+///
 /// OWASP A09:2025 – SSRF: URL is validated before fetching.
 #[tauri::command]
-pub async fn fetch_spec(url: String) -> Result<String, String> {
+pub async fn fetch_spec(
+    webview: tauri::Webview,
+    clients: tauri::State<'_, HttpClients>,
+    url: String,
+    workspace_id: Option<String>,
+) -> Result<String, CommandError> {
+    ensure_main_window(webview.label())?;
+
+    fetch_spec_text(&clients, &url, workspace_id.as_deref()).await
+}
+
+/// Body of `fetch_spec`, shared with the stdio automation interface.
+pub(crate) async fn fetch_spec_text(
+    clients: &HttpClients,
+    url: &str,
+    workspace_id: Option<&str>,
+) -> Result<String, CommandError> {
     // OWASP A09:2025 – SSRF: validate URL before fetching
-    let parsed_url = validate_url(&url)?;
-
-    let client = reqwest::Client::builder()
-        .redirect(reqwest::redirect::Policy::limited(3))
-        // OWASP A05:2025 – Cryptographic Failures: enforce TLS via rustls
-        .use_rustls_tls()
-        .timeout(std::time::Duration::from_secs(15))
-        .build()
-        .map_err(|e| format!("Failed to build HTTP client: {e}"))?;
-
-    let response = client
-        .get(parsed_url)
-        // Only request YAML/JSON content types for spec files
-        .header(
-            "Accept",
-            "application/json, application/yaml, text/yaml, text/plain, */*",
-        )
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch spec: {e}"))?;
+    let parsed_url = validate_url(url)?;
+
+    let response = send_spec_request(
+        clients,
+        HttpClients::spec_client,
+        &parsed_url,
+        workspace_id,
+        |client| {
+            client
+                .get(parsed_url.clone())
+                // Only request YAML/JSON content types for spec files
+                .header(
+                    "Accept",
+                    "application/json, application/yaml, text/yaml, text/plain, */*",
+                )
+        },
+    )
+    .await?;
 
-    if !response.status().is_success() {
-        return Err(format!(
-            "Failed to fetch spec: HTTP {}",
-            response.status().as_u16()
-        ));
+    let status = response.status();
+    if !status.is_success() {
+        return Err(CommandError::new(ErrorCode::HttpStatus)
+            .arg("status", status.as_u16())
+            .retryable(is_retryable_status(status)));
     }
 
     // OWASP A04:2025 – Insecure Design: enforce 5MB limit for spec files
     let body_bytes = response
         .bytes()
         .await
-        .map_err(|e| format!("Failed to read spec: {e}"))?;
+        .map_err(|e| CommandError::new(ErrorCode::BodyReadFailed).detail(e))?;
     const MAX_SPEC_BYTES: usize = 5 * 1024 * 1024; // 5 MB
     if body_bytes.len() > MAX_SPEC_BYTES {
-        return Err("Spec file exceeds 5MB limit.".to_string());
+        return Err(CommandError::new(ErrorCode::BodyTooLarge).arg("limit", "5MB"));
     }
 
-    String::from_utf8(body_bytes.to_vec())
-        .map_err(|_| "Spec content is not valid UTF-8.".to_string())
+    String::from_utf8(body_bytes.to_vec()).map_err(|_| CommandError::new(ErrorCode::InvalidUtf8))
 }
 
 // ─── Tests ───────────────────────────────────────────────────────────────────
@@ -334,6 +1021,55 @@ mod tests {
         assert!(validate_url("").is_err());
     }
 
+    #[test]
+    fn test_validate_url_reports_stable_codes() {
+        let code = |url: &str| validate_url(url).unwrap_err().code;
+        assert_eq!(code("not-a-url"), ErrorCode::InvalidUrl);
+        assert_eq!(code("ftp://example.com/file"), ErrorCode::DisallowedScheme);
+        assert_eq!(code("http://169.254.169.254/"), ErrorCode::BlockedHost);
+        assert_eq!(code("http://10.0.0.1/"), ErrorCode::BlockedIp);
+        assert_eq!(code("http://example.com:22/"), ErrorCode::BlockedPort);
+    }
+
+    #[test]
+    fn test_validate_method_normalizes_standard_methods() {
+        assert_eq!(validate_method("get", false).unwrap(), reqwest::Method::GET);
+        assert_eq!(
+            validate_method("Patch", true).unwrap(),
+            reqwest::Method::PATCH
+        );
+    }
+
+    #[test]
+    fn test_validate_method_requires_flag_for_extended_methods() {
+        let err = validate_method("PROPFIND", false).unwrap_err();
+        assert_eq!(err.code, ErrorCode::DisallowedMethod);
+        assert_eq!(
+            validate_method("PROPFIND", true).unwrap().as_str(),
+            "PROPFIND"
+        );
+        assert_eq!(validate_method("Purge", true).unwrap().as_str(), "Purge");
+    }
+
+    #[test]
+    fn test_validate_method_rejects_connect_and_invalid_tokens() {
+        assert!(validate_method("CONNECT", true).is_err());
+        assert!(validate_method("connect", true).is_err());
+        assert!(validate_method("BAD METHOD", true).is_err());
+        assert!(validate_method("", true).is_err());
+    }
+
+    #[test]
+    fn test_ensure_main_window_allows_main() {
+        assert!(ensure_main_window(MAIN_WINDOW).is_ok());
+    }
+
+    #[test]
+    fn test_ensure_main_window_rejects_auxiliary_webviews() {
+        let err = ensure_main_window("splashscreen").unwrap_err();
+        assert_eq!(err.code, ErrorCode::WindowNotAllowed);
+    }
+
     #[test]
     fn test_check_ip_allows_public() {
         let ip: IpAddr = "8.8.8.8".parse().unwrap();
@@ -351,4 +1087,49 @@ mod tests {
         let ip: IpAddr = "169.254.1.1".parse().unwrap();
         assert!(check_ip_allowed(&ip).is_err());
     }
+
+    #[test]
+    fn test_download_progress_payload_is_flat() {
+        let progress = DownloadProgress {
+            request_id: "req-1".to_string(),
+            transfer: Transfer {
+                received_bytes: 512,
+                total_bytes: None,
+                elapsed_ms: 40,
+            },
+        };
+        assert_eq!(
+            serde_json::to_value(progress).unwrap(),
+            serde_json::json!({
+                "request_id": "req-1",
+                "received_bytes": 512,
+                "total_bytes": null,
+                "elapsed_ms": 40,
+            })
+        );
+    }
+
+    #[test]
+    fn test_in_flight_requests_cancel_the_newest_registration() {
+        let in_flight = InFlightRequests::default();
+        let mut first = in_flight.register("req-1");
+        let mut second = in_flight.register("req-1");
+        // The replaced sender is dropped, which is not a cancellation
+        assert!(first.try_recv().is_err());
+
+        assert!(in_flight.cancel("req-1"));
+        assert_eq!(second.try_recv(), Ok(()));
+        assert!(!in_flight.cancel("req-1"));
+        assert!(!in_flight.cancel("unknown"));
+    }
+
+    #[test]
+    fn test_in_flight_finish_keeps_a_newer_request() {
+        let in_flight = InFlightRequests::default();
+        let first = in_flight.register("req-1");
+        let _second = in_flight.register("req-1");
+        drop(first);
+        in_flight.finish("req-1");
+        assert!(in_flight.cancel("req-1"));
+    }
 }
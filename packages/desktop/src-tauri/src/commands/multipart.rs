@@ -0,0 +1,222 @@
+use std::fmt::Display;
+use std::path::Path;
+
+use reqwest::multipart::{Form, Part};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{CommandError, ErrorCode};
+
+/// OWASP A04:2025 – Insecure Design: attachments are read into memory, so
+/// each file is capped.
+const MAX_FILE_BYTES: u64 = 50 * 1024 * 1024;
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+/// One part of a `multipart/form-data` request body.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FormPart {
+    Text {
+        name: String,
+        value: String,
+        #[serde(default)]
+        content_type: Option<String>,
+    },
+    /// A file from disk, chosen by the user in a file dialog.
+    File {
+        name: String,
+        /// Absolute path to the file.
+        path: String,
+        /// Defaults to the file's own name.
+        #[serde(default)]
+        filename: Option<String>,
+        /// Defaults to a type guessed from the extension.
+        #[serde(default)]
+        content_type: Option<String>,
+    },
+}
+
+// ─── Files ───────────────────────────────────────────────────────────────────
+
+fn unreadable(path: &str, detail: impl Display) -> CommandError {
+    CommandError::new(ErrorCode::FileUnreadable)
+        .arg("path", path)
+        .detail(detail)
+}
+
+/// Size of a user-chosen file, which must be absolute and regular.
+async fn file_len(path: &str) -> Result<u64, CommandError> {
+    if !Path::new(path).is_absolute() {
+        return Err(unreadable(path, "the path must be absolute"));
+    }
+    let metadata = tokio::fs::metadata(path)
+        .await
+        .map_err(|e| unreadable(path, e))?;
+    if !metadata.is_file() {
+        return Err(unreadable(path, "not a regular file"));
+    }
+    Ok(metadata.len())
+}
+
+async fn read_file(path: &str) -> Result<Vec<u8>, CommandError> {
+    if file_len(path).await? > MAX_FILE_BYTES {
+        return Err(CommandError::new(ErrorCode::FileTooLarge)
+            .arg("path", path)
+            .arg("limit", "50MB"));
+    }
+    tokio::fs::read(path).await.map_err(|e| unreadable(path, e))
+}
+
+/// Open a file to stream as the whole request body, with its length. It is
+/// never held in memory, so unlike attachments it has no size cap.
+pub(super) async fn open_body_file(path: &str) -> Result<(tokio::fs::File, u64), CommandError> {
+    let len = file_len(path).await?;
+    let file = tokio::fs::File::open(path)
+        .await
+        .map_err(|e| unreadable(path, e))?;
+    Ok((file, len))
+}
+
+pub(super) fn guess_content_type(path: &str) -> &'static str {
+    let extension = Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    match extension.as_str() {
+        "json" => "application/json",
+        "xml" => "application/xml",
+        "txt" => "text/plain",
+        "csv" => "text/csv",
+        "html" | "htm" => "text/html",
+        "yaml" | "yml" => "application/yaml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        _ => "application/octet-stream",
+    }
+}
+
+// ─── Form ────────────────────────────────────────────────────────────────────
+
+fn with_type(part: Part, content_type: Option<&str>) -> Result<Part, CommandError> {
+    match content_type {
+        Some(content_type) => part.mime_str(content_type).map_err(|_| {
+            CommandError::new(ErrorCode::InvalidHeaderValue).arg("name", "Content-Type")
+        }),
+        None => Ok(part),
+    }
+}
+
+/// Build the form, reading attached files. reqwest generates the boundary
+/// and the request's `Content-Type`.
+pub(super) async fn build_form(parts: &[FormPart]) -> Result<Form, CommandError> {
+    let mut form = Form::new();
+    for part in parts {
+        let (name, part) = match part {
+            FormPart::Text {
+                name,
+                value,
+                content_type,
+            } => (
+                name,
+                with_type(Part::text(value.clone()), content_type.as_deref())?,
+            ),
+            FormPart::File {
+                name,
+                path,
+                filename,
+                content_type,
+            } => {
+                let filename = filename
+                    .clone()
+                    .or_else(|| {
+                        Path::new(path)
+                            .file_name()
+                            .map(|name| name.to_string_lossy().into_owned())
+                    })
+                    .unwrap_or_else(|| "file".to_string());
+                let content_type = content_type
+                    .as_deref()
+                    .unwrap_or_else(|| guess_content_type(path));
+                let part = Part::bytes(read_file(path).await?).file_name(filename);
+                (name, with_type(part, Some(content_type))?)
+            }
+        };
+        form = form.part(name.clone(), part);
+    }
+    Ok(form)
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_read_file_rejects_relative_paths_and_directories() {
+        let err = read_file("spec.json").await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::FileUnreadable);
+
+        let dir = std::env::temp_dir();
+        let err = read_file(&dir.to_string_lossy()).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::FileUnreadable);
+
+        let file = dir.join(format!("yasp-multipart-{}.txt", std::process::id()));
+        std::fs::write(&file, b"hello").unwrap();
+        assert_eq!(read_file(&file.to_string_lossy()).await.unwrap(), b"hello");
+        let _ = std::fs::remove_file(file);
+    }
+
+    #[tokio::test]
+    async fn test_open_body_file_reports_length() {
+        let file = std::env::temp_dir().join(format!("yasp-body-{}.json", std::process::id()));
+        std::fs::write(&file, b"[1,2,3]").unwrap();
+        let (_, len) = open_body_file(&file.to_string_lossy()).await.unwrap();
+        assert_eq!(len, 7);
+        let _ = std::fs::remove_file(file);
+
+        let err = open_body_file("body.json").await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::FileUnreadable);
+    }
+
+    #[test]
+    fn test_guess_content_type_from_extension() {
+        assert_eq!(guess_content_type("/tmp/avatar.PNG"), "image/png");
+        assert_eq!(guess_content_type("/tmp/data"), "application/octet-stream");
+    }
+
+    #[tokio::test]
+    async fn test_build_form_reads_files_and_rejects_bad_types() {
+        let file = std::env::temp_dir().join(format!("yasp-form-{}.csv", std::process::id()));
+        std::fs::write(&file, b"a,b\n1,2\n").unwrap();
+        let parts = vec![
+            FormPart::Text {
+                name: "title".to_string(),
+                value: "Report".to_string(),
+                content_type: None,
+            },
+            FormPart::File {
+                name: "upload".to_string(),
+                path: file.to_string_lossy().into_owned(),
+                filename: None,
+                content_type: None,
+            },
+        ];
+        assert!(build_form(&parts).await.is_ok());
+
+        let bad_type = [FormPart::Text {
+            name: "x".to_string(),
+            value: "y".to_string(),
+            content_type: Some("not a type".to_string()),
+        }];
+        let err = build_form(&bad_type).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidHeaderValue);
+        let _ = std::fs::remove_file(file);
+    }
+}
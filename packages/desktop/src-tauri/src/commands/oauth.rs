@@ -0,0 +1,684 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use base64::Engine;
+use ring::digest;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+use tauri_plugin_opener::OpenerExt;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+use super::ensure_main_window;
+use super::http::HttpClients;
+use super::{send_spec_request, validate_url};
+use crate::error::{CommandError, ErrorCode};
+
+/// How long the user has to finish signing in in the browser.
+const CALLBACK_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Tokens this close to expiry are refreshed before they are sent.
+const EXPIRY_MARGIN_SECS: u64 = 30;
+
+const CALLBACK_PATH: &str = "/callback";
+
+const CALLBACK_PAGE: &str = "<!doctype html><title>YASP</title>\
+    <p>Signed in. You can close this window and return to YASP.</p>";
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+/// How a token is obtained.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OauthGrant {
+    /// Sign in through the system browser. The code comes back to a
+    /// loopback listener (RFC 8252) and is exchanged with a PKCE verifier
+    /// (RFC 7636).
+    AuthorizationCode {
+        authorization_url: String,
+        /// Port of the `http://127.0.0.1:<port>/callback` redirect URI, for
+        /// providers that only accept registered redirect URIs; any free
+        /// port when unset.
+        #[serde(default)]
+        redirect_port: Option<u16>,
+    },
+    /// A token for the client itself, without a user.
+    ClientCredentials,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct OauthConfig {
+    pub grant: OauthGrant,
+    pub token_url: String,
+    pub client_id: String,
+    /// Omitted for public clients, which rely on PKCE alone.
+    #[serde(default)]
+    pub client_secret: Option<String>,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    /// Sent with the authorization and token requests, e.g. `audience`.
+    #[serde(default)]
+    pub extra_params: BTreeMap<String, String>,
+}
+
+// Never print the secret, even in debug logs.
+impl fmt::Debug for OauthConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OauthConfig")
+            .field("grant", &self.grant)
+            .field("token_url", &self.token_url)
+            .field("client_id", &self.client_id)
+            .field("scopes", &self.scopes)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Clone)]
+struct Token {
+    access_token: String,
+    token_type: String,
+    /// Unix time in seconds; none when the server did not say.
+    expires_at: Option<u64>,
+    refresh_token: Option<String>,
+    scope: Option<String>,
+}
+
+impl fmt::Debug for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Token")
+            .field("token_type", &self.token_type)
+            .field("expires_at", &self.expires_at)
+            .field("scope", &self.scope)
+            .finish_non_exhaustive()
+    }
+}
+
+/// What the frontend sees of a token. The token itself never leaves the
+/// backend; requests name the profile to send it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OauthTokenInfo {
+    pub profile: String,
+    pub token_type: String,
+    /// Unix time in seconds.
+    pub expires_at: Option<u64>,
+    /// Scopes granted, when they differ from those asked for.
+    pub scope: Option<String>,
+    /// Whether the token can be refreshed without signing in again.
+    pub refreshable: bool,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    token_type: Option<String>,
+    #[serde(default)]
+    expires_in: Option<u64>,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    #[serde(default)]
+    scope: Option<String>,
+}
+
+/// Tokens by profile name, with the configuration that obtained them so
+/// they can be refreshed. Held in memory only: signing in again after a
+/// restart is the price of never writing tokens to disk.
+#[derive(Default)]
+pub struct OauthTokens {
+    profiles: Mutex<HashMap<String, (OauthConfig, Token)>>,
+}
+
+// ─── PKCE ────────────────────────────────────────────────────────────────────
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// `bytes` random bytes, base64url-encoded.
+fn random_string(bytes: usize) -> Result<String, CommandError> {
+    let mut buf = vec![0u8; bytes];
+    SystemRandom::new()
+        .fill(&mut buf)
+        .map_err(|_| CommandError::new(ErrorCode::OauthFailed).arg("error", "no randomness"))?;
+    Ok(base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(buf))
+}
+
+/// The S256 code challenge for `verifier`.
+fn code_challenge(verifier: &str) -> String {
+    base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .encode(digest::digest(&digest::SHA256, verifier.as_bytes()))
+}
+
+fn authorization_request(
+    config: &OauthConfig,
+    authorization_url: &str,
+    redirect_uri: &str,
+    state: &str,
+    verifier: &str,
+) -> Result<url::Url, CommandError> {
+    let mut url = validate_url(authorization_url)?;
+    {
+        let mut query = url.query_pairs_mut();
+        query
+            .append_pair("response_type", "code")
+            .append_pair("client_id", &config.client_id)
+            .append_pair("redirect_uri", redirect_uri)
+            .append_pair("state", state)
+            .append_pair("code_challenge", &code_challenge(verifier))
+            .append_pair("code_challenge_method", "S256");
+        if !config.scopes.is_empty() {
+            query.append_pair("scope", &config.scopes.join(" "));
+        }
+        for (name, value) in &config.extra_params {
+            query.append_pair(name, value);
+        }
+    }
+    Ok(url)
+}
+
+// ─── Loopback Callback ───────────────────────────────────────────────────────
+
+/// The query parameters of a request to the callback path, from the
+/// request line (`GET /callback?code=…&state=… HTTP/1.1`).
+fn callback_params(request: &str) -> Option<HashMap<String, String>> {
+    let target = request
+        .lines()
+        .next()?
+        .strip_prefix("GET ")?
+        .split(' ')
+        .next()?;
+    let url = url::Url::parse(&format!("http://127.0.0.1{target}")).ok()?;
+    (url.path() == CALLBACK_PATH).then(|| url.query_pairs().into_owned().collect())
+}
+
+/// Wait for the browser to come back with the authorization code. Other
+/// requests (a favicon, a stray tab) are answered with a 404 and ignored.
+async fn receive_code(listener: TcpListener, state: &str) -> Result<String, CommandError> {
+    loop {
+        let (mut stream, _) = listener
+            .accept()
+            .await
+            .map_err(|e| CommandError::new(ErrorCode::OauthFailed).arg("error", e.to_string()))?;
+        let mut buf = vec![0u8; 8192];
+        let n = stream.read(&mut buf).await.unwrap_or(0);
+        let Some(params) = callback_params(&String::from_utf8_lossy(&buf[..n])) else {
+            let _ = stream
+                .write_all(
+                    b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                )
+                .await;
+            continue;
+        };
+        let _ = stream
+            .write_all(
+                format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{CALLBACK_PAGE}",
+                    CALLBACK_PAGE.len()
+                )
+                .as_bytes(),
+            )
+            .await;
+
+        let failed = |error: &str| CommandError::new(ErrorCode::OauthFailed).arg("error", error);
+        if let Some(error) = params.get("error") {
+            let description = params.get("error_description").cloned().unwrap_or_default();
+            return Err(failed(error).detail(description));
+        }
+        // OWASP A01:2025 – Broken Access Control: a callback without our
+        // state was not started by us (RFC 6749 §10.12)
+        if params.get("state").map(String::as_str) != Some(state) {
+            return Err(failed("state_mismatch"));
+        }
+        return params
+            .get("code")
+            .cloned()
+            .ok_or_else(|| failed("missing_code"));
+    }
+}
+
+// ─── Token Endpoint ──────────────────────────────────────────────────────────
+
+/// A token from a token endpoint answer; `refresh_token` is kept when a
+/// refresh does not rotate it.
+fn parse_token_response(
+    status: u16,
+    body: &str,
+    refresh_token: Option<String>,
+    now: u64,
+) -> Result<Token, CommandError> {
+    if !(200..300).contains(&status) {
+        let error: serde_json::Value = serde_json::from_str(body).unwrap_or_default();
+        return Err(CommandError::new(ErrorCode::OauthFailed)
+            .arg(
+                "error",
+                error["error"]
+                    .as_str()
+                    .map_or_else(|| format!("HTTP {status}"), str::to_string),
+            )
+            .detail(error["error_description"].as_str().unwrap_or(body)));
+    }
+    let response: TokenResponse = serde_json::from_str(body)
+        .map_err(|e| CommandError::new(ErrorCode::InvalidResponse).detail(e))?;
+    let token_type = match response.token_type {
+        Some(token_type) if !token_type.eq_ignore_ascii_case("bearer") => token_type,
+        _ => "Bearer".to_string(),
+    };
+    Ok(Token {
+        access_token: response.access_token,
+        token_type,
+        expires_at: response.expires_in.map(|secs| now + secs),
+        refresh_token: response.refresh_token.or(refresh_token),
+        scope: response.scope,
+    })
+}
+
+/// POST `params` with the client's credentials to the token endpoint. Token
+/// calls go through the spec download client, routed, trusting certificates
+/// and answering proxy challenges as requests do. Redirects are not
+/// followed, so the secrets in the body never reach another host; a 3xx
+/// fails like any other non-2xx answer.
+async fn token_request(
+    clients: &HttpClients,
+    config: &OauthConfig,
+    params: &[(&str, &str)],
+    refresh_token: Option<String>,
+) -> Result<Token, CommandError> {
+    let url = validate_url(&config.token_url)?;
+    // The serializer is not `Send`, so it must be gone before the await
+    let form = {
+        let mut form = url::form_urlencoded::Serializer::new(String::new());
+        form.extend_pairs(params)
+            .append_pair("client_id", &config.client_id);
+        if let Some(secret) = &config.client_secret {
+            form.append_pair("client_secret", secret);
+        }
+        form.extend_pairs(&config.extra_params);
+        form.finish()
+    };
+    let response = send_spec_request(
+        clients,
+        HttpClients::spec_client_without_redirects,
+        &url,
+        None,
+        |client| {
+            client
+                .post(url.clone())
+                .header("Content-Type", "application/x-www-form-urlencoded")
+                .header("Accept", "application/json")
+                .body(form.clone())
+        },
+    )
+    .await?;
+    let status = response.status().as_u16();
+    let body = response
+        .text()
+        .await
+        .map_err(|e| CommandError::new(ErrorCode::BodyReadFailed).detail(e))?;
+    parse_token_response(status, &body, refresh_token, now_secs())
+}
+
+async fn authorize(
+    app: &AppHandle,
+    clients: &HttpClients,
+    config: &OauthConfig,
+    authorization_url: &str,
+    redirect_port: Option<u16>,
+) -> Result<Token, CommandError> {
+    let listener = TcpListener::bind(("127.0.0.1", redirect_port.unwrap_or(0)))
+        .await
+        .map_err(|e| CommandError::new(ErrorCode::OauthFailed).arg("error", e.to_string()))?;
+    let port = listener
+        .local_addr()
+        .map_err(|e| CommandError::new(ErrorCode::OauthFailed).arg("error", e.to_string()))?
+        .port();
+    let redirect_uri = format!("http://127.0.0.1:{port}{CALLBACK_PATH}");
+    let state = random_string(16)?;
+    let verifier = random_string(32)?;
+    let url = authorization_request(config, authorization_url, &redirect_uri, &state, &verifier)?;
+
+    app.opener()
+        .open_url(url.as_str(), None::<&str>)
+        .map_err(|e| {
+            CommandError::new(ErrorCode::ToolUnavailable)
+                .arg("tool", "browser")
+                .detail(e)
+        })?;
+    let code = tokio::time::timeout(CALLBACK_TIMEOUT, receive_code(listener, &state))
+        .await
+        .map_err(|_| CommandError::new(ErrorCode::Timeout))??;
+
+    let scope = config.scopes.join(" ");
+    let mut params = vec![
+        ("grant_type", "authorization_code"),
+        ("code", code.as_str()),
+        ("redirect_uri", redirect_uri.as_str()),
+        ("code_verifier", verifier.as_str()),
+    ];
+    if !scope.is_empty() {
+        params.push(("scope", &scope));
+    }
+    token_request(clients, config, &params, None).await
+}
+
+// ─── Token Store ─────────────────────────────────────────────────────────────
+
+fn info(profile: &str, token: &Token) -> OauthTokenInfo {
+    OauthTokenInfo {
+        profile: profile.to_string(),
+        token_type: token.token_type.clone(),
+        expires_at: token.expires_at,
+        scope: token.scope.clone(),
+        refreshable: token.refresh_token.is_some(),
+    }
+}
+
+impl OauthTokens {
+    fn store(&self, profile: &str, config: OauthConfig, token: Token) -> OauthTokenInfo {
+        let info = info(profile, &token);
+        self.profiles
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(profile.to_string(), (config, token));
+        info
+    }
+
+    fn get(&self, profile: &str) -> Result<(OauthConfig, Token), CommandError> {
+        self.profiles
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(profile)
+            .cloned()
+            .ok_or_else(|| CommandError::new(ErrorCode::OauthTokenNotFound).arg("profile", profile))
+    }
+
+    /// Exchange the refresh token of `profile` for a new token, or obtain a
+    /// new one outright for client credentials, which need no user.
+    async fn refresh(
+        &self,
+        clients: &HttpClients,
+        profile: &str,
+    ) -> Result<OauthTokenInfo, CommandError> {
+        let (config, token) = self.get(profile)?;
+        let scope = config.scopes.join(" ");
+        let token = match (&token.refresh_token, &config.grant) {
+            (Some(refresh_token), _) => {
+                let params = [
+                    ("grant_type", "refresh_token"),
+                    ("refresh_token", refresh_token.as_str()),
+                ];
+                token_request(clients, &config, &params, token.refresh_token.clone()).await?
+            }
+            (None, OauthGrant::ClientCredentials) => {
+                client_credentials(clients, &config, &scope).await?
+            }
+            (None, OauthGrant::AuthorizationCode { .. }) => {
+                return Err(CommandError::new(ErrorCode::OauthTokenExpired).arg("profile", profile));
+            }
+        };
+        Ok(self.store(profile, config, token))
+    }
+
+    /// The `Authorization` value for `profile`, refreshed first when the
+    /// token is about to expire.
+    pub(super) async fn authorization(
+        &self,
+        clients: &HttpClients,
+        profile: &str,
+    ) -> Result<String, CommandError> {
+        let (_, token) = self.get(profile)?;
+        let expiring = token
+            .expires_at
+            .is_some_and(|expires_at| expires_at <= now_secs() + EXPIRY_MARGIN_SECS);
+        if expiring {
+            self.refresh(clients, profile).await?;
+        }
+        let (_, token) = self.get(profile)?;
+        Ok(format!("{} {}", token.token_type, token.access_token))
+    }
+}
+
+async fn client_credentials(
+    clients: &HttpClients,
+    config: &OauthConfig,
+    scope: &str,
+) -> Result<Token, CommandError> {
+    let mut params = vec![("grant_type", "client_credentials")];
+    if !scope.is_empty() {
+        params.push(("scope", scope));
+    }
+    token_request(clients, config, &params, None).await
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// Obtain a token for `profile` with `config`, replacing any it had. The
+/// authorization code grant opens the system browser and waits up to five
+/// minutes for the user to sign in. Pass the profile to
+/// `execute_api_request` as `oauth_profile` to send the token.
+///
+/// OWASP A07:2025 – Identification and Authentication Failures: PKCE and a
+/// `state` check on every authorization code flow; tokens stay in backend
+/// memory and are never returned to the webview.
+#[tauri::command]
+pub async fn request_oauth_token(
+    app: AppHandle,
+    webview: tauri::Webview,
+    clients: State<'_, HttpClients>,
+    tokens: State<'_, OauthTokens>,
+    profile: String,
+    config: OauthConfig,
+) -> Result<OauthTokenInfo, CommandError> {
+    ensure_main_window(webview.label())?;
+
+    let token = match &config.grant {
+        OauthGrant::AuthorizationCode {
+            authorization_url,
+            redirect_port,
+        } => authorize(&app, &clients, &config, authorization_url, *redirect_port).await?,
+        OauthGrant::ClientCredentials => {
+            client_credentials(&clients, &config, &config.scopes.join(" ")).await?
+        }
+    };
+    Ok(tokens.store(&profile, config, token))
+}
+
+/// Renew the token of `profile` ahead of its expiry. Tokens sent with
+/// requests are renewed on their own when they are about to expire.
+#[tauri::command]
+pub async fn refresh_oauth_token(
+    webview: tauri::Webview,
+    clients: State<'_, HttpClients>,
+    tokens: State<'_, OauthTokens>,
+    profile: String,
+) -> Result<OauthTokenInfo, CommandError> {
+    ensure_main_window(webview.label())?;
+
+    tokens.refresh(&clients, &profile).await
+}
+
+/// Profiles holding a token, by name.
+#[tauri::command]
+pub fn list_oauth_tokens(tokens: State<'_, OauthTokens>) -> Vec<OauthTokenInfo> {
+    let profiles = tokens.profiles.lock().unwrap_or_else(|e| e.into_inner());
+    let mut infos: Vec<OauthTokenInfo> = profiles
+        .iter()
+        .map(|(profile, (_, token))| info(profile, token))
+        .collect();
+    infos.sort_by(|a, b| a.profile.cmp(&b.profile));
+    infos
+}
+
+/// Forget the token of `profile`. Returns `false` when it had none.
+#[tauri::command]
+pub fn remove_oauth_token(
+    webview: tauri::Webview,
+    tokens: State<'_, OauthTokens>,
+    profile: String,
+) -> Result<bool, CommandError> {
+    ensure_main_window(webview.label())?;
+
+    Ok(tokens
+        .profiles
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(&profile)
+        .is_some())
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> OauthConfig {
+        OauthConfig {
+            grant: OauthGrant::AuthorizationCode {
+                authorization_url: "https://auth.example.com/authorize?prompt=login".to_string(),
+                redirect_port: None,
+            },
+            token_url: "https://auth.example.com/token".to_string(),
+            client_id: "yasp".to_string(),
+            client_secret: Some("s3cret".to_string()),
+            scopes: vec!["read".to_string(), "write".to_string()],
+            extra_params: BTreeMap::from([("audience".to_string(), "api".to_string())]),
+        }
+    }
+
+    #[test]
+    fn test_code_challenge_matches_rfc_7636_example() {
+        assert_eq!(
+            code_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+        assert_eq!(random_string(32).unwrap().len(), 43);
+        assert!(!format!("{:?}", config()).contains("s3cret"));
+    }
+
+    #[test]
+    fn test_authorization_request_carries_pkce_and_state() {
+        let url = authorization_request(
+            &config(),
+            "https://auth.example.com/authorize?prompt=login",
+            "http://127.0.0.1:8123/callback",
+            "st4te",
+            "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk",
+        )
+        .unwrap();
+        let params: HashMap<String, String> = url.query_pairs().into_owned().collect();
+        assert_eq!(params["prompt"], "login");
+        assert_eq!(params["response_type"], "code");
+        assert_eq!(params["redirect_uri"], "http://127.0.0.1:8123/callback");
+        assert_eq!(params["state"], "st4te");
+        assert_eq!(
+            params["code_challenge"],
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+        assert_eq!(params["code_challenge_method"], "S256");
+        assert_eq!(params["scope"], "read write");
+        assert_eq!(params["audience"], "api");
+    }
+
+    #[tokio::test]
+    async fn test_callback_needs_our_state() {
+        let callback = |query: &'static str| async move {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let port = listener.local_addr().unwrap().port();
+            tokio::spawn(async move {
+                let client = reqwest::Client::new();
+                let base = format!("http://127.0.0.1:{port}");
+                let _ = client.get(format!("{base}/favicon.ico")).send().await;
+                let _ = client.get(format!("{base}/callback?{query}")).send().await;
+            });
+            receive_code(listener, "st4te").await
+        };
+
+        assert_eq!(callback("code=abc&state=st4te").await.unwrap(), "abc");
+        let err = callback("code=abc&state=other").await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::OauthFailed);
+        assert_eq!(err.args["error"], "state_mismatch");
+        let err = callback("error=access_denied&state=st4te")
+            .await
+            .unwrap_err();
+        assert_eq!(err.args["error"], "access_denied");
+    }
+
+    #[test]
+    fn test_token_responses_are_parsed() {
+        let token = parse_token_response(
+            200,
+            r#"{"access_token":"at","token_type":"bearer","expires_in":3600}"#,
+            Some("rt".to_string()),
+            1_000,
+        )
+        .unwrap();
+        assert_eq!(token.token_type, "Bearer");
+        assert_eq!(token.expires_at, Some(4_600));
+        // A refresh that does not rotate the refresh token keeps it
+        assert_eq!(token.refresh_token.as_deref(), Some("rt"));
+
+        let err = parse_token_response(
+            400,
+            r#"{"error":"invalid_grant","error_description":"code expired"}"#,
+            None,
+            1_000,
+        )
+        .unwrap_err();
+        assert_eq!(err.code, ErrorCode::OauthFailed);
+        assert_eq!(err.args["error"], "invalid_grant");
+        let err = parse_token_response(502, "Bad Gateway", None, 1_000).unwrap_err();
+        assert_eq!(err.args["error"], "HTTP 502");
+        // Redirects are not followed, so the secrets never reach their target
+        let err = parse_token_response(307, "", None, 1_000).unwrap_err();
+        assert_eq!(err.args["error"], "HTTP 307");
+    }
+
+    #[tokio::test]
+    async fn test_expired_tokens_without_refresh_need_a_new_sign_in() {
+        let tokens = OauthTokens::default();
+        let clients = HttpClients::default();
+        assert_eq!(
+            tokens
+                .authorization(&clients, "api")
+                .await
+                .unwrap_err()
+                .code,
+            ErrorCode::OauthTokenNotFound
+        );
+
+        let token = Token {
+            access_token: "at".to_string(),
+            token_type: "Bearer".to_string(),
+            expires_at: Some(now_secs() + 3600),
+            refresh_token: None,
+            scope: None,
+        };
+        tokens.store("api", config(), token.clone());
+        assert_eq!(
+            tokens.authorization(&clients, "api").await.unwrap(),
+            "Bearer at"
+        );
+
+        tokens.store(
+            "api",
+            config(),
+            Token {
+                expires_at: Some(now_secs()),
+                ..token
+            },
+        );
+        assert_eq!(
+            tokens
+                .authorization(&clients, "api")
+                .await
+                .unwrap_err()
+                .code,
+            ErrorCode::OauthTokenExpired
+        );
+    }
+}
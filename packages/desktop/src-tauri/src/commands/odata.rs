@@ -0,0 +1,427 @@
+use std::collections::HashMap;
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use super::http::HttpClients;
+use super::{
+    build_header_map, ensure_main_window, is_retryable_status, transport_error, validate_url,
+};
+use crate::error::{CommandError, ErrorCode};
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+/// System query options for an entity set request. Property paths and the
+/// filter expression are OData syntax, written by the user.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ODataQuery {
+    pub filter: Option<String>,
+    #[serde(default)]
+    pub select: Vec<String>,
+    #[serde(default)]
+    pub expand: Vec<String>,
+    #[serde(default)]
+    pub orderby: Vec<ODataOrder>,
+    pub top: Option<u64>,
+    pub skip: Option<u64>,
+    #[serde(default)]
+    pub count: bool,
+    pub search: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ODataOrder {
+    pub property: String,
+    #[serde(default)]
+    pub descending: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ODataProperty {
+    pub name: String,
+    /// `Edm.*` primitive or a qualified complex type name.
+    #[serde(rename = "type")]
+    pub type_name: String,
+    pub nullable: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ODataNavigation {
+    pub name: String,
+    /// Target type (v4), or the target role for v2/v3 associations.
+    pub target: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ODataEntityType {
+    /// Namespace-qualified, as referenced by entity sets.
+    pub name: String,
+    pub keys: Vec<String>,
+    pub properties: Vec<ODataProperty>,
+    pub navigation: Vec<ODataNavigation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ODataEntitySet {
+    pub name: String,
+    pub entity_type: String,
+}
+
+/// The parts of a `$metadata` document the spec directory needs to list a
+/// service's entity sets and build queries against them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ODataMetadata {
+    /// EDMX version, e.g. `4.0` or `1.0` (OData v2).
+    pub version: Option<String>,
+    pub entity_sets: Vec<ODataEntitySet>,
+    pub entity_types: Vec<ODataEntityType>,
+}
+
+// ─── Query Encoding ──────────────────────────────────────────────────────────
+
+/// Percent-encode a query option value. Spaces become `%20` (OData servers
+/// do not all read `+` as a space) and `&`, `=`, `+`, `#` and `%` are escaped;
+/// the punctuation OData expressions are made of stays readable.
+fn encode_option(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' => encoded.push(byte as char),
+            b'-' | b'.' | b'_' | b'~' | b'$' | b'\'' | b'(' | b')' | b'*' | b',' | b':' | b'/'
+            | b'@' | b';' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+/// Options in the conventional order, so the same query always produces the
+/// same URL.
+fn encode_query(query: &ODataQuery) -> String {
+    let mut options: Vec<(&str, String)> = Vec::new();
+    if let Some(filter) = query.filter.as_deref().filter(|f| !f.trim().is_empty()) {
+        options.push(("$filter", filter.to_string()));
+    }
+    if let Some(search) = query.search.as_deref().filter(|s| !s.trim().is_empty()) {
+        options.push(("$search", search.to_string()));
+    }
+    if !query.select.is_empty() {
+        options.push(("$select", query.select.join(",")));
+    }
+    if !query.expand.is_empty() {
+        options.push(("$expand", query.expand.join(",")));
+    }
+    if !query.orderby.is_empty() {
+        let orderby: Vec<String> = query
+            .orderby
+            .iter()
+            .map(|order| {
+                if order.descending {
+                    format!("{} desc", order.property)
+                } else {
+                    order.property.clone()
+                }
+            })
+            .collect();
+        options.push(("$orderby", orderby.join(",")));
+    }
+    if let Some(top) = query.top {
+        options.push(("$top", top.to_string()));
+    }
+    if let Some(skip) = query.skip {
+        options.push(("$skip", skip.to_string()));
+    }
+    if query.count {
+        options.push(("$count", "true".to_string()));
+    }
+
+    options
+        .iter()
+        .map(|(name, value)| format!("{name}={}", encode_option(value)))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+fn entity_set_url(
+    service_url: &str,
+    entity_set: &str,
+    query: &ODataQuery,
+) -> Result<url::Url, CommandError> {
+    // OWASP A09:2025 – SSRF: the service URL is validated like any outbound request
+    let mut url = validate_url(service_url)?;
+    if !entity_set.is_empty() {
+        url.path_segments_mut()
+            .map_err(|_| CommandError::new(ErrorCode::InvalidUrl).detail(service_url))?
+            .pop_if_empty()
+            .push(entity_set);
+    }
+    let encoded = encode_query(query);
+    url.set_query((!encoded.is_empty()).then_some(encoded.as_str()));
+    Ok(url)
+}
+
+// ─── Metadata Parsing ────────────────────────────────────────────────────────
+
+fn invalid_metadata(detail: impl std::fmt::Display) -> CommandError {
+    CommandError::new(ErrorCode::InvalidResponse).detail(detail)
+}
+
+fn attribute(element: &BytesStart, name: &str) -> Result<Option<String>, CommandError> {
+    match element.try_get_attribute(name).map_err(invalid_metadata)? {
+        Some(attr) => Ok(Some(
+            attr.unescape_value()
+                .map_err(invalid_metadata)?
+                .into_owned(),
+        )),
+        None => Ok(None),
+    }
+}
+
+/// Read entity types and entity sets from a CSDL/EDMX document. Works for
+/// v2 through v4: only elements and attributes common to all are used, and
+/// namespace prefixes are ignored.
+fn parse_metadata(xml: &str) -> Result<ODataMetadata, CommandError> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    let mut metadata = ODataMetadata::default();
+    let mut namespace = String::new();
+    let mut current: Option<ODataEntityType> = None;
+
+    loop {
+        let event = reader.read_event().map_err(invalid_metadata)?;
+        let (element, self_closing) = match &event {
+            Event::Start(element) => (element, false),
+            Event::Empty(element) => (element, true),
+            Event::End(element) => {
+                if element.local_name().as_ref() == b"EntityType" {
+                    metadata.entity_types.extend(current.take());
+                }
+                continue;
+            }
+            Event::Eof => break,
+            _ => continue,
+        };
+
+        match element.local_name().as_ref() {
+            b"Edmx" => metadata.version = attribute(element, "Version")?,
+            b"Schema" => namespace = attribute(element, "Namespace")?.unwrap_or_default(),
+            b"EntityType" => {
+                let name = attribute(element, "Name")?.unwrap_or_default();
+                let entity_type = ODataEntityType {
+                    name: if namespace.is_empty() {
+                        name
+                    } else {
+                        format!("{namespace}.{name}")
+                    },
+                    keys: Vec::new(),
+                    properties: Vec::new(),
+                    navigation: Vec::new(),
+                };
+                if self_closing {
+                    metadata.entity_types.push(entity_type);
+                } else {
+                    current = Some(entity_type);
+                }
+            }
+            b"PropertyRef" => {
+                if let (Some(entity_type), Some(name)) =
+                    (current.as_mut(), attribute(element, "Name")?)
+                {
+                    entity_type.keys.push(name);
+                }
+            }
+            b"Property" => {
+                if let Some(entity_type) = current.as_mut() {
+                    entity_type.properties.push(ODataProperty {
+                        name: attribute(element, "Name")?.unwrap_or_default(),
+                        type_name: attribute(element, "Type")?.unwrap_or_default(),
+                        nullable: attribute(element, "Nullable")?.as_deref() != Some("false"),
+                    });
+                }
+            }
+            b"NavigationProperty" => {
+                if let Some(entity_type) = current.as_mut() {
+                    let target = match attribute(element, "Type")? {
+                        Some(target) => target,
+                        None => attribute(element, "ToRole")?.unwrap_or_default(),
+                    };
+                    entity_type.navigation.push(ODataNavigation {
+                        name: attribute(element, "Name")?.unwrap_or_default(),
+                        target,
+                    });
+                }
+            }
+            b"EntitySet" => metadata.entity_sets.push(ODataEntitySet {
+                name: attribute(element, "Name")?.unwrap_or_default(),
+                entity_type: attribute(element, "EntityType")?.unwrap_or_default(),
+            }),
+            _ => {}
+        }
+    }
+
+    if metadata.entity_sets.is_empty() && metadata.entity_types.is_empty() {
+        return Err(invalid_metadata("no entity types or entity sets found"));
+    }
+    Ok(metadata)
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// Build the URL for an entity set request with `$filter`, `$select`,
+/// `$expand`, `$orderby` and paging options encoded.
+#[tauri::command]
+pub fn build_odata_url(
+    service_url: String,
+    entity_set: String,
+    query: ODataQuery,
+) -> Result<String, CommandError> {
+    Ok(entity_set_url(&service_url, &entity_set, &query)?.into())
+}
+
+/// Fetch and parse `<service>/$metadata` for the spec directory.
+///
+/// OWASP A09:2025 – SSRF: the service URL is validated before fetching.
+#[tauri::command]
+pub async fn fetch_odata_metadata(
+    webview: tauri::Webview,
+    clients: State<'_, HttpClients>,
+    service_url: String,
+    headers: Option<HashMap<String, String>>,
+) -> Result<ODataMetadata, CommandError> {
+    ensure_main_window(webview.label())?;
+
+    let url = entity_set_url(&service_url, "$metadata", &ODataQuery::default())?;
+    let header_map = build_header_map(&headers.unwrap_or_default())?;
+
+    let response = clients
+        .spec_client(&url, None)?
+        .get(url)
+        .headers(header_map)
+        .header("Accept", "application/xml")
+        .send()
+        .await
+        .map_err(|e| transport_error(&e))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        return Err(CommandError::new(ErrorCode::HttpStatus)
+            .arg("status", status.as_u16())
+            .retryable(is_retryable_status(status)));
+    }
+
+    // OWASP A04:2025 – Insecure Design: metadata documents share the 5MB spec limit
+    let body_bytes = response
+        .bytes()
+        .await
+        .map_err(|e| CommandError::new(ErrorCode::BodyReadFailed).detail(e))?;
+    const MAX_METADATA_BYTES: usize = 5 * 1024 * 1024; // 5 MB
+    if body_bytes.len() > MAX_METADATA_BYTES {
+        return Err(CommandError::new(ErrorCode::BodyTooLarge).arg("limit", "5MB"));
+    }
+
+    let xml =
+        std::str::from_utf8(&body_bytes).map_err(|_| CommandError::new(ErrorCode::InvalidUtf8))?;
+    parse_metadata(xml)
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const METADATA_V4: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<edmx:Edmx Version="4.0" xmlns:edmx="http://docs.oasis-open.org/odata/ns/edmx">
+  <edmx:DataServices>
+    <Schema Namespace="ODataDemo" xmlns="http://docs.oasis-open.org/odata/ns/edm">
+      <EntityType Name="Product">
+        <Key><PropertyRef Name="ID"/></Key>
+        <Property Name="ID" Type="Edm.Int32" Nullable="false"/>
+        <Property Name="Name" Type="Edm.String"/>
+        <NavigationProperty Name="Supplier" Type="ODataDemo.Supplier"/>
+      </EntityType>
+      <EntityType Name="Supplier"/>
+      <EntityContainer Name="DemoService">
+        <EntitySet Name="Products" EntityType="ODataDemo.Product"/>
+      </EntityContainer>
+    </Schema>
+  </edmx:DataServices>
+</edmx:Edmx>"#;
+
+    #[test]
+    fn test_encode_query_orders_and_encodes_options() {
+        let query = ODataQuery {
+            filter: Some("Price gt 20 and Name eq 'A&B'".to_string()),
+            select: vec!["ID".to_string(), "Name".to_string()],
+            expand: vec!["Supplier".to_string()],
+            orderby: vec![ODataOrder {
+                property: "Price".to_string(),
+                descending: true,
+            }],
+            top: Some(10),
+            count: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            encode_query(&query),
+            "$filter=Price%20gt%2020%20and%20Name%20eq%20'A%26B'\
+             &$select=ID,Name&$expand=Supplier&$orderby=Price%20desc&$top=10&$count=true"
+        );
+    }
+
+    #[test]
+    fn test_entity_set_url_appends_set_and_query() {
+        let query = ODataQuery {
+            top: Some(5),
+            ..Default::default()
+        };
+        let url = entity_set_url(
+            "https://services.odata.org/V4/OData/OData.svc/",
+            "Products",
+            &query,
+        )
+        .unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://services.odata.org/V4/OData/OData.svc/Products?$top=5"
+        );
+    }
+
+    #[test]
+    fn test_entity_set_url_applies_ssrf_policy() {
+        assert!(
+            entity_set_url("http://10.0.0.5/odata", "Products", &ODataQuery::default()).is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_metadata_reads_types_and_sets() {
+        let metadata = parse_metadata(METADATA_V4).unwrap();
+        assert_eq!(metadata.version.as_deref(), Some("4.0"));
+        assert_eq!(
+            metadata.entity_sets,
+            vec![ODataEntitySet {
+                name: "Products".to_string(),
+                entity_type: "ODataDemo.Product".to_string(),
+            }]
+        );
+
+        let product = &metadata.entity_types[0];
+        assert_eq!(product.name, "ODataDemo.Product");
+        assert_eq!(product.keys, vec!["ID"]);
+        assert!(!product.properties[0].nullable);
+        assert!(product.properties[1].nullable);
+        assert_eq!(product.navigation[0].target, "ODataDemo.Supplier");
+        assert_eq!(metadata.entity_types[1].name, "ODataDemo.Supplier");
+    }
+
+    #[test]
+    fn test_parse_metadata_rejects_non_edmx() {
+        let err = parse_metadata("<html><body>Login</body></html>").unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidResponse);
+        assert!(parse_metadata("<Edmx><Schema").is_err());
+    }
+}
@@ -0,0 +1,733 @@
+use std::collections::HashMap;
+use std::io::Read;
+use std::time::Duration;
+
+use base64::Engine;
+use reqwest::header::{HeaderMap, WWW_AUTHENTICATE};
+use ring::digest;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use super::http::HttpClients;
+use super::{
+    build_header_map, ensure_main_window, is_retryable_status, transport_error, validate_url,
+};
+use crate::error::{CommandError, ErrorCode};
+
+const NPM_REGISTRY: &str = "https://registry.npmjs.org";
+
+/// Same limit as specs fetched over HTTP, applied to the extracted document.
+const MAX_SPEC_BYTES: usize = 5 * 1024 * 1024; // 5 MB
+/// Packuments of long-lived packages list every version ever published.
+const MAX_METADATA_BYTES: usize = 20 * 1024 * 1024; // 20 MB
+const MAX_ARCHIVE_BYTES: usize = 20 * 1024 * 1024; // 20 MB
+/// Longer than the spec client's own timeout, for archives.
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// File names tried, in order, when no path inside the package is given.
+const SPEC_FILE_NAMES: &[&str] = &[
+    "openapi.json",
+    "openapi.yaml",
+    "openapi.yml",
+    "swagger.json",
+    "swagger.yaml",
+    "swagger.yml",
+];
+
+const OCI_MANIFEST_TYPES: &str = "application/vnd.oci.image.manifest.v1+json, \
+     application/vnd.docker.distribution.manifest.v2+json";
+const OCI_TITLE_ANNOTATION: &str = "org.opencontainers.image.title";
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum PackageSource {
+    /// `name` may be scoped (`@acme/orders-api`); `version` defaults to the
+    /// `latest` dist-tag.
+    Npm {
+        name: String,
+        version: Option<String>,
+        registry: Option<String>,
+    },
+    /// `registry/repository[:tag|@digest]`, e.g. `ghcr.io/acme/orders-api:2.1.0`.
+    /// The tag defaults to `latest`.
+    Oci { reference: String },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PackageSpec {
+    /// Resolved npm version, or the OCI manifest digest.
+    pub version: String,
+    /// Path of the document inside the package.
+    pub path: String,
+    pub content: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Packument {
+    #[serde(rename = "dist-tags", default)]
+    dist_tags: HashMap<String, String>,
+    #[serde(default)]
+    versions: HashMap<String, PackumentVersion>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PackumentVersion {
+    dist: PackumentDist,
+}
+
+#[derive(Debug, Deserialize)]
+struct PackumentDist {
+    tarball: String,
+    /// Subresource Integrity string, e.g. `sha512-<base64>`.
+    integrity: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+struct OciReference {
+    registry: String,
+    repository: String,
+    /// Tag or `sha256:` digest.
+    reference: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OciManifest {
+    #[serde(default)]
+    layers: Vec<OciLayer>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OciLayer {
+    digest: String,
+    #[serde(default)]
+    annotations: HashMap<String, String>,
+}
+
+// ─── Download ────────────────────────────────────────────────────────────────
+
+fn status_error(status: reqwest::StatusCode) -> CommandError {
+    CommandError::new(ErrorCode::HttpStatus)
+        .arg("status", status.as_u16())
+        .retryable(is_retryable_status(status))
+}
+
+async fn read_body(response: reqwest::Response, limit: usize) -> Result<Vec<u8>, CommandError> {
+    // OWASP A04:2025 – Insecure Design: bound every download
+    if response
+        .content_length()
+        .is_some_and(|len| len > limit as u64)
+    {
+        return Err(body_too_large(limit));
+    }
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| CommandError::new(ErrorCode::BodyReadFailed).detail(e))?;
+    if bytes.len() > limit {
+        return Err(body_too_large(limit));
+    }
+    Ok(bytes.to_vec())
+}
+
+fn body_too_large(limit: usize) -> CommandError {
+    CommandError::new(ErrorCode::BodyTooLarge).arg("limit", format!("{}MB", limit >> 20))
+}
+
+/// GET `url` after the SSRF check, returning the body.
+async fn download(
+    clients: &HttpClients,
+    url: &str,
+    headers: HeaderMap,
+    limit: usize,
+) -> Result<Vec<u8>, CommandError> {
+    // OWASP A09:2025 – SSRF: registry and tarball URLs are validated like any request
+    let url = validate_url(url)?;
+    let response = clients
+        .spec_client(&url, None)?
+        .get(url)
+        .headers(headers)
+        .timeout(DOWNLOAD_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| transport_error(&e))?;
+    if !response.status().is_success() {
+        return Err(status_error(response.status()));
+    }
+    read_body(response, limit).await
+}
+
+fn integrity_error(detail: impl std::fmt::Display) -> CommandError {
+    CommandError::new(ErrorCode::IntegrityMismatch).detail(detail)
+}
+
+/// Check a Subresource Integrity string (`sha512-…`, as npm publishes).
+fn verify_integrity(data: &[u8], integrity: &str) -> Result<(), CommandError> {
+    let (algorithm, expected) = integrity
+        .split_once('-')
+        .ok_or_else(|| integrity_error(integrity))?;
+    let algorithm = match algorithm {
+        "sha512" => &digest::SHA512,
+        "sha384" => &digest::SHA384,
+        "sha256" => &digest::SHA256,
+        other => return Err(integrity_error(format!("unsupported algorithm '{other}'"))),
+    };
+    let actual = base64::engine::general_purpose::STANDARD.encode(digest::digest(algorithm, data));
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(integrity_error(integrity))
+    }
+}
+
+/// Check an OCI content digest (`sha256:<hex>`).
+fn verify_digest(data: &[u8], expected: &str) -> Result<(), CommandError> {
+    let actual: String = digest::digest(&digest::SHA256, data)
+        .as_ref()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect();
+    if expected.strip_prefix("sha256:") == Some(actual.as_str()) {
+        Ok(())
+    } else {
+        Err(integrity_error(expected))
+    }
+}
+
+// ─── Extraction ──────────────────────────────────────────────────────────────
+
+/// Pick the document: `path` when given, otherwise the shallowest file named
+/// like a spec, preferring the order of `SPEC_FILE_NAMES`.
+fn choose_spec_path<'a>(
+    mut paths: impl Iterator<Item = &'a str>,
+    path: Option<&str>,
+) -> Option<String> {
+    if let Some(wanted) = path {
+        let wanted = wanted.trim_start_matches("./").trim_start_matches('/');
+        return paths.find(|p| *p == wanted).map(str::to_string);
+    }
+    paths
+        .filter_map(|p| {
+            let file_name = p.rsplit('/').next()?;
+            let rank = SPEC_FILE_NAMES.iter().position(|n| *n == file_name)?;
+            Some((p.matches('/').count(), rank, p))
+        })
+        .min()
+        .map(|(_, _, p)| p.to_string())
+}
+
+fn spec_text(bytes: Vec<u8>) -> Result<String, CommandError> {
+    if bytes.len() > MAX_SPEC_BYTES {
+        return Err(body_too_large(MAX_SPEC_BYTES));
+    }
+    String::from_utf8(bytes).map_err(|_| CommandError::new(ErrorCode::InvalidUtf8))
+}
+
+/// Read regular files from an npm tarball, keyed by their path below the
+/// top-level directory (`package/` for tarballs built by `npm pack`).
+/// Nothing is written to disk.
+fn tarball_files(tgz: &[u8]) -> Result<HashMap<String, Vec<u8>>, CommandError> {
+    let invalid = |e: std::io::Error| CommandError::new(ErrorCode::InvalidResponse).detail(e);
+    let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(tgz));
+    let mut files = HashMap::new();
+    let mut total = 0usize;
+    for entry in archive.entries().map_err(invalid)? {
+        let entry = entry.map_err(invalid)?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = entry
+            .path()
+            .map_err(invalid)?
+            .to_string_lossy()
+            .into_owned();
+        let Some((_, relative)) = path.split_once('/') else {
+            continue;
+        };
+        let relative = relative.to_string();
+        // OWASP A04:2025 – Insecure Design: bound decompression (gzip bombs)
+        let mut content = Vec::new();
+        entry
+            .take(MAX_SPEC_BYTES as u64 + 1)
+            .read_to_end(&mut content)
+            .map_err(invalid)?;
+        total += content.len();
+        if total > MAX_ARCHIVE_BYTES {
+            return Err(body_too_large(MAX_ARCHIVE_BYTES));
+        }
+        files.insert(relative, content);
+    }
+    Ok(files)
+}
+
+// ─── npm ─────────────────────────────────────────────────────────────────────
+
+fn packument_url(registry: &str, name: &str) -> String {
+    // Scoped names keep the `@` but encode the slash: `@acme%2forders-api`
+    format!(
+        "{}/{}",
+        registry.trim_end_matches('/'),
+        name.replace('/', "%2f")
+    )
+}
+
+fn resolve_npm_version<'a>(
+    packument: &'a Packument,
+    version: Option<&str>,
+) -> Result<(&'a str, &'a PackumentVersion), CommandError> {
+    let requested = version.unwrap_or("latest");
+    // A dist-tag (`latest`, `next`) or an exact version
+    let resolved = packument
+        .dist_tags
+        .get(requested)
+        .map(String::as_str)
+        .unwrap_or(requested);
+    packument
+        .versions
+        .get_key_value(resolved)
+        .map(|(v, meta)| (v.as_str(), meta))
+        .ok_or_else(|| {
+            CommandError::new(ErrorCode::PackageVersionNotFound).arg("version", requested)
+        })
+}
+
+async fn fetch_npm(
+    clients: &HttpClients,
+    name: &str,
+    version: Option<&str>,
+    registry: Option<&str>,
+    headers: &HeaderMap,
+    path: Option<&str>,
+) -> Result<PackageSpec, CommandError> {
+    let registry = registry.unwrap_or(NPM_REGISTRY);
+    let mut metadata_headers = headers.clone();
+    metadata_headers.insert(
+        reqwest::header::ACCEPT,
+        reqwest::header::HeaderValue::from_static("application/json"),
+    );
+    let body = download(
+        clients,
+        &packument_url(registry, name),
+        metadata_headers,
+        MAX_METADATA_BYTES,
+    )
+    .await?;
+    let packument: Packument = serde_json::from_slice(&body)
+        .map_err(|e| CommandError::new(ErrorCode::InvalidResponse).detail(e))?;
+    let (version, meta) = resolve_npm_version(&packument, version)?;
+
+    let tgz = download(
+        clients,
+        &meta.dist.tarball,
+        tarball_headers(registry, &meta.dist.tarball, headers),
+        MAX_ARCHIVE_BYTES,
+    )
+    .await?;
+    if let Some(integrity) = &meta.dist.integrity {
+        verify_integrity(&tgz, integrity)?;
+    }
+
+    let mut files = tarball_files(&tgz)?;
+    let chosen = choose_spec_path(files.keys().map(String::as_str), path)
+        .ok_or_else(|| spec_not_found(path))?;
+    let content = spec_text(files.remove(&chosen).unwrap_or_default())?;
+    Ok(PackageSpec {
+        version: version.to_string(),
+        path: chosen,
+        content,
+    })
+}
+
+/// Registry credentials go with the tarball only when the registry serves
+/// it: a packument may point tarballs at any host.
+fn tarball_headers(registry: &str, tarball: &str, headers: &HeaderMap) -> HeaderMap {
+    let origin = |url: &str| url::Url::parse(url).ok().map(|url| url.origin());
+    match (origin(registry), origin(tarball)) {
+        (Some(registry), Some(tarball)) if registry == tarball => headers.clone(),
+        _ => HeaderMap::new(),
+    }
+}
+
+fn spec_not_found(path: Option<&str>) -> CommandError {
+    CommandError::new(ErrorCode::SpecNotFound).arg("path", path.unwrap_or("openapi.*"))
+}
+
+// ─── OCI ─────────────────────────────────────────────────────────────────────
+
+fn parse_oci_reference(reference: &str) -> Result<OciReference, CommandError> {
+    let invalid = || {
+        CommandError::new(ErrorCode::InvalidUrl).detail(format!(
+            "expected registry/repository[:tag], got '{reference}'"
+        ))
+    };
+    let reference = reference.strip_prefix("oci://").unwrap_or(reference);
+    let (registry, rest) = reference.split_once('/').ok_or_else(invalid)?;
+    let (repository, tag) = if let Some((repository, digest)) = rest.split_once('@') {
+        (repository, digest)
+    } else {
+        match rest.rsplit_once(':') {
+            Some((repository, tag)) if !tag.contains('/') => (repository, tag),
+            _ => (rest, "latest"),
+        }
+    };
+    let valid_repository = !repository.is_empty()
+        && repository
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b"-._/".contains(&b));
+    if registry.is_empty() || !valid_repository || tag.is_empty() {
+        return Err(invalid());
+    }
+    Ok(OciReference {
+        registry: registry.to_string(),
+        repository: repository.to_string(),
+        reference: tag.to_string(),
+    })
+}
+
+/// Parameters of a `Bearer realm="…",service="…",scope="…"` challenge.
+fn parse_bearer_challenge(header: &str) -> Option<HashMap<String, String>> {
+    let params = header.strip_prefix("Bearer ")?;
+    let mut parsed = HashMap::new();
+    let mut rest = params.trim();
+    while let Some((key, after)) = rest.split_once('=') {
+        let after = after.trim_start();
+        let (value, remaining) = if let Some(quoted) = after.strip_prefix('"') {
+            let end = quoted.find('"')?;
+            (&quoted[..end], &quoted[end + 1..])
+        } else {
+            after.split_once(',').unwrap_or((after, ""))
+        };
+        parsed.insert(key.trim().to_string(), value.to_string());
+        rest = remaining.trim_start_matches(',').trim();
+    }
+    Some(parsed)
+}
+
+/// Anonymous pull token for registries (ghcr.io, Docker Hub) that require a
+/// token even for public repositories.
+async fn oci_token(clients: &HttpClients, challenge: &str) -> Result<String, CommandError> {
+    let params = parse_bearer_challenge(challenge)
+        .ok_or_else(|| CommandError::new(ErrorCode::InvalidResponse).detail(challenge))?;
+    let realm = params
+        .get("realm")
+        .ok_or_else(|| CommandError::new(ErrorCode::InvalidResponse).detail(challenge))?;
+    let mut url = validate_url(realm)?;
+    {
+        let mut query = url.query_pairs_mut();
+        for key in ["service", "scope"] {
+            if let Some(value) = params.get(key) {
+                query.append_pair(key, value);
+            }
+        }
+    }
+
+    #[derive(Deserialize)]
+    struct TokenResponse {
+        token: Option<String>,
+        access_token: Option<String>,
+    }
+    let body = download(clients, url.as_str(), HeaderMap::new(), MAX_SPEC_BYTES).await?;
+    let response: TokenResponse = serde_json::from_slice(&body)
+        .map_err(|e| CommandError::new(ErrorCode::InvalidResponse).detail(e))?;
+    response
+        .token
+        .or(response.access_token)
+        .ok_or_else(|| CommandError::new(ErrorCode::InvalidResponse).detail("no token issued"))
+}
+
+/// GET from the registry API, retrying once with an anonymous token when
+/// challenged. Returns the body and the `Docker-Content-Digest` header.
+async fn registry_get(
+    clients: &HttpClients,
+    url: &str,
+    headers: &HeaderMap,
+    token: &mut Option<String>,
+    limit: usize,
+) -> Result<(Vec<u8>, Option<String>), CommandError> {
+    let parsed = validate_url(url)?;
+    for attempt in 0..2 {
+        let mut request = clients
+            .spec_client(&parsed, None)?
+            .get(parsed.clone())
+            .headers(headers.clone())
+            .timeout(DOWNLOAD_TIMEOUT);
+        if let Some(token) = token.as_ref() {
+            request = request.bearer_auth(token);
+        }
+        let response = request.send().await.map_err(|e| transport_error(&e))?;
+        let status = response.status();
+        if status == reqwest::StatusCode::UNAUTHORIZED && attempt == 0 && token.is_none() {
+            let challenge = response
+                .headers()
+                .get(WWW_AUTHENTICATE)
+                .and_then(|v| v.to_str().ok())
+                .ok_or_else(|| status_error(status))?
+                .to_string();
+            *token = Some(oci_token(clients, &challenge).await?);
+            continue;
+        }
+        if !status.is_success() {
+            return Err(status_error(status));
+        }
+        let digest = response
+            .headers()
+            .get("docker-content-digest")
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        return Ok((read_body(response, limit).await?, digest));
+    }
+    Err(status_error(reqwest::StatusCode::UNAUTHORIZED))
+}
+
+async fn fetch_oci(
+    clients: &HttpClients,
+    reference: &str,
+    headers: &HeaderMap,
+    path: Option<&str>,
+) -> Result<PackageSpec, CommandError> {
+    let oci = parse_oci_reference(reference)?;
+    let base = format!("https://{}/v2/{}", oci.registry, oci.repository);
+    let mut token = None;
+
+    let mut manifest_headers = headers.clone();
+    manifest_headers.insert(
+        reqwest::header::ACCEPT,
+        reqwest::header::HeaderValue::from_static(OCI_MANIFEST_TYPES),
+    );
+    let (body, manifest_digest) = registry_get(
+        clients,
+        &format!("{base}/manifests/{}", oci.reference),
+        &manifest_headers,
+        &mut token,
+        MAX_SPEC_BYTES,
+    )
+    .await?;
+    if oci.reference.starts_with("sha256:") {
+        verify_digest(&body, &oci.reference)?;
+    }
+    let manifest: OciManifest = serde_json::from_slice(&body)
+        .map_err(|e| CommandError::new(ErrorCode::InvalidResponse).detail(e))?;
+
+    // Spec artifacts (e.g. pushed with `oras push`) name each layer by its
+    // file title
+    let titles: Vec<&str> = manifest
+        .layers
+        .iter()
+        .filter_map(|layer| layer.annotations.get(OCI_TITLE_ANNOTATION))
+        .map(String::as_str)
+        .collect();
+    let chosen = choose_spec_path(titles.into_iter(), path).ok_or_else(|| spec_not_found(path))?;
+    let layer = manifest
+        .layers
+        .iter()
+        .find(|layer| layer.annotations.get(OCI_TITLE_ANNOTATION) == Some(&chosen))
+        .ok_or_else(|| spec_not_found(path))?;
+
+    let (blob, _) = registry_get(
+        clients,
+        &format!("{base}/blobs/{}", layer.digest),
+        headers,
+        &mut token,
+        MAX_SPEC_BYTES,
+    )
+    .await?;
+    verify_digest(&blob, &layer.digest)?;
+
+    Ok(PackageSpec {
+        version: manifest_digest.unwrap_or(oci.reference),
+        path: chosen,
+        content: spec_text(blob)?,
+    })
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// Fetch a spec distributed as an npm package or an OCI artifact, resolving
+/// the version (npm dist-tag, OCI tag) and extracting the document in the
+/// backend. Downloads are checked against the published integrity hash or
+/// content digest.
+///
+/// `path` selects the file inside the package; by default the shallowest
+/// `openapi.*` or `swagger.*` file is used.
+#[tauri::command]
+pub async fn fetch_spec_package(
+    webview: tauri::Webview,
+    clients: State<'_, HttpClients>,
+    source: PackageSource,
+    path: Option<String>,
+    headers: Option<HashMap<String, String>>,
+) -> Result<PackageSpec, CommandError> {
+    ensure_main_window(webview.label())?;
+
+    let headers = build_header_map(&headers.unwrap_or_default())?;
+    match source {
+        PackageSource::Npm {
+            name,
+            version,
+            registry,
+        } => {
+            fetch_npm(
+                &clients,
+                &name,
+                version.as_deref(),
+                registry.as_deref(),
+                &headers,
+                path.as_deref(),
+            )
+            .await
+        }
+        PackageSource::Oci { reference } => {
+            fetch_oci(&clients, &reference, &headers, path.as_deref()).await
+        }
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn tarball(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (path, content) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, content.as_bytes())
+                .unwrap();
+        }
+        let tar = builder.into_inner().unwrap();
+        let mut gz = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+        gz.write_all(&tar).unwrap();
+        gz.finish().unwrap()
+    }
+
+    #[test]
+    fn test_tarball_files_strip_top_level_directory() {
+        let tgz = tarball(&[
+            ("package/package.json", "{}"),
+            ("package/spec/openapi.yaml", "openapi: 3.1.0"),
+        ]);
+        let files = tarball_files(&tgz).unwrap();
+        assert_eq!(files["spec/openapi.yaml"], b"openapi: 3.1.0");
+        assert!(files.contains_key("package.json"));
+    }
+
+    #[test]
+    fn test_choose_spec_path_prefers_shallow_openapi_files() {
+        let paths = [
+            "docs/openapi.json",
+            "swagger.json",
+            "openapi.yaml",
+            "README.md",
+        ];
+        assert_eq!(
+            choose_spec_path(paths.into_iter(), None).as_deref(),
+            Some("openapi.yaml")
+        );
+        assert_eq!(
+            choose_spec_path(paths.into_iter(), Some("./docs/openapi.json")).as_deref(),
+            Some("docs/openapi.json")
+        );
+        assert!(choose_spec_path(["README.md"].into_iter(), None).is_none());
+    }
+
+    #[test]
+    fn test_verify_integrity_checks_sri_hash() {
+        // `printf hello | openssl dgst -sha512 -binary | base64`
+        let integrity = "sha512-m3HSJL1i83hdltRq0+o9czGb+8KJDKra4t/3JRlnPKcjI8PZm6XBHXx6zG4UuMXaDEZjR1wuXDre9G9zvN7AQw==";
+        assert!(verify_integrity(b"hello", integrity).is_ok());
+        let err = verify_integrity(b"tampered", integrity).unwrap_err();
+        assert_eq!(err.code, ErrorCode::IntegrityMismatch);
+    }
+
+    #[test]
+    fn test_verify_digest_checks_sha256() {
+        let digest = "sha256:2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+        assert!(verify_digest(b"hello", digest).is_ok());
+        assert!(verify_digest(b"hello!", digest).is_err());
+    }
+
+    #[test]
+    fn test_resolve_npm_version_follows_dist_tags() {
+        let packument: Packument = serde_json::from_str(
+            r#"{"dist-tags":{"latest":"2.0.0"},"versions":{
+                "1.0.0":{"dist":{"tarball":"https://registry.npmjs.org/a/-/a-1.0.0.tgz"}},
+                "2.0.0":{"dist":{"tarball":"https://registry.npmjs.org/a/-/a-2.0.0.tgz"}}}}"#,
+        )
+        .unwrap();
+        assert_eq!(resolve_npm_version(&packument, None).unwrap().0, "2.0.0");
+        assert_eq!(
+            resolve_npm_version(&packument, Some("1.0.0")).unwrap().0,
+            "1.0.0"
+        );
+        let err = resolve_npm_version(&packument, Some("next")).unwrap_err();
+        assert_eq!(err.code, ErrorCode::PackageVersionNotFound);
+    }
+
+    #[test]
+    fn test_packument_url_encodes_scope_slash() {
+        assert_eq!(
+            packument_url("https://registry.npmjs.org/", "@acme/orders-api"),
+            "https://registry.npmjs.org/@acme%2forders-api"
+        );
+    }
+
+    #[test]
+    fn test_registry_headers_only_go_to_its_own_tarballs() {
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", "Bearer npm_token".parse().unwrap());
+        let registry = "https://npm.acme.example/";
+        let own = tarball_headers(
+            registry,
+            "https://npm.acme.example/a/-/a-1.0.0.tgz",
+            &headers,
+        );
+        assert_eq!(own, headers);
+        for tarball in [
+            "https://cdn.example.net/a/-/a-1.0.0.tgz",
+            "http://npm.acme.example/a/-/a-1.0.0.tgz",
+            "https://npm.acme.example:8443/a/-/a-1.0.0.tgz",
+            "not a url",
+        ] {
+            assert!(
+                tarball_headers(registry, tarball, &headers).is_empty(),
+                "{tarball}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_oci_reference_defaults_to_latest() {
+        assert_eq!(
+            parse_oci_reference("ghcr.io/acme/specs").unwrap(),
+            OciReference {
+                registry: "ghcr.io".to_string(),
+                repository: "acme/specs".to_string(),
+                reference: "latest".to_string(),
+            }
+        );
+        let pinned = parse_oci_reference("oci://localhost:5000/specs:1.2.0").unwrap();
+        assert_eq!(pinned.registry, "localhost:5000");
+        assert_eq!(pinned.reference, "1.2.0");
+        let by_digest = parse_oci_reference("ghcr.io/acme/specs@sha256:abc").unwrap();
+        assert_eq!(by_digest.reference, "sha256:abc");
+        assert!(parse_oci_reference("specs").is_err());
+    }
+
+    #[test]
+    fn test_parse_bearer_challenge() {
+        let params = parse_bearer_challenge(
+            r#"Bearer realm="https://ghcr.io/token",service="ghcr.io",scope="repository:acme/specs:pull""#,
+        )
+        .unwrap();
+        assert_eq!(params["realm"], "https://ghcr.io/token");
+        assert_eq!(params["scope"], "repository:acme/specs:pull");
+        assert!(parse_bearer_challenge("Basic realm=\"x\"").is_none());
+    }
+}
@@ -0,0 +1,162 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+
+use super::ensure_main_window;
+use super::http::HttpClients;
+use super::insecure::normalize_host;
+use super::store::JsonStore;
+use super::tls::CertificatePins;
+use crate::error::{CommandError, ErrorCode};
+
+const CERTIFICATE_PINS_FILE: &str = "certificate_pins.json";
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+/// Public keys API hosts must present, as base64 SHA-256 hashes of the
+/// SubjectPublicKeyInfo, by host.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct CertificatePinStore {
+    hosts: BTreeMap<String, BTreeSet<String>>,
+}
+
+// ─── Pins ────────────────────────────────────────────────────────────────────
+
+/// `pin` as the verifier compares it: the base64 of a SHA-256 hash, with
+/// the `sha256/` prefix of HPKP-style pins dropped.
+fn normalize_pin(pin: &str) -> Result<String, CommandError> {
+    let trimmed = pin.trim();
+    let encoded = trimmed.strip_prefix("sha256/").unwrap_or(trimmed);
+    let engine = base64::engine::general_purpose::STANDARD;
+    match engine.decode(encoded) {
+        Ok(hash) if hash.len() == 32 => Ok(engine.encode(hash)),
+        _ => Err(CommandError::new(ErrorCode::InvalidCertificatePin).arg("pin", pin)),
+    }
+}
+
+// ─── Storage ─────────────────────────────────────────────────────────────────
+
+static CERTIFICATE_PINS: JsonStore<CertificatePinStore> = JsonStore::new(CERTIFICATE_PINS_FILE);
+
+fn listed(hosts: BTreeMap<String, BTreeSet<String>>) -> BTreeMap<String, Vec<String>> {
+    hosts
+        .into_iter()
+        .map(|(host, pins)| (host, pins.into_iter().collect()))
+        .collect()
+}
+
+/// Startup task: hand the stored pins to the HTTP clients. Until it runs, no
+/// host is pinned. When they cannot be read, no TLS connection is trusted
+/// until the file is fixed or removed.
+pub(crate) fn load(app: &AppHandle) -> Result<(), CommandError> {
+    let clients = app.state::<HttpClients>();
+    match CERTIFICATE_PINS
+        .path(app)
+        .and_then(|path| CERTIFICATE_PINS.read(&path))
+    {
+        Ok(store) => {
+            clients.set_certificate_pins(CertificatePins::Hosts(store.hosts));
+            Ok(())
+        }
+        Err(e) => {
+            clients.set_certificate_pins(CertificatePins::Unavailable);
+            Err(e)
+        }
+    }
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// Pinned public key hashes by host.
+#[tauri::command]
+pub fn list_certificate_pins(
+    app: AppHandle,
+    webview: tauri::Webview,
+) -> Result<BTreeMap<String, Vec<String>>, CommandError> {
+    ensure_main_window(webview.label())?;
+
+    Ok(listed(
+        CERTIFICATE_PINS.read(&CERTIFICATE_PINS.path(&app)?)?.hosts,
+    ))
+}
+
+/// Pin the public keys `host` may present, as base64 SHA-256 hashes of the
+/// SubjectPublicKeyInfo (`ApiResponse::certificates` lists them as
+/// `spki_sha256`). A connection to the host then fails with
+/// `certificate_pin_mismatch` unless a certificate in its chain has one of
+/// them, for requests and spec downloads alike. No pins unpins the host.
+/// Returns the pins of every host. Stored pins that cannot be read are
+/// left alone and reported as `storage_failed`.
+///
+/// OWASP A05:2025 – Cryptographic Failures: pins narrow the certificates a
+/// host is trusted with, never widen them.
+#[tauri::command]
+pub fn set_certificate_pins(
+    app: AppHandle,
+    webview: tauri::Webview,
+    clients: State<'_, HttpClients>,
+    host: String,
+    pins: Vec<String>,
+) -> Result<BTreeMap<String, Vec<String>>, CommandError> {
+    ensure_main_window(webview.label())?;
+    let host = normalize_host(&host)?;
+    let pins = pins
+        .iter()
+        .map(|pin| normalize_pin(pin))
+        .collect::<Result<BTreeSet<_>, _>>()?;
+
+    let hosts = CERTIFICATE_PINS.update(&CERTIFICATE_PINS.path(&app)?, |store| {
+        if pins.is_empty() {
+            store.hosts.remove(&host);
+        } else {
+            store.hosts.insert(host, pins);
+        }
+        Ok(store.hosts.clone())
+    })?;
+    clients.set_certificate_pins(CertificatePins::Hosts(hosts.clone()));
+    Ok(listed(hosts))
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pins_are_normalized() {
+        let pin = "XxWKssZLC9DSwUKA5ltcjtU3+RxUEntfw92CnQIkgU0=";
+        assert_eq!(normalize_pin(pin).unwrap(), pin);
+        assert_eq!(normalize_pin(&format!(" sha256/{pin} ")).unwrap(), pin);
+
+        for pin in [
+            "",
+            "sha1/XxWKssZLC9DSwUKA5ltcjtU3",
+            "bm9wZQ==",
+            "not base64",
+        ] {
+            assert_eq!(
+                normalize_pin(pin).unwrap_err().code,
+                ErrorCode::InvalidCertificatePin,
+                "{pin}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_corrupt_pins_are_an_error_not_no_pins() {
+        let dir = std::env::temp_dir().join(format!("yasp-pins-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(CERTIFICATE_PINS_FILE);
+        let _ = std::fs::remove_file(&path);
+        assert!(CERTIFICATE_PINS.read(&path).unwrap().hosts.is_empty());
+
+        std::fs::write(&path, b"{not json").unwrap();
+        assert_eq!(
+            CERTIFICATE_PINS.read(&path).unwrap_err().code,
+            ErrorCode::StorageFailed
+        );
+    }
+}
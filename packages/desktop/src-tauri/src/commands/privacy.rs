@@ -0,0 +1,690 @@
+use std::collections::BTreeSet;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::routes::WorkspaceSpec;
+
+const METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Field names, lower-cased without `_` or `-`, that end in one of these are
+/// likely sensitive: `newPassword`, `user_ssn`, `refresh-token`.
+const CREDENTIAL_NAMES: &[&str] = &[
+    "password",
+    "passwd",
+    "passphrase",
+    "secret",
+    "token",
+    "apikey",
+    "privatekey",
+    "pin",
+    "otp",
+];
+const PERSONAL_ID_NAMES: &[&str] = &[
+    "ssn",
+    "socialsecuritynumber",
+    "nationalid",
+    "passportnumber",
+    "taxid",
+    "driverslicense",
+    "dateofbirth",
+    "birthdate",
+    "dob",
+];
+const FINANCIAL_NAMES: &[&str] = &[
+    "cardnumber",
+    "creditcard",
+    "cvv",
+    "cvc",
+    "iban",
+    "accountnumber",
+    "routingnumber",
+];
+const CONTACT_NAMES: &[&str] = &["email", "emailaddress", "phone", "phonenumber", "mobile"];
+const PERSONAL_NAMES: &[&str] = &[
+    "firstname",
+    "lastname",
+    "middlename",
+    "fullname",
+    "givenname",
+    "familyname",
+    "surname",
+    "address",
+    "street",
+    "postcode",
+    "postalcode",
+    "zipcode",
+    "ipaddress",
+    "gender",
+    "nationality",
+    "latitude",
+    "longitude",
+];
+
+/// Names that end like a credential but are not one.
+const NOT_SENSITIVE_NAMES: &[&str] = &["pagetoken", "continuationtoken"];
+
+/// `x-data-classification` values that don't need a privacy review.
+const PUBLIC_CLASSIFICATIONS: &[&str] = &["public", "internal"];
+
+/// Nested schemas deeper than this are not inspected.
+const MAX_DEPTH: usize = 16;
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    /// Sent by the client, as a parameter or in the request body.
+    Request,
+    /// Returned by the API.
+    Response,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SensitiveCategory {
+    Credential,
+    PersonalId,
+    Financial,
+    Contact,
+    /// Names, postal addresses, location and other personal details.
+    Personal,
+    /// Marked sensitive by an extension, with no recognizable name.
+    Declared,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HintSource {
+    /// `x-sensitive`, `x-pii` or `x-data-classification` on the field.
+    Extension,
+    /// `format: password`.
+    Format,
+    /// The field name alone.
+    Name,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct SensitiveField {
+    pub direction: Direction,
+    /// Response status code, for response fields.
+    pub status: Option<String>,
+    /// `body.user.password`, `query.token`, `header.X-Api-Key`; `[]` marks
+    /// array items.
+    pub field: String,
+    pub category: SensitiveCategory,
+    pub source: HintSource,
+}
+
+/// An operation that accepts or returns likely-sensitive data.
+#[derive(Debug, Clone, Serialize)]
+pub struct SensitiveOperation {
+    /// Upper-case HTTP method.
+    pub method: String,
+    pub path: String,
+    pub operation_id: Option<String>,
+    pub fields: Vec<SensitiveField>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InventoryLocation {
+    /// A parameter or body field of an operation.
+    Endpoint,
+    /// A property of a named schema under `components/schemas` or
+    /// `definitions`.
+    Schema,
+}
+
+/// One field likely to carry personal data, as a row of the inventory.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct PersonalDataField {
+    pub spec_id: String,
+    pub location: InventoryLocation,
+    /// `GET /users/{id}` for endpoints, the schema name for schemas.
+    pub subject: String,
+    /// For endpoint fields.
+    pub direction: Option<Direction>,
+    pub status: Option<String>,
+    /// As in `SensitiveField`; schema fields start with the schema name.
+    pub field: String,
+    pub category: SensitiveCategory,
+    pub source: HintSource,
+}
+
+// ─── Classification ──────────────────────────────────────────────────────────
+
+fn category_of_name(name: &str) -> Option<SensitiveCategory> {
+    let normalized: String = name
+        .chars()
+        .filter(|c| *c != '_' && *c != '-')
+        .flat_map(char::to_lowercase)
+        .collect();
+    if NOT_SENSITIVE_NAMES
+        .iter()
+        .any(|safe| normalized.ends_with(safe))
+    {
+        return None;
+    }
+    [
+        (CREDENTIAL_NAMES, SensitiveCategory::Credential),
+        (PERSONAL_ID_NAMES, SensitiveCategory::PersonalId),
+        (FINANCIAL_NAMES, SensitiveCategory::Financial),
+        (CONTACT_NAMES, SensitiveCategory::Contact),
+        (PERSONAL_NAMES, SensitiveCategory::Personal),
+    ]
+    .into_iter()
+    .find(|(names, _)| names.iter().any(|n| normalized.ends_with(n)))
+    .map(|(_, category)| category)
+}
+
+/// `Some(true)` when the field is declared sensitive, `Some(false)` when it
+/// is declared not to be, which also silences the name heuristics.
+fn declared(annotated: &Value) -> Option<bool> {
+    let flags = [&annotated["x-sensitive"], &annotated["x-pii"]];
+    if let Some(flag) = flags.iter().find_map(|flag| flag.as_bool()) {
+        return Some(flag);
+    }
+    annotated["x-data-classification"]
+        .as_str()
+        .map(|class| !PUBLIC_CLASSIFICATIONS.contains(&class.to_ascii_lowercase().as_str()))
+}
+
+/// Why a field looks sensitive, if it does. Annotations may sit on the
+/// parameter or property itself or on the schema it references.
+fn classify(name: &str, annotated: &[&Value]) -> Option<(SensitiveCategory, HintSource)> {
+    let category = category_of_name(name);
+    match annotated.iter().find_map(|value| declared(value)) {
+        Some(true) => {
+            return Some((
+                category.unwrap_or(SensitiveCategory::Declared),
+                HintSource::Extension,
+            ))
+        }
+        Some(false) => return None,
+        None => {}
+    }
+    if annotated
+        .iter()
+        .any(|value| value["format"].as_str() == Some("password"))
+    {
+        return Some((SensitiveCategory::Credential, HintSource::Format));
+    }
+    category.map(|category| (category, HintSource::Name))
+}
+
+// ─── Schemas ─────────────────────────────────────────────────────────────────
+
+/// The target of a local `$ref`, else the value itself.
+fn resolve<'a>(spec: &'a Value, value: &'a Value) -> &'a Value {
+    let mut value = value;
+    for _ in 0..MAX_DEPTH {
+        match value["$ref"].as_str().and_then(|r| r.strip_prefix('#')) {
+            Some(pointer) => match spec.pointer(pointer) {
+                Some(target) => value = target,
+                None => break,
+            },
+            None => break,
+        }
+    }
+    value
+}
+
+struct Walk<'a> {
+    spec: &'a Value,
+    direction: Direction,
+    status: Option<&'a str>,
+    /// `$ref`s being expanded, to stop at recursive schemas.
+    expanding: Vec<&'a str>,
+    found: BTreeSet<SensitiveField>,
+}
+
+impl<'a> Walk<'a> {
+    fn flag(&mut self, field: String, hint: Option<(SensitiveCategory, HintSource)>) {
+        if let Some((category, source)) = hint {
+            self.found.insert(SensitiveField {
+                direction: self.direction,
+                status: self.status.map(str::to_string),
+                field,
+                category,
+                source,
+            });
+        }
+    }
+
+    fn schema(&mut self, schema: &'a Value, path: &str, depth: usize) {
+        if depth > MAX_DEPTH {
+            return;
+        }
+        if let Some(reference) = schema["$ref"].as_str() {
+            if self.expanding.contains(&reference) {
+                return;
+            }
+            self.expanding.push(reference);
+            self.schema(resolve(self.spec, schema), path, depth + 1);
+            self.expanding.pop();
+            return;
+        }
+
+        for (name, property) in schema["properties"].as_object().into_iter().flatten() {
+            let field = format!("{path}.{name}");
+            let hint = classify(name, &[property, resolve(self.spec, property)]);
+            self.flag(field.clone(), hint);
+            self.schema(property, &field, depth + 1);
+        }
+        if schema["items"].is_object() {
+            self.schema(&schema["items"], &format!("{path}[]"), depth + 1);
+        }
+        for combined in ["allOf", "oneOf", "anyOf"] {
+            for member in schema[combined].as_array().into_iter().flatten() {
+                self.schema(member, path, depth + 1);
+            }
+        }
+    }
+
+    /// Schemas of each media type in an OpenAPI 3 request body or response,
+    /// or the Swagger 2 response schema.
+    fn content(&mut self, holder: &'a Value) {
+        let holder = resolve(self.spec, holder);
+        for media in holder["content"]
+            .as_object()
+            .into_iter()
+            .flatten()
+            .map(|(_, m)| m)
+        {
+            self.schema(&media["schema"], "body", 0);
+        }
+        if holder["schema"].is_object() {
+            self.schema(&holder["schema"], "body", 0);
+        }
+    }
+
+    fn parameter(&mut self, parameter: &'a Value) {
+        let parameter = resolve(self.spec, parameter);
+        let (Some(name), Some(location)) = (parameter["name"].as_str(), parameter["in"].as_str())
+        else {
+            return;
+        };
+        if location == "body" {
+            // Swagger 2 request body
+            self.schema(&parameter["schema"], "body", 0);
+            return;
+        }
+        let schema = resolve(self.spec, &parameter["schema"]);
+        let hint = classify(name, &[parameter, schema]);
+        self.flag(format!("{location}.{name}"), hint);
+    }
+}
+
+fn audit_operation<'a>(
+    spec: &'a Value,
+    shared_parameters: &'a Value,
+    operation: &'a Value,
+) -> Vec<SensitiveField> {
+    let mut walk = Walk {
+        spec,
+        direction: Direction::Request,
+        status: None,
+        expanding: Vec::new(),
+        found: BTreeSet::new(),
+    };
+    for parameter in shared_parameters
+        .as_array()
+        .into_iter()
+        .chain(operation["parameters"].as_array())
+        .flatten()
+    {
+        walk.parameter(parameter);
+    }
+    walk.content(&operation["requestBody"]);
+
+    walk.direction = Direction::Response;
+    for (status, response) in operation["responses"].as_object().into_iter().flatten() {
+        walk.status = Some(status);
+        walk.content(response);
+    }
+    walk.found.into_iter().collect()
+}
+
+fn audit(spec: &Value) -> Vec<SensitiveOperation> {
+    let mut operations = Vec::new();
+    for (path, item) in spec["paths"].as_object().into_iter().flatten() {
+        for method in METHODS {
+            let Some(operation) = item.get(*method).filter(|op| op.is_object()) else {
+                continue;
+            };
+            let fields = audit_operation(spec, &item["parameters"], operation);
+            if fields.is_empty() {
+                continue;
+            }
+            operations.push(SensitiveOperation {
+                method: method.to_ascii_uppercase(),
+                path: path.clone(),
+                operation_id: operation["operationId"].as_str().map(str::to_string),
+                fields,
+            });
+        }
+    }
+    operations
+}
+
+// ─── Inventory ───────────────────────────────────────────────────────────────
+
+fn schema_fields(spec: &Value) -> Vec<(String, SensitiveField)> {
+    let definitions = spec["components"]["schemas"]
+        .as_object()
+        .or_else(|| spec["definitions"].as_object());
+    let mut fields = Vec::new();
+    for (name, schema) in definitions.into_iter().flatten() {
+        let mut walk = Walk {
+            spec,
+            direction: Direction::Response,
+            status: None,
+            expanding: Vec::new(),
+            found: BTreeSet::new(),
+        };
+        walk.schema(schema, name, 0);
+        fields.extend(walk.found.into_iter().map(|field| (name.clone(), field)));
+    }
+    fields
+}
+
+/// Personal data fields of every spec; credentials are not personal data and
+/// are left to `audit_sensitive_fields`.
+fn inventory(specs: &[WorkspaceSpec]) -> Vec<PersonalDataField> {
+    let mut rows = BTreeSet::new();
+    for spec in specs {
+        let row = |location, subject: String, field: SensitiveField| PersonalDataField {
+            spec_id: spec.id.clone(),
+            location,
+            subject,
+            direction: (location == InventoryLocation::Endpoint).then_some(field.direction),
+            status: field.status,
+            field: field.field,
+            category: field.category,
+            source: field.source,
+        };
+        for operation in audit(&spec.spec) {
+            let subject = format!("{} {}", operation.method, operation.path);
+            for field in operation.fields {
+                rows.insert(row(InventoryLocation::Endpoint, subject.clone(), field));
+            }
+        }
+        for (schema, field) in schema_fields(&spec.spec) {
+            rows.insert(row(InventoryLocation::Schema, schema, field));
+        }
+    }
+    rows.into_iter()
+        .filter(|row| row.category != SensitiveCategory::Credential)
+        .collect()
+}
+
+fn label<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// A CSV cell. OWASP A03:2025 – Injection: spec text starting like a formula
+/// is prefixed with `'` so spreadsheets show it as text.
+fn csv_cell(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{value}")
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+fn to_csv(rows: &[PersonalDataField]) -> String {
+    let mut csv =
+        String::from("spec_id,location,subject,direction,status,field,category,source\r\n");
+    for row in rows {
+        let cells = [
+            row.spec_id.clone(),
+            label(&row.location),
+            row.subject.clone(),
+            row.direction.as_ref().map(label).unwrap_or_default(),
+            row.status.clone().unwrap_or_default(),
+            row.field.clone(),
+            label(&row.category),
+            label(&row.source),
+        ];
+        let cells: Vec<String> = cells.iter().map(|cell| csv_cell(cell)).collect();
+        csv.push_str(&cells.join(","));
+        csv.push_str("\r\n");
+    }
+    csv
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// Operations that accept or return likely-sensitive fields (credentials,
+/// personal identifiers, financial and contact data), for the privacy
+/// section of the security audit.
+///
+/// Fields are flagged by `x-sensitive: true`, `x-pii: true` or a
+/// non-public `x-data-classification`, by `format: password`, or by name.
+/// `x-sensitive: false` marks a field as reviewed and not sensitive.
+#[tauri::command]
+pub fn audit_sensitive_fields(spec: Value) -> Vec<SensitiveOperation> {
+    audit(&spec)
+}
+
+/// Endpoint and schema fields likely to carry personal data across the
+/// workspace specs, for a GDPR record of processing. Fields are found as by
+/// `audit_sensitive_fields`, without credentials.
+#[tauri::command]
+pub fn personal_data_inventory(specs: Vec<WorkspaceSpec>) -> Vec<PersonalDataField> {
+    inventory(&specs)
+}
+
+/// `personal_data_inventory` as CSV with a header row, for compliance
+/// documentation.
+#[tauri::command]
+pub fn export_personal_data_inventory(specs: Vec<WorkspaceSpec>) -> String {
+    to_csv(&inventory(&specs))
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn field(
+        direction: Direction,
+        status: Option<&str>,
+        field: &str,
+        category: SensitiveCategory,
+        source: HintSource,
+    ) -> SensitiveField {
+        SensitiveField {
+            direction,
+            status: status.map(str::to_string),
+            field: field.to_string(),
+            category,
+            source,
+        }
+    }
+
+    #[test]
+    fn test_category_of_name() {
+        assert_eq!(
+            category_of_name("newPassword"),
+            Some(SensitiveCategory::Credential)
+        );
+        assert_eq!(
+            category_of_name("user_ssn"),
+            Some(SensitiveCategory::PersonalId)
+        );
+        assert_eq!(category_of_name("IBAN"), Some(SensitiveCategory::Financial));
+        assert_eq!(
+            category_of_name("contact-email"),
+            Some(SensitiveCategory::Contact)
+        );
+        assert_eq!(
+            category_of_name("billing_postcode"),
+            Some(SensitiveCategory::Personal)
+        );
+        assert_eq!(category_of_name("nextPageToken"), None);
+        assert_eq!(category_of_name("shipping"), None);
+    }
+
+    #[test]
+    fn test_audit_flags_request_and_response_fields() {
+        let spec = json!({
+            "openapi": "3.0.3",
+            "paths": {
+                "/sessions": {
+                    "parameters": [{ "name": "X-Api-Key", "in": "header", "schema": { "type": "string" } }],
+                    "post": {
+                        "operationId": "login",
+                        "requestBody": { "content": { "application/json": { "schema": {
+                            "properties": {
+                                "username": { "type": "string" },
+                                "secret": { "type": "string", "x-sensitive": false },
+                                "passcode": { "type": "string", "format": "password" }
+                            }
+                        } } } },
+                        "responses": { "201": { "content": { "application/json": {
+                            "schema": { "$ref": "#/components/schemas/Session" }
+                        } } } }
+                    }
+                },
+                "/health": { "get": { "responses": { "200": { "description": "ok" } } } }
+            },
+            "components": { "schemas": {
+                "Session": { "properties": {
+                    "access_token": { "type": "string" },
+                    "user": { "$ref": "#/components/schemas/User" }
+                } },
+                "User": { "properties": {
+                    "friends": { "type": "array", "items": { "$ref": "#/components/schemas/User" } },
+                    "nickname": { "$ref": "#/components/schemas/Nickname" }
+                } },
+                "Nickname": { "type": "string", "x-data-classification": "PII" }
+            } }
+        });
+        let report = audit_sensitive_fields(spec);
+        // Recursive `friends` items are not expanded again
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].operation_id.as_deref(), Some("login"));
+        assert_eq!(
+            report[0].fields,
+            vec![
+                field(
+                    Direction::Request,
+                    None,
+                    "body.passcode",
+                    SensitiveCategory::Credential,
+                    HintSource::Format
+                ),
+                field(
+                    Direction::Request,
+                    None,
+                    "header.X-Api-Key",
+                    SensitiveCategory::Credential,
+                    HintSource::Name
+                ),
+                field(
+                    Direction::Response,
+                    Some("201"),
+                    "body.access_token",
+                    SensitiveCategory::Credential,
+                    HintSource::Name
+                ),
+                field(
+                    Direction::Response,
+                    Some("201"),
+                    "body.user.nickname",
+                    SensitiveCategory::Declared,
+                    HintSource::Extension
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_swagger2_body_parameters() {
+        let spec = json!({
+            "swagger": "2.0",
+            "paths": { "/payments": { "post": {
+                "parameters": [{ "name": "payment", "in": "body", "schema": {
+                    "properties": { "card": { "properties": { "cardNumber": { "type": "string" } } } }
+                } }],
+                "responses": { "204": { "description": "" } }
+            } } }
+        });
+        let report = audit_sensitive_fields(spec);
+        assert_eq!(report[0].fields[0].field, "body.card.cardNumber");
+        assert_eq!(report[0].fields[0].category, SensitiveCategory::Financial);
+    }
+
+    #[test]
+    fn test_inventory_lists_endpoint_and_schema_fields_as_csv() {
+        let spec = WorkspaceSpec {
+            id: "users".to_string(),
+            spec: json!({
+                "openapi": "3.0.3",
+                "paths": { "/users": { "post": {
+                    "requestBody": { "content": { "application/json": {
+                        "schema": { "$ref": "#/components/schemas/User" }
+                    } } },
+                    "responses": { "201": { "description": "" } }
+                } } },
+                "components": { "schemas": { "User": { "properties": {
+                    "password": { "type": "string" },
+                    "last_name": { "type": "string" },
+                    "email": { "type": "string" }
+                } } } }
+            }),
+        };
+        let rows = personal_data_inventory(vec![spec.clone()]);
+        let fields: Vec<(&str, &str)> = rows
+            .iter()
+            .map(|row| (row.subject.as_str(), row.field.as_str()))
+            .collect();
+        // The password is a credential, not personal data
+        assert_eq!(
+            fields,
+            vec![
+                ("POST /users", "body.email"),
+                ("POST /users", "body.last_name"),
+                ("User", "User.email"),
+                ("User", "User.last_name"),
+            ]
+        );
+        assert_eq!(rows[0].direction, Some(Direction::Request));
+        assert_eq!(rows[2].direction, None);
+
+        let csv = export_personal_data_inventory(vec![spec]);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "spec_id,location,subject,direction,status,field,category,source"
+        );
+        assert_eq!(
+            lines[1],
+            "users,endpoint,POST /users,request,,body.email,contact,name"
+        );
+        assert_eq!(lines.len(), 5);
+    }
+
+    #[test]
+    fn test_csv_cells_are_quoted_and_defused() {
+        assert_eq!(csv_cell("plain"), "plain");
+        assert_eq!(csv_cell("a,b"), "\"a,b\"");
+        assert_eq!(csv_cell("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_cell("=SUM(A1)"), "'=SUM(A1)");
+    }
+}
@@ -0,0 +1,317 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::net::IpAddr;
+
+use ipnetwork::IpNetwork;
+use reqwest::header::HeaderValue;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use url::Url;
+
+use super::ensure_main_window;
+use super::http::HttpClients;
+use crate::error::{CommandError, ErrorCode};
+
+/// `socks5h` leaves DNS resolution to the proxy.
+const PROXY_SCHEMES: &[&str] = &["http", "https", "socks5", "socks5h"];
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+/// Credentials for a proxy: an HTTP proxy that answers `407 Proxy
+/// Authentication Required`, or a SOCKS5 proxy that asks for them in its
+/// handshake.
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "scheme", rename_all = "snake_case")]
+pub enum ProxyAuth {
+    Basic {
+        username: String,
+        password: String,
+    },
+    /// `Proxy-Authorization: Bearer <token>`; HTTP proxies only, as SOCKS5
+    /// knows no tokens.
+    Bearer {
+        token: String,
+    },
+}
+
+// Never print the secrets, even in debug logs.
+impl fmt::Debug for ProxyAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProxyAuth::Basic { username, .. } => f
+                .debug_struct("Basic")
+                .field("username", username)
+                .field("password", &"***")
+                .finish(),
+            ProxyAuth::Bearer { .. } => f.debug_struct("Bearer").field("token", &"***").finish(),
+        }
+    }
+}
+
+/// An explicit proxy server: one proxy profile of the frontend.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ManualProxy {
+    /// `http://`, `https://`, `socks5://` or `socks5h://` URL.
+    pub url: String,
+    /// Sent to an HTTP proxy once it asks for credentials, and upfront from
+    /// then on; always sent to a SOCKS5 proxy, which never sends a `407`.
+    #[serde(default)]
+    pub auth: Option<ProxyAuth>,
+}
+
+/// How user requests reach the API.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum ProxyRoute {
+    /// Straight to the API, whatever the environment says.
+    Direct,
+    /// The operating system's proxy: `HTTP_PROXY`, `HTTPS_PROXY`,
+    /// `ALL_PROXY` and `NO_PROXY`, else the system configuration on macOS
+    /// and Windows.
+    #[default]
+    System,
+    Manual(ManualProxy),
+}
+
+/// Proxy configuration of the shared HTTP clients. Held in memory only, so
+/// proxy credentials are never written to disk by the backend.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProxySettings {
+    pub proxy: ProxyRoute,
+    /// Hosts always reached directly, in `NO_PROXY` syntax: `*`, a domain
+    /// (with its subdomains), an IP address or a CIDR range.
+    pub bypass: Vec<String>,
+    /// Further hosts reached directly by requests of a workspace, by
+    /// workspace id.
+    pub workspace_bypass: HashMap<String, Vec<String>>,
+}
+
+// ─── Routing ─────────────────────────────────────────────────────────────────
+
+impl ManualProxy {
+    /// Whether this is a SOCKS5 proxy, which authenticates in its handshake
+    /// rather than with a `407` challenge.
+    pub(super) fn is_socks(&self) -> bool {
+        Url::parse(&self.url).is_ok_and(|url| url.scheme().starts_with("socks"))
+    }
+
+    fn to_proxy(&self) -> Result<reqwest::Proxy, CommandError> {
+        let invalid = || CommandError::new(ErrorCode::InvalidProxy).arg("url", self.url.as_str());
+        let url = Url::parse(&self.url).map_err(|_| invalid())?;
+        if !PROXY_SCHEMES.contains(&url.scheme()) || url.host_str().is_none() {
+            return Err(invalid());
+        }
+        let socks = url.scheme().starts_with("socks");
+        let proxy = reqwest::Proxy::all(url).map_err(|e| invalid().detail(e))?;
+        match &self.auth {
+            None => Ok(proxy),
+            Some(ProxyAuth::Basic { username, password }) => {
+                Ok(proxy.basic_auth(username, password))
+            }
+            Some(ProxyAuth::Bearer { .. }) if socks => {
+                Err(invalid().detail("bearer tokens need an http or https proxy"))
+            }
+            Some(ProxyAuth::Bearer { token }) => {
+                let value =
+                    HeaderValue::from_str(&format!("Bearer {token}")).map_err(|_| invalid())?;
+                Ok(proxy.custom_http_auth(value))
+            }
+        }
+    }
+}
+
+impl ProxyRoute {
+    pub(super) fn apply(
+        &self,
+        builder: reqwest::ClientBuilder,
+    ) -> Result<reqwest::ClientBuilder, CommandError> {
+        match self {
+            ProxyRoute::Direct => Ok(builder.no_proxy()),
+            // reqwest reads the system proxy unless told otherwise
+            ProxyRoute::System => Ok(builder),
+            ProxyRoute::Manual(proxy) => Ok(builder.proxy(proxy.to_proxy()?)),
+        }
+    }
+}
+
+/// Whether `host` matches a `NO_PROXY` style `entry`.
+fn bypasses(entry: &str, host: &str) -> bool {
+    let entry = entry.trim();
+    if entry == "*" {
+        return true;
+    }
+    if let Ok(ip) = host.parse::<IpAddr>() {
+        return entry
+            .parse::<IpNetwork>()
+            .is_ok_and(|network| network.contains(ip));
+    }
+    let domain = entry.trim_start_matches("*.").trim_start_matches('.');
+    !domain.is_empty()
+        && (host.eq_ignore_ascii_case(domain)
+            || host
+                .to_ascii_lowercase()
+                .ends_with(&format!(".{}", domain.to_ascii_lowercase())))
+}
+
+impl ProxySettings {
+    pub(super) fn validate(&self) -> Result<(), CommandError> {
+        match &self.proxy {
+            ProxyRoute::Manual(proxy) => proxy.to_proxy().map(|_| ()),
+            _ => Ok(()),
+        }
+    }
+
+    /// The route of a request to `url`, made in `workspace_id` if given.
+    /// Redirects take the same route.
+    pub(super) fn route(&self, url: &Url, workspace_id: Option<&str>) -> ProxyRoute {
+        let Some(host) = url.host_str() else {
+            return self.proxy.clone();
+        };
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        let workspace = workspace_id.and_then(|id| self.workspace_bypass.get(id));
+        if self
+            .bypass
+            .iter()
+            .chain(workspace.into_iter().flatten())
+            .any(|entry| bypasses(entry, host))
+        {
+            ProxyRoute::Direct
+        } else {
+            self.proxy.clone()
+        }
+    }
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// The proxy settings user requests and spec downloads are sent with,
+/// credentials included.
+#[tauri::command]
+pub fn get_proxy_settings(
+    webview: tauri::Webview,
+    clients: State<'_, HttpClients>,
+) -> Result<ProxySettings, CommandError> {
+    ensure_main_window(webview.label())?;
+
+    Ok(clients.proxy())
+}
+
+/// Change the proxy settings; later requests use new clients. The frontend
+/// keeps the settings and sets them again at startup.
+///
+/// A manual proxy that answers `407` gets the request again with its
+/// credentials; without credentials, or when it rejects them, the request
+/// fails with `proxy_auth_required` or `proxy_auth_rejected`.
+#[tauri::command]
+pub fn set_proxy_settings(
+    webview: tauri::Webview,
+    clients: State<'_, HttpClients>,
+    settings: ProxySettings,
+) -> Result<(), CommandError> {
+    ensure_main_window(webview.label())?;
+
+    clients.set_proxy(settings)
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manual(url: &str) -> ProxyRoute {
+        ProxyRoute::Manual(ManualProxy {
+            url: url.to_string(),
+            auth: Some(ProxyAuth::Basic {
+                username: "alice".to_string(),
+                password: "hunter2".to_string(),
+            }),
+        })
+    }
+
+    fn route(settings: &ProxySettings, url: &str, workspace_id: Option<&str>) -> ProxyRoute {
+        settings.route(&Url::parse(url).unwrap(), workspace_id)
+    }
+
+    #[test]
+    fn test_bypass_lists_route_hosts_directly() {
+        let settings = ProxySettings {
+            proxy: manual("socks5h://proxy.corp.example:1080"),
+            bypass: vec![".internal.example".to_string(), "10.0.0.0/8".to_string()],
+            workspace_bypass: HashMap::from([(
+                "staging".to_string(),
+                vec!["staging.example.com".to_string()],
+            )]),
+        };
+        assert_eq!(
+            route(&settings, "https://api.internal.example/v1", None),
+            ProxyRoute::Direct
+        );
+        assert_eq!(
+            route(&settings, "http://10.1.2.3:8080/health", None),
+            ProxyRoute::Direct
+        );
+        assert_eq!(
+            route(&settings, "https://notinternal.example/", None),
+            settings.proxy
+        );
+
+        let staging = "https://API.staging.example.com/orders";
+        assert_eq!(route(&settings, staging, None), settings.proxy);
+        assert_eq!(route(&settings, staging, Some("prod")), settings.proxy);
+        assert_eq!(
+            route(&settings, staging, Some("staging")),
+            ProxyRoute::Direct
+        );
+    }
+
+    #[test]
+    fn test_manual_proxies_are_validated() {
+        for url in [
+            "http://proxy.corp.example:3128",
+            "https://proxy.corp.example",
+            "socks5://127.0.0.1:1080",
+        ] {
+            let settings = ProxySettings {
+                proxy: manual(url),
+                ..Default::default()
+            };
+            assert!(settings.validate().is_ok(), "{url}");
+        }
+        let socks_bearer = ProxySettings {
+            proxy: ProxyRoute::Manual(ManualProxy {
+                url: "socks5://127.0.0.1:1080".to_string(),
+                auth: Some(ProxyAuth::Bearer {
+                    token: "t0ken".to_string(),
+                }),
+            }),
+            ..Default::default()
+        };
+        assert_eq!(
+            socks_bearer.validate().unwrap_err().code,
+            ErrorCode::InvalidProxy
+        );
+        for url in ["ftp://proxy.corp.example", "proxy.corp.example:3128"] {
+            let settings = ProxySettings {
+                proxy: manual(url),
+                ..Default::default()
+            };
+            assert_eq!(
+                settings.validate().unwrap_err().code,
+                ErrorCode::InvalidProxy
+            );
+        }
+    }
+
+    #[test]
+    fn test_settings_serialize_by_mode_and_debug_hides_password() {
+        let proxy = manual("http://proxy.corp.example:3128");
+        let json = serde_json::to_value(&proxy).unwrap();
+        assert_eq!(json["mode"], "manual");
+        assert_eq!(json["url"], "http://proxy.corp.example:3128");
+        assert_eq!(json["auth"]["scheme"], "basic");
+        assert_eq!(serde_json::from_value::<ProxyRoute>(json).unwrap(), proxy);
+        assert!(!format!("{proxy:?}").contains("hunter2"));
+    }
+}
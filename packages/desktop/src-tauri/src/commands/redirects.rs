@@ -0,0 +1,281 @@
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use reqwest::redirect::{Attempt, Policy};
+use serde::{Deserialize, Serialize};
+
+use super::http;
+use crate::error::CommandError;
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+/// One redirect followed while sending a request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Redirect {
+    /// The URL that answered with the redirect.
+    pub url: String,
+    pub status: u16,
+    /// The `Location` header, resolved against `url`.
+    pub location: String,
+}
+
+/// How a request handles redirects. A redirect that is not followed is
+/// returned as the response, `Location` header and all.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RedirectOptions {
+    pub follow: bool,
+    /// Redirects followed before the request fails; unset for
+    /// `ClientDefaults::max_redirects`.
+    pub max_redirects: Option<usize>,
+    /// Follow 307 and 308 redirects, which repeat the method and body.
+    /// 301, 302 and 303 redirects are followed with a body-less `GET`.
+    pub resend_body: bool,
+}
+
+impl Default for RedirectOptions {
+    fn default() -> Self {
+        Self {
+            follow: true,
+            max_redirects: None,
+            resend_body: true,
+        }
+    }
+}
+
+tokio::task_local! {
+    /// Redirect handling of the request being sent on this task. The policy
+    /// belongs to a client shared by every request.
+    static CHAIN: RedirectChain;
+}
+
+/// The redirects a request follows, under its options.
+#[derive(Debug, Clone, Default)]
+pub(super) struct RedirectChain {
+    options: RedirectOptions,
+    followed: Arc<Mutex<Vec<Redirect>>>,
+}
+
+// ─── Policy ──────────────────────────────────────────────────────────────────
+
+impl RedirectOptions {
+    pub(super) fn validate(&self) -> Result<(), CommandError> {
+        match self.max_redirects {
+            Some(max) => http::check_range("max_redirects", max as u64, 0, 20),
+            None => Ok(()),
+        }
+    }
+}
+
+impl RedirectChain {
+    pub(super) fn new(options: RedirectOptions) -> Self {
+        Self {
+            options,
+            followed: Arc::default(),
+        }
+    }
+
+    /// Run `send` with the redirects it follows added to this chain.
+    pub(super) async fn scope<F: Future>(&self, send: F) -> F::Output {
+        CHAIN.scope(self.clone(), send).await
+    }
+
+    pub(super) fn redirects(&self) -> Vec<Redirect> {
+        self.followed
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    fn push(&self, attempt: &Attempt) {
+        self.followed
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(Redirect {
+                url: attempt
+                    .previous()
+                    .last()
+                    .map(|url| url.to_string())
+                    .unwrap_or_default(),
+                status: attempt.status().as_u16(),
+                location: attempt.url().to_string(),
+            });
+    }
+}
+
+/// Follow redirects as the request being sent asks, up to `default_max`
+/// unless it sets its own limit, and record each hop.
+pub(super) fn policy(default_max: usize) -> Policy {
+    Policy::custom(move |attempt| {
+        let chain = CHAIN.try_with(RedirectChain::clone).unwrap_or_default();
+        let options = &chain.options;
+        let repeats_body = matches!(attempt.status().as_u16(), 307 | 308);
+        if !options.follow || (repeats_body && !options.resend_body) {
+            return attempt.stop();
+        }
+        // The first previous URL is the request's own, not a redirect
+        if attempt.previous().len() > options.max_redirects.unwrap_or(default_max) {
+            return attempt.error("too many redirects");
+        }
+        chain.push(&attempt);
+        attempt.follow()
+    })
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// Serves `/a` → 302 `/b`, `/b` → 301 `/c`, `/d` → 307 `/c` and `/c` →
+    /// 200, one request per connection.
+    async fn redirecting_server() -> u16 {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut buf = [0u8; 1024];
+                let n = stream.read(&mut buf).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buf[..n]);
+                let response = if request.starts_with("GET /a ") {
+                    "HTTP/1.1 302 Found\r\nLocation: /b\r\n"
+                } else if request.starts_with("GET /b ") {
+                    "HTTP/1.1 301 Moved Permanently\r\nLocation: /c\r\n"
+                } else if request.starts_with("POST /d ") {
+                    "HTTP/1.1 307 Temporary Redirect\r\nLocation: /c\r\n"
+                } else {
+                    "HTTP/1.1 200 OK\r\n"
+                };
+                let _ = stream
+                    .write_all(
+                        format!("{response}Content-Length: 0\r\nConnection: close\r\n\r\n")
+                            .as_bytes(),
+                    )
+                    .await;
+            }
+        });
+        port
+    }
+
+    #[tokio::test]
+    async fn test_chain_records_each_hop() {
+        let port = redirecting_server().await;
+        let client = reqwest::Client::builder()
+            .redirect(policy(5))
+            .build()
+            .unwrap();
+        let chain = RedirectChain::default();
+        let response = chain
+            .scope(client.get(format!("http://127.0.0.1:{port}/a")).send())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+
+        let base = format!("http://127.0.0.1:{port}");
+        assert_eq!(
+            chain.redirects(),
+            vec![
+                Redirect {
+                    url: format!("{base}/a"),
+                    status: 302,
+                    location: format!("{base}/b"),
+                },
+                Redirect {
+                    url: format!("{base}/b"),
+                    status: 301,
+                    location: format!("{base}/c"),
+                },
+            ]
+        );
+    }
+
+    async fn send(
+        port: u16,
+        method: reqwest::Method,
+        path: &str,
+        options: RedirectOptions,
+    ) -> (u16, Vec<Redirect>) {
+        let client = reqwest::Client::builder()
+            .redirect(policy(5))
+            .build()
+            .unwrap();
+        let chain = RedirectChain::new(options);
+        let response = chain
+            .scope(
+                client
+                    .request(method, format!("http://127.0.0.1:{port}{path}"))
+                    .body("{}")
+                    .send(),
+            )
+            .await
+            .unwrap();
+        (response.status().as_u16(), chain.redirects())
+    }
+
+    #[tokio::test]
+    async fn test_options_stop_at_redirects() {
+        let port = redirecting_server().await;
+        let no_follow = RedirectOptions {
+            follow: false,
+            ..Default::default()
+        };
+        let (status, redirects) = send(port, reqwest::Method::GET, "/a", no_follow).await;
+        assert_eq!(status, 302);
+        assert!(redirects.is_empty());
+
+        let keep_body = RedirectOptions {
+            resend_body: false,
+            ..Default::default()
+        };
+        let (status, _) = send(port, reqwest::Method::POST, "/d", keep_body.clone()).await;
+        assert_eq!(status, 307);
+        // Other redirects are still followed
+        let (status, redirects) = send(port, reqwest::Method::GET, "/a", keep_body).await;
+        assert_eq!(status, 200);
+        assert_eq!(redirects.len(), 2);
+
+        let (status, redirects) = send(
+            port,
+            reqwest::Method::POST,
+            "/d",
+            RedirectOptions::default(),
+        )
+        .await;
+        assert_eq!(status, 200);
+        assert_eq!(redirects[0].status, 307);
+    }
+
+    #[tokio::test]
+    async fn test_policy_limits_redirects() {
+        let port = redirecting_server().await;
+        let client = reqwest::Client::builder()
+            .redirect(policy(1))
+            .build()
+            .unwrap();
+        let err = client
+            .get(format!("http://127.0.0.1:{port}/a"))
+            .send()
+            .await
+            .unwrap_err();
+        assert!(err.is_redirect());
+
+        // A request's own limit replaces the client's
+        let chain = RedirectChain::new(RedirectOptions {
+            max_redirects: Some(2),
+            ..Default::default()
+        });
+        let response = chain
+            .scope(client.get(format!("http://127.0.0.1:{port}/a")).send())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+
+        let too_many = RedirectOptions {
+            max_redirects: Some(21),
+            ..Default::default()
+        };
+        assert!(too_many.validate().is_err());
+    }
+}
@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use super::http::HttpClients;
+use super::{
+    build_header_map, ensure_main_window, is_retryable_status, transport_error, validate_url,
+};
+use crate::error::{CommandError, ErrorCode};
+
+/// Schemas kept in `RegistryCache`; the cache starts over beyond that.
+const MAX_CACHED_SCHEMAS: usize = 256;
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+/// A schema as returned by a Confluent-compatible schema registry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegistrySchema {
+    pub subject: String,
+    pub version: u32,
+    pub id: u32,
+    /// `AVRO`, `JSON` or `PROTOBUF`. The registry omits it for Avro.
+    #[serde(default = "default_schema_type")]
+    pub schema_type: String,
+    /// The schema document, verbatim.
+    pub schema: String,
+}
+
+fn default_schema_type() -> String {
+    "AVRO".to_string()
+}
+
+/// Cache of fetched schemas keyed by (registry URL, subject, version).
+///
+/// A registered schema version is immutable, so entries never go stale; only
+/// `latest` lookups bypass the cache.
+#[derive(Default)]
+pub struct RegistryCache(Mutex<HashMap<(String, String, u32), RegistrySchema>>);
+
+impl RegistryCache {
+    fn get(&self, key: &(String, String, u32)) -> Option<RegistrySchema> {
+        let schemas = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        schemas.get(key).cloned()
+    }
+
+    fn insert(&self, key: (String, String, u32), schema: RegistrySchema) {
+        let mut schemas = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        if schemas.len() >= MAX_CACHED_SCHEMAS && !schemas.contains_key(&key) {
+            schemas.clear();
+        }
+        schemas.insert(key, schema);
+    }
+}
+
+// ─── Helpers ─────────────────────────────────────────────────────────────────
+
+/// Append path segments to the registry base URL, percent-encoding each one
+/// so subject names like `orders-value` or `com.acme/Order` stay a single segment.
+fn registry_url(base: &str, segments: &[&str]) -> Result<url::Url, CommandError> {
+    // OWASP A09:2025 – SSRF: validate the registry URL before any request
+    let mut url = validate_url(base)?;
+    url.path_segments_mut()
+        .map_err(|_| CommandError::new(ErrorCode::InvalidUrl).detail(base))?
+        .pop_if_empty()
+        .extend(segments);
+    Ok(url)
+}
+
+async fn registry_get<T: DeserializeOwned>(
+    clients: &HttpClients,
+    url: url::Url,
+    headers: &HashMap<String, String>,
+) -> Result<T, CommandError> {
+    let header_map = build_header_map(headers)?;
+
+    let response = clients
+        .spec_client(&url, None)?
+        .get(url)
+        .headers(header_map)
+        .header(
+            "Accept",
+            "application/vnd.schemaregistry.v1+json, application/json",
+        )
+        .send()
+        .await
+        .map_err(|e| transport_error(&e))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        return Err(CommandError::new(ErrorCode::HttpStatus)
+            .arg("status", status.as_u16())
+            .retryable(is_retryable_status(status)));
+    }
+
+    // OWASP A04:2025 – Insecure Design: registry documents share the 5MB spec limit
+    let body_bytes = response
+        .bytes()
+        .await
+        .map_err(|e| CommandError::new(ErrorCode::BodyReadFailed).detail(e))?;
+    const MAX_REGISTRY_BYTES: usize = 5 * 1024 * 1024; // 5 MB
+    if body_bytes.len() > MAX_REGISTRY_BYTES {
+        return Err(CommandError::new(ErrorCode::BodyTooLarge).arg("limit", "5MB"));
+    }
+
+    serde_json::from_slice(&body_bytes)
+        .map_err(|e| CommandError::new(ErrorCode::InvalidResponse).detail(e))
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// List the subjects registered in a Confluent-compatible schema registry.
+///
+/// OWASP A09:2025 – SSRF: the registry URL is validated like any outbound request.
+#[tauri::command]
+pub async fn list_registry_subjects(
+    webview: tauri::Webview,
+    clients: State<'_, HttpClients>,
+    registry_url: String,
+    headers: Option<HashMap<String, String>>,
+) -> Result<Vec<String>, CommandError> {
+    ensure_main_window(webview.label())?;
+
+    let url = self::registry_url(&registry_url, &["subjects"])?;
+    registry_get(&clients, url, &headers.unwrap_or_default()).await
+}
+
+/// List the registered versions of a subject.
+#[tauri::command]
+pub async fn list_registry_versions(
+    webview: tauri::Webview,
+    clients: State<'_, HttpClients>,
+    registry_url: String,
+    subject: String,
+    headers: Option<HashMap<String, String>>,
+) -> Result<Vec<u32>, CommandError> {
+    ensure_main_window(webview.label())?;
+
+    let url = self::registry_url(&registry_url, &["subjects", &subject, "versions"])?;
+    registry_get(&clients, url, &headers.unwrap_or_default()).await
+}
+
+/// Fetch one version of a subject's schema (`None` for the latest).
+/// Pinned versions are served from the cache after the first fetch.
+#[tauri::command]
+pub async fn get_registry_schema(
+    webview: tauri::Webview,
+    clients: State<'_, HttpClients>,
+    cache: tauri::State<'_, RegistryCache>,
+    registry_url: String,
+    subject: String,
+    version: Option<u32>,
+    headers: Option<HashMap<String, String>>,
+) -> Result<RegistrySchema, CommandError> {
+    ensure_main_window(webview.label())?;
+
+    if let Some(version) = version {
+        let key = (registry_url.clone(), subject.clone(), version);
+        if let Some(schema) = cache.get(&key) {
+            return Ok(schema);
+        }
+    }
+
+    let version_segment = version.map_or_else(|| "latest".to_string(), |v| v.to_string());
+    let url = self::registry_url(
+        &registry_url,
+        &["subjects", &subject, "versions", &version_segment],
+    )?;
+    let schema: RegistrySchema = registry_get(&clients, url, &headers.unwrap_or_default()).await?;
+
+    cache.insert(
+        (registry_url, schema.subject.clone(), schema.version),
+        schema.clone(),
+    );
+    Ok(schema)
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_url_appends_segments() {
+        let url = registry_url("https://registry.example.com/", &["subjects"]).unwrap();
+        assert_eq!(url.as_str(), "https://registry.example.com/subjects");
+    }
+
+    #[test]
+    fn test_registry_url_keeps_base_path() {
+        let url = registry_url("https://example.com/registry", &["subjects", "orders"]).unwrap();
+        assert_eq!(url.as_str(), "https://example.com/registry/subjects/orders");
+    }
+
+    #[test]
+    fn test_registry_url_encodes_subject_as_one_segment() {
+        let url = registry_url("https://example.com", &["subjects", "com.acme/Order"]).unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://example.com/subjects/com.acme%2FOrder"
+        );
+    }
+
+    #[test]
+    fn test_registry_url_applies_ssrf_policy() {
+        assert!(registry_url("http://10.0.0.5:8081", &["subjects"]).is_err());
+    }
+
+    #[test]
+    fn test_schema_type_defaults_to_avro() {
+        let schema: RegistrySchema = serde_json::from_str(
+            r#"{"subject":"orders-value","version":3,"id":42,"schema":"{\"type\":\"string\"}"}"#,
+        )
+        .unwrap();
+        assert_eq!(schema.schema_type, "AVRO");
+        assert_eq!(schema.version, 3);
+    }
+
+    #[test]
+    fn test_cache_starts_over_when_full() {
+        let cache = RegistryCache::default();
+        let schema = |version| RegistrySchema {
+            subject: "orders-value".to_string(),
+            version,
+            id: version,
+            schema_type: "AVRO".to_string(),
+            schema: r#""string""#.to_string(),
+        };
+        let key = |version| {
+            (
+                "https://registry.example.com".to_string(),
+                "orders-value".to_string(),
+                version,
+            )
+        };
+        for version in 0..MAX_CACHED_SCHEMAS as u32 {
+            cache.insert(key(version), schema(version));
+        }
+        cache.insert(key(0), schema(0));
+        assert!(cache.get(&key(1)).is_some());
+
+        cache.insert(key(9_999), schema(9_999));
+        assert!(cache.get(&key(1)).is_none());
+        assert_eq!(cache.get(&key(9_999)).unwrap().id, 9_999);
+    }
+}
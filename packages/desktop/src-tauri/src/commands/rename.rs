@@ -0,0 +1,201 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::error::{CommandError, ErrorCode};
+
+/// Keys whose contents are sample data, not spec structure: a field called
+/// `operationId` or `tags` inside them is left alone.
+const DATA_KEYS: &[&str] = &["example", "examples", "default", "enum", "const"];
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+/// The renamed spec and how many places changed. The frontend applies the
+/// same rename to the saved requests and history it owns.
+#[derive(Debug, Clone, Serialize)]
+pub struct RenameResult {
+    pub spec: Value,
+    pub replaced: usize,
+}
+
+// ─── Walking ─────────────────────────────────────────────────────────────────
+
+/// Call `visit` on every object in `value` outside sample data.
+fn walk(value: &mut Value, visit: &mut impl FnMut(&mut serde_json::Map<String, Value>)) {
+    match value {
+        Value::Object(map) => {
+            visit(map);
+            for (key, entry) in map.iter_mut() {
+                if !DATA_KEYS.contains(&key.as_str()) {
+                    walk(entry, visit);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| walk(item, visit)),
+        _ => {}
+    }
+}
+
+/// Operation `operationId`s and the links that target them.
+fn rename_operation_ids(spec: &mut Value, from: &str, to: &str) -> usize {
+    let mut replaced = 0;
+    walk(spec, &mut |map| {
+        if let Some(id) = map.get_mut("operationId") {
+            if id == from {
+                *id = Value::String(to.to_string());
+                replaced += 1;
+            }
+        }
+    });
+    replaced
+}
+
+/// Tag definitions (`tags[].name`), operation `tags` and tag groups.
+fn rename_tags(spec: &mut Value, from: &str, to: &str) -> usize {
+    let mut replaced = 0;
+    if let Some(Value::Array(tags)) = spec.get_mut("tags") {
+        for tag in tags {
+            if let Some(name) = tag.get_mut("name").filter(|name| *name == from) {
+                *name = Value::String(to.to_string());
+                replaced += 1;
+            }
+        }
+    }
+    walk(spec, &mut |map| {
+        if let Some(Value::Array(tags)) = map.get_mut("tags") {
+            for tag in tags.iter_mut().filter(|tag| *tag == from) {
+                *tag = Value::String(to.to_string());
+                replaced += 1;
+            }
+        }
+    });
+    replaced
+}
+
+type Renamer = fn(&mut Value, &str, &str) -> usize;
+
+fn rename(
+    mut spec: Value,
+    from: &str,
+    to: &str,
+    renamer: Renamer,
+) -> Result<RenameResult, CommandError> {
+    let to = to.trim();
+    if to.is_empty() {
+        return Err(CommandError::new(ErrorCode::InvalidName).arg("name", to));
+    }
+    // Renaming onto an existing name would merge two operations or tags;
+    // renaming `to` onto itself just counts its uses
+    if from != to && renamer(&mut spec.clone(), to, to) > 0 {
+        return Err(CommandError::new(ErrorCode::NameInUse).arg("name", to));
+    }
+    let replaced = renamer(&mut spec, from, to);
+    if replaced == 0 {
+        return Err(CommandError::new(ErrorCode::NameNotFound).arg("name", from));
+    }
+    Ok(RenameResult { spec, replaced })
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// Rename an operationId everywhere it appears: the operation itself
+/// (including callbacks and webhooks) and every link that targets it.
+#[tauri::command]
+pub fn rename_operation_id(
+    spec: Value,
+    from: String,
+    to: String,
+) -> Result<RenameResult, CommandError> {
+    rename(spec, &from, &to, rename_operation_ids)
+}
+
+/// Rename a tag in the top-level tag list, on every operation and in
+/// `x-tagGroups`.
+#[tauri::command]
+pub fn rename_tag(spec: Value, from: String, to: String) -> Result<RenameResult, CommandError> {
+    rename(spec, &from, &to, rename_tags)
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn spec() -> Value {
+        json!({
+            "openapi": "3.1.0",
+            "tags": [{ "name": "pets" }, { "name": "store" }],
+            "x-tagGroups": [{ "name": "Core", "tags": ["pets", "store"] }],
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "operationId": "listPets",
+                        "tags": ["pets"],
+                        "responses": {
+                            "200": {
+                                "description": "OK",
+                                "links": { "first": { "operationId": "getPet" } },
+                                "content": {
+                                    "application/json": {
+                                        "example": { "operationId": "getPet", "tags": ["pets"] }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                },
+                "/pets/{id}": {
+                    "get": { "operationId": "getPet", "tags": ["pets", "store"] }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_rename_operation_id_updates_links_but_not_examples() {
+        let result =
+            rename_operation_id(spec(), "getPet".to_string(), "showPet".to_string()).unwrap();
+        assert_eq!(result.replaced, 2);
+        assert_eq!(
+            result.spec["paths"]["/pets/{id}"]["get"]["operationId"],
+            "showPet"
+        );
+        let response = &result.spec["paths"]["/pets"]["get"]["responses"]["200"];
+        assert_eq!(response["links"]["first"]["operationId"], "showPet");
+        assert_eq!(
+            response["content"]["application/json"]["example"]["operationId"],
+            "getPet"
+        );
+    }
+
+    #[test]
+    fn test_rename_tag_updates_definitions_operations_and_groups() {
+        let result = rename_tag(spec(), "pets".to_string(), " animals ".to_string()).unwrap();
+        assert_eq!(result.replaced, 4);
+        assert_eq!(result.spec["tags"][0]["name"], "animals");
+        assert_eq!(
+            result.spec["x-tagGroups"][0]["tags"],
+            json!(["animals", "store"])
+        );
+        assert_eq!(
+            result.spec["paths"]["/pets/{id}"]["get"]["tags"],
+            json!(["animals", "store"])
+        );
+    }
+
+    #[test]
+    fn test_rename_rejects_missing_taken_and_empty_names() {
+        for (from, to, code) in [
+            ("pets", "store", ErrorCode::NameInUse),
+            ("birds", "fowl", ErrorCode::NameNotFound),
+            ("pets", "  ", ErrorCode::InvalidName),
+        ] {
+            let err = rename_tag(spec(), from.to_string(), to.to_string()).unwrap_err();
+            assert_eq!(err.code, code, "{from} -> {to}");
+        }
+        let err =
+            rename_operation_id(spec(), "listPets".to_string(), "getPet".to_string()).unwrap_err();
+        assert_eq!(err.code, ErrorCode::NameInUse);
+    }
+}
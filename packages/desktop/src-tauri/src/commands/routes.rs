@@ -0,0 +1,304 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+const METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+/// A parsed spec from the workspace.
+#[derive(Debug, Clone, Deserialize)]
+pub struct WorkspaceSpec {
+    pub id: String,
+    pub spec: Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RouteRef {
+    pub spec_id: String,
+    /// The path as written in the spec.
+    pub path: String,
+    /// The path behind a gateway: the server's base path plus `path`.
+    pub route: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictKind {
+    /// The same method and route, up to parameter names.
+    Duplicate,
+    /// Templates from different specs that match some of the same paths,
+    /// e.g. `/users/{id}` and `/users/me`.
+    Ambiguous,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RouteConflict {
+    pub kind: ConflictKind,
+    /// Upper-case HTTP method.
+    pub method: String,
+    pub routes: Vec<RouteRef>,
+}
+
+struct Route {
+    method: String,
+    reference: RouteRef,
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Segment {
+    Literal(String),
+    /// Any segment containing a template expression (`{id}`, `v{n}.json`).
+    Param,
+}
+
+// ─── Routes ──────────────────────────────────────────────────────────────────
+
+/// Path of the first server URL (OpenAPI 3), or `basePath` (Swagger 2).
+fn base_path(spec: &Value) -> String {
+    let base = match spec["servers"][0]["url"].as_str() {
+        Some(url) => match url.find("://") {
+            Some(scheme_end) => {
+                let rest = &url[scheme_end + 3..];
+                rest.find('/').map_or("", |i| &rest[i..])
+            }
+            None => url,
+        },
+        None => spec["basePath"].as_str().unwrap_or_default(),
+    };
+    base.trim_end_matches('/').to_string()
+}
+
+fn segments(route: &str) -> Vec<Segment> {
+    route
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| {
+            if segment.contains('{') {
+                Segment::Param
+            } else {
+                Segment::Literal(segment.to_string())
+            }
+        })
+        .collect()
+}
+
+fn routes(spec: &WorkspaceSpec) -> Vec<Route> {
+    let base = base_path(&spec.spec);
+    let Some(paths) = spec.spec["paths"].as_object() else {
+        return Vec::new();
+    };
+    paths
+        .iter()
+        .flat_map(|(path, item)| {
+            let route = format!("{base}{path}");
+            METHODS
+                .iter()
+                .filter(|method| item.get(**method).is_some_and(Value::is_object))
+                .map(move |method| Route {
+                    method: method.to_ascii_uppercase(),
+                    segments: segments(&route),
+                    reference: RouteRef {
+                        spec_id: spec.id.clone(),
+                        path: path.clone(),
+                        route: route.clone(),
+                    },
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Whether some concrete path matches both templates.
+fn overlaps(a: &[Segment], b: &[Segment]) -> bool {
+    a.len() == b.len()
+        && a.iter().zip(b).all(|pair| match pair {
+            (Segment::Literal(a), Segment::Literal(b)) => a == b,
+            _ => true,
+        })
+}
+
+fn find_conflicts(specs: &[WorkspaceSpec]) -> Vec<RouteConflict> {
+    let all: Vec<Route> = specs.iter().flat_map(routes).collect();
+
+    let mut groups: BTreeMap<(&str, &[Segment]), Vec<&Route>> = BTreeMap::new();
+    for route in &all {
+        groups
+            .entry((route.method.as_str(), route.segments.as_slice()))
+            .or_default()
+            .push(route);
+    }
+
+    let mut conflicts: Vec<RouteConflict> = groups
+        .iter()
+        .filter(|(_, routes)| routes.len() > 1)
+        .map(|((method, _), routes)| RouteConflict {
+            kind: ConflictKind::Duplicate,
+            method: method.to_string(),
+            routes: routes.iter().map(|route| route.reference.clone()).collect(),
+        })
+        .collect();
+
+    // Ambiguity within one spec is ordinary routing (`/users/me` before
+    // `/users/{id}`); across services it depends on gateway rule order
+    let keys: Vec<_> = groups.keys().collect();
+    for (i, a) in keys.iter().enumerate() {
+        for b in &keys[i + 1..] {
+            if a.0 != b.0 || !overlaps(a.1, b.1) {
+                continue;
+            }
+            for route_a in &groups[*a] {
+                for route_b in &groups[*b] {
+                    if route_a.reference.spec_id != route_b.reference.spec_id {
+                        conflicts.push(RouteConflict {
+                            kind: ConflictKind::Ambiguous,
+                            method: a.0.to_string(),
+                            routes: vec![route_a.reference.clone(), route_b.reference.clone()],
+                        });
+                    }
+                }
+            }
+        }
+    }
+    conflicts
+}
+
+/// The route serving a request for `method` and the concrete `path`,
+/// preferring the template with the most literal segments as routers do
+/// (`/users/me` over `/users/{id}`).
+pub(super) fn match_route(specs: &[WorkspaceSpec], method: &str, path: &str) -> Option<RouteRef> {
+    let concrete = segments(path);
+    specs
+        .iter()
+        .flat_map(routes)
+        .filter(|route| route.method.eq_ignore_ascii_case(method))
+        .filter(|route| overlaps(&route.segments, &concrete))
+        .max_by_key(|route| {
+            route
+                .segments
+                .iter()
+                .filter(|segment| matches!(segment, Segment::Literal(_)))
+                .count()
+        })
+        .map(|route| route.reference)
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// Find routes that would collide behind one gateway: duplicate method and
+/// path pairs (including across specs, or the same path with different
+/// parameter names) and templates from different specs that overlap.
+#[tauri::command]
+pub fn find_route_conflicts(specs: Vec<WorkspaceSpec>) -> Vec<RouteConflict> {
+    find_conflicts(&specs)
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn spec(id: &str, server: &str, paths: Value) -> WorkspaceSpec {
+        WorkspaceSpec {
+            id: id.to_string(),
+            spec: json!({ "openapi": "3.0.3", "servers": [{ "url": server }], "paths": paths }),
+        }
+    }
+
+    #[test]
+    fn test_duplicates_across_specs_include_base_paths() {
+        let conflicts = find_route_conflicts(vec![
+            spec(
+                "orders",
+                "https://api.example.com/v1",
+                json!({ "/orders/{orderId}": { "get": {}, "delete": {} } }),
+            ),
+            spec(
+                "legacy",
+                "/v1/orders",
+                json!({ "/{id}": { "get": {} }, "/": { "post": {} } }),
+            ),
+        ]);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].kind, ConflictKind::Duplicate);
+        assert_eq!(conflicts[0].method, "GET");
+        let routes: Vec<&str> = conflicts[0]
+            .routes
+            .iter()
+            .map(|r| r.route.as_str())
+            .collect();
+        assert_eq!(routes, vec!["/v1/orders/{orderId}", "/v1/orders/{id}"]);
+    }
+
+    #[test]
+    fn test_ambiguous_templates_only_across_specs() {
+        let conflicts = find_route_conflicts(vec![
+            spec(
+                "users",
+                "/",
+                json!({ "/users/{id}": { "get": {} }, "/users/me": { "get": {} } }),
+            ),
+            spec(
+                "profile",
+                "/",
+                json!({ "/users/me": { "put": {} }, "/users/{id}/avatar": { "get": {} } }),
+            ),
+            spec("accounts", "/", json!({ "/users/{id}": { "put": {} } })),
+        ]);
+        assert_eq!(
+            conflicts,
+            vec![RouteConflict {
+                kind: ConflictKind::Ambiguous,
+                method: "PUT".to_string(),
+                routes: vec![
+                    RouteRef {
+                        spec_id: "profile".to_string(),
+                        path: "/users/me".to_string(),
+                        route: "/users/me".to_string(),
+                    },
+                    RouteRef {
+                        spec_id: "accounts".to_string(),
+                        path: "/users/{id}".to_string(),
+                        route: "/users/{id}".to_string(),
+                    },
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_swagger2_base_path() {
+        let swagger = WorkspaceSpec {
+            id: "s".to_string(),
+            spec: json!({ "swagger": "2.0", "basePath": "/api/", "paths": {} }),
+        };
+        assert_eq!(base_path(&swagger.spec), "/api");
+        assert!(find_route_conflicts(vec![swagger]).is_empty());
+    }
+
+    #[test]
+    fn test_match_route_prefers_literal_segments() {
+        let specs = vec![spec(
+            "users",
+            "https://api.example.com/v2",
+            json!({ "/users/{id}": { "get": {} }, "/users/me": { "get": {} } }),
+        )];
+        let matched = |method, path| match_route(&specs, method, path).map(|route| route.path);
+        assert_eq!(
+            matched("GET", "/v2/users/me"),
+            Some("/users/me".to_string())
+        );
+        assert_eq!(
+            matched("get", "/v2/users/42"),
+            Some("/users/{id}".to_string())
+        );
+        assert_eq!(matched("DELETE", "/v2/users/42"), None);
+        assert_eq!(matched("GET", "/users/42"), None);
+    }
+}
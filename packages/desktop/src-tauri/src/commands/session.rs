@@ -0,0 +1,129 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use super::ensure_main_window;
+use super::store::JsonStore;
+use crate::error::CommandError;
+
+const SESSION_FILE: &str = "session.json";
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TabKind {
+    Spec,
+    Operation,
+    Request,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionTab {
+    pub kind: TabKind,
+    /// Frontend id of the spec, operation or saved request.
+    pub id: String,
+    pub title: String,
+    /// Opaque view state (scroll position, selected panel, unsaved edits),
+    /// owned by the frontend.
+    #[serde(default)]
+    pub view_state: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Session {
+    pub tabs: Vec<SessionTab>,
+    /// Index into `tabs` of the focused tab.
+    pub active: Option<usize>,
+}
+
+// ─── Storage ─────────────────────────────────────────────────────────────────
+
+static SESSION: JsonStore<Session> = JsonStore::new(SESSION_FILE);
+
+/// A missing or unreadable session starts the app with no tabs rather than
+/// failing the launch; a file from an incompatible version is discarded.
+fn read_session(path: &Path) -> Session {
+    let mut session = SESSION.read(path).unwrap_or_default();
+    if session.active.is_some_and(|i| i >= session.tabs.len()) {
+        session.active = None;
+    }
+    session
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// Persist the open tabs. The frontend calls this whenever tabs change
+/// (debounced) and before the window closes.
+#[tauri::command]
+pub fn save_session(
+    app: AppHandle,
+    webview: tauri::Webview,
+    session: Session,
+) -> Result<(), CommandError> {
+    ensure_main_window(webview.label())?;
+
+    SESSION.write(&SESSION.path(&app)?, &session)
+}
+
+/// The tabs open at the end of the previous run, for restoring on launch.
+#[tauri::command]
+pub fn load_session(app: AppHandle, webview: tauri::Webview) -> Result<Session, CommandError> {
+    ensure_main_window(webview.label())?;
+
+    Ok(read_session(&SESSION.path(&app)?))
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("yasp-session-{name}-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir.join(SESSION_FILE)
+    }
+
+    #[test]
+    fn test_session_round_trips() {
+        let path = temp_path("round-trip");
+        let session = Session {
+            tabs: vec![SessionTab {
+                kind: TabKind::Operation,
+                id: "listPets".to_string(),
+                title: "GET /pets".to_string(),
+                view_state: serde_json::json!({"panel": "response"}),
+            }],
+            active: Some(0),
+        };
+        SESSION.write(&path, &session).unwrap();
+
+        let restored = read_session(&path);
+        assert_eq!(restored.tabs.len(), 1);
+        assert_eq!(restored.tabs[0].kind, TabKind::Operation);
+        assert_eq!(restored.tabs[0].view_state["panel"], "response");
+        assert_eq!(restored.active, Some(0));
+        assert!(!path.with_extension("json.tmp").exists());
+    }
+
+    #[test]
+    fn test_missing_or_corrupt_session_is_empty() {
+        let path = temp_path("corrupt");
+        let _ = std::fs::remove_file(&path);
+        assert!(read_session(&path).tabs.is_empty());
+
+        std::fs::write(&path, b"{not json").unwrap();
+        assert!(read_session(&path).tabs.is_empty());
+    }
+
+    #[test]
+    fn test_out_of_range_active_tab_is_dropped() {
+        let path = temp_path("active");
+        std::fs::write(&path, br#"{"tabs":[],"active":3}"#).unwrap();
+        assert_eq!(read_session(&path).active, None);
+    }
+}
@@ -0,0 +1,274 @@
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use super::{ensure_main_window, validate_url};
+use crate::error::{CommandError, ErrorCode};
+
+/// Same limit as specs fetched over HTTP.
+const MAX_SPEC_BYTES: u64 = 5 * 1024 * 1024; // 5 MB
+const SFTP_TIMEOUT: Duration = Duration::from_secs(30);
+const SFTP_CONNECT_TIMEOUT_SECS: &str = "10";
+
+/// Distinguishes the download files of concurrent fetches.
+static NEXT_DOWNLOAD: AtomicU64 = AtomicU64::new(1);
+
+// ─── Helpers ─────────────────────────────────────────────────────────────────
+
+/// Where a spec lives on an SFTP server: `sftp://[user@]host[:port]/path`.
+#[derive(Debug, PartialEq)]
+struct SftpTarget {
+    /// `user@host`, or just `host` to use the SSH config's user.
+    destination: String,
+    port: u16,
+    path: String,
+}
+
+fn invalid_sftp_url(url: &str) -> CommandError {
+    CommandError::new(ErrorCode::InvalidUrl)
+        .detail(format!("expected sftp://host/path, got '{url}'"))
+}
+
+/// OWASP A09:2025 – SSRF: the host goes through the same policy as HTTP
+/// requests. Only the port check is skipped, since SFTP runs on 22.
+fn parse_sftp_url(url: &str) -> Result<SftpTarget, CommandError> {
+    let parsed =
+        url::Url::parse(url).map_err(|e| CommandError::new(ErrorCode::InvalidUrl).detail(e))?;
+    if parsed.scheme() != "sftp" {
+        return Err(invalid_sftp_url(url));
+    }
+    let host = parsed
+        .host()
+        .ok_or_else(|| CommandError::new(ErrorCode::MissingHost))?;
+    validate_url(&format!("https://{host}/"))?;
+
+    let path = percent_decode(parsed.path()).ok_or_else(|| invalid_sftp_url(url))?;
+    // A newline would end the batch command and start another one
+    if path.len() <= 1 || path.contains(['\n', '\r']) {
+        return Err(invalid_sftp_url(url));
+    }
+
+    let host = match host {
+        url::Host::Ipv6(ip) => ip.to_string(),
+        host => host.to_string(),
+    };
+    let user = percent_decode(parsed.username()).ok_or_else(|| invalid_sftp_url(url))?;
+    // Never let the destination look like an option, even after `--`
+    if user.starts_with('-') || user.contains(['\n', '\r']) {
+        return Err(invalid_sftp_url(url));
+    }
+    Ok(SftpTarget {
+        destination: if user.is_empty() {
+            host
+        } else {
+            format!("{user}@{host}")
+        },
+        port: parsed.port().unwrap_or(22),
+        path,
+    })
+}
+
+fn percent_decode(value: &str) -> Option<String> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = std::str::from_utf8(bytes.get(i + 1..i + 3)?).ok()?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+/// Quote a path for an sftp batch file.
+fn batch_quote(path: &str) -> String {
+    format!("\"{}\"", path.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// OpenSSH `sftp` in batch mode: key authentication only, no prompts, and
+/// the server must already be in `known_hosts`.
+///
+/// OWASP A07:2025 – Authentication Failures: an unknown or changed host key
+/// fails the fetch instead of being trusted on first use.
+fn sftp_args(target: &SftpTarget, identity_file: &Path) -> Vec<String> {
+    [
+        "-b",
+        "-",
+        "-P",
+        &target.port.to_string(),
+        "-i",
+        &identity_file.to_string_lossy(),
+        "-o",
+        "BatchMode=yes",
+        "-o",
+        "IdentitiesOnly=yes",
+        "-o",
+        "StrictHostKeyChecking=yes",
+        "-o",
+        &format!("ConnectTimeout={SFTP_CONNECT_TIMEOUT_SECS}"),
+        "--",
+        &target.destination,
+    ]
+    .iter()
+    .map(|arg| arg.to_string())
+    .collect()
+}
+
+/// Download `target` into `local` with the system `sftp` client.
+async fn run_sftp_get(
+    target: &SftpTarget,
+    identity_file: &Path,
+    local: &Path,
+) -> Result<(), CommandError> {
+    let batch = format!(
+        "get {} {}\n",
+        batch_quote(&target.path),
+        batch_quote(&local.to_string_lossy())
+    );
+
+    let mut child = Command::new("sftp")
+        .args(sftp_args(target, identity_file))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| {
+            CommandError::new(ErrorCode::ToolUnavailable)
+                .arg("tool", "sftp")
+                .detail(e)
+        })?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(batch.as_bytes())
+            .await
+            .map_err(|e| CommandError::new(ErrorCode::RequestFailed).detail(e))?;
+    }
+
+    let output = tokio::time::timeout(SFTP_TIMEOUT, child.wait_with_output())
+        .await
+        .map_err(|_| CommandError::new(ErrorCode::Timeout))?
+        .map_err(|e| CommandError::new(ErrorCode::RequestFailed).detail(e))?;
+    if !output.status.success() {
+        return Err(CommandError::new(ErrorCode::RequestFailed)
+            .detail(String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(())
+}
+
+fn download_path() -> PathBuf {
+    let n = NEXT_DOWNLOAD.fetch_add(1, Ordering::Relaxed);
+    std::env::temp_dir().join(format!("yasp-sftp-{}-{n}.spec", std::process::id()))
+}
+
+fn read_spec(path: &Path) -> Result<String, CommandError> {
+    let size = std::fs::metadata(path)
+        .map_err(|e| CommandError::new(ErrorCode::BodyReadFailed).detail(e))?
+        .len();
+    // OWASP A04:2025 – Insecure Design: enforce the 5MB spec limit
+    if size > MAX_SPEC_BYTES {
+        return Err(CommandError::new(ErrorCode::BodyTooLarge).arg("limit", "5MB"));
+    }
+    let bytes =
+        std::fs::read(path).map_err(|e| CommandError::new(ErrorCode::BodyReadFailed).detail(e))?;
+    String::from_utf8(bytes).map_err(|_| CommandError::new(ErrorCode::InvalidUtf8))
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// Fetch a spec file from `sftp://[user@]host[:port]/path`, authenticating
+/// with the private key at `identity_file`.
+///
+/// This is a spec source only: the SFTP transport is never used for API
+/// requests. Plain FTP is not offered, since it sends credentials in clear text.
+#[tauri::command]
+pub async fn fetch_spec_sftp(
+    webview: tauri::Webview,
+    url: String,
+    identity_file: String,
+) -> Result<String, CommandError> {
+    ensure_main_window(webview.label())?;
+
+    let target = parse_sftp_url(&url)?;
+    let identity_file = PathBuf::from(identity_file);
+    if !identity_file.is_file() {
+        return Err(
+            CommandError::new(ErrorCode::InvalidKeyFile).arg("path", identity_file.display())
+        );
+    }
+
+    let local = download_path();
+    let result = run_sftp_get(&target, &identity_file, &local)
+        .await
+        .and_then(|()| read_spec(&local));
+    let _ = std::fs::remove_file(&local);
+    result
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sftp_url_reads_user_port_and_path() {
+        let target = parse_sftp_url("sftp://ci@specs.example.com:2222/pub/api%20v2.yaml").unwrap();
+        assert_eq!(
+            target,
+            SftpTarget {
+                destination: "ci@specs.example.com".to_string(),
+                port: 2222,
+                path: "/pub/api v2.yaml".to_string(),
+            }
+        );
+        assert_eq!(
+            parse_sftp_url("sftp://specs.example.com/openapi.json")
+                .unwrap()
+                .port,
+            22
+        );
+    }
+
+    #[test]
+    fn test_parse_sftp_url_rejects_other_schemes_and_missing_paths() {
+        let err = parse_sftp_url("https://specs.example.com/openapi.json").unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidUrl);
+        assert!(parse_sftp_url("sftp://specs.example.com").is_err());
+        assert!(parse_sftp_url("sftp://specs.example.com/a%0Aget%20x").is_err());
+        assert!(parse_sftp_url("sftp://-oProxyCommand@specs.example.com/a").is_err());
+    }
+
+    #[test]
+    fn test_parse_sftp_url_applies_ssrf_policy() {
+        let err = parse_sftp_url("sftp://10.0.0.5/openapi.json").unwrap_err();
+        assert_eq!(err.code, ErrorCode::BlockedIp);
+        let err = parse_sftp_url("sftp://169.254.169.254/openapi.json").unwrap_err();
+        assert_eq!(err.code, ErrorCode::BlockedHost);
+    }
+
+    #[test]
+    fn test_batch_quote_escapes_quotes() {
+        assert_eq!(batch_quote(r#"/a "b"\c"#), r#""/a \"b\"\\c""#);
+    }
+
+    #[test]
+    fn test_sftp_args_enforce_key_auth_and_host_checking() {
+        let target = parse_sftp_url("sftp://ci@specs.example.com/openapi.json").unwrap();
+        let args = sftp_args(&target, Path::new("/keys/id_ed25519"));
+        assert!(args.windows(2).any(|w| w == ["-o", "BatchMode=yes"]));
+        assert!(args
+            .windows(2)
+            .any(|w| w == ["-o", "StrictHostKeyChecking=yes"]));
+        assert_eq!(args[args.len() - 2..], ["--", "ci@specs.example.com"]);
+    }
+}
@@ -0,0 +1,230 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::routes::WorkspaceSpec;
+
+/// Pairs below this score are not reported unless the caller lowers it.
+const DEFAULT_THRESHOLD: f64 = 0.7;
+
+/// Schemas with fewer properties match too easily to be worth reporting.
+const MIN_PROPERTIES: usize = 2;
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SchemaRef {
+    pub spec_id: String,
+    pub name: String,
+}
+
+/// Two schemas from different specs that look like the same model.
+#[derive(Debug, Clone, Serialize)]
+pub struct SimilarSchemas {
+    pub a: SchemaRef,
+    pub b: SchemaRef,
+    /// 0–1: shared properties over all properties, with shared names of
+    /// different types counting half.
+    pub similarity: f64,
+    /// Properties with the same name and type in both.
+    pub shared: Vec<String>,
+    /// Properties in both whose types differ.
+    pub type_mismatches: Vec<String>,
+    pub only_in_a: Vec<String>,
+    pub only_in_b: Vec<String>,
+}
+
+struct Schema {
+    reference: SchemaRef,
+    /// Property name to a short type description.
+    properties: BTreeMap<String, String>,
+}
+
+// ─── Fingerprints ────────────────────────────────────────────────────────────
+
+/// `string`, `integer`, `array<string>`, `ref:Address`, ...
+fn type_of(schema: &Value) -> String {
+    if let Some(reference) = schema["$ref"].as_str() {
+        return format!("ref:{}", reference.rsplit('/').next().unwrap_or(reference));
+    }
+    match schema["type"].as_str() {
+        Some("array") => format!("array<{}>", type_of(&schema["items"])),
+        Some(kind) => kind.to_string(),
+        None if schema["properties"].is_object() => "object".to_string(),
+        None => "any".to_string(),
+    }
+}
+
+/// Own properties plus those of inline `allOf` members.
+fn properties(schema: &Value, into: &mut BTreeMap<String, String>) {
+    if let Some(properties) = schema["properties"].as_object() {
+        for (name, property) in properties {
+            into.insert(name.clone(), type_of(property));
+        }
+    }
+    for member in schema["allOf"].as_array().into_iter().flatten() {
+        properties(member, into);
+    }
+}
+
+fn schemas(spec: &WorkspaceSpec) -> Vec<Schema> {
+    let definitions = spec.spec["components"]["schemas"]
+        .as_object()
+        .or_else(|| spec.spec["definitions"].as_object());
+    definitions
+        .into_iter()
+        .flatten()
+        .filter_map(|(name, schema)| {
+            let mut props = BTreeMap::new();
+            properties(schema, &mut props);
+            (props.len() >= MIN_PROPERTIES).then(|| Schema {
+                reference: SchemaRef {
+                    spec_id: spec.id.clone(),
+                    name: name.clone(),
+                },
+                properties: props,
+            })
+        })
+        .collect()
+}
+
+fn compare(a: &Schema, b: &Schema) -> SimilarSchemas {
+    let mut shared = Vec::new();
+    let mut type_mismatches = Vec::new();
+    let mut only_in_a = Vec::new();
+    for (name, kind) in &a.properties {
+        match b.properties.get(name) {
+            Some(other) if other == kind => shared.push(name.clone()),
+            Some(_) => type_mismatches.push(name.clone()),
+            None => only_in_a.push(name.clone()),
+        }
+    }
+    let only_in_b: Vec<String> = b
+        .properties
+        .keys()
+        .filter(|name| !a.properties.contains_key(*name))
+        .cloned()
+        .collect();
+
+    let union = shared.len() + type_mismatches.len() + only_in_a.len() + only_in_b.len();
+    let similarity = (shared.len() as f64 + 0.5 * type_mismatches.len() as f64) / union as f64;
+    SimilarSchemas {
+        a: a.reference.clone(),
+        b: b.reference.clone(),
+        similarity,
+        shared,
+        type_mismatches,
+        only_in_a,
+        only_in_b,
+    }
+}
+
+fn similar_schemas(specs: &[WorkspaceSpec], threshold: f64) -> Vec<SimilarSchemas> {
+    let all: Vec<Schema> = specs.iter().flat_map(schemas).collect();
+    let mut pairs: Vec<SimilarSchemas> = all
+        .iter()
+        .enumerate()
+        .flat_map(|(i, a)| {
+            all[i + 1..]
+                .iter()
+                .filter(move |b| a.reference.spec_id != b.reference.spec_id)
+                .map(move |b| compare(a, b))
+        })
+        .filter(|pair| pair.similarity >= threshold)
+        .collect();
+    pairs.sort_by(|x, y| y.similarity.total_cmp(&x.similarity));
+    pairs
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// Pairs of structurally similar schemas in different specs, most similar
+/// first, as candidates for a shared model. Schemas are compared by
+/// property names and types, whatever they are called; `threshold`
+/// (0–1, default 0.7) sets the minimum similarity reported.
+#[tauri::command]
+pub fn find_similar_schemas(
+    specs: Vec<WorkspaceSpec>,
+    threshold: Option<f64>,
+) -> Vec<SimilarSchemas> {
+    similar_schemas(
+        &specs,
+        threshold.unwrap_or(DEFAULT_THRESHOLD).clamp(0.0, 1.0),
+    )
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn spec(id: &str, schemas: Value) -> WorkspaceSpec {
+        WorkspaceSpec {
+            id: id.to_string(),
+            spec: json!({ "openapi": "3.0.3", "components": { "schemas": schemas } }),
+        }
+    }
+
+    fn address(extra: Value) -> Value {
+        let mut properties = json!({
+            "street": { "type": "string" },
+            "city": { "type": "string" },
+            "postcode": { "type": "string" }
+        });
+        properties
+            .as_object_mut()
+            .unwrap()
+            .extend(extra.as_object().unwrap().clone());
+        json!({ "type": "object", "properties": properties })
+    }
+
+    #[test]
+    fn test_reports_similar_models_across_specs_only() {
+        let pairs = find_similar_schemas(
+            vec![
+                spec(
+                    "orders",
+                    json!({
+                        "Address": address(json!({ "country": { "type": "string" } })),
+                        "ShippingAddress": address(json!({})),
+                    }),
+                ),
+                spec(
+                    "users",
+                    json!({
+                        "PostalAddress": address(json!({ "country": { "$ref": "#/components/schemas/Country" } })),
+                        "User": { "properties": { "id": { "type": "integer" }, "name": { "type": "string" } } }
+                    }),
+                ),
+            ],
+            None,
+        );
+        // ShippingAddress/Address are in the same spec and not compared
+        assert_eq!(pairs.len(), 2);
+        assert_eq!(pairs[0].a.name, "Address");
+        assert_eq!(pairs[0].b.name, "PostalAddress");
+        assert_eq!(pairs[0].type_mismatches, vec!["country"]);
+        assert_eq!(pairs[0].similarity, 0.875);
+        assert_eq!(pairs[1].a.name, "ShippingAddress");
+        assert_eq!(pairs[1].only_in_b, vec!["country"]);
+        assert_eq!(pairs[1].similarity, 0.75);
+    }
+
+    #[test]
+    fn test_type_of_and_all_of_properties() {
+        let schema = json!({
+            "allOf": [
+                { "$ref": "#/components/schemas/Base" },
+                { "properties": { "tags": { "type": "array", "items": { "type": "string" } } } }
+            ],
+            "properties": { "owner": { "$ref": "#/definitions/User" } }
+        });
+        let mut props = BTreeMap::new();
+        properties(&schema, &mut props);
+        assert_eq!(props["tags"], "array<string>");
+        assert_eq!(props["owner"], "ref:User");
+    }
+}
@@ -0,0 +1,306 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_deep_link::DeepLinkExt;
+
+use super::MAIN_WINDOW;
+use crate::error::{CommandError, ErrorCode};
+
+/// Custom URL scheme registered for the desktop app (see tauri.conf.json).
+pub const DEEP_LINK_SCHEME: &str = "yasp";
+
+/// Emitted to the main window with an `OperationLink` when a `yasp://` link
+/// opens the app or reaches it while running. The frontend owns the specs,
+/// so it opens the operation.
+pub const OPEN_OPERATION_EVENT: &str = "deep-link://open-operation";
+
+/// OWASP A06:2025 – Identification and Authentication Failures: credentials
+/// must never be copied into shared documentation.
+const REDACTED_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "x-api-key",
+];
+pub(super) const REDACTED_VALUE: &str = "<redacted>";
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+/// Identifies the operation a snippet documents.
+#[derive(Debug, Deserialize)]
+pub struct OperationRef {
+    pub spec_id: String,
+    pub operation_id: String,
+    pub summary: Option<String>,
+}
+
+/// The operation a `yasp://` link points at.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OperationLink {
+    pub spec_id: String,
+    pub operation_id: String,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SnippetFormat {
+    Markdown,
+    Html,
+}
+
+// ─── Rendering ───────────────────────────────────────────────────────────────
+
+/// `yasp://operation?spec=<id>&operation=<operationId>`
+fn deep_link(operation: &OperationRef) -> String {
+    let mut link = url::Url::parse(&format!("{DEEP_LINK_SCHEME}://operation"))
+        .expect("static deep link base is a valid URL");
+    link.query_pairs_mut()
+        .append_pair("spec", &operation.spec_id)
+        .append_pair("operation", &operation.operation_id);
+    link.into()
+}
+
+/// Quote for POSIX shells: wrap in single quotes, closing and escaping any
+/// embedded single quote.
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+fn curl_command(
+    method: &str,
+    url: &url::Url,
+    headers: &HashMap<String, String>,
+    body: Option<&str>,
+) -> String {
+    let method = method.to_uppercase();
+    // Method and URL on the first line, one option per continuation line
+    let mut parts = vec![if method != "GET" || body.is_some() {
+        format!("curl -X {method} {}", shell_quote(url.as_str()))
+    } else {
+        format!("curl {}", shell_quote(url.as_str()))
+    }];
+
+    // Sorted so the same request always produces the same snippet
+    let sorted: BTreeMap<_, _> = headers.iter().collect();
+    for (name, value) in sorted {
+        let value = if REDACTED_HEADERS.contains(&name.to_ascii_lowercase().as_str()) {
+            REDACTED_VALUE
+        } else {
+            value.as_str()
+        };
+        parts.push(format!("-H {}", shell_quote(&format!("{name}: {value}"))));
+    }
+
+    if let Some(body) = body {
+        parts.push(format!("--data-raw {}", shell_quote(body)));
+    }
+    parts.join(" \\\n  ")
+}
+
+/// A fence longer than any backtick run in the content, so the code block
+/// can't be terminated early by the request body.
+fn markdown_fence(content: &str) -> String {
+    let longest_run = content.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    "`".repeat(longest_run.max(2) + 1)
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+fn render(
+    operation: &OperationRef,
+    method: &str,
+    url: &url::Url,
+    curl: &str,
+    format: SnippetFormat,
+) -> String {
+    let title = operation
+        .summary
+        .as_deref()
+        .unwrap_or(&operation.operation_id);
+    let heading = format!("{} {} — {title}", method.to_uppercase(), url.path());
+    let link = deep_link(operation);
+
+    match format {
+        SnippetFormat::Markdown => {
+            let fence = markdown_fence(curl);
+            format!("### {heading}\n\n[Open in YASP](<{link}>)\n\n{fence}bash\n{curl}\n{fence}\n")
+        }
+        SnippetFormat::Html => format!(
+            "<h3>{}</h3>\n<p><a href=\"{}\">Open in YASP</a></p>\n<pre><code class=\"language-bash\">{}</code></pre>\n",
+            escape_html(&heading),
+            escape_html(&link),
+            escape_html(curl),
+        ),
+    }
+}
+
+// ─── Deep links ──────────────────────────────────────────────────────────────
+
+/// Reads back a link made by `deep_link`; `None` for any other URL.
+fn parse_deep_link(link: &url::Url) -> Option<OperationLink> {
+    if link.scheme() != DEEP_LINK_SCHEME || link.host_str() != Some("operation") {
+        return None;
+    }
+    let query = |name: &str| {
+        link.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    };
+    Some(OperationLink {
+        spec_id: query("spec")?,
+        operation_id: query("operation")?,
+    })
+}
+
+fn open_links(app: &AppHandle, links: &[url::Url]) {
+    for link in links.iter().filter_map(parse_deep_link) {
+        let _ = app.emit_to(MAIN_WINDOW, OPEN_OPERATION_EVENT, link);
+    }
+}
+
+/// Forward links that reach the running app to the main window.
+pub fn watch_deep_links(app: &AppHandle) {
+    let handle = app.clone();
+    app.deep_link()
+        .on_open_url(move |event| open_links(&handle, &event.urls()));
+}
+
+/// Forward the link the app was launched with, once the frontend listens.
+pub fn open_launch_link(app: &AppHandle) {
+    if let Ok(Some(links)) = app.deep_link().get_current() {
+        open_links(app, &links);
+    }
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// Generate a paste-ready documentation snippet for an operation: a curl
+/// command for the given request plus a `yasp://` link that opens the
+/// operation in the desktop app. Credential headers are redacted.
+#[tauri::command]
+pub fn generate_operation_snippet(
+    operation: OperationRef,
+    method: String,
+    url: String,
+    headers: HashMap<String, String>,
+    body: Option<String>,
+    format: SnippetFormat,
+) -> Result<String, CommandError> {
+    let parsed_url =
+        url::Url::parse(&url).map_err(|e| CommandError::new(ErrorCode::InvalidUrl).detail(e))?;
+    let curl = curl_command(&method, &parsed_url, &headers, body.as_deref());
+    Ok(render(&operation, &method, &parsed_url, &curl, format))
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn operation() -> OperationRef {
+        OperationRef {
+            spec_id: "petstore v2".to_string(),
+            operation_id: "listPets".to_string(),
+            summary: Some("List pets".to_string()),
+        }
+    }
+
+    fn url(value: &str) -> url::Url {
+        url::Url::parse(value).unwrap()
+    }
+
+    #[test]
+    fn test_deep_link_encodes_query() {
+        assert_eq!(
+            deep_link(&operation()),
+            "yasp://operation?spec=petstore+v2&operation=listPets"
+        );
+    }
+
+    #[test]
+    fn test_parse_deep_link_reads_back_deep_link() {
+        assert_eq!(
+            parse_deep_link(&url(&deep_link(&operation()))),
+            Some(OperationLink {
+                spec_id: "petstore v2".to_string(),
+                operation_id: "listPets".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_deep_link(&url("yasp://operation?spec=petstore")),
+            None
+        );
+        assert_eq!(
+            parse_deep_link(&url("https://operation?spec=a&operation=b")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_shell_quote_escapes_single_quotes() {
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+    }
+
+    #[test]
+    fn test_curl_omits_method_for_plain_get() {
+        let curl = curl_command(
+            "get",
+            &url("https://api.example.com/pets"),
+            &HashMap::new(),
+            None,
+        );
+        assert_eq!(curl, "curl 'https://api.example.com/pets'");
+    }
+
+    #[test]
+    fn test_curl_redacts_credentials() {
+        let headers = HashMap::from([
+            ("Authorization".to_string(), "Bearer secret".to_string()),
+            ("Accept".to_string(), "application/json".to_string()),
+        ]);
+        let curl = curl_command(
+            "POST",
+            &url("https://api.example.com/pets"),
+            &headers,
+            Some("{}"),
+        );
+        assert!(!curl.contains("secret"));
+        assert!(curl.contains("-H 'Authorization: <redacted>'"));
+        assert!(curl.contains("-H 'Accept: application/json'"));
+        assert!(curl.contains("--data-raw '{}'"));
+    }
+
+    #[test]
+    fn test_markdown_fence_outgrows_body_backticks() {
+        assert_eq!(markdown_fence("no ticks"), "```");
+        assert_eq!(markdown_fence("has ```` four"), "`````");
+    }
+
+    #[test]
+    fn test_html_escapes_content() {
+        let curl = curl_command(
+            "POST",
+            &url("https://api.example.com/pets"),
+            &HashMap::new(),
+            Some("<script>"),
+        );
+        let html = render(
+            &operation(),
+            "POST",
+            &url("https://api.example.com/pets"),
+            &curl,
+            SnippetFormat::Html,
+        );
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(!html.contains("<script>"));
+    }
+}
@@ -0,0 +1,277 @@
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::error::{CommandError, ErrorCode};
+
+/// How long changes to a `CachedJsonStore` gather before they are written.
+const SAVE_DELAY: Duration = Duration::from_secs(2);
+
+/// A JSON file in the app data directory holding one `T`.
+pub struct JsonStore<T> {
+    file: &'static str,
+    /// Serializes read-modify-write cycles on the file.
+    lock: Mutex<()>,
+    value: PhantomData<fn() -> T>,
+}
+
+impl<T: Default + Serialize + DeserializeOwned> JsonStore<T> {
+    pub const fn new(file: &'static str) -> Self {
+        Self {
+            file,
+            lock: Mutex::new(()),
+            value: PhantomData,
+        }
+    }
+
+    pub fn path(&self, app: &AppHandle) -> Result<PathBuf, CommandError> {
+        let dir = app
+            .path()
+            .app_data_dir()
+            .map_err(|e| CommandError::new(ErrorCode::StorageFailed).detail(e))?;
+        Ok(dir.join(self.file))
+    }
+
+    /// A missing file reads as the default. A file that cannot be read or
+    /// parsed is an error, never the default, so an update cannot replace
+    /// what it held with nothing.
+    pub fn read(&self, path: &Path) -> Result<T, CommandError> {
+        let bytes = match std::fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(T::default()),
+            Err(e) => return Err(CommandError::new(ErrorCode::StorageFailed).detail(e)),
+        };
+        serde_json::from_slice(&bytes)
+            .map_err(|e| CommandError::new(ErrorCode::StorageFailed).detail(e))
+    }
+
+    /// Replace the file's contents.
+    pub fn write(&self, path: &Path, value: &T) -> Result<(), CommandError> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        write_json(path, value)
+    }
+
+    /// Read, change and write the file while holding its lock. Nothing is
+    /// written if `update` fails.
+    pub fn update<R>(
+        &self,
+        path: &Path,
+        update: impl FnOnce(&mut T) -> Result<R, CommandError>,
+    ) -> Result<R, CommandError> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut value = self.read(path)?;
+        let result = update(&mut value)?;
+        write_json(path, &value)?;
+        Ok(result)
+    }
+}
+
+/// A `JsonStore` for files changed by every request. The file is read once
+/// and kept in memory; changes are written back from a blocking task, at
+/// most once per `SAVE_DELAY`, so requests never wait on the disk.
+pub struct CachedJsonStore<T> {
+    store: JsonStore<T>,
+    state: Mutex<Cached<T>>,
+}
+
+struct Cached<T> {
+    /// `None` until first used. A file that could not be read stays an
+    /// error, and is never written, until the value is replaced.
+    value: Option<Result<T, CommandError>>,
+    save_pending: bool,
+}
+
+impl<T: Clone + Default + Serialize + DeserializeOwned + Send + 'static> CachedJsonStore<T> {
+    pub const fn new(file: &'static str) -> Self {
+        Self {
+            store: JsonStore::new(file),
+            state: Mutex::new(Cached {
+                value: None,
+                save_pending: false,
+            }),
+        }
+    }
+
+    pub fn path(&self, app: &AppHandle) -> Result<PathBuf, CommandError> {
+        self.store.path(app)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Cached<T>> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn loaded<'a>(&self, path: &Path, state: &'a mut Cached<T>) -> Result<&'a mut T, CommandError> {
+        state
+            .value
+            .get_or_insert_with(|| self.store.read(path))
+            .as_mut()
+            .map_err(|e| e.clone())
+    }
+
+    pub fn read<R>(&self, path: &Path, read: impl FnOnce(&T) -> R) -> Result<R, CommandError> {
+        let mut state = self.lock();
+        Ok(read(self.loaded(path, &mut state)?))
+    }
+
+    /// Change the value in memory and schedule a save.
+    pub fn update<R>(
+        &'static self,
+        path: &Path,
+        update: impl FnOnce(&mut T) -> R,
+    ) -> Result<R, CommandError> {
+        let mut state = self.lock();
+        let result = update(self.loaded(path, &mut state)?);
+        self.schedule_save(path, &mut state);
+        Ok(result)
+    }
+
+    /// Start afresh with `value`, replacing a file that could not be read.
+    pub fn replace(&'static self, path: &Path, value: T) {
+        let mut state = self.lock();
+        state.value = Some(Ok(value));
+        self.schedule_save(path, &mut state);
+    }
+
+    fn schedule_save(&'static self, path: &Path, state: &mut Cached<T>) {
+        if std::mem::replace(&mut state.save_pending, true) {
+            return;
+        }
+        let path = path.to_path_buf();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(SAVE_DELAY).await;
+            // A failed save is retried with the next change; the value is
+            // still in memory
+            let _ = tauri::async_runtime::spawn_blocking(move || self.save(&path)).await;
+        });
+    }
+
+    /// Write pending changes now, e.g. before the app exits.
+    pub fn save(&self, path: &Path) -> Result<(), CommandError> {
+        let value = {
+            let mut state = self.lock();
+            if !std::mem::replace(&mut state.save_pending, false) {
+                return Ok(());
+            }
+            match &state.value {
+                Some(Ok(value)) => value.clone(),
+                _ => return Ok(()),
+            }
+        };
+        self.store.write(path, &value)
+    }
+}
+
+/// Write via a temporary file and rename, so a crash mid-write never leaves
+/// a truncated file behind.
+fn write_json(path: &Path, value: &impl Serialize) -> Result<(), CommandError> {
+    let json = serde_json::to_vec_pretty(value)
+        .map_err(|e| CommandError::new(ErrorCode::StorageFailed).detail(e))?;
+    let tmp = path.with_extension("json.tmp");
+    path.parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|_| std::fs::write(&tmp, json))
+        .and_then(|_| std::fs::rename(&tmp, path))
+        .map_err(|e| CommandError::new(ErrorCode::StorageFailed).detail(e))
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    static STORE: JsonStore<BTreeMap<String, u32>> = JsonStore::new("store.json");
+
+    fn temp_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("yasp-store-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir.join("store.json")
+    }
+
+    #[test]
+    fn test_missing_file_reads_as_default() {
+        let path = temp_path("missing");
+        assert!(STORE.read(&path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_update_round_trips() {
+        let path = temp_path("update");
+        let count = STORE
+            .update(&path, |counts| {
+                counts.insert("a".to_string(), 1);
+                Ok(counts.len())
+            })
+            .unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(STORE.read(&path).unwrap()["a"], 1);
+    }
+
+    #[test]
+    fn test_corrupt_file_is_an_error_and_never_overwritten() {
+        let path = temp_path("corrupt");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "{not json").unwrap();
+
+        assert_eq!(
+            STORE.read(&path).unwrap_err().code,
+            ErrorCode::StorageFailed
+        );
+        let updated = STORE.update(&path, |counts| {
+            counts.insert("a".to_string(), 1);
+            Ok(())
+        });
+        assert_eq!(updated.unwrap_err().code, ErrorCode::StorageFailed);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "{not json");
+    }
+
+    #[test]
+    fn test_cached_changes_are_written_by_save() {
+        let path = temp_path("cached");
+        let cached: &'static CachedJsonStore<BTreeMap<String, u32>> =
+            Box::leak(Box::new(CachedJsonStore::new("store.json")));
+        cached
+            .update(&path, |counts| counts.insert("a".to_string(), 1))
+            .unwrap();
+        assert_eq!(cached.read(&path, |counts| counts["a"]).unwrap(), 1);
+        assert!(!path.exists());
+
+        cached.save(&path).unwrap();
+        assert_eq!(STORE.read(&path).unwrap()["a"], 1);
+    }
+
+    #[test]
+    fn test_cached_corrupt_file_is_kept_until_replaced() {
+        let path = temp_path("cached-corrupt");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "{not json").unwrap();
+        let cached: &'static CachedJsonStore<BTreeMap<String, u32>> =
+            Box::leak(Box::new(CachedJsonStore::new("store.json")));
+
+        let err = cached.update(&path, |counts| counts.clear()).unwrap_err();
+        assert_eq!(err.code, ErrorCode::StorageFailed);
+        cached.save(&path).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "{not json");
+
+        cached.replace(&path, BTreeMap::new());
+        cached.save(&path).unwrap();
+        assert!(STORE.read(&path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_failed_update_writes_nothing() {
+        let path = temp_path("failed");
+        let updated: Result<(), _> = STORE.update(&path, |counts| {
+            counts.insert("a".to_string(), 1);
+            Err(CommandError::new(ErrorCode::StorageFailed))
+        });
+        assert!(updated.is_err());
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,404 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager};
+
+use super::ensure_main_window;
+use crate::error::{CommandError, ErrorCode};
+
+const TEMPLATES_DIR: &str = "spec-templates";
+
+/// Built-in templates as (name, description), in `list_spec_templates` order.
+const BUILTIN_TEMPLATES: &[(&str, &str)] = &[
+    ("minimal", "Info, servers and an empty path list"),
+    (
+        "rest",
+        "REST service with problem+json errors and JWT bearer auth",
+    ),
+    (
+        "rest-api-key",
+        "REST service with problem+json errors and an API key header",
+    ),
+    (
+        "rest-oauth2",
+        "REST service with problem+json errors and OAuth 2.0 client credentials",
+    ),
+];
+
+const DEFAULT_SERVER: &str = "https://api.example.com/v1";
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SpecTemplate {
+    pub name: String,
+    pub description: Option<String>,
+    pub builtin: bool,
+}
+
+/// Values filled into the generated document.
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewSpecOptions {
+    pub title: String,
+    #[serde(default = "default_version")]
+    pub version: String,
+    pub description: Option<String>,
+    /// Replaces the template's servers when set.
+    pub server_url: Option<String>,
+}
+
+fn default_version() -> String {
+    "0.1.0".to_string()
+}
+
+// ─── Built-ins ───────────────────────────────────────────────────────────────
+
+/// RFC 9457 problem details, shared by every error response.
+fn problem_components() -> Value {
+    let problem_response = |description: &str| {
+        json!({
+            "description": description,
+            "content": {
+                "application/problem+json": {
+                    "schema": { "$ref": "#/components/schemas/Problem" }
+                }
+            }
+        })
+    };
+    json!({
+        "schemas": {
+            "Problem": {
+                "type": "object",
+                "description": "Error details (RFC 9457)",
+                "properties": {
+                    "type": { "type": "string", "format": "uri", "default": "about:blank" },
+                    "title": { "type": "string" },
+                    "status": { "type": "integer", "format": "int32" },
+                    "detail": { "type": "string" },
+                    "instance": { "type": "string", "format": "uri-reference" }
+                },
+                "required": ["title", "status"]
+            }
+        },
+        "responses": {
+            "BadRequest": problem_response("The request is malformed"),
+            "Unauthorized": problem_response("Authentication is missing or invalid"),
+            "NotFound": problem_response("The resource does not exist"),
+            "Error": problem_response("Unexpected error")
+        }
+    })
+}
+
+fn security_scheme(template: &str) -> Option<(&'static str, Value)> {
+    match template {
+        "rest" => Some((
+            "bearerAuth",
+            json!({ "type": "http", "scheme": "bearer", "bearerFormat": "JWT" }),
+        )),
+        "rest-api-key" => Some((
+            "apiKeyAuth",
+            json!({ "type": "apiKey", "in": "header", "name": "X-API-Key" }),
+        )),
+        "rest-oauth2" => Some((
+            "oauth2",
+            json!({
+                "type": "oauth2",
+                "flows": {
+                    "clientCredentials": {
+                        "tokenUrl": "https://auth.example.com/oauth/token",
+                        "scopes": {}
+                    }
+                }
+            }),
+        )),
+        _ => None,
+    }
+}
+
+fn builtin_template(name: &str) -> Option<Value> {
+    let mut spec = json!({
+        "openapi": "3.0.3",
+        "info": {},
+        "servers": [{ "url": DEFAULT_SERVER, "description": "Production" }],
+        "paths": {}
+    });
+    match name {
+        "minimal" => {}
+        _ => {
+            let (scheme, definition) = security_scheme(name)?;
+            let mut components = problem_components();
+            components["securitySchemes"] = json!({ scheme: definition });
+            spec["components"] = components;
+            spec["security"] = json!([{ scheme: [] }]);
+            spec["tags"] = json!([{ "name": "health", "description": "Service status" }]);
+            spec["paths"] = json!({
+                "/health": {
+                    "get": {
+                        "operationId": "getHealth",
+                        "tags": ["health"],
+                        "summary": "Check service health",
+                        // Health checks stay reachable without credentials
+                        "security": [],
+                        "responses": {
+                            "200": { "description": "The service is healthy" },
+                            "default": { "$ref": "#/components/responses/Error" }
+                        }
+                    }
+                }
+            });
+        }
+    }
+    Some(spec)
+}
+
+// ─── User Templates ──────────────────────────────────────────────────────────
+
+fn templates_dir(app: &AppHandle) -> Result<PathBuf, CommandError> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| CommandError::new(ErrorCode::StorageFailed).detail(e))?;
+    Ok(dir.join(TEMPLATES_DIR))
+}
+
+/// User template names become file names, so they are kept to a safe set.
+fn validate_name(name: &str) -> Result<(), CommandError> {
+    let valid = (1..=64).contains(&name.len())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'));
+    if !valid {
+        return Err(CommandError::new(ErrorCode::InvalidName).arg("name", name));
+    }
+    if BUILTIN_TEMPLATES
+        .iter()
+        .any(|(builtin, _)| *builtin == name)
+    {
+        return Err(CommandError::new(ErrorCode::NameInUse).arg("name", name));
+    }
+    Ok(())
+}
+
+fn is_openapi(spec: &Value) -> bool {
+    spec.get("openapi")
+        .or_else(|| spec.get("swagger"))
+        .is_some_and(Value::is_string)
+}
+
+fn read_user_template(dir: &Path, name: &str) -> Result<Value, CommandError> {
+    let bytes = std::fs::read(dir.join(format!("{name}.json")))
+        .map_err(|_| CommandError::new(ErrorCode::TemplateNotFound).arg("name", name))?;
+    serde_json::from_slice::<Value>(&bytes)
+        .ok()
+        .filter(is_openapi)
+        .ok_or_else(|| CommandError::new(ErrorCode::InvalidTemplate))
+}
+
+/// Unreadable files are left out rather than failing the whole listing.
+fn list_user_templates(dir: &Path) -> Vec<SpecTemplate> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut templates: Vec<SpecTemplate> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "json" {
+                return None;
+            }
+            let name = path.file_stem()?.to_str()?.to_string();
+            let spec = read_user_template(dir, &name).ok()?;
+            Some(SpecTemplate {
+                name,
+                description: spec["info"]["description"].as_str().map(str::to_string),
+                builtin: false,
+            })
+        })
+        .collect();
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+    templates
+}
+
+fn write_user_template(dir: &Path, name: &str, spec: &Value) -> Result<(), CommandError> {
+    validate_name(name)?;
+    if !is_openapi(spec) {
+        return Err(CommandError::new(ErrorCode::InvalidTemplate));
+    }
+    let json = serde_json::to_vec_pretty(spec)
+        .map_err(|e| CommandError::new(ErrorCode::StorageFailed).detail(e))?;
+    std::fs::create_dir_all(dir)
+        .and_then(|_| std::fs::write(dir.join(format!("{name}.json")), json))
+        .map_err(|e| CommandError::new(ErrorCode::StorageFailed).detail(e))
+}
+
+// ─── Generation ──────────────────────────────────────────────────────────────
+
+fn generate(dir: &Path, template: &str, options: NewSpecOptions) -> Result<Value, CommandError> {
+    let mut spec = match builtin_template(template) {
+        Some(spec) => spec,
+        None => {
+            validate_name(template)?;
+            read_user_template(dir, template)?
+        }
+    };
+    if !spec["info"].is_object() {
+        spec["info"] = json!({});
+    }
+    spec["info"]["title"] = json!(options.title);
+    spec["info"]["version"] = json!(options.version);
+    if let Some(description) = options.description {
+        spec["info"]["description"] = json!(description);
+    }
+    if let Some(url) = options.server_url {
+        if spec.get("swagger").is_some() {
+            // Swagger 2.0 has no servers list
+            let parsed = url::Url::parse(&url)
+                .map_err(|e| CommandError::new(ErrorCode::InvalidUrl).detail(e))?;
+            spec["host"] = json!(parsed[url::Position::BeforeHost..url::Position::AfterPort]);
+            spec["basePath"] = json!(parsed.path());
+            spec["schemes"] = json!([parsed.scheme()]);
+        } else {
+            spec["servers"] = json!([{ "url": url }]);
+        }
+    }
+    Ok(spec)
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// Built-in templates followed by the user's own, by name.
+#[tauri::command]
+pub fn list_spec_templates(app: AppHandle) -> Result<Vec<SpecTemplate>, CommandError> {
+    let mut templates: Vec<SpecTemplate> = BUILTIN_TEMPLATES
+        .iter()
+        .map(|(name, description)| SpecTemplate {
+            name: name.to_string(),
+            description: Some(description.to_string()),
+            builtin: true,
+        })
+        .collect();
+    templates.extend(list_user_templates(&templates_dir(&app)?));
+    Ok(templates)
+}
+
+/// A starter OpenAPI document from a built-in or user template, with the
+/// given title, version, description and server filled in. The frontend
+/// serializes it and opens it as a new, unsaved spec.
+#[tauri::command]
+pub fn new_spec(
+    app: AppHandle,
+    template: String,
+    options: NewSpecOptions,
+) -> Result<Value, CommandError> {
+    generate(&templates_dir(&app)?, &template, options)
+}
+
+/// Save a spec as a user template, replacing any user template of the same
+/// name. Built-in names are reserved.
+#[tauri::command]
+pub fn save_spec_template(
+    app: AppHandle,
+    webview: tauri::Webview,
+    name: String,
+    spec: Value,
+) -> Result<(), CommandError> {
+    ensure_main_window(webview.label())?;
+
+    write_user_template(&templates_dir(&app)?, &name, &spec)
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("yasp-templates-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn options(title: &str) -> NewSpecOptions {
+        NewSpecOptions {
+            title: title.to_string(),
+            version: default_version(),
+            description: None,
+            server_url: Some("https://orders.internal.example.com".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_builtin_templates_reference_their_components() {
+        let dir = temp_dir("builtin");
+        for (name, _) in BUILTIN_TEMPLATES {
+            let spec = generate(&dir, name, options("Orders")).unwrap();
+            assert_eq!(spec["info"]["title"], "Orders", "{name}");
+            assert_eq!(spec["info"]["version"], "0.1.0");
+            assert_eq!(
+                spec["servers"][0]["url"],
+                "https://orders.internal.example.com"
+            );
+            for requirement in spec["security"].as_array().into_iter().flatten() {
+                for scheme in requirement.as_object().unwrap().keys() {
+                    assert!(
+                        spec["components"]["securitySchemes"][scheme].is_object(),
+                        "{name}: {scheme}"
+                    );
+                }
+            }
+        }
+        let spec = generate(&dir, "rest", options("Orders")).unwrap();
+        assert_eq!(
+            spec["components"]["responses"]["NotFound"]["content"]["application/problem+json"]
+                ["schema"]["$ref"],
+            "#/components/schemas/Problem"
+        );
+    }
+
+    #[test]
+    fn test_user_templates_round_trip() {
+        let dir = temp_dir("user");
+        let spec = json!({
+            "openapi": "3.1.0",
+            "info": { "title": "Team", "version": "1", "description": "Team defaults" },
+            "x-owner": "payments"
+        });
+        write_user_template(&dir, "team_std", &spec).unwrap();
+        std::fs::write(dir.join("broken.json"), b"{").unwrap();
+
+        let listed = list_user_templates(&dir);
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].name, "team_std");
+        assert_eq!(listed[0].description.as_deref(), Some("Team defaults"));
+
+        let generated = generate(&dir, "team_std", options("Refunds")).unwrap();
+        assert_eq!(generated["info"]["title"], "Refunds");
+        assert_eq!(generated["info"]["description"], "Team defaults");
+        assert_eq!(generated["x-owner"], "payments");
+
+        let err = generate(&dir, "broken", options("x")).unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidTemplate);
+        let err = generate(&dir, "missing", options("x")).unwrap_err();
+        assert_eq!(err.code, ErrorCode::TemplateNotFound);
+        let err = generate(&dir, "../user/team_std", options("x")).unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidName);
+    }
+
+    #[test]
+    fn test_save_rejects_unsafe_or_reserved_names_and_non_specs() {
+        let dir = temp_dir("names");
+        let spec = json!({ "openapi": "3.0.3" });
+        for (name, code) in [
+            ("../escape", ErrorCode::InvalidName),
+            ("", ErrorCode::InvalidName),
+            ("rest", ErrorCode::NameInUse),
+        ] {
+            let err = write_user_template(&dir, name, &spec).unwrap_err();
+            assert_eq!(err.code, code, "{name}");
+        }
+        let err = write_user_template(&dir, "plain", &json!({ "title": "x" })).unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidTemplate);
+    }
+}
@@ -0,0 +1,288 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use rustls::client::{ClientSessionMemoryCache, ClientSessionStore, Resumption};
+use rustls::NamedGroup;
+use rustls_pki_types::ServerName;
+use serde::{Deserialize, Serialize};
+
+/// Same size as rustls' default session cache.
+const SESSION_CACHE_SIZE: usize = 256;
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+/// Where the time of a request went, phase by phase, like curl's `-w`
+/// timings. The phases add up to `total_ms`.
+///
+/// Connection phases are `None` when the request reused a pooled connection.
+/// When redirects opened several connections, each phase is their sum.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Timing {
+    /// Resolving the host name; 0 for IP address hosts.
+    pub dns_ms: Option<u64>,
+    /// TCP handshake.
+    pub connect_ms: Option<u64>,
+    /// TLS handshake; `None` for plain HTTP.
+    pub tls_ms: Option<u64>,
+    /// Waiting for the response headers once connected: upload, server
+    /// processing and the network round trip.
+    pub ttfb_ms: u64,
+    /// Reading the response body.
+    pub download_ms: u64,
+    pub total_ms: u64,
+}
+
+/// Connection phases seen while a request is in flight.
+#[derive(Debug, Default)]
+struct Phases {
+    dns: Option<Duration>,
+    connect: Option<Duration>,
+    tls: Option<Duration>,
+    /// DNS time of the connection being set up.
+    resolving: Duration,
+    /// When the TLS handshake of the connection being set up began.
+    handshake_started: Option<Instant>,
+}
+
+tokio::task_local! {
+    /// Phases of the request being sent on this task. The resolver, connector
+    /// and TLS session store are shared by every request on a client, so
+    /// they report to whichever request is connecting.
+    static PHASES: Recorder;
+}
+
+// ─── Recording ───────────────────────────────────────────────────────────────
+
+fn add(total: &mut Option<Duration>, phase: Duration) {
+    *total = Some(total.unwrap_or_default() + phase);
+}
+
+/// Report to the request on the current task, if it is being timed. A
+/// connection finishing in the background after its request was served from
+/// the pool reports nothing.
+fn record(update: impl FnOnce(&mut Phases)) {
+    let _ = PHASES.try_with(|recorder| {
+        update(&mut recorder.0.lock().unwrap_or_else(|e| e.into_inner()));
+    });
+}
+
+impl Phases {
+    /// A connection set up in `elapsed`, DNS and TLS included.
+    fn connected(&mut self, elapsed: Duration) {
+        let dns = std::mem::take(&mut self.resolving);
+        let tls = self.handshake_started.take().map(|start| start.elapsed());
+        add(&mut self.dns, dns);
+        add(
+            &mut self.connect,
+            elapsed.saturating_sub(dns + tls.unwrap_or_default()),
+        );
+        if let Some(tls) = tls {
+            add(&mut self.tls, tls);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub(super) struct Recorder(Arc<Mutex<Phases>>);
+
+impl Recorder {
+    /// Run `send` with connection phases reported to this recorder.
+    pub(super) async fn scope<F: Future>(&self, send: F) -> F::Output {
+        PHASES.scope(self.clone(), send).await
+    }
+
+    /// The breakdown of a request whose headers arrived after `headers` and
+    /// whose body was read by `total`.
+    pub(super) fn timing(&self, headers: Duration, total: Duration) -> Timing {
+        let phases = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        let setup = [phases.dns, phases.connect, phases.tls]
+            .into_iter()
+            .flatten()
+            .sum::<Duration>();
+        let ms = |duration: Duration| duration.as_millis() as u64;
+        Timing {
+            dns_ms: phases.dns.map(ms),
+            connect_ms: phases.connect.map(ms),
+            tls_ms: phases.tls.map(ms),
+            ttfb_ms: ms(headers.saturating_sub(setup)),
+            download_ms: ms(total.saturating_sub(headers)),
+            total_ms: ms(total),
+        }
+    }
+}
+
+// ─── Client hooks ────────────────────────────────────────────────────────────
+
+/// The system resolver, as reqwest uses by default, timing each lookup.
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct TimingResolver;
+
+impl Resolve for TimingResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let start = Instant::now();
+            let addrs = tokio::net::lookup_host(format!("{}:0", name.as_str())).await?;
+            record(|phases| phases.resolving += start.elapsed());
+            Ok(Box::new(addrs) as Addrs)
+        })
+    }
+}
+
+/// Connector layer timing connection setup, which covers DNS, TCP and TLS.
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct ConnectTimingLayer;
+
+impl<S> tower::Layer<S> for ConnectTimingLayer {
+    type Service = ConnectTiming<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ConnectTiming(inner)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct ConnectTiming<S>(S);
+
+impl<S, R> tower::Service<R> for ConnectTiming<S>
+where
+    S: tower::Service<R>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.0.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: R) -> Self::Future {
+        let start = Instant::now();
+        let connecting = self.0.call(request);
+        Box::pin(async move {
+            let connection = connecting.await?;
+            record(|phases| phases.connected(start.elapsed()));
+            Ok(connection)
+        })
+    }
+}
+
+/// rustls' in-memory session cache, noting when a handshake starts: rustls
+/// looks for a session to resume as it writes the ClientHello, right after
+/// the TCP connection is made.
+#[derive(Debug)]
+struct HandshakeTimer(ClientSessionMemoryCache);
+
+/// TLS session resumption for user requests, timing each handshake.
+pub(super) fn resumption() -> Resumption {
+    Resumption::store(Arc::new(HandshakeTimer(ClientSessionMemoryCache::new(
+        SESSION_CACHE_SIZE,
+    ))))
+}
+
+impl ClientSessionStore for HandshakeTimer {
+    fn set_kx_hint(&self, server_name: ServerName<'static>, group: NamedGroup) {
+        self.0.set_kx_hint(server_name, group)
+    }
+
+    fn kx_hint(&self, server_name: &ServerName<'_>) -> Option<NamedGroup> {
+        self.0.kx_hint(server_name)
+    }
+
+    fn set_tls12_session(
+        &self,
+        server_name: ServerName<'static>,
+        value: rustls::client::Tls12ClientSessionValue,
+    ) {
+        self.0.set_tls12_session(server_name, value)
+    }
+
+    fn tls12_session(
+        &self,
+        server_name: &ServerName<'_>,
+    ) -> Option<rustls::client::Tls12ClientSessionValue> {
+        self.0.tls12_session(server_name)
+    }
+
+    fn remove_tls12_session(&self, server_name: &ServerName<'static>) {
+        self.0.remove_tls12_session(server_name)
+    }
+
+    fn insert_tls13_ticket(
+        &self,
+        server_name: ServerName<'static>,
+        value: rustls::client::Tls13ClientSessionValue,
+    ) {
+        self.0.insert_tls13_ticket(server_name, value)
+    }
+
+    /// Called once per handshake, before anything else is sent.
+    fn take_tls13_ticket(
+        &self,
+        server_name: &ServerName<'static>,
+    ) -> Option<rustls::client::Tls13ClientSessionValue> {
+        record(|phases| phases.handshake_started = Some(Instant::now()));
+        self.0.take_tls13_ticket(server_name)
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pooled_connection_reports_no_connection_phases() {
+        let timing =
+            Recorder::default().timing(Duration::from_millis(40), Duration::from_millis(55));
+        assert_eq!(
+            timing,
+            Timing {
+                dns_ms: None,
+                connect_ms: None,
+                tls_ms: None,
+                ttfb_ms: 40,
+                download_ms: 15,
+                total_ms: 55,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_connection_phases_add_up() {
+        let recorder = Recorder::default();
+        recorder
+            .scope(async {
+                record(|phases| phases.resolving = Duration::from_millis(5));
+                record(|phases| phases.connected(Duration::from_millis(30)));
+            })
+            .await;
+        // Outside a timed request nothing is recorded
+        record(|phases| phases.connected(Duration::from_secs(1)));
+
+        let timing = recorder.timing(Duration::from_millis(100), Duration::from_millis(120));
+        assert_eq!(timing.dns_ms, Some(5));
+        assert_eq!(timing.connect_ms, Some(25));
+        assert_eq!(timing.tls_ms, None);
+        assert_eq!(timing.ttfb_ms, 70);
+        assert_eq!(timing.download_ms, 20);
+
+        // A TLS handshake is split off the connection time
+        recorder
+            .scope(async {
+                record(|phases| {
+                    phases.handshake_started = Some(Instant::now() - Duration::from_millis(10))
+                });
+                record(|phases| phases.connected(Duration::from_millis(30)));
+            })
+            .await;
+        let timing = recorder.timing(Duration::from_millis(100), Duration::from_millis(120));
+        assert!(timing.tls_ms.unwrap() >= 10);
+        assert_eq!(timing.dns_ms, Some(5));
+    }
+}
@@ -0,0 +1,184 @@
+use std::sync::Mutex;
+
+use serde::Deserialize;
+use tauri::menu::{IsMenuItem, Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+use tauri::tray::TrayIconBuilder;
+use tauri::{AppHandle, Manager, State, Wry};
+use tauri_plugin_notification::NotificationExt;
+
+use super::http::HttpClients;
+use super::{ensure_main_window, send_api_request, ApiRequest, ApiResponse, MAIN_WINDOW};
+use crate::error::{CommandError, ErrorCode};
+
+pub const TRAY_ID: &str = "main";
+
+const SHOW_ITEM_ID: &str = "show";
+const PIN_ITEM_PREFIX: &str = "pin:";
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+/// A pinned operation or saved request, executable from the tray menu.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TrayPin {
+    pub id: String,
+    pub label: String,
+    /// The request with the active environment already applied. The frontend
+    /// re-sends the pins when the active environment changes.
+    pub request: ApiRequest,
+}
+
+#[derive(Default)]
+pub struct TrayPins(Mutex<Vec<TrayPin>>);
+
+// ─── Menu ────────────────────────────────────────────────────────────────────
+
+fn tray_error(e: tauri::Error) -> CommandError {
+    CommandError::new(ErrorCode::TrayUnavailable).detail(e)
+}
+
+fn build_menu(app: &AppHandle, pins: &[TrayPin]) -> tauri::Result<Menu<Wry>> {
+    let mut pin_items = Vec::with_capacity(pins.len());
+    for pin in pins {
+        pin_items.push(MenuItem::with_id(
+            app,
+            format!("{PIN_ITEM_PREFIX}{}", pin.id),
+            &pin.label,
+            true,
+            None::<&str>,
+        )?);
+    }
+    if pin_items.is_empty() {
+        pin_items.push(MenuItem::with_id(
+            app,
+            "empty",
+            "No pinned requests",
+            false,
+            None::<&str>,
+        )?);
+    }
+    let separator = PredefinedMenuItem::separator(app)?;
+    let show = MenuItem::with_id(app, SHOW_ITEM_ID, "Open YASP", true, None::<&str>)?;
+
+    let mut items: Vec<&dyn IsMenuItem<Wry>> = pin_items
+        .iter()
+        .map(|item| item as &dyn IsMenuItem<Wry>)
+        .collect();
+    items.push(&separator);
+    items.push(&show);
+    Menu::with_items(app, &items)
+}
+
+fn summary(result: &Result<ApiResponse, CommandError>) -> String {
+    match result {
+        Ok(response) => format!(
+            "{} {} · {} ms",
+            response.status, response.status_text, response.duration_ms
+        ),
+        Err(error) => error.to_string(),
+    }
+}
+
+async fn run_pin(app: AppHandle, pin: TrayPin) {
+    let result = send_api_request(&app.state::<HttpClients>(), &pin.request).await;
+    let _ = app
+        .notification()
+        .builder()
+        .title(&pin.label)
+        .body(summary(&result))
+        .show();
+}
+
+fn on_menu_event(app: &AppHandle, event: MenuEvent) {
+    let id = event.id().as_ref();
+    if id == SHOW_ITEM_ID {
+        if let Some(main) = app.get_webview_window(MAIN_WINDOW) {
+            let _ = main.show();
+            let _ = main.set_focus();
+        }
+        return;
+    }
+
+    let Some(pin_id) = id.strip_prefix(PIN_ITEM_PREFIX) else {
+        return;
+    };
+    let pin = {
+        let pins = app.state::<TrayPins>();
+        let pins = pins.0.lock().unwrap_or_else(|e| e.into_inner());
+        pins.iter().find(|pin| pin.id == pin_id).cloned()
+    };
+    if let Some(pin) = pin {
+        tauri::async_runtime::spawn(run_pin(app.clone(), pin));
+    }
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// Replace the pinned requests shown in the tray menu, creating the tray
+/// icon on first use. Clicking a pin executes it with the same SSRF and
+/// size limits as `execute_api_request` and shows the outcome as a system
+/// notification.
+#[tauri::command]
+pub fn set_tray_pins(
+    app: AppHandle,
+    webview: tauri::Webview,
+    tray_pins: State<'_, TrayPins>,
+    pins: Vec<TrayPin>,
+) -> Result<(), CommandError> {
+    ensure_main_window(webview.label())?;
+
+    let menu = build_menu(&app, &pins).map_err(tray_error)?;
+    *tray_pins.0.lock().unwrap_or_else(|e| e.into_inner()) = pins;
+
+    match app.tray_by_id(TRAY_ID) {
+        Some(tray) => tray.set_menu(Some(menu)).map_err(tray_error),
+        None => {
+            let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+                .tooltip("YASP")
+                .menu(&menu)
+                .on_menu_event(on_menu_event);
+            if let Some(icon) = app.default_window_icon() {
+                builder = builder.icon(icon.clone());
+            }
+            builder.build(&app).map(|_| ()).map_err(tray_error)
+        }
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn test_summary_reports_status_and_timing() {
+        let response = ApiResponse {
+            status: 200,
+            status_text: "OK".to_string(),
+            headers: HashMap::new(),
+            body: String::new(),
+            body_encoding: Default::default(),
+            body_path: None,
+            content_type: None,
+            duration_ms: 42,
+            http_version: Default::default(),
+            timing: Default::default(),
+            redirects: Vec::new(),
+            tls: None,
+            tls_unverified_hosts: Vec::new(),
+            certificates: Vec::new(),
+        };
+        assert_eq!(summary(&Ok(response)), "200 OK · 42 ms");
+    }
+
+    #[test]
+    fn test_summary_reports_error_message() {
+        let error = CommandError::new(ErrorCode::BlockedPort).arg("port", 22);
+        assert_eq!(
+            summary(&Err(error)),
+            "Blocked port: 22 is not allowed for outbound requests."
+        );
+    }
+}
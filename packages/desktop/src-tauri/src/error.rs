@@ -0,0 +1,453 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::OnceLock;
+
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use unic_langid::LanguageIdentifier;
+
+// ─── Error Codes ─────────────────────────────────────────────────────────────
+
+/// Declares `ErrorCode` from `Variant => "catalog_id"` pairs, so `ALL` and
+/// `as_str` cannot fall out of step with the variants.
+macro_rules! error_codes {
+    ($(#[$meta:meta])* pub enum ErrorCode { $($code:ident => $id:literal,)* }) => {
+        $(#[$meta])*
+        pub enum ErrorCode {
+            $($code,)*
+        }
+
+        impl ErrorCode {
+            /// Every code, for the catalog and wire-format tests.
+            #[cfg(test)]
+            pub const ALL: &'static [ErrorCode] = &[$(ErrorCode::$code,)*];
+
+            /// The code as it appears on the wire and in the message catalog.
+            pub fn as_str(self) -> &'static str {
+                match self {
+                    $(ErrorCode::$code => $id,)*
+                }
+            }
+        }
+    };
+}
+
+error_codes! {
+    /// Stable, machine-readable error codes returned to the frontend.
+    ///
+    /// Codes are part of the IPC contract: the frontend branches on them and uses
+    /// them as translation keys, so existing variants must never be renamed.
+    /// Each code has a matching message id in `locales/en-US/errors.ftl`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+    #[serde(rename_all = "snake_case")]
+    pub enum ErrorCode {
+        InvalidUrl => "invalid_url",
+        DisallowedScheme => "disallowed_scheme",
+        MissingHost => "missing_host",
+        BlockedHost => "blocked_host",
+        BlockedIp => "blocked_ip",
+        BlockedPort => "blocked_port",
+        DisallowedMethod => "disallowed_method",
+        WindowNotAllowed => "window_not_allowed",
+        InvalidHeaderName => "invalid_header_name",
+        InvalidHeaderValue => "invalid_header_value",
+        ClientBuildFailed => "client_build_failed",
+        StorageFailed => "storage_failed",
+        DnsFailed => "dns_failed",
+        ConnectFailed => "connect_failed",
+        Timeout => "timeout",
+        RequestFailed => "request_failed",
+        BodyReadFailed => "body_read_failed",
+        BodyTooLarge => "body_too_large",
+        HttpStatus => "http_status",
+        InvalidUtf8 => "invalid_utf8",
+        InvalidResponse => "invalid_response",
+        InvalidTlsPolicy => "invalid_tls_policy",
+        TlsVersionUnsupported => "tls_version_unsupported",
+        TlsHandshakeFailed => "tls_handshake_failed",
+        InvalidHostname => "invalid_hostname",
+        ToolUnavailable => "tool_unavailable",
+        InvalidShortcut => "invalid_shortcut",
+        ShortcutUnavailable => "shortcut_unavailable",
+        TrayUnavailable => "tray_unavailable",
+        InvalidPath => "invalid_path",
+        EmptyBatch => "empty_batch",
+        InvalidKeyFile => "invalid_key_file",
+        CredentialsUnavailable => "credentials_unavailable",
+        PackageVersionNotFound => "package_version_not_found",
+        SpecNotFound => "spec_not_found",
+        IntegrityMismatch => "integrity_mismatch",
+        ExampleNotFound => "example_not_found",
+        InvalidSetting => "invalid_setting",
+        OperationNotFound => "operation_not_found",
+        InvalidSpecEdit => "invalid_spec_edit",
+        InvalidName => "invalid_name",
+        NameInUse => "name_in_use",
+        NameNotFound => "name_not_found",
+        TemplateNotFound => "template_not_found",
+        InvalidTemplate => "invalid_template",
+        BodyConflict => "body_conflict",
+        FileUnreadable => "file_unreadable",
+        FileTooLarge => "file_too_large",
+        UnknownRuleset => "unknown_ruleset",
+        FileWriteFailed => "file_write_failed",
+        InvalidCookie => "invalid_cookie",
+        ComponentNotFound => "component_not_found",
+        Http3Unavailable => "http3_unavailable",
+        InvalidProxy => "invalid_proxy",
+        ProxyAuthRequired => "proxy_auth_required",
+        ProxyAuthRejected => "proxy_auth_rejected",
+        InvalidClientIdentity => "invalid_client_identity",
+        InvalidManifest => "invalid_manifest",
+        InvalidCaCertificate => "invalid_ca_certificate",
+        InvalidHost => "invalid_host",
+        InvalidValueMapping => "invalid_value_mapping",
+        InvalidCertificatePin => "invalid_certificate_pin",
+        CertificatePinMismatch => "certificate_pin_mismatch",
+        InvalidDotenvKey => "invalid_dotenv_key",
+        VaultEnvironmentNotFound => "vault_environment_not_found",
+        OauthFailed => "oauth_failed",
+        OauthTokenNotFound => "oauth_token_not_found",
+        OauthTokenExpired => "oauth_token_expired",
+        InvalidSigningKey => "invalid_signing_key",
+        WorkspaceSpecNotFound => "workspace_spec_not_found",
+        Cancelled => "cancelled",
+    }
+}
+
+impl ErrorCode {
+    /// Coarse category, used by the frontend to pick a remediation hint.
+    pub fn kind(self) -> ErrorKind {
+        match self {
+            ErrorCode::DisallowedScheme
+            | ErrorCode::BlockedHost
+            | ErrorCode::BlockedIp
+            | ErrorCode::BlockedPort
+            | ErrorCode::DisallowedMethod
+            | ErrorCode::WindowNotAllowed
+            | ErrorCode::ProxyAuthRequired
+            | ErrorCode::ProxyAuthRejected => ErrorKind::Policy,
+            ErrorCode::InvalidUrl
+            | ErrorCode::MissingHost
+            | ErrorCode::InvalidHeaderName
+            | ErrorCode::InvalidHeaderValue
+            | ErrorCode::InvalidTlsPolicy
+            | ErrorCode::TlsVersionUnsupported
+            | ErrorCode::InvalidHostname
+            | ErrorCode::InvalidShortcut
+            | ErrorCode::InvalidPath
+            | ErrorCode::EmptyBatch
+            | ErrorCode::InvalidKeyFile
+            | ErrorCode::CredentialsUnavailable
+            | ErrorCode::ExampleNotFound
+            | ErrorCode::InvalidSetting
+            | ErrorCode::OperationNotFound
+            | ErrorCode::InvalidSpecEdit
+            | ErrorCode::InvalidName
+            | ErrorCode::NameInUse
+            | ErrorCode::NameNotFound
+            | ErrorCode::TemplateNotFound
+            | ErrorCode::InvalidTemplate
+            | ErrorCode::BodyConflict
+            | ErrorCode::FileUnreadable
+            | ErrorCode::FileTooLarge
+            | ErrorCode::UnknownRuleset
+            | ErrorCode::InvalidCookie
+            | ErrorCode::ComponentNotFound
+            | ErrorCode::Http3Unavailable
+            | ErrorCode::InvalidProxy
+            | ErrorCode::InvalidClientIdentity
+            | ErrorCode::InvalidManifest
+            | ErrorCode::InvalidCaCertificate
+            | ErrorCode::InvalidHost
+            | ErrorCode::InvalidValueMapping
+            | ErrorCode::InvalidCertificatePin
+            | ErrorCode::InvalidDotenvKey
+            | ErrorCode::VaultEnvironmentNotFound
+            | ErrorCode::OauthTokenNotFound
+            | ErrorCode::OauthTokenExpired
+            | ErrorCode::InvalidSigningKey
+            | ErrorCode::WorkspaceSpecNotFound => ErrorKind::InvalidInput,
+            ErrorCode::DnsFailed
+            | ErrorCode::ConnectFailed
+            | ErrorCode::RequestFailed
+            | ErrorCode::BodyReadFailed
+            | ErrorCode::TlsHandshakeFailed => ErrorKind::Network,
+            ErrorCode::Timeout => ErrorKind::Timeout,
+            ErrorCode::Cancelled => ErrorKind::Cancelled,
+            ErrorCode::BodyTooLarge
+            | ErrorCode::HttpStatus
+            | ErrorCode::InvalidUtf8
+            | ErrorCode::InvalidResponse
+            | ErrorCode::PackageVersionNotFound
+            | ErrorCode::SpecNotFound
+            | ErrorCode::IntegrityMismatch
+            | ErrorCode::CertificatePinMismatch
+            | ErrorCode::OauthFailed => ErrorKind::Response,
+            ErrorCode::ClientBuildFailed
+            | ErrorCode::StorageFailed
+            | ErrorCode::ToolUnavailable
+            | ErrorCode::ShortcutUnavailable
+            | ErrorCode::TrayUnavailable
+            | ErrorCode::FileWriteFailed => ErrorKind::Internal,
+        }
+    }
+
+    /// Phase the error is raised in unless the call site knows better.
+    fn default_phase(self) -> ErrorPhase {
+        match self.kind() {
+            ErrorKind::Policy | ErrorKind::InvalidInput => ErrorPhase::Validation,
+            ErrorKind::Internal => ErrorPhase::Setup,
+            ErrorKind::Response => ErrorPhase::Receive,
+            ErrorKind::Cancelled => ErrorPhase::Send,
+            ErrorKind::Network | ErrorKind::Timeout => match self {
+                ErrorCode::DnsFailed | ErrorCode::ConnectFailed | ErrorCode::TlsHandshakeFailed => {
+                    ErrorPhase::Connect
+                }
+                ErrorCode::BodyReadFailed => ErrorPhase::Receive,
+                _ => ErrorPhase::Send,
+            },
+        }
+    }
+
+    /// Whether repeating the same request unchanged may succeed, unless the
+    /// call site knows better (e.g. an HTTP 503 is, an HTTP 404 is not).
+    fn default_retryable(self) -> bool {
+        matches!(self.kind(), ErrorKind::Network | ErrorKind::Timeout)
+    }
+}
+
+/// Coarse error category.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// Blocked by the network or access policy (SSRF protection, method
+    /// allowlist, window scoping).
+    Policy,
+    /// The request itself is malformed.
+    InvalidInput,
+    /// DNS, connection or transfer failure.
+    Network,
+    Timeout,
+    /// Cancelled by the user before it completed.
+    Cancelled,
+    /// The server answered, but the response is unusable.
+    Response,
+    /// A bug or environment problem in the backend.
+    Internal,
+}
+
+/// Stage of request processing an error came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorPhase {
+    Validation,
+    Setup,
+    Connect,
+    Send,
+    Receive,
+}
+
+// ─── Command Error ───────────────────────────────────────────────────────────
+
+/// Error returned by every Tauri command.
+///
+/// Serialized as `{ code, kind, phase, retryable, message, detail?, args }`.
+/// `message` is the English rendering from the bundled catalog; the frontend
+/// may instead localize `code` with `args` as the message variables. `detail`
+/// carries the underlying library error verbatim and is never translated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandError {
+    pub code: ErrorCode,
+    pub phase: ErrorPhase,
+    pub retryable: bool,
+    pub detail: Option<String>,
+    pub args: BTreeMap<&'static str, String>,
+}
+
+impl CommandError {
+    pub fn new(code: ErrorCode) -> Self {
+        Self {
+            code,
+            phase: code.default_phase(),
+            retryable: code.default_retryable(),
+            detail: None,
+            args: BTreeMap::new(),
+        }
+    }
+
+    /// Attach a message variable, e.g. `.arg("host", host)`.
+    pub fn arg(mut self, name: &'static str, value: impl ToString) -> Self {
+        self.args.insert(name, value.to_string());
+        self
+    }
+
+    /// Attach the underlying error text.
+    pub fn detail(mut self, detail: impl ToString) -> Self {
+        self.detail = Some(detail.to_string());
+        self
+    }
+
+    pub fn phase(mut self, phase: ErrorPhase) -> Self {
+        self.phase = phase;
+        self
+    }
+
+    pub fn retryable(mut self, retryable: bool) -> Self {
+        self.retryable = retryable;
+        self
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        self.code.kind()
+    }
+
+    /// Render the English message for this error from the catalog.
+    pub fn message(&self) -> String {
+        let bundle = catalog();
+        let Some(pattern) = bundle
+            .get_message(self.code.as_str())
+            .and_then(|message| message.value())
+        else {
+            return self.code.as_str().to_string();
+        };
+
+        let mut args = FluentArgs::new();
+        for (name, value) in &self.args {
+            args.set(*name, value.as_str());
+        }
+
+        // Formatting errors (e.g. a missing variable) still yield usable text,
+        // with the placeholder left in place, so they are ignored here.
+        let mut errors = Vec::new();
+        bundle
+            .format_pattern(pattern, Some(&args), &mut errors)
+            .into_owned()
+    }
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message())?;
+        if let Some(detail) = &self.detail {
+            write!(f, ": {detail}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+impl Serialize for CommandError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("CommandError", 7)?;
+        state.serialize_field("code", &self.code)?;
+        state.serialize_field("kind", &self.kind())?;
+        state.serialize_field("phase", &self.phase)?;
+        state.serialize_field("retryable", &self.retryable)?;
+        state.serialize_field("message", &self.message())?;
+        if let Some(detail) = &self.detail {
+            state.serialize_field("detail", detail)?;
+        } else {
+            state.skip_field("detail")?;
+        }
+        state.serialize_field("args", &self.args)?;
+        state.end()
+    }
+}
+
+// ─── Message Catalog ─────────────────────────────────────────────────────────
+
+const EN_US_CATALOG: &str = include_str!("../locales/en-US/errors.ftl");
+
+fn catalog() -> &'static FluentBundle<FluentResource> {
+    static CATALOG: OnceLock<FluentBundle<FluentResource>> = OnceLock::new();
+    CATALOG.get_or_init(|| {
+        let locale: LanguageIdentifier = "en-US".parse().expect("valid locale identifier");
+        let resource = FluentResource::try_new(EN_US_CATALOG.to_string())
+            .expect("bundled error catalog must parse");
+        let mut bundle = FluentBundle::new_concurrent(vec![locale]);
+        // Messages are plain text in the UI; Unicode isolation marks would leak
+        // into toasts and logs.
+        bundle.set_use_isolating(false);
+        bundle
+            .add_resource(resource)
+            .expect("bundled error catalog must not contain duplicate ids");
+        bundle
+    })
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_code_has_a_catalog_entry() {
+        for code in ErrorCode::ALL {
+            assert!(
+                catalog().has_message(code.as_str()),
+                "missing catalog entry for '{}'",
+                code.as_str()
+            );
+        }
+    }
+
+    #[test]
+    fn test_code_serializes_as_catalog_id() {
+        for code in ErrorCode::ALL {
+            let json = serde_json::to_value(code).unwrap();
+            assert_eq!(json, code.as_str());
+        }
+    }
+
+    #[test]
+    fn test_message_interpolates_args() {
+        let err = CommandError::new(ErrorCode::BlockedPort).arg("port", 22);
+        assert_eq!(
+            err.message(),
+            "Blocked port: 22 is not allowed for outbound requests."
+        );
+    }
+
+    #[test]
+    fn test_policy_errors_are_validation_phase_and_not_retryable() {
+        let err = CommandError::new(ErrorCode::BlockedIp);
+        assert_eq!(err.kind(), ErrorKind::Policy);
+        assert_eq!(err.phase, ErrorPhase::Validation);
+        assert!(!err.retryable);
+    }
+
+    #[test]
+    fn test_network_errors_default_to_retryable() {
+        let err = CommandError::new(ErrorCode::DnsFailed);
+        assert_eq!(err.kind(), ErrorKind::Network);
+        assert_eq!(err.phase, ErrorPhase::Connect);
+        assert!(err.retryable);
+    }
+
+    #[test]
+    fn test_display_appends_detail() {
+        let err = CommandError::new(ErrorCode::InvalidUrl).detail("empty host");
+        assert_eq!(err.to_string(), "Invalid URL: empty host");
+    }
+
+    #[test]
+    fn test_serializes_code_message_and_args() {
+        let err = CommandError::new(ErrorCode::DisallowedScheme).arg("scheme", "ftp");
+        let json = serde_json::to_value(&err).unwrap();
+        assert_eq!(json["code"], "disallowed_scheme");
+        assert_eq!(json["kind"], "policy");
+        assert_eq!(json["phase"], "validation");
+        assert_eq!(json["retryable"], false);
+        assert!(json.get("detail").is_none());
+        assert_eq!(json["args"]["scheme"], "ftp");
+        assert_eq!(
+            json["message"],
+            "Disallowed URL scheme: 'ftp'. Only http/https are permitted."
+        );
+    }
+}
@@ -1,14 +1,14 @@
 mod commands;
+mod error;
+mod startup;
+mod stdio;
 
-use tauri::Manager;
+pub use stdio::STDIO_FLAG;
 
-#[tauri::command]
-async fn close_splashscreen(app: tauri::AppHandle) -> Result<(), ()> {
-    let splash = app.get_webview_window("splashscreen").unwrap();
-    let main = app.get_webview_window("main").unwrap();
-    splash.close().unwrap();
-    main.show().unwrap();
-    Ok(())
+/// Serve the backend commands as JSON-RPC on stdin/stdout, without the GUI.
+/// Exits with a non-zero status if a quality gate failed.
+pub fn run_stdio() {
+    std::process::exit(stdio::serve());
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -23,11 +23,123 @@ pub fn run() {
             tauri_plugin_updater::Builder::new().build(),
         )
         .plugin(tauri_plugin_process::init())
+        .plugin(tauri_plugin_deep_link::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .plugin(tauri_plugin_global_shortcut::Builder::new().build())
+        .plugin(tauri_plugin_notification::init())
+        .manage(startup::Startup::default())
+        .manage(commands::registry::RegistryCache::default())
+        .manage(commands::clipboard::ClipboardWatch::default())
+        .manage(commands::finder::FinderIndex::default())
+        .manage(commands::automation::Automation::default())
+        .manage(commands::tray::TrayPins::default())
+        .manage(commands::http::HttpClients::default())
+        .manage(commands::InFlightRequests::default())
+        .manage(commands::cookies::CookieJars::default())
+        .manage(commands::oauth::OauthTokens::default())
+        .setup(|app| {
+            commands::snippet::watch_deep_links(app.handle());
+            startup::spawn(app.handle().clone());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             commands::execute_api_request,
+            commands::cancel_request,
             commands::fetch_spec,
-            close_splashscreen,
+            commands::sftp::fetch_spec_sftp,
+            commands::artifacts::fetch_spec_object,
+            commands::packages::fetch_spec_package,
+            commands::forge::list_forge_repos,
+            commands::forge::search_forge_specs,
+            commands::forge::import_forge_spec,
+            commands::registry::list_registry_subjects,
+            commands::registry::list_registry_versions,
+            commands::registry::get_registry_schema,
+            commands::snippet::generate_operation_snippet,
+            commands::examples::list_body_examples,
+            commands::examples::materialize_body_example,
+            commands::defaults::get_request_defaults,
+            commands::capture::capture_response_example,
+            commands::edit::edit_spec,
+            commands::rename::rename_operation_id,
+            commands::rename::rename_tag,
+            commands::templates::list_spec_templates,
+            commands::templates::new_spec,
+            commands::templates::save_spec_template,
+            commands::lint::get_lint_profile,
+            commands::lint::set_lint_profile,
+            commands::lint::record_lint_result,
+            commands::lint::list_lint_results,
+            commands::routes::find_route_conflicts,
+            commands::similarity::find_similar_schemas,
+            commands::cookies::list_cookies,
+            commands::cookies::set_cookie,
+            commands::cookies::delete_cookie,
+            commands::cookies::clear_cookies,
+            commands::cookies::set_cookie_persistence,
+            commands::graph::dependency_graph,
+            commands::graph::component_impact,
+            commands::graph::export_dependency_graph,
+            commands::privacy::audit_sensitive_fields,
+            commands::privacy::personal_data_inventory,
+            commands::privacy::export_personal_data_inventory,
+            commands::extensions::list_extension_definitions,
+            commands::extensions::register_extension,
+            commands::extensions::unregister_extension,
+            commands::extensions::spec_extensions,
+            commands::extensions::filter_operations_by_extension,
+            commands::deprecations::deprecation_report,
+            commands::deprecations::find_deprecated_requests,
+            commands::deprecations::list_observed_deprecations,
+            commands::deprecations::forget_observed_deprecation,
+            commands::proxy::get_proxy_settings,
+            commands::proxy::set_proxy_settings,
+            commands::tls::get_trusted_certificates,
+            commands::tls::set_trusted_certificates,
+            commands::insecure::list_insecure_hosts,
+            commands::insecure::add_insecure_host,
+            commands::insecure::remove_insecure_host,
+            commands::environments::get_environment_mapping,
+            commands::environments::set_environment_mapping,
+            commands::environments::rewrite_for_environment,
+            commands::pinning::list_certificate_pins,
+            commands::pinning::set_certificate_pins,
+            commands::providers::fetch_environment_values,
+            commands::oauth::request_oauth_token,
+            commands::oauth::refresh_oauth_token,
+            commands::oauth::list_oauth_tokens,
+            commands::oauth::remove_oauth_token,
+            commands::drift::list_inferred_schemas,
+            commands::drift::schema_drift,
+            commands::drift::clear_inferred_schemas,
+            commands::coverage::get_coverage,
+            commands::coverage::clear_coverage,
+            commands::matrix::execute_request_matrix,
+            commands::authz::run_access_control_test,
+            commands::diagnostics::resolve_host,
+            commands::diagnostics::probe_connectivity,
+            commands::clipboard::set_clipboard_watch,
+            commands::finder::set_finder_items,
+            commands::finder::fuzzy_find,
+            commands::automation::bind_shortcut,
+            commands::automation::unbind_shortcut,
+            commands::automation::list_shortcuts,
+            commands::session::save_session,
+            commands::session::load_session,
+            commands::tray::set_tray_pins,
+            commands::fixtures::export_fixtures,
+            commands::jsonrpc::execute_jsonrpc,
+            commands::odata::build_odata_url,
+            commands::odata::fetch_odata_metadata,
+            commands::http::get_http_client_defaults,
+            commands::http::set_http_client_defaults,
+            startup::close_splashscreen,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running YASP desktop application");
+        .build(tauri::generate_context!())
+        .expect("error while running YASP desktop application")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                commands::on_exit(app);
+            }
+        });
 }
@@ -2,5 +2,9 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
-    yasp_desktop_lib::run();
+    if std::env::args().any(|arg| arg == yasp_desktop_lib::STDIO_FLAG) {
+        yasp_desktop_lib::run_stdio();
+    } else {
+        yasp_desktop_lib::run();
+    }
 }
@@ -0,0 +1,195 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::commands::{self, MAIN_WINDOW};
+use crate::error::{CommandError, ErrorCode};
+
+pub const SPLASH_WINDOW: &str = "splashscreen";
+
+/// Emitted before each startup task runs.
+pub const PROGRESS_EVENT: &str = "startup://progress";
+/// Emitted once every task has finished (successfully or not).
+pub const READY_EVENT: &str = "startup://ready";
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StartupProgress {
+    pub task: &'static str,
+    pub index: usize,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StartupReport {
+    /// Tasks that failed. The app still opens; features backed by a failed
+    /// task report their own errors when used.
+    pub failed: Vec<StartupFailure>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StartupFailure {
+    pub task: &'static str,
+    pub error: CommandError,
+}
+
+struct StartupTask {
+    name: &'static str,
+    run: fn(&AppHandle) -> Result<(), CommandError>,
+}
+
+/// Initialization run in the background before the splashscreen closes.
+/// Tasks run in order; later tasks may rely on earlier ones.
+const TASKS: &[StartupTask] = &[
+    StartupTask {
+        name: "app_data_dir",
+        run: ensure_app_data_dir,
+    },
+    StartupTask {
+        name: "response_files",
+        run: commands::body::init,
+    },
+    StartupTask {
+        name: "insecure_hosts",
+        run: commands::insecure::load,
+    },
+    StartupTask {
+        name: "certificate_pins",
+        run: commands::pinning::load,
+    },
+];
+
+// ─── Readiness ───────────────────────────────────────────────────────────────
+
+/// Tracks the two conditions for leaving the splashscreen: the backend has
+/// finished its startup tasks and the frontend has rendered.
+///
+/// Either side may finish first, and the frontend may signal more than once
+/// (e.g. on a dev reload); the splash is handed over exactly once.
+#[derive(Default)]
+pub struct Startup {
+    backend_ready: AtomicBool,
+    frontend_ready: AtomicBool,
+    handed_over: AtomicBool,
+}
+
+impl Startup {
+    /// Returns true if this call completed readiness and the caller should
+    /// perform the handover.
+    fn mark_backend_ready(&self) -> bool {
+        self.backend_ready.store(true, Ordering::SeqCst);
+        self.try_hand_over()
+    }
+
+    fn mark_frontend_ready(&self) -> bool {
+        self.frontend_ready.store(true, Ordering::SeqCst);
+        self.try_hand_over()
+    }
+
+    fn try_hand_over(&self) -> bool {
+        self.backend_ready.load(Ordering::SeqCst)
+            && self.frontend_ready.load(Ordering::SeqCst)
+            && !self.handed_over.swap(true, Ordering::SeqCst)
+    }
+}
+
+// ─── Pipeline ────────────────────────────────────────────────────────────────
+
+/// Run the startup tasks on a background thread so window creation is not
+/// blocked, then hand over from the splashscreen once the frontend is ready.
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn_blocking(move || {
+        let report = run_tasks(&app);
+        let _ = app.emit(READY_EVENT, &report);
+        if app.state::<Startup>().mark_backend_ready() {
+            hand_over(&app);
+        }
+    });
+}
+
+fn run_tasks(app: &AppHandle) -> StartupReport {
+    let mut failed = Vec::new();
+    for (index, task) in TASKS.iter().enumerate() {
+        let _ = app.emit(
+            PROGRESS_EVENT,
+            StartupProgress {
+                task: task.name,
+                index,
+                total: TASKS.len(),
+            },
+        );
+        if let Err(error) = (task.run)(app) {
+            failed.push(StartupFailure {
+                task: task.name,
+                error,
+            });
+        }
+    }
+    StartupReport { failed }
+}
+
+/// Close the splashscreen and reveal the main window, then open the link the
+/// app was launched with. Missing windows are ignored: the splash may already
+/// be gone, or absent in dev builds.
+fn hand_over(app: &AppHandle) {
+    if let Some(main) = app.get_webview_window(MAIN_WINDOW) {
+        let _ = main.show();
+        let _ = main.set_focus();
+    }
+    if let Some(splash) = app.get_webview_window(SPLASH_WINDOW) {
+        let _ = splash.close();
+    }
+    commands::snippet::open_launch_link(app);
+}
+
+fn ensure_app_data_dir(app: &AppHandle) -> Result<(), CommandError> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| CommandError::new(ErrorCode::StorageFailed).detail(e))?;
+    std::fs::create_dir_all(&dir).map_err(|e| CommandError::new(ErrorCode::StorageFailed).detail(e))
+}
+
+// ─── Commands ─────────────────────────────────────────────────────────────────
+
+/// Called by the frontend once it has rendered. The splashscreen closes when
+/// both this and the backend startup tasks have completed; repeated calls are
+/// harmless.
+#[tauri::command]
+pub fn close_splashscreen(app: AppHandle) {
+    if app.state::<Startup>().mark_frontend_ready() {
+        hand_over(&app);
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hands_over_when_backend_finishes_last() {
+        let startup = Startup::default();
+        assert!(!startup.mark_frontend_ready());
+        assert!(startup.mark_backend_ready());
+    }
+
+    #[test]
+    fn test_hands_over_when_frontend_finishes_last() {
+        let startup = Startup::default();
+        assert!(!startup.mark_backend_ready());
+        assert!(startup.mark_frontend_ready());
+    }
+
+    #[test]
+    fn test_hands_over_only_once() {
+        let startup = Startup::default();
+        startup.mark_backend_ready();
+        assert!(startup.mark_frontend_ready());
+        assert!(!startup.mark_frontend_ready());
+        assert!(!startup.mark_backend_ready());
+    }
+}
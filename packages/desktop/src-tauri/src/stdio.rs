@@ -0,0 +1,405 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::mpsc;
+
+use crate::commands::coverage::TrafficStore;
+use crate::commands::gate::{self, ContractResults};
+use crate::commands::http::HttpClients;
+use crate::commands::jsonrpc::JsonRpcError;
+use crate::commands::odata::{self, ODataQuery};
+use crate::commands::routes::WorkspaceSpec;
+use crate::commands::snippet::{self, OperationRef, SnippetFormat};
+use crate::commands::{self, ApiRequest};
+use crate::error::CommandError;
+
+/// Command-line flag that starts the stdio interface instead of the GUI.
+pub const STDIO_FLAG: &str = "--stdio";
+
+/// Process exit status after a failed `check_quality_gate`, so CI jobs fail.
+pub const GATE_FAILED_EXIT_CODE: i32 = 3;
+
+// JSON-RPC 2.0 error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+/// A backend command failed; `data` is the `CommandError` as the GUI sees it.
+const COMMAND_ERROR: i64 = -32000;
+
+/// Methods available without the GUI, in `list_methods` order. Commands that
+/// need a window, app state or plugins (tray, shortcuts, clipboard, session)
+/// are GUI-only.
+const METHODS: &[&str] = &[
+    "list_methods",
+    "execute_api_request",
+    "fetch_spec",
+    "generate_operation_snippet",
+    "build_odata_url",
+    "get_coverage",
+    "check_quality_gate",
+];
+
+// ─── Params ──────────────────────────────────────────────────────────────────
+
+#[derive(Deserialize)]
+struct FetchSpecParams {
+    url: String,
+}
+
+#[derive(Deserialize)]
+struct SnippetParams {
+    operation: OperationRef,
+    method: String,
+    url: String,
+    #[serde(default)]
+    headers: HashMap<String, String>,
+    body: Option<String>,
+    format: SnippetFormat,
+}
+
+#[derive(Deserialize)]
+struct CoverageParams {
+    spec_id: String,
+    specs: Vec<WorkspaceSpec>,
+}
+
+#[derive(Deserialize)]
+struct QualityGateParams {
+    /// Absolute path of the workspace manifest holding the thresholds.
+    manifest: String,
+    spec_id: String,
+    specs: Vec<WorkspaceSpec>,
+    #[serde(default)]
+    contract_tests: ContractResults,
+}
+
+#[derive(Deserialize)]
+struct ODataUrlParams {
+    service_url: String,
+    entity_set: String,
+    #[serde(default)]
+    query: ODataQuery,
+}
+
+// ─── Session ─────────────────────────────────────────────────────────────────
+
+/// State of one headless run.
+#[derive(Default)]
+struct Session {
+    clients: HttpClients,
+    /// Requests sent in this run; coverage is measured on them alone, not on
+    /// the GUI's traffic.
+    traffic: Mutex<TrafficStore>,
+    gate_failed: AtomicBool,
+}
+
+// ─── Dispatch ────────────────────────────────────────────────────────────────
+
+fn rpc_error(code: i64, message: impl ToString) -> JsonRpcError {
+    JsonRpcError {
+        code,
+        message: message.to_string(),
+        data: None,
+    }
+}
+
+impl From<CommandError> for JsonRpcError {
+    fn from(error: CommandError) -> Self {
+        Self {
+            code: COMMAND_ERROR,
+            message: error.message(),
+            data: serde_json::to_value(&error).ok(),
+        }
+    }
+}
+
+fn params<T: DeserializeOwned>(params: Value) -> Result<T, JsonRpcError> {
+    // Omitted params are read as an empty object so all-optional methods work
+    let params = if params.is_null() { json!({}) } else { params };
+    serde_json::from_value(params).map_err(|e| rpc_error(INVALID_PARAMS, e))
+}
+
+fn to_value(value: impl serde::Serialize) -> Result<Value, JsonRpcError> {
+    serde_json::to_value(value).map_err(|e| rpc_error(COMMAND_ERROR, e))
+}
+
+async fn call(session: &Session, method: &str, raw_params: Value) -> Result<Value, JsonRpcError> {
+    let clients = &session.clients;
+    match method {
+        "list_methods" => to_value(METHODS),
+        "execute_api_request" => {
+            let request: ApiRequest = params(raw_params)?;
+            let response = commands::send_api_request(clients, &request).await?;
+            session
+                .traffic
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .record(
+                    &request.method,
+                    &request.url,
+                    &request.headers,
+                    response.status,
+                );
+            to_value(response)
+        }
+        "fetch_spec" => {
+            let FetchSpecParams { url } = params(raw_params)?;
+            to_value(commands::fetch_spec_text(clients, &url, None).await?)
+        }
+        "get_coverage" => {
+            let p: CoverageParams = params(raw_params)?;
+            let traffic = session.traffic.lock().unwrap_or_else(|e| e.into_inner());
+            to_value(traffic.coverage(&p.specs, &p.spec_id)?)
+        }
+        "check_quality_gate" => {
+            let p: QualityGateParams = params(raw_params)?;
+            let gate = gate::read_gate(&p.manifest)?;
+            let coverage = session
+                .traffic
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .coverage(&p.specs, &p.spec_id)?;
+            let report = gate.evaluate(&coverage, p.contract_tests);
+            if !report.passed {
+                session.gate_failed.store(true, Ordering::SeqCst);
+            }
+            to_value(report)
+        }
+        "generate_operation_snippet" => {
+            let p: SnippetParams = params(raw_params)?;
+            to_value(snippet::generate_operation_snippet(
+                p.operation,
+                p.method,
+                p.url,
+                p.headers,
+                p.body,
+                p.format,
+            )?)
+        }
+        "build_odata_url" => {
+            let p: ODataUrlParams = params(raw_params)?;
+            to_value(odata::build_odata_url(
+                p.service_url,
+                p.entity_set,
+                p.query,
+            )?)
+        }
+        _ => Err(rpc_error(
+            METHOD_NOT_FOUND,
+            format!("Method not found: {method}"),
+        )),
+    }
+}
+
+fn response(id: Value, result: Result<Value, JsonRpcError>) -> Value {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => json!({ "jsonrpc": "2.0", "id": id, "error": error }),
+    }
+}
+
+/// Handle one request object. Notifications (no `id`) run but get no
+/// response.
+async fn handle_message(session: &Session, message: Value) -> Option<Value> {
+    let id = message.get("id").cloned();
+    let method = match (
+        message.get("jsonrpc").and_then(Value::as_str),
+        message.get("method").and_then(Value::as_str),
+    ) {
+        (Some("2.0"), Some(method)) => method.to_string(),
+        _ => {
+            return Some(response(
+                id.unwrap_or(Value::Null),
+                Err(rpc_error(INVALID_REQUEST, "Invalid Request")),
+            ))
+        }
+    };
+    let params = message.get("params").cloned().unwrap_or(Value::Null);
+    let result = call(session, &method, params).await;
+    id.map(|id| response(id, result))
+}
+
+/// Handle one input line: a request object or a batch array.
+async fn handle_line(session: &Session, line: &str) -> Option<Value> {
+    let parsed: Value = match serde_json::from_str(line) {
+        Ok(parsed) => parsed,
+        Err(e) => return Some(response(Value::Null, Err(rpc_error(PARSE_ERROR, e)))),
+    };
+    match parsed {
+        Value::Array(batch) if batch.is_empty() => Some(response(
+            Value::Null,
+            Err(rpc_error(INVALID_REQUEST, "Invalid Request")),
+        )),
+        Value::Array(batch) => {
+            let mut responses = Vec::new();
+            for message in batch {
+                responses.extend(handle_message(session, message).await);
+            }
+            (!responses.is_empty()).then_some(Value::Array(responses))
+        }
+        message => handle_message(session, message).await,
+    }
+}
+
+// ─── Entry Point ─────────────────────────────────────────────────────────────
+
+/// Serve newline-delimited JSON-RPC 2.0 on stdin/stdout until stdin closes,
+/// returning the process exit status: `GATE_FAILED_EXIT_CODE` if a
+/// `check_quality_gate` failed, else 0.
+///
+/// Each line is handled concurrently, so a slow request does not block the
+/// ones after it; responses are written as they complete and matched by id.
+/// Nothing else is written to stdout.
+pub fn serve() -> i32 {
+    let runtime = tokio::runtime::Runtime::new().expect("failed to start the async runtime");
+    runtime.block_on(async {
+        let (tx, mut rx) = mpsc::unbounded_channel::<Value>();
+        let writer = tokio::spawn(async move {
+            let mut stdout = tokio::io::stdout();
+            while let Some(message) = rx.recv().await {
+                let mut line = message.to_string();
+                line.push('\n');
+                if stdout.write_all(line.as_bytes()).await.is_err() {
+                    break;
+                }
+                let _ = stdout.flush().await;
+            }
+        });
+
+        let session = Arc::new(Session::default());
+        let mut lines = BufReader::new(tokio::io::stdin()).lines();
+        let mut tasks = tokio::task::JoinSet::new();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (tx, session) = (tx.clone(), session.clone());
+            tasks.spawn(async move {
+                if let Some(reply) = handle_line(&session, &line).await {
+                    let _ = tx.send(reply);
+                }
+            });
+        }
+        while tasks.join_next().await.is_some() {}
+        drop(tx);
+        let _ = writer.await;
+
+        if session.gate_failed.load(Ordering::SeqCst) {
+            GATE_FAILED_EXIT_CODE
+        } else {
+            0
+        }
+    })
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_list_methods() {
+        let reply = handle_line(
+            &Session::default(),
+            r#"{"jsonrpc":"2.0","id":1,"method":"list_methods"}"#,
+        )
+        .await
+        .unwrap();
+        assert_eq!(reply["id"], 1);
+        assert_eq!(reply["result"][0], "list_methods");
+    }
+
+    #[tokio::test]
+    async fn test_command_errors_carry_the_command_error() {
+        let reply = handle_line(
+            &Session::default(),
+            r#"{"jsonrpc":"2.0","id":"a","method":"fetch_spec","params":{"url":"http://10.0.0.1/spec"}}"#,
+        )
+        .await
+        .unwrap();
+        assert_eq!(reply["error"]["code"], COMMAND_ERROR);
+        assert_eq!(reply["error"]["data"]["code"], "blocked_ip");
+    }
+
+    #[tokio::test]
+    async fn test_protocol_errors() {
+        let reply = handle_line(&Session::default(), "{not json").await.unwrap();
+        assert_eq!(reply["error"]["code"], PARSE_ERROR);
+
+        let reply = handle_line(
+            &Session::default(),
+            r#"{"jsonrpc":"2.0","id":2,"method":"close_splashscreen"}"#,
+        )
+        .await
+        .unwrap();
+        assert_eq!(reply["error"]["code"], METHOD_NOT_FOUND);
+
+        let reply = handle_line(
+            &Session::default(),
+            r#"{"jsonrpc":"2.0","id":3,"method":"fetch_spec","params":{}}"#,
+        )
+        .await
+        .unwrap();
+        assert_eq!(reply["error"]["code"], INVALID_PARAMS);
+
+        let reply = handle_line(&Session::default(), r#"{"id":4,"method":"list_methods"}"#)
+            .await
+            .unwrap();
+        assert_eq!(reply["error"]["code"], INVALID_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_failed_quality_gate_fails_the_run() {
+        let manifest =
+            std::env::temp_dir().join(format!("yasp-stdio-gate-{}.json", std::process::id()));
+        std::fs::write(
+            &manifest,
+            r#"{ "quality_gate": { "min_operation_coverage": 0.5 } }"#,
+        )
+        .unwrap();
+        let message = json!({
+            "jsonrpc": "2.0",
+            "id": 6,
+            "method": "check_quality_gate",
+            "params": {
+                "manifest": manifest,
+                "spec_id": "pets",
+                "specs": [{
+                    "id": "pets",
+                    "spec": { "openapi": "3.0.3", "paths": { "/pets": { "get": {} } } }
+                }]
+            }
+        });
+        let session = Session::default();
+        let reply = handle_line(&session, &message.to_string()).await.unwrap();
+        assert_eq!(reply["result"]["passed"], false);
+        assert_eq!(reply["result"]["checks"][0]["metric"], "operation_coverage");
+        assert!(session.gate_failed.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_batches_skip_notifications() {
+        let reply = handle_line(
+            &Session::default(),
+            r#"[{"jsonrpc":"2.0","method":"list_methods"},{"jsonrpc":"2.0","id":5,"method":"list_methods"}]"#,
+        )
+        .await
+        .unwrap();
+        assert_eq!(reply.as_array().unwrap().len(), 1);
+        assert_eq!(reply[0]["id"], 5);
+
+        assert!(handle_line(
+            &Session::default(),
+            r#"{"jsonrpc":"2.0","method":"list_methods"}"#
+        )
+        .await
+        .is_none());
+    }
+}
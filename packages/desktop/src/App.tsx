@@ -5,11 +5,17 @@ import { AppRoutes } from "@yasp/core/AppRoutes";
 import { invoke } from "@tauri-apps/api/core";
 import { useUpdateCheck } from "./hooks/useUpdateCheck";
 import { UpdateDialog } from "./components/UpdateDialog";
+import { commandErrorMessage } from "./actions/command-error";
 
 // OWASP A09:2025 – SSRF: URL validation is enforced in the Rust `fetch_spec` command.
 // This function is passed as a prop so @yasp/core stays platform-agnostic.
 async function tauriFetchUrl(url: string): Promise<string> {
-    return invoke<string>("fetch_spec", { url });
+    try {
+        return await invoke<string>("fetch_spec", { url });
+    } catch (error: unknown) {
+        // Surface the backend's message — callers display `Error.message`.
+        throw new Error(commandErrorMessage(error, "Failed to fetch URL"));
+    }
 }
 
 export default function App() {
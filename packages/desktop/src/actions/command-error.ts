@@ -0,0 +1,52 @@
+/**
+ * Desktop Command Errors
+ * Shape of the error every Tauri command rejects with (see src-tauri/src/error.rs).
+ *
+ * `code` is stable and can be used to branch or localize; `message` is the
+ * English rendering from the backend catalog, with `args` as its variables.
+ * `kind`, `phase` and `retryable` let the UI pick a remediation (e.g. a policy
+ * block vs. a flaky network) without parsing text.
+ */
+
+export type CommandErrorKind =
+    | 'policy'
+    | 'invalid_input'
+    | 'network'
+    | 'timeout'
+    | 'cancelled'
+    | 'response'
+    | 'internal';
+
+export type CommandErrorPhase = 'validation' | 'setup' | 'connect' | 'send' | 'receive';
+
+export interface CommandError {
+    code: string;
+    kind: CommandErrorKind;
+    phase: CommandErrorPhase;
+    retryable: boolean;
+    message: string;
+    /** Underlying library error text, untranslated. */
+    detail?: string;
+    args: Record<string, string>;
+}
+
+export function isCommandError(error: unknown): error is CommandError {
+    return (
+        typeof error === 'object' &&
+        error !== null &&
+        typeof (error as CommandError).code === 'string' &&
+        typeof (error as CommandError).message === 'string'
+    );
+}
+
+/**
+ * Extract a human-readable message from anything `invoke()` may reject with.
+ */
+export function commandErrorMessage(error: unknown, fallback: string): string {
+    if (isCommandError(error)) {
+        return error.detail ? `${error.message}: ${error.detail}` : error.message;
+    }
+    if (typeof error === 'string') return error;
+    if (error instanceof Error) return error.message;
+    return fallback;
+}
@@ -4,7 +4,7 @@
  *
  * Security:
  * - OWASP A09:2025 (SSRF): URL validation performed in Rust command (see commands/mod.rs)
- * - OWASP A04:2025 (Insecure Design): 30s timeout, 10MB body limit enforced in Rust
+ * - OWASP A04:2025 (Insecure Design): timeouts (30s default), 10MB body limit enforced in Rust
  * - OWASP A05:2025 (Cryptographic Failures): TLS enforced via reqwest + rustls
  *
  * This module re-exports the same ApiRequestData/ApiResponseData types as the web
@@ -20,8 +20,28 @@ interface TauriApiResponse {
     status: number;
     status_text: string;
     headers: Record<string, string>;
+    /** Text, base64 or empty, per body_encoding (see commands/body.rs). */
     body: string;
+    body_encoding: 'text' | 'base64' | 'file';
+    /** Temporary file holding large binary bodies, or the download_to path. */
+    body_path: string | null;
+    content_type: string | null;
     duration_ms: number;
+    /** HTTP version the response came over (see HttpVersion in commands/http.rs). */
+    http_version: '0.9' | '1.0' | '1.1' | '2' | '3';
+    /** Per-phase breakdown; connection phases are null on a reused connection (see commands/timing.rs). */
+    timing: {
+        dns_ms: number | null;
+        connect_ms: number | null;
+        tls_ms: number | null;
+        ttfb_ms: number;
+        download_ms: number;
+        total_ms: number;
+    };
+    /** Redirects followed, in order; location is resolved against url. */
+    redirects: { url: string; status: number; location: string }[];
+    /** Set when the request asked for TLS inspection (see commands/tls.rs). */
+    tls: { version: '1.2' | '1.3'; cipher_suite: string } | null;
 }
 
 /**
@@ -61,18 +81,25 @@ export async function executeApiRequest(request: ApiRequestData): Promise<ApiRes
     }
 
     const response = await invoke<TauriApiResponse>('execute_api_request', {
-        method: request.method,
-        url: request.url,
-        headers,
-        body: bodyStr ?? null,
+        request: {
+            method: request.method,
+            url: request.url,
+            headers,
+            body: bodyStr ?? null,
+        },
     });
 
     const time = Date.now() - startTime;
 
-    // Parse body: try JSON first, fall back to text
+    // Parse body: binary as a Blob (or the file path), then JSON, then text
     let body: unknown;
-    const contentType = response.headers['content-type'] ?? '';
-    if (contentType.includes('json')) {
+    const contentType = response.content_type ?? '';
+    if (response.body_encoding === 'base64') {
+        const bytes = Uint8Array.from(atob(response.body), (c) => c.charCodeAt(0));
+        body = new Blob([bytes], { type: contentType });
+    } else if (response.body_encoding === 'file') {
+        body = response.body_path;
+    } else if (contentType.includes('json')) {
         try {
             body = JSON.parse(response.body);
         } catch {
@@ -83,7 +110,7 @@ export async function executeApiRequest(request: ApiRequestData): Promise<ApiRes
     }
 
     const bodyStr2 = typeof body === 'string' ? body : JSON.stringify(body);
-    const size = new Blob([bodyStr2]).size / 1024; // KB
+    const size = (body instanceof Blob ? body.size : new Blob([bodyStr2]).size) / 1024; // KB
 
     return {
         status: response.status,
@@ -11,6 +11,8 @@
 
 import { invoke } from '@tauri-apps/api/core';
 
+import { commandErrorMessage } from './command-error';
+
 export interface FetchSpecResult {
     content?: string;
     error?: string;
@@ -29,15 +31,10 @@ export async function fetchSpec(url: string): Promise<FetchSpecResult> {
     }
 
     try {
-        // invoke throws on Rust Err() — the Rust command returns Result<String, String>
+        // invoke throws on Rust Err() — the Rust command returns Result<String, CommandError>
         const content = await invoke<string>('fetch_spec', { url });
         return { content };
     } catch (error: unknown) {
-        const message = typeof error === 'string'
-            ? error
-            : error instanceof Error
-                ? error.message
-                : 'Failed to fetch specification';
-        return { error: message };
+        return { error: commandErrorMessage(error, 'Failed to fetch specification') };
     }
 }